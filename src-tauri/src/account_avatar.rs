@@ -0,0 +1,217 @@
+//! 账户头像缓存
+//! 账户备份中不包含头像，头像地址由前端通过 userinfo 接口获取后交给后端下载，
+//! 缓存在配置目录的 `avatars` 下（按不透明的账户 ID 命名，文件名中不出现邮箱）。
+//! 之后离线或接口失败时仍使用缓存的图片；没有头像时前端显示邮箱或别名的首字母。
+//! 托盘菜单暂不显示头像：解码图片需要额外启用 tauri 的图片特性，菜单仍只显示文字
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::error::{AgentError, AgentResult};
+use crate::proxy::ProxySettings;
+
+/// 下载超时
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 头像文件大小上限
+const MAX_AVATAR_BYTES: usize = 512 * 1024;
+
+/// 缓存超过该时间后重新下载（地址未变化时）
+const REFRESH_AFTER_DAYS: i64 = 7;
+
+/// 缓存的头像信息（与图片同名的 `.json` 文件）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AvatarMeta {
+    url: String,
+    mime: String,
+    /// 下载时间（RFC 3339）
+    fetched_at: String,
+}
+
+/// 账户头像
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountAvatar {
+    pub email: String,
+    /// 没有头像时显示的首字母（别名优先，其次邮箱）
+    pub initial: String,
+    /// 缓存的图片（`data:` URL），没有缓存时为 None
+    pub data_url: Option<String>,
+    /// 缓存时间（RFC 3339）
+    pub cached_at: Option<String>,
+}
+
+fn image_path(id: &str) -> PathBuf {
+    crate::directories::get_avatars_directory().join(format!("{id}.img"))
+}
+
+fn meta_path(id: &str) -> PathBuf {
+    crate::directories::get_avatars_directory().join(format!("{id}.json"))
+}
+
+/// 首字母（大写）
+fn initial(email: &str, alias: Option<&str>) -> String {
+    alias
+        .filter(|alias| !alias.trim().is_empty())
+        .unwrap_or(email)
+        .trim()
+        .chars()
+        .next()
+        .map(|c| c.to_uppercase().collect())
+        .unwrap_or_else(|| "?".to_string())
+}
+
+/// 按文件头识别图片格式，不是图片时返回 None
+fn sniff_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("image/gif")
+    } else if bytes.len() > 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+fn read_meta(id: &str) -> Option<AvatarMeta> {
+    let content = fs::read_to_string(meta_path(id)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// 读取缓存的头像（不访问网络）
+fn cached(id: &str, email: &str, alias: Option<&str>) -> AccountAvatar {
+    let cache = read_meta(id).and_then(|meta| {
+        let bytes = fs::read(image_path(id)).ok()?;
+        let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+        Some((
+            format!("data:{};base64,{encoded}", meta.mime),
+            meta.fetched_at,
+        ))
+    });
+    let (data_url, cached_at) = cache.unzip();
+    AccountAvatar {
+        email: email.to_string(),
+        initial: initial(email, alias),
+        data_url,
+        cached_at,
+    }
+}
+
+/// 缓存是否仍可直接使用（地址相同且未过期）
+fn is_fresh(meta: &AvatarMeta, url: &str) -> bool {
+    meta.url == url
+        && chrono::DateTime::parse_from_rfc3339(&meta.fetched_at).is_ok_and(|fetched_at| {
+            chrono::Local::now().signed_duration_since(fetched_at)
+                < chrono::Duration::days(REFRESH_AFTER_DAYS)
+        })
+}
+
+async fn download(url: &tauri::Url, proxy: &ProxySettings) -> AgentResult<Vec<u8>> {
+    use tauri_plugin_http::reqwest;
+    let mut builder = reqwest::Client::builder().timeout(DOWNLOAD_TIMEOUT);
+    // HTTP 客户端未启用 SOCKS 支持，与更新检查一样只使用 HTTP 代理
+    if proxy.kind == crate::proxy::ProxyKind::Http {
+        if let Some(proxy_url) = proxy.url_for_host(url.host_str().unwrap_or_default()) {
+            let proxy = reqwest::Proxy::all(&proxy_url)
+                .map_err(|e| AgentError::InvalidInput(format!("代理地址无效: {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+    }
+    let response = builder
+        .build()
+        .map_err(|e| AgentError::Network(e.to_string()))?
+        .get(url.clone())
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| AgentError::Network(e.to_string()))?;
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_AVATAR_BYTES as u64)
+    {
+        return Err(AgentError::InvalidInput("头像文件过大".to_string()));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| AgentError::Network(e.to_string()))?;
+    if bytes.len() > MAX_AVATAR_BYTES {
+        return Err(AgentError::InvalidInput("头像文件过大".to_string()));
+    }
+    Ok(bytes.to_vec())
+}
+
+fn store(id: &str, url: &str, mime: &str, bytes: &[u8]) -> AgentResult<()> {
+    crate::utils::permissions::create_private_dir(&crate::directories::get_avatars_directory())?;
+    fs::write(image_path(id), bytes)?;
+    let meta = AvatarMeta {
+        url: url.to_string(),
+        mime: mime.to_string(),
+        fetched_at: chrono::Local::now().to_rfc3339(),
+    };
+    fs::write(meta_path(id), serde_json::to_vec_pretty(&meta)?)?;
+    Ok(())
+}
+
+/// 获取缓存的头像（不访问网络）
+pub fn get(email: &str) -> AgentResult<AccountAvatar> {
+    let identity = crate::account_metadata::ensure_identities(&[email.to_string()])?
+        .pop()
+        .ok_or_else(|| AgentError::InvalidInput("邮箱不能为空".to_string()))?;
+    Ok(cached(&identity.id, email, identity.alias.as_deref()))
+}
+
+/// 下载并缓存头像；地址未变化且缓存未过期时直接使用缓存，下载失败时返回已有的缓存
+pub async fn cache(email: &str, url: &str, proxy: &ProxySettings) -> AgentResult<AccountAvatar> {
+    let parsed = tauri::Url::parse(url)
+        .map_err(|e| AgentError::InvalidInput(format!("头像地址无效: {e}")))?;
+    if parsed.scheme() != "https" {
+        return Err(AgentError::InvalidInput(
+            "头像地址必须使用 HTTPS".to_string(),
+        ));
+    }
+    let identity = crate::account_metadata::ensure_identities(&[email.to_string()])?
+        .pop()
+        .ok_or_else(|| AgentError::InvalidInput("邮箱不能为空".to_string()))?;
+    let id = identity.id.as_str();
+    let alias = identity.alias.as_deref();
+
+    if read_meta(id).is_some_and(|meta| is_fresh(&meta, url)) {
+        return Ok(cached(id, email, alias));
+    }
+
+    let result = download(&parsed, proxy).await.and_then(|bytes| {
+        let mime = sniff_mime(&bytes)
+            .ok_or_else(|| AgentError::InvalidInput("头像不是支持的图片格式".to_string()))?;
+        store(id, url, mime, &bytes)
+    });
+    match result {
+        Ok(()) => tracing::debug!(target: "account_avatar", account_id = id, "头像已缓存"),
+        // 离线时继续使用旧缓存
+        Err(e) => {
+            tracing::warn!(target: "account_avatar", account_id = id, error = %e, "下载头像失败，使用已有缓存")
+        }
+    }
+    Ok(cached(id, email, alias))
+}
+
+/// 删除账户的头像缓存（删除备份时调用，失败只记录日志）
+pub fn remove(email: &str) {
+    let Some(id) = crate::account_metadata::get(email).id else {
+        return;
+    };
+    for path in [image_path(&id), meta_path(&id)] {
+        if let Err(e) = fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(target: "account_avatar", account_id = %id, error = %e, "删除头像缓存失败");
+            }
+        }
+    }
+}
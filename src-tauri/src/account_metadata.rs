@@ -0,0 +1,354 @@
+//! 账户元数据
+//! 以邮箱为键保存每个账户的附加信息（如启动配置），与账户备份文件分开存放，
+//! 备份文件被覆盖或重新生成时不会丢失。最近使用的账户（切换、恢复、备份时更新）也记录在这里。
+//! 数据保存在 agent.db（见 [`crate::agent_db`]），每次更新都在事务中完成
+
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::agent_db;
+use crate::error::{AgentError, AgentResult};
+use crate::proxy::ProxySettings;
+
+/// 最近使用列表保留的最大账户数
+const MAX_RECENT_ACCOUNTS: usize = 50;
+
+/// 账户启动配置：切换到该账户后启动 Antigravity 时替代全局默认值
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LaunchProfile {
+    /// 附加的命令行参数
+    pub args: Vec<String>,
+    /// 启动时打开的工作区（文件夹或 .code-workspace 文件）
+    pub workspace: Option<String>,
+    /// 代理设置（为 None 时使用全局代理设置）
+    pub proxy: Option<ProxySettings>,
+}
+
+impl LaunchProfile {
+    /// 校验启动配置
+    pub fn validate(&self) -> AgentResult<()> {
+        if let Some(workspace) = &self.workspace {
+            if workspace.trim().is_empty() {
+                return Err(AgentError::InvalidInput("工作区路径不能为空".to_string()));
+            }
+        }
+        if let Some(proxy) = &self.proxy {
+            proxy.validate()?;
+        }
+        Ok(())
+    }
+
+    /// 传给 Antigravity 的参数（附加参数 + 工作区）
+    pub fn app_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = self
+            .args
+            .iter()
+            .filter(|a| !a.trim().is_empty())
+            .cloned()
+            .collect();
+        if let Some(workspace) = &self.workspace {
+            args.push(workspace.trim().to_string());
+        }
+        args
+    }
+}
+
+/// 单个账户的元数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AccountMetadata {
+    /// 不透明的账户 ID，用于托盘菜单等不宜直接使用邮箱的场景
+    pub id: Option<String>,
+    /// 账户别名（显示名称）
+    pub alias: Option<String>,
+    /// 备份文件名（位于账户备份目录，未记录时使用 `{email}.json`）
+    pub backup_file: Option<String>,
+    /// 最近一次成功备份的时间（RFC 3339，备份内容未变化时也会更新）
+    pub last_backup_at: Option<String>,
+    /// 置顶（在最近使用列表中始终排在前面）
+    pub pinned: bool,
+    pub launch_profile: Option<LaunchProfile>,
+}
+
+/// 账户身份：ID、邮箱与别名
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountIdentity {
+    pub id: String,
+    pub email: String,
+    pub alias: Option<String>,
+}
+
+/// 最近使用记录
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecentEntry {
+    email: String,
+    used_at: String,
+}
+
+/// 最近使用列表中的账户
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentAccount {
+    pub email: String,
+    pub alias: Option<String>,
+    pub pinned: bool,
+    /// 最近一次切换、恢复或备份的时间（None 表示没有记录）
+    pub last_used_at: Option<String>,
+}
+
+/// 旧版本的元数据文件（account_metadata.json），升级时导入 agent.db
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct LegacyMetadataFile {
+    accounts: BTreeMap<String, AccountMetadata>,
+    /// 最近使用的账户（最近的在前）
+    recent: Vec<RecentEntry>,
+}
+
+/// 导入旧版本的 account_metadata.json（由 agent.db 迁移调用），导入后文件重命名为 `.migrated`
+pub fn import_legacy_file(tx: &Transaction) -> AgentResult<()> {
+    let path = crate::directories::get_account_metadata_file();
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(());
+    };
+    let legacy: LegacyMetadataFile = serde_json::from_str(&content).unwrap_or_else(|e| {
+        tracing::warn!(target: "account_metadata", error = %e, "旧版账户元数据解析失败，跳过导入");
+        LegacyMetadataFile::default()
+    });
+
+    for (email, meta) in &legacy.accounts {
+        save(tx, email, meta)?;
+    }
+    for entry in &legacy.recent {
+        let used_ms = chrono::DateTime::parse_from_rfc3339(&entry.used_at)
+            .map(|t| t.timestamp_millis())
+            .unwrap_or(0);
+        tx.execute(
+            "INSERT OR REPLACE INTO recent_accounts (email, used_at, used_ms) VALUES (?1, ?2, ?3)",
+            params![entry.email, entry.used_at, used_ms],
+        )?;
+    }
+
+    fs::rename(&path, path.with_extension("json.migrated"))?;
+    tracing::info!(
+        target: "account_metadata",
+        accounts = legacy.accounts.len(),
+        "已将账户元数据导入 agent.db"
+    );
+    Ok(())
+}
+
+fn parse(email: &str, data: &str) -> AccountMetadata {
+    serde_json::from_str(data).unwrap_or_else(|e| {
+        tracing::warn!(target: "account_metadata", email, error = %e, "账户元数据解析失败，使用默认值");
+        AccountMetadata::default()
+    })
+}
+
+fn load(conn: &Connection, email: &str) -> AgentResult<AccountMetadata> {
+    let data: Option<String> = conn
+        .query_row(
+            "SELECT data FROM account_metadata WHERE email = ?1",
+            [email],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(data.map(|data| parse(email, &data)).unwrap_or_default())
+}
+
+fn save(conn: &Connection, email: &str, meta: &AccountMetadata) -> AgentResult<()> {
+    conn.execute(
+        "INSERT INTO account_metadata (email, account_id, data) VALUES (?1, ?2, ?3)
+         ON CONFLICT (email) DO UPDATE SET account_id = excluded.account_id, data = excluded.data",
+        params![email, meta.id, serde_json::to_string(meta)?],
+    )?;
+    Ok(())
+}
+
+/// 读取所有账户的元数据（以邮箱为键）
+pub fn all() -> BTreeMap<String, AccountMetadata> {
+    let result = agent_db::with_connection(|conn| {
+        let mut stmt = conn.prepare("SELECT email, data FROM account_metadata")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        let mut accounts = BTreeMap::new();
+        for row in rows {
+            let (email, data) = row?;
+            let meta = parse(&email, &data);
+            accounts.insert(email, meta);
+        }
+        Ok(accounts)
+    });
+    result.unwrap_or_else(|e| {
+        tracing::warn!(target: "account_metadata", error = %e, "读取账户元数据失败");
+        BTreeMap::new()
+    })
+}
+
+/// 读取指定账户的元数据（不存在时返回默认值）
+pub fn get(email: &str) -> AccountMetadata {
+    agent_db::with_connection(|conn| load(conn, email)).unwrap_or_else(|e| {
+        tracing::warn!(target: "account_metadata", error = %e, "读取账户元数据失败");
+        AccountMetadata::default()
+    })
+}
+
+/// 在事务中更新指定账户的元数据，返回更新后的元数据
+pub fn update(
+    email: &str,
+    f: impl FnOnce(&mut AccountMetadata) -> AgentResult<()>,
+) -> AgentResult<AccountMetadata> {
+    if email.trim().is_empty() {
+        return Err(AgentError::InvalidInput("账户邮箱不能为空".to_string()));
+    }
+    crate::app_settings::ensure_writable()?;
+
+    let updated = agent_db::transaction(|tx| {
+        let mut meta = load(tx, email)?;
+        f(&mut meta)?;
+        save(tx, email, &meta)?;
+        Ok(meta)
+    })?;
+    tracing::debug!(target: "account_metadata", email, "账户元数据已更新");
+    Ok(updated)
+}
+
+/// 为账户分配 ID（已有则沿用），返回与输入顺序一致的身份信息
+///
+/// ID 是内部标识而非用户数据，只读模式下也允许写入
+pub fn ensure_identities(emails: &[String]) -> AgentResult<Vec<AccountIdentity>> {
+    agent_db::transaction(|tx| {
+        let mut identities = Vec::with_capacity(emails.len());
+        for email in emails.iter().filter(|email| !email.trim().is_empty()) {
+            let mut meta = load(tx, email)?;
+            let id = match &meta.id {
+                Some(id) => id.clone(),
+                None => {
+                    let id = uuid::Uuid::new_v4().simple().to_string();
+                    meta.id = Some(id.clone());
+                    save(tx, email, &meta)?;
+                    tracing::debug!(target: "account_metadata", "已为新账户分配 ID");
+                    id
+                }
+            };
+            identities.push(AccountIdentity {
+                id,
+                email: email.clone(),
+                alias: meta.alias,
+            });
+        }
+        Ok(identities)
+    })
+}
+
+/// 按 ID 查找账户
+pub fn resolve_id(id: &str) -> Option<AccountIdentity> {
+    let row = agent_db::with_connection(|conn| {
+        Ok(conn
+            .query_row(
+                "SELECT email, data FROM account_metadata WHERE account_id = ?1",
+                [id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?)
+    })
+    .ok()
+    .flatten()?;
+
+    let (email, data) = row;
+    let meta = parse(&email, &data);
+    Some(AccountIdentity {
+        id: id.to_string(),
+        email,
+        alias: meta.alias,
+    })
+}
+
+/// 把账户移到最近使用列表的最前面（切换、恢复、备份成功后调用）
+///
+/// 最近使用记录不是用户数据，只读模式下也允许写入；失败只记录日志
+pub fn touch_recent(email: &str) {
+    if email.trim().is_empty() {
+        return;
+    }
+    let now = chrono::Local::now();
+    let result = agent_db::transaction(|tx| {
+        tx.execute(
+            "INSERT OR REPLACE INTO recent_accounts (email, used_at, used_ms) VALUES (?1, ?2, ?3)",
+            params![email, now.to_rfc3339(), now.timestamp_millis()],
+        )?;
+        tx.execute(
+            "DELETE FROM recent_accounts WHERE email NOT IN
+             (SELECT email FROM recent_accounts ORDER BY used_ms DESC LIMIT ?1)",
+            [MAX_RECENT_ACCOUNTS as i64],
+        )?;
+        Ok(())
+    });
+    if let Err(e) = result {
+        tracing::warn!(target: "account_metadata", error = %e, "更新最近使用账户失败");
+    }
+}
+
+/// 最近使用记录（最近的在前）
+fn load_recent() -> Vec<RecentEntry> {
+    let result = agent_db::with_connection(|conn| {
+        let mut stmt =
+            conn.prepare("SELECT email, used_at FROM recent_accounts ORDER BY used_ms DESC")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(RecentEntry {
+                email: row.get(0)?,
+                used_at: row.get(1)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    });
+    result.unwrap_or_else(|e| {
+        tracing::warn!(target: "account_metadata", error = %e, "读取最近使用账户失败");
+        Vec::new()
+    })
+}
+
+/// 按「置顶在前，其余按最近使用排序」排列已知账户；没有使用记录的账户按邮箱排在最后
+pub fn recent_accounts(known: &[String]) -> Vec<RecentAccount> {
+    let metadata = all();
+    let recent = load_recent();
+    let rank = |email: &str| recent.iter().position(|entry| entry.email == email);
+
+    let mut accounts: Vec<(Option<usize>, RecentAccount)> = known
+        .iter()
+        .map(|email| {
+            let meta = metadata.get(email);
+            let position = rank(email);
+            let account = RecentAccount {
+                email: email.clone(),
+                alias: meta.and_then(|m| m.alias.clone()),
+                pinned: meta.is_some_and(|m| m.pinned),
+                last_used_at: position.map(|i| recent[i].used_at.clone()),
+            };
+            (position, account)
+        })
+        .collect();
+
+    accounts.sort_by(|(a_rank, a), (b_rank, b)| {
+        b.pinned
+            .cmp(&a.pinned)
+            .then_with(|| match (a_rank, b_rank) {
+                (Some(a_rank), Some(b_rank)) => a_rank.cmp(b_rank),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => a.email.cmp(&b.email),
+            })
+    });
+    accounts.into_iter().map(|(_, account)| account).collect()
+}
+
+/// 指定账户的启动配置
+pub fn launch_profile(email: &str) -> Option<LaunchProfile> {
+    get(email).launch_profile
+}
@@ -0,0 +1,297 @@
+//! 账户变化监听模块
+//! 轮询 Antigravity 的 state.vscdb（及 -wal）修改时间，检测到登录账户变化时推送 `account:changed` 事件；
+//! 当前账户超过设置的天数未备份时推送 `account:backup-stale` 事件
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex;
+use tokio::time::{interval, Duration};
+use tracing::{error, info, warn};
+
+use crate::state_bus::StateUpdate;
+use crate::system_tray::TooltipUpdate;
+
+/// 账户变化事件名
+pub const ACCOUNT_CHANGED_EVENT: &str = "account:changed";
+
+/// 备份过期提醒事件名
+pub const BACKUP_STALE_EVENT: &str = "account:backup-stale";
+
+/// 轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 每隔多少次轮询刷新一次托盘提示中的运行状态与备份时间（进程扫描开销较大）
+const STATUS_REFRESH_TICKS: u32 = 5;
+
+/// 账户变化事件数据
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountChangedEvent {
+    /// 变化前的账户邮箱（None 表示之前未登录）
+    pub previous_email: Option<String>,
+    /// 当前账户邮箱（None 表示已登出）
+    pub email: Option<String>,
+    /// 是否已自动备份新账户
+    pub backed_up: bool,
+}
+
+/// 备份过期提醒事件数据
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupStaleEvent {
+    pub email: String,
+    /// 最近一次成功备份的时间（None 表示从未备份）
+    pub last_backup_at: Option<String>,
+    /// 设置的提醒天数
+    pub stale_days: u32,
+}
+
+/// 已提醒过的账户，备份恢复新鲜或账户变化前不重复提醒
+static STALE_NOTIFIED: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+
+/// 账户变化监听器
+pub struct AccountWatcher {
+    app_handle: AppHandle,
+    is_running: Arc<Mutex<bool>>,
+}
+
+/// 数据库文件（含 WAL）的修改时间
+fn db_fingerprint(db_path: &Path) -> (Option<SystemTime>, Option<SystemTime>) {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+    let mut wal = db_path.as_os_str().to_owned();
+    wal.push("-wal");
+
+    (modified(db_path), modified(&PathBuf::from(wal)))
+}
+
+/// 读取当前登录账户邮箱（未登录或解析失败时返回 None）
+fn read_current_email(db_path: &Path) -> Option<String> {
+    let state = crate::antigravity::backup::read_agent_state(db_path)
+        .ok()
+        .flatten()?;
+    crate::antigravity::backup::extract_email(state.expose()).ok()
+}
+
+impl AccountWatcher {
+    /// 创建新的账户监听器
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            app_handle,
+            is_running: Arc::new(Mutex::new(false)),
+        }
+    }
+
+    /// 监听是否正在运行
+    pub async fn is_running(&self) -> bool {
+        *self.is_running.lock().await
+    }
+
+    /// 启动监听
+    pub async fn start(&self) {
+        let mut running = self.is_running.lock().await;
+        if *running {
+            return;
+        }
+        *running = true;
+        drop(running);
+
+        info!(target: "account_watcher", "🔧 启动账户变化监听");
+
+        let app_handle = self.app_handle.clone();
+
+        tokio::spawn(async move {
+            let mut ticker = interval(POLL_INTERVAL);
+            let mut last_fingerprint = None;
+            let mut last_email: Option<Option<String>> = None;
+            let mut tick: u32 = 0;
+            let mut last_db_exists: Option<bool> = None;
+
+            loop {
+                ticker.tick().await;
+
+                if tick.is_multiple_of(STATUS_REFRESH_TICKS) {
+                    Self::refresh_tray_status(&app_handle, last_email.clone().flatten()).await;
+                }
+                tick = tick.wrapping_add(1);
+
+                let Ok(db_path) = crate::platform::resolve_antigravity_db_path() else {
+                    continue;
+                };
+
+                // 数据库出现或消失（安装/卸载 Antigravity）时重新检测安装位置
+                let db_exists = db_path.exists();
+                if last_db_exists
+                    .replace(db_exists)
+                    .is_some_and(|previous| previous != db_exists)
+                {
+                    tokio::task::spawn_blocking(crate::platform::refresh_platform_detection);
+                }
+
+                // 文件未修改时跳过读取数据库
+                let fingerprint = db_fingerprint(&db_path);
+                if last_fingerprint.as_ref() == Some(&fingerprint) {
+                    continue;
+                }
+                last_fingerprint = Some(fingerprint);
+
+                // 读取数据库、扫描进程与备份都是阻塞操作，放到阻塞线程池执行
+                let email = match crate::utils::blocking::run_blocking(move || {
+                    Ok(read_current_email(&db_path))
+                })
+                .await
+                {
+                    Ok(email) => email,
+                    Err(e) => {
+                        warn!(target: "account_watcher", error = %e, "读取当前登录账户失败");
+                        continue;
+                    }
+                };
+
+                match last_email.replace(email.clone()) {
+                    // 首次读取记录基线，检查备份一致性并初始化托盘提示
+                    None => {
+                        if let Some(notified) =
+                            crate::health_check::spawn_startup_consistency_check(&app_handle)
+                        {
+                            // 启动提醒已包含备份过期信息，不再重复推送过期提醒
+                            *STALE_NOTIFIED.lock().unwrap_or_else(|e| e.into_inner()) =
+                                Some(notified);
+                        }
+                        crate::system_tray::update_tray_tooltip(
+                            &app_handle,
+                            TooltipUpdate::Account(email.clone()),
+                        );
+                        crate::state_bus::publish(StateUpdate::ActiveAccount(email.clone()));
+                        Self::refresh_tray_status(&app_handle, email).await;
+                    }
+                    Some(previous) if previous == email => continue,
+                    Some(previous) => Self::handle_change(&app_handle, previous, email).await,
+                }
+            }
+        });
+    }
+
+    /// 刷新托盘提示中的 Antigravity 运行状态与当前账户的备份时间
+    async fn refresh_tray_status(app: &AppHandle, email: Option<String>) {
+        let lookup = email.clone();
+        let status = crate::utils::blocking::run_blocking(move || {
+            Ok((
+                crate::platform::is_antigravity_running(),
+                lookup
+                    .as_deref()
+                    .and_then(crate::antigravity::backup::last_backup_time),
+            ))
+        })
+        .await;
+        let (running, last_backup) = match status {
+            Ok(status) => status,
+            Err(e) => {
+                warn!(target: "account_watcher", error = %e, "刷新托盘状态失败");
+                return;
+            }
+        };
+        crate::system_tray::update_tray_tooltip(app, TooltipUpdate::AntigravityRunning(running));
+        crate::state_bus::publish(StateUpdate::AntigravityRunning(running));
+
+        crate::system_tray::update_tray_tooltip(app, TooltipUpdate::LastBackup(last_backup));
+        Self::check_backup_staleness(app, email, last_backup);
+    }
+
+    /// 当前账户超过设置的天数未备份时推送提醒（同一账户只提醒一次）
+    fn check_backup_staleness(
+        app: &AppHandle,
+        email: Option<String>,
+        last_backup: Option<chrono::DateTime<chrono::Local>>,
+    ) {
+        let stale_days = app
+            .state::<crate::app_settings::AppSettingsManager>()
+            .get_settings()
+            .backup_stale_days;
+
+        let stale = stale_days > 0
+            && last_backup.is_none_or(|time| {
+                chrono::Local::now() - time > chrono::Duration::days(i64::from(stale_days))
+            });
+        let stale_email = email.filter(|_| stale);
+
+        let mut notified = STALE_NOTIFIED.lock().unwrap_or_else(|e| e.into_inner());
+        if *notified == stale_email {
+            return;
+        }
+        notified.clone_from(&stale_email);
+        drop(notified);
+
+        let Some(email) = stale_email else {
+            return;
+        };
+        warn!(
+            target: "account_watcher",
+            email = %email,
+            stale_days,
+            "当前账户已超过提醒天数未备份"
+        );
+        let event = BackupStaleEvent {
+            email,
+            last_backup_at: last_backup.map(|time| time.to_rfc3339()),
+            stale_days,
+        };
+        if let Err(e) = app.emit(BACKUP_STALE_EVENT, &event) {
+            error!(target: "account_watcher", "❌ 推送备份过期提醒失败: {}", e);
+        }
+    }
+
+    async fn handle_change(app: &AppHandle, previous_email: Option<String>, email: Option<String>) {
+        info!(
+            target: "account_watcher",
+            previous = ?previous_email,
+            current = ?email,
+            "📢 检测到 Antigravity 登录账户变化"
+        );
+
+        let auto_backup = app
+            .state::<crate::app_settings::AppSettingsManager>()
+            .get_settings()
+            .auto_backup_on_account_change;
+
+        let mut backed_up = false;
+        if auto_backup && email.is_some() {
+            let result = crate::utils::blocking::run_blocking(|| {
+                crate::antigravity::backup::smart_backup_antigravity_account(
+                    crate::antigravity::backup::BackupTrigger::AccountChange,
+                    None,
+                )
+            })
+            .await;
+            match result {
+                Ok(outcome) => {
+                    backed_up = true;
+                    info!(
+                        target: "account_watcher",
+                        email = %outcome.email,
+                        changed = outcome.changed,
+                        "已自动备份新登录账户"
+                    );
+                }
+                Err(e) => warn!(target: "account_watcher", error = %e, "自动备份新账户失败"),
+            }
+        }
+
+        crate::system_tray::update_tray_tooltip(app, TooltipUpdate::Account(email.clone()));
+        crate::state_bus::publish(StateUpdate::ActiveAccount(email.clone()));
+        Self::refresh_tray_status(app, email.clone()).await;
+        crate::platform::jump_list::refresh_jump_list();
+
+        let event = AccountChangedEvent {
+            previous_email,
+            email,
+            backed_up,
+        };
+        if let Err(e) = app.emit(ACCOUNT_CHANGED_EVENT, &event) {
+            error!(target: "account_watcher", "❌ 推送账户变化事件失败: {}", e);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Agent 内部数据库（agent.db）
+//! 窗口状态、账户元数据、配额历史等需要持久化的数据统一保存在配置目录下的 SQLite 数据库中，
+//! 写入走事务，避免 JSON 文件读-改-写时互相覆盖或写到一半崩溃。
+//! 表结构通过按版本号递增的迁移维护（版本号记录在 `PRAGMA user_version`），
+//! 首次升级时会导入旧版本的 JSON 文件
+
+use rusqlite::{Connection, Transaction};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::error::AgentResult;
+
+/// 等待其他连接释放写锁的最长时间
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 单个迁移
+struct Migration {
+    version: u32,
+    description: &'static str,
+    apply: fn(&Transaction) -> AgentResult<()>,
+}
+
+/// 所有迁移（按版本号递增，已发布的迁移不能修改，只能追加）
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "创建窗口状态与账户元数据表",
+        apply: |tx| {
+            tx.execute_batch(
+                "CREATE TABLE window_state (
+                    id INTEGER PRIMARY KEY CHECK (id = 1),
+                    x REAL NOT NULL,
+                    y REAL NOT NULL,
+                    width REAL NOT NULL,
+                    height REAL NOT NULL,
+                    maximized INTEGER NOT NULL
+                );
+                CREATE TABLE account_metadata (
+                    email TEXT PRIMARY KEY,
+                    account_id TEXT UNIQUE,
+                    data TEXT NOT NULL
+                );
+                CREATE TABLE recent_accounts (
+                    email TEXT PRIMARY KEY,
+                    used_at TEXT NOT NULL,
+                    used_ms INTEGER NOT NULL
+                );
+                CREATE INDEX idx_recent_accounts_used ON recent_accounts (used_ms DESC);",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 2,
+        description: "导入旧版本的 JSON 文件",
+        apply: |tx| {
+            crate::window::state_manager::import_legacy_file(tx)?;
+            crate::account_metadata::import_legacy_file(tx)?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 3,
+        description: "记录退出时窗口是否隐藏在托盘中",
+        apply: |tx| {
+            tx.execute_batch(
+                "ALTER TABLE window_state ADD COLUMN hidden_in_tray INTEGER NOT NULL DEFAULT 0;",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 4,
+        description: "窗口状态按窗口 label 分别记录",
+        apply: |tx| {
+            tx.execute_batch(
+                "CREATE TABLE window_states (
+                    label TEXT PRIMARY KEY,
+                    x REAL NOT NULL,
+                    y REAL NOT NULL,
+                    width REAL NOT NULL,
+                    height REAL NOT NULL,
+                    maximized INTEGER NOT NULL,
+                    hidden_in_tray INTEGER NOT NULL DEFAULT 0
+                );
+                INSERT INTO window_states (label, x, y, width, height, maximized, hidden_in_tray)
+                    SELECT 'main', x, y, width, height, maximized, hidden_in_tray FROM window_state;
+                DROP TABLE window_state;",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 5,
+        description: "创建账户配额历史表",
+        apply: |tx| {
+            tx.execute_batch(
+                "CREATE TABLE quota_samples (
+                    email TEXT NOT NULL,
+                    model TEXT NOT NULL,
+                    remaining_fraction REAL NOT NULL,
+                    reset_time TEXT,
+                    recorded_at TEXT NOT NULL,
+                    recorded_ms INTEGER NOT NULL,
+                    PRIMARY KEY (email, model, recorded_ms)
+                );
+                CREATE INDEX idx_quota_samples_recorded ON quota_samples (recorded_ms);",
+            )?;
+            Ok(())
+        },
+    },
+];
+
+/// 共享连接（首次使用时打开并执行迁移）
+static CONNECTION: Mutex<Option<Connection>> = Mutex::new(None);
+
+/// 执行尚未应用的迁移，每个迁移单独一个事务
+fn migrate(conn: &mut Connection) -> AgentResult<()> {
+    let current: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let tx = conn.transaction()?;
+        (migration.apply)(&tx)?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+        tracing::info!(
+            target: "agent_db",
+            version = migration.version,
+            description = migration.description,
+            "数据库迁移完成"
+        );
+    }
+    Ok(())
+}
+
+fn open() -> AgentResult<Connection> {
+    let path = crate::directories::get_agent_db_file();
+    let mut conn = Connection::open(&path)?;
+    // 数据库中包含账户信息，WAL 等附属文件沿用数据库文件的权限
+    if let Err(e) = crate::utils::permissions::restrict_file(&path) {
+        tracing::warn!(target: "agent_db", error = %e, "无法收紧数据库文件权限");
+    }
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    migrate(&mut conn)?;
+    tracing::debug!(target: "agent_db", path = %path.display(), "Agent 数据库已打开");
+    Ok(conn)
+}
+
+/// 把 WAL 中的内容合并回数据库文件（退出前调用；连接尚未打开时跳过）
+pub fn checkpoint() {
+    let guard = CONNECTION.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(conn) = guard.as_ref() else {
+        return;
+    };
+    if let Err(e) = conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE)") {
+        tracing::warn!(target: "agent_db", error = %e, "数据库检查点执行失败");
+    }
+}
+
+/// 在共享连接上执行操作（调用之间串行化）；打开失败时下次调用会重试
+pub fn with_connection<T>(f: impl FnOnce(&mut Connection) -> AgentResult<T>) -> AgentResult<T> {
+    let mut guard = CONNECTION.lock().unwrap_or_else(|e| e.into_inner());
+    let conn = match guard.as_mut() {
+        Some(conn) => conn,
+        None => guard.insert(open()?),
+    };
+    f(conn)
+}
+
+/// 在事务中执行操作，返回错误时回滚
+pub fn transaction<T>(f: impl FnOnce(&Transaction) -> AgentResult<T>) -> AgentResult<T> {
+    with_connection(|conn| {
+        let tx = conn.transaction()?;
+        let value = f(&tx)?;
+        tx.commit()?;
+        Ok(value)
+    })
+}
@@ -0,0 +1,680 @@
+//! Antigravity 账户备份模块
+//! 负责从 Antigravity 状态数据库读取当前登录账户并写入备份文件。
+//! 账户状态含访问令牌，读取后保存在 [`SecretString`]/[`SecretJson`] 中，用完即清零
+
+use base64::Engine;
+use prost::Message;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::antigravity::backup_reader::read_fields;
+use crate::constants::database;
+use crate::error::{AgentError, AgentResult};
+use crate::hooks::HookEvent;
+use crate::operation_lock::{self, LockResource};
+use crate::utils::permissions;
+use crate::utils::secret::{SecretJson, SecretString};
+use crate::utils::time_format;
+use zeroize::Zeroizing;
+
+/// 备份元数据在备份文件中的键名
+pub const BACKUP_META_KEY: &str = "antigravityAgent.meta";
+
+/// 备份触发来源（记录在备份元数据中，可在 [`search_backups`] 中按来源筛选）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupTrigger {
+    /// 用户手动备份
+    Manual,
+    /// 监听数据库文件时检测到 Antigravity 内登录账户变化
+    AccountChange,
+    /// 检测到 Antigravity 版本更新
+    VersionChange,
+    /// 清除登录数据前自动备份
+    BeforeClear,
+    /// 定时任务自动备份
+    Scheduled,
+    /// 切换账户或登录新账户前自动备份
+    BeforeSwitch,
+}
+
+impl BackupTrigger {
+    /// 所有触发来源
+    pub const ALL: [Self; 6] = [
+        Self::Manual,
+        Self::AccountChange,
+        Self::VersionChange,
+        Self::BeforeClear,
+        Self::Scheduled,
+        Self::BeforeSwitch,
+    ];
+
+    /// 是否为自动备份（手动备份以外的来源）
+    pub fn is_automatic(self) -> bool {
+        self != Self::Manual
+    }
+}
+
+/// 备份元数据（与 jetski 状态一同写入备份文件）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupMeta {
+    /// 备份时间（RFC 3339）
+    pub created_at: String,
+    pub trigger: BackupTrigger,
+    /// 备份时已安装的 Antigravity 版本
+    pub antigravity_version: Option<String>,
+    /// 备份来源平台
+    pub platform: String,
+    /// 备注，如「重装系统前」「自动：登录新账户前」
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl BackupMeta {
+    pub fn new(trigger: BackupTrigger) -> Self {
+        Self {
+            created_at: chrono::Local::now().to_rfc3339(),
+            trigger,
+            antigravity_version: crate::antigravity::version::detect_antigravity_version(),
+            platform: std::env::consts::OS.to_string(),
+            note: None,
+        }
+    }
+
+    /// 附加备注（空白备注忽略）
+    pub fn with_note(mut self, note: Option<String>) -> Self {
+        self.note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        self
+    }
+}
+
+/// 备份结果
+#[derive(Debug, Clone)]
+pub struct BackupOutcome {
+    /// 账户邮箱
+    pub email: String,
+    /// 备份文件路径
+    pub path: PathBuf,
+    /// 备份内容是否有变化（内容一致时跳过写入）
+    pub changed: bool,
+}
+
+/// 从状态数据库读取 jetskiStateSync.agentManagerInitState
+pub fn read_agent_state(db_path: &Path) -> AgentResult<Option<SecretString>> {
+    if !db_path.exists() {
+        return Err(AgentError::DatabaseNotFound(db_path.to_path_buf()));
+    }
+
+    let key = crate::antigravity::key_mapping::load().agent_state;
+    let conn = Connection::open(db_path)?;
+    let state = conn
+        .query_row("SELECT value FROM ItemTable WHERE key = ?", [&key], |row| {
+            row.get(0).map(SecretString::new)
+        })
+        .optional()?;
+
+    Ok(state)
+}
+
+/// 从状态数据库读取设置中额外备份的键（数据库中不存在的键跳过），返回 JSON 对象
+pub fn read_extra_values(db_path: &Path) -> AgentResult<SecretJson> {
+    let keys = crate::antigravity::key_mapping::effective_keys();
+    let mut values = serde_json::Map::new();
+    if keys.extra_backup_keys().is_empty() {
+        return Ok(SecretJson::new(values.into()));
+    }
+
+    let conn = Connection::open(db_path)?;
+    for key in keys.extra_backup_keys() {
+        let value: Option<String> = conn
+            .query_row("SELECT value FROM ItemTable WHERE key = ?", [key], |row| {
+                row.get(0)
+            })
+            .optional()?;
+        if let Some(value) = value {
+            values.insert(key.clone(), serde_json::Value::String(value));
+        }
+    }
+    Ok(SecretJson::new(values.into()))
+}
+
+/// 从 jetski 状态中解码账户邮箱
+pub fn extract_email(agent_state: &str) -> AgentResult<String> {
+    let bytes = Zeroizing::new(
+        base64::engine::general_purpose::STANDARD
+            .decode(agent_state.trim())
+            .map_err(|e| {
+                AgentError::Serialization(format!("jetskiStateSync Base64 解码失败: {}", e))
+            })?,
+    );
+    let msg = crate::proto::SessionResponse::decode(bytes.as_slice()).map_err(|e| {
+        AgentError::Serialization(format!("jetskiStateSync Protobuf 解码失败: {}", e))
+    })?;
+
+    msg.context
+        .map(|c| c.email)
+        .filter(|email| !email.is_empty())
+        .ok_or_else(|| {
+            AgentError::NotLoggedIn(
+                "jetskiStateSync 中未找到邮箱字段，无法确定备份文件名".to_string(),
+            )
+        })
+}
+
+/// 读取备份文件中保存的 jetski 状态
+fn read_backup_state(path: &Path) -> Option<SecretString> {
+    let (value, _) = read_fields(path, &[database::AGENT_STATE]).ok()?;
+    SecretJson::new(value)
+        .get(database::AGENT_STATE)
+        .and_then(|v| v.as_str())
+        .map(|s| SecretString::new(s.to_string()))
+}
+
+/// 备份文件中的额外键是否与当前值一致
+fn backup_extras_match(path: &Path, extras: &serde_json::Value) -> bool {
+    let Some(extras) = extras.as_object().filter(|map| !map.is_empty()) else {
+        return true;
+    };
+    let keys: Vec<&str> = extras.keys().map(String::as_str).collect();
+    read_fields(path, &keys).is_ok_and(|(value, _)| {
+        let value = SecretJson::new(value);
+        extras
+            .iter()
+            .all(|(key, expected)| value.get(key) == Some(expected))
+    })
+}
+
+/// 从备份文件中解码账户邮箱
+pub fn read_backup_email(path: &Path) -> Option<String> {
+    read_backup_state(path).and_then(|state| extract_email(state.expose()).ok())
+}
+
+/// 指定账户最近一次成功备份的时间（优先使用账户元数据中的记录，旧数据回退到备份文件）
+pub fn last_backup_time(email: &str) -> Option<chrono::DateTime<chrono::Local>> {
+    crate::account_metadata::get(email)
+        .last_backup_at
+        .and_then(|time| chrono::DateTime::parse_from_rfc3339(&time).ok())
+        .map(|time| time.with_timezone(&chrono::Local))
+        .or_else(|| read_backup_time(&crate::antigravity::backup_naming::find_backup_file(email)?))
+}
+
+/// 在账户元数据中记录备份成功的时间并更新最近使用列表（失败只记录日志，不影响备份结果）
+fn record_backup_time(email: &str) {
+    crate::account_metadata::touch_recent(email);
+    let now = chrono::Local::now().to_rfc3339();
+    if let Err(e) = crate::account_metadata::update(email, |meta| {
+        meta.last_backup_at = Some(now);
+        Ok(())
+    }) {
+        tracing::warn!(target: "backup::smart", error = %e, "记录备份时间失败");
+    }
+}
+
+/// 备份文件的备份时间（优先使用备份元数据，旧备份回退到文件修改时间）
+pub fn read_backup_time(path: &Path) -> Option<chrono::DateTime<chrono::Local>> {
+    let from_meta = read_fields(path, &[BACKUP_META_KEY])
+        .ok()
+        .and_then(|(v, _)| v.get(BACKUP_META_KEY).cloned())
+        .and_then(|meta| serde_json::from_value::<BackupMeta>(meta).ok())
+        .and_then(|meta| chrono::DateTime::parse_from_rfc3339(&meta.created_at).ok())
+        .map(|time| time.with_timezone(&chrono::Local));
+
+    from_meta.or_else(|| {
+        fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(chrono::DateTime::<chrono::Local>::from)
+    })
+}
+
+/// 读取当前登录账户的 jetski 状态及邮箱
+pub fn read_current_account() -> AgentResult<(String, SecretString)> {
+    read_account_at(&crate::platform::resolve_antigravity_db_path()?)
+}
+
+/// 读取指定状态数据库中登录账户的 jetski 状态及邮箱
+pub fn read_account_at(db_path: &Path) -> AgentResult<(String, SecretString)> {
+    let agent_state = read_agent_state(db_path)?.ok_or_else(|| {
+        AgentError::NotLoggedIn("未找到 jetskiStateSync.agentManagerInitState".to_string())
+    })?;
+    let email = extract_email(agent_state.expose())?;
+
+    Ok((email, agent_state))
+}
+
+/// 构建备份文件内容：原始 jetski 状态（不解码）+ 额外备份的键 + 备份元数据
+pub fn build_backup_content(
+    agent_state: &str,
+    extras: &serde_json::Value,
+    meta: &BackupMeta,
+) -> SecretJson {
+    let mut content = SecretJson::new(serde_json::json!({
+        database::AGENT_STATE: agent_state,
+        BACKUP_META_KEY: meta,
+    }));
+    if let (Some(object), Some(extras)) = (content.as_object_mut(), extras.as_object()) {
+        for (key, value) in extras {
+            object.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    content
+}
+
+/// 序列化备份内容（返回的字符串释放时清零）
+pub fn serialize_backup_content(content: &SecretJson) -> AgentResult<Zeroizing<String>> {
+    Ok(Zeroizing::new(serde_json::to_string_pretty(&**content)?))
+}
+
+/// 智能备份当前登录的 Antigravity 账户
+///
+/// 备份文件名见 [`crate::antigravity::backup_naming`]；若已有备份且内容一致则不重复写入
+pub fn smart_backup_antigravity_account(
+    trigger: BackupTrigger,
+    note: Option<String>,
+) -> AgentResult<BackupOutcome> {
+    let _guard =
+        operation_lock::acquire("backup", &[LockResource::StateDb, LockResource::BackupDir])?;
+    let db_path = crate::platform::resolve_antigravity_db_path()?;
+    let (email, agent_state) = read_account_at(&db_path)?;
+    write_account_backup(
+        email,
+        agent_state.expose(),
+        BackupMeta::new(trigger).with_note(note),
+        &db_path,
+    )
+}
+
+/// 备份状态数据库中登录的账户（调用方需持有 StateDb 与 BackupDir 操作锁），未登录时返回 None
+pub fn backup_logged_in_account(
+    db_path: &Path,
+    trigger: BackupTrigger,
+) -> AgentResult<Option<BackupOutcome>> {
+    let (email, agent_state) = match read_account_at(db_path) {
+        Ok(account) => account,
+        Err(AgentError::NotLoggedIn(_) | AgentError::DatabaseNotFound(_)) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    write_account_backup(
+        email,
+        agent_state.expose(),
+        BackupMeta::new(trigger),
+        db_path,
+    )
+    .map(Some)
+}
+
+/// 备份文件元数据中记录的触发来源（旧备份没有元数据时为 None）
+fn read_backup_trigger(path: &Path) -> Option<BackupTrigger> {
+    read_fields(path, &[BACKUP_META_KEY])
+        .ok()
+        .and_then(|(value, _)| value.get(BACKUP_META_KEY).cloned())
+        .and_then(|meta| serde_json::from_value::<BackupMeta>(meta).ok())
+        .map(|meta| meta.trigger)
+}
+
+/// 写入账户备份（调用方需持有操作锁），内容未变化且没有备注时跳过；额外备份的键从 `db_path` 读取
+///
+/// 手动备份内容未变化但原备份来自自动备份时仍然写入，使其标记为手动备份
+fn write_account_backup(
+    email: String,
+    agent_state: &str,
+    meta: BackupMeta,
+    db_path: &Path,
+) -> AgentResult<BackupOutcome> {
+    let trigger = meta.trigger;
+    crate::hooks::run_hooks(
+        HookEvent::PreBackup,
+        serde_json::json!({ "email": email, "trigger": trigger }),
+    )?;
+
+    let accounts_dir = crate::directories::get_accounts_directory();
+    permissions::create_private_dir(&accounts_dir)?;
+    crate::disk_space::ensure_free_space(&accounts_dir, "backup")?;
+    let account_file = crate::antigravity::backup_naming::backup_file_for_write(&email)?;
+    let extras = read_extra_values(db_path)?;
+
+    if meta.note.is_none()
+        && (trigger.is_automatic() || read_backup_trigger(&account_file) == Some(trigger))
+        && read_backup_state(&account_file)
+            .as_ref()
+            .map(SecretString::expose)
+            == Some(agent_state)
+        && backup_extras_match(&account_file, &extras)
+    {
+        tracing::debug!(target: "backup::smart", file = %account_file.display(), "备份内容未变化，跳过写入");
+        record_backup_time(&email);
+        return Ok(BackupOutcome {
+            email,
+            path: account_file,
+            changed: false,
+        });
+    }
+
+    let content = build_backup_content(agent_state, &extras, &meta);
+    permissions::write_private(
+        &account_file,
+        serialize_backup_content(&content)?.as_bytes(),
+    )?;
+    record_backup_time(&email);
+
+    tracing::info!(
+        target: "backup::smart",
+        file = %account_file.display(),
+        trigger = ?trigger,
+        "✅ 账户备份完成"
+    );
+
+    crate::hooks::run_hooks(
+        HookEvent::PostBackup,
+        serde_json::json!({
+            "email": email,
+            "trigger": trigger,
+            "file": account_file.to_string_lossy(),
+        }),
+    )?;
+
+    Ok(BackupOutcome {
+        email,
+        path: account_file,
+        changed: true,
+    })
+}
+
+/// 批量备份中单个账户的处理状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountBackupStatus {
+    /// 已写入新的备份
+    Refreshed,
+    /// 备份内容与当前登录状态一致
+    Unchanged,
+    /// 该账户当前未在 Antigravity 中登录，保留原备份
+    NotLoggedIn,
+    Failed,
+}
+
+/// 批量备份中单个账户的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBackupResult {
+    pub email: String,
+    pub status: AccountBackupStatus,
+    pub file: String,
+    pub error: Option<AgentError>,
+}
+
+/// 遍历所有已备份的账户，为当前登录的账户重新生成备份，并返回每个账户的处理结果
+///
+/// state.vscdb 同一时间只保存一个登录账户，其余账户的备份保持不变（状态为 `NotLoggedIn`）
+pub fn backup_all_known_accounts() -> AgentResult<Vec<AccountBackupResult>> {
+    let _guard = operation_lock::acquire(
+        "backup_all",
+        &[LockResource::StateDb, LockResource::BackupDir],
+    )?;
+
+    let db_path = crate::platform::resolve_antigravity_db_path()?;
+    let current = match read_account_at(&db_path) {
+        Ok(account) => Some(account),
+        Err(AgentError::NotLoggedIn(_)) => None,
+        Err(e) => return Err(e),
+    };
+
+    let accounts_dir = crate::directories::get_accounts_directory();
+    let known = if accounts_dir.exists() {
+        list_backups()?
+    } else {
+        Vec::new()
+    };
+
+    let results: Vec<AccountBackupResult> = known
+        .iter()
+        .filter_map(|item| {
+            let email = item["email"].as_str()?.to_string();
+            let file = item["file"].as_str().unwrap_or_default().to_string();
+
+            let (status, error) = match &current {
+                Some((current_email, agent_state)) if *current_email == email => {
+                    match write_account_backup(
+                        email.clone(),
+                        agent_state.expose(),
+                        BackupMeta::new(BackupTrigger::Manual),
+                        &db_path,
+                    ) {
+                        Ok(outcome) if outcome.changed => (AccountBackupStatus::Refreshed, None),
+                        Ok(_) => (AccountBackupStatus::Unchanged, None),
+                        Err(e) => (AccountBackupStatus::Failed, Some(e)),
+                    }
+                }
+                _ => (AccountBackupStatus::NotLoggedIn, None),
+            };
+
+            Some(AccountBackupResult {
+                email,
+                status,
+                file,
+                error,
+            })
+        })
+        .collect();
+
+    tracing::info!(
+        target: "backup::batch",
+        total = results.len(),
+        refreshed = results
+            .iter()
+            .filter(|r| r.status == AccountBackupStatus::Refreshed)
+            .count(),
+        failed = results
+            .iter()
+            .filter(|r| r.status == AccountBackupStatus::Failed)
+            .count(),
+        "批量备份完成"
+    );
+
+    Ok(results)
+}
+
+/// 距离过期不足该时长时视为即将过期
+const TOKEN_EXPIRING_SOON: chrono::Duration = chrono::Duration::hours(24);
+
+/// 备份中访问令牌的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenStatus {
+    Valid,
+    /// 24 小时内过期
+    ExpiringSoon,
+    /// 已过期，恢复后需要在 Antigravity 中重新登录
+    Expired,
+    /// 备份中没有可解析的过期时间
+    Unknown,
+}
+
+/// 访问令牌检查结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenValidation {
+    pub status: TokenStatus,
+    pub expires_at: Option<String>,
+}
+
+/// 检查 jetski 状态中访问令牌的过期时间
+///
+/// 备份只保存访问令牌与 ID 令牌，没有刷新令牌，因此无法在本地刷新，只能提示用户
+pub fn validate_token(agent_state: &str) -> TokenValidation {
+    let expiry = base64::engine::general_purpose::STANDARD
+        .decode(agent_state.trim())
+        .ok()
+        .and_then(|bytes| crate::proto::SessionResponse::decode(bytes.as_slice()).ok())
+        .and_then(|msg| msg.auth)
+        .and_then(|auth| auth.meta)
+        .map(|meta| meta.expiry_timestamp)
+        .filter(|ts| *ts > 0)
+        .and_then(|ts| {
+            // 兼容毫秒时间戳
+            if ts > 10_000_000_000 {
+                chrono::DateTime::from_timestamp_millis(ts)
+            } else {
+                chrono::DateTime::from_timestamp(ts, 0)
+            }
+        });
+
+    let Some(expiry) = expiry else {
+        return TokenValidation {
+            status: TokenStatus::Unknown,
+            expires_at: None,
+        };
+    };
+
+    let now = chrono::Utc::now();
+    let status = if expiry <= now {
+        TokenStatus::Expired
+    } else if expiry - now <= TOKEN_EXPIRING_SOON {
+        TokenStatus::ExpiringSoon
+    } else {
+        TokenStatus::Valid
+    };
+
+    TokenValidation {
+        status,
+        expires_at: Some(expiry.with_timezone(&chrono::Local).to_rfc3339()),
+    }
+}
+
+/// 所有已备份账户的邮箱
+pub fn list_backup_emails() -> Vec<String> {
+    list_backups()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| item["email"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// 列出所有账户备份（邮箱、文件路径、修改时间、最近备份时间、备份元数据及令牌状态），按邮箱排序
+pub fn list_backups() -> AgentResult<Vec<serde_json::Value>> {
+    let accounts_dir = crate::directories::get_accounts_directory();
+    let account_metadata = crate::account_metadata::all();
+    let mut items = Vec::new();
+
+    for entry in fs::read_dir(&accounts_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+
+        let modified = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(chrono::DateTime::<chrono::Local>::from);
+        let modified_at = modified.map(|t| t.to_rfc3339());
+        let content = read_fields(&path, &[database::AGENT_STATE, BACKUP_META_KEY])
+            .ok()
+            .map(|(value, _)| SecretJson::new(value));
+        let meta = content
+            .as_ref()
+            .and_then(|v| v.get(BACKUP_META_KEY).cloned());
+        let agent_state = content
+            .as_ref()
+            .and_then(|v| v.get(database::AGENT_STATE))
+            .and_then(|v| v.as_str());
+        // 文件名可能按模板生成，邮箱以备份内容为准，无法解码时回退到文件名
+        let email = agent_state
+            .and_then(|state| extract_email(state).ok())
+            .or_else(|| path.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_default();
+        let token = agent_state.map(validate_token).unwrap_or(TokenValidation {
+            status: TokenStatus::Unknown,
+            expires_at: None,
+        });
+
+        // 旧数据没有记录时回退到备份元数据中的创建时间或文件修改时间
+        let last_backup_at = account_metadata
+            .get(&email)
+            .and_then(|m| m.last_backup_at.clone())
+            .or_else(|| {
+                meta.as_ref()
+                    .and_then(|m| m.get("created_at"))
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            })
+            .or_else(|| modified_at.clone());
+
+        items.push(serde_json::json!({
+            "email": email,
+            "file": path.to_string_lossy(),
+            "modifiedAt": modified_at,
+            "lastBackupAt": last_backup_at,
+            "displayTimes": {
+                "modifiedAt": modified.map(|t| time_format::format_time(&t)),
+                "lastBackupAt": last_backup_at.as_deref().and_then(time_format::format_rfc3339),
+            },
+            "meta": meta,
+            "token": token,
+        }));
+    }
+
+    items.sort_by(|a, b| a["email"].as_str().cmp(&b["email"].as_str()));
+    Ok(items)
+}
+
+/// 备份搜索条件（各条件同时满足）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BackupSearchQuery {
+    /// 匹配邮箱、别名、备注或文件名（不区分大小写）
+    pub text: Option<String>,
+    /// 只返回这些来源的备份（为空时不限）
+    pub triggers: Vec<BackupTrigger>,
+    /// true 只返回自动备份，false 只返回手动备份（旧备份没有来源记录，按手动备份处理）
+    pub automatic: Option<bool>,
+}
+
+/// 按条件筛选账户备份（结果格式与 [`list_backups`] 相同）
+pub fn search_backups(query: &BackupSearchQuery) -> AgentResult<Vec<serde_json::Value>> {
+    let text = query
+        .text
+        .as_deref()
+        .map(|text| text.trim().to_lowercase())
+        .filter(|text| !text.is_empty());
+    let aliases = crate::account_metadata::all();
+
+    let items = list_backups()?
+        .into_iter()
+        .filter(|item| {
+            let trigger = item["meta"]
+                .get("trigger")
+                .and_then(|trigger| serde_json::from_value::<BackupTrigger>(trigger.clone()).ok());
+            if !query.triggers.is_empty()
+                && !trigger.is_some_and(|trigger| query.triggers.contains(&trigger))
+            {
+                return false;
+            }
+            if let Some(automatic) = query.automatic {
+                if trigger.is_some_and(BackupTrigger::is_automatic) != automatic {
+                    return false;
+                }
+            }
+            let Some(text) = &text else {
+                return true;
+            };
+            let email = item["email"].as_str().unwrap_or_default();
+            let alias = aliases.get(email).and_then(|meta| meta.alias.as_deref());
+            let file_name = Path::new(item["file"].as_str().unwrap_or_default())
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string());
+            let fields = [
+                Some(email),
+                alias,
+                item["meta"].get("note").and_then(|note| note.as_str()),
+                file_name.as_deref(),
+            ];
+            fields
+                .iter()
+                .flatten()
+                .any(|field| field.to_lowercase().contains(text.as_str()))
+        })
+        .collect();
+    Ok(items)
+}
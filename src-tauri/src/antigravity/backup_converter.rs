@@ -0,0 +1,207 @@
+//! 旧版本与其他工具备份的格式转换
+//! 导入账户时先识别备份文件的格式，再转换为当前的备份结构（登录状态 + 额外备份的键 + 备份元数据）。
+//! 每种格式是 [`FORMATS`] 中的一项（识别函数 + 转换函数），支持新格式时在列表中追加即可；
+//! 无法对应到当前结构的字段不会写入，在导入结果中列出
+
+use serde_json::{Map, Value};
+
+use crate::antigravity::backup::{self, BackupMeta, BackupTrigger, BACKUP_META_KEY};
+use crate::constants::database;
+use crate::error::{AgentError, AgentResult};
+use crate::utils::secret::SecretJson;
+
+/// 其他工具中保存登录状态的字段名
+const FOREIGN_STATE_FIELDS: [&str; 4] = [
+    "agentManagerInitState",
+    "jetskiState",
+    "agentState",
+    "state",
+];
+
+/// 其他工具中与登录状态一起保存、可以忽略的描述字段
+const FOREIGN_DESCRIPTIVE_FIELDS: [&str; 3] = ["email", "name", "createdAt"];
+
+/// 转换后的备份内容与未能对应的字段
+type Converted = (Map<String, Value>, Vec<String>);
+
+/// 单个备份格式
+struct BackupFormat {
+    /// 格式标识（导入结果中返回）
+    name: &'static str,
+    detect: fn(&Value) -> bool,
+    /// 转换为当前备份结构，返回未能对应的字段
+    convert: fn(&Value) -> AgentResult<Converted>,
+}
+
+/// 所有支持的格式（按顺序识别，先匹配的优先）
+const FORMATS: &[BackupFormat] = &[
+    BackupFormat {
+        name: "current",
+        detect: |value| has_agent_state(value) && value.get(BACKUP_META_KEY).is_some(),
+        convert: |value| Ok((value.as_object().cloned().unwrap_or_default(), Vec::new())),
+    },
+    BackupFormat {
+        name: "legacy_agent",
+        detect: |value| has_agent_state(value) && value.get(BACKUP_META_KEY).is_none(),
+        convert: convert_legacy_agent,
+    },
+    BackupFormat {
+        name: "item_table_rows",
+        detect: |value| {
+            value.as_array().is_some_and(|rows| {
+                rows.iter().any(|row| {
+                    row.get("key").and_then(Value::as_str) == Some(database::AGENT_STATE)
+                })
+            })
+        },
+        convert: convert_item_table_rows,
+    },
+    BackupFormat {
+        name: "wrapped_state",
+        detect: |value| foreign_state_field(value).is_some(),
+        convert: convert_wrapped_state,
+    },
+];
+
+/// 支持导入的格式标识
+pub fn format_names() -> Vec<&'static str> {
+    FORMATS.iter().map(|format| format.name).collect()
+}
+
+/// 转换结果
+#[derive(Debug)]
+pub struct BackupConversion {
+    /// 识别出的格式
+    pub format: &'static str,
+    /// 未能对应到当前结构、没有写入的字段
+    pub unmapped_fields: Vec<String>,
+    pub content: SecretJson,
+}
+
+impl BackupConversion {
+    /// 是否为当前格式（无需转换）
+    pub fn is_native(&self) -> bool {
+        self.format == "current"
+    }
+}
+
+fn has_agent_state(value: &Value) -> bool {
+    value
+        .get(database::AGENT_STATE)
+        .is_some_and(Value::is_string)
+}
+
+fn foreign_state_field(value: &Value) -> Option<&'static str> {
+    let object = value.as_object()?;
+    FOREIGN_STATE_FIELDS
+        .into_iter()
+        .find(|field| object.get(*field).is_some_and(Value::is_string))
+}
+
+/// 额外备份的键可以保留，其余字段视为无法对应
+fn split_extras(
+    fields: impl Iterator<Item = (String, Value)>,
+) -> (Map<String, Value>, Vec<String>) {
+    let keys = crate::antigravity::key_mapping::effective_keys();
+    let mut extras = Map::new();
+    let mut unmapped = Vec::new();
+    for (key, value) in fields {
+        if value.is_string() && keys.extra_backup_keys().contains(&key) {
+            extras.insert(key, value);
+        } else {
+            unmapped.push(key);
+        }
+    }
+    (extras, unmapped)
+}
+
+/// 旧版本 Agent：只有登录状态，没有备份元数据
+fn convert_legacy_agent(value: &Value) -> AgentResult<Converted> {
+    let object = value.as_object().cloned().unwrap_or_default();
+    let (mut content, unmapped) = split_extras(
+        object
+            .into_iter()
+            .filter(|(key, _)| key != database::AGENT_STATE),
+    );
+    content.insert(
+        database::AGENT_STATE.to_string(),
+        value[database::AGENT_STATE].clone(),
+    );
+    Ok((content, unmapped))
+}
+
+/// 从 state.vscdb 的 ItemTable 导出的 `[{ "key": ..., "value": ... }]` 行
+fn convert_item_table_rows(value: &Value) -> AgentResult<Converted> {
+    let rows = value.as_array().cloned().unwrap_or_default();
+    let mut state = None;
+    let mut others = Vec::new();
+    let mut malformed = 0;
+    for row in rows {
+        match (
+            row.get("key").and_then(Value::as_str),
+            row.get("value").filter(|v| v.is_string()),
+        ) {
+            (Some(database::AGENT_STATE), Some(value)) => state = Some(value.clone()),
+            (Some(key), Some(value)) => others.push((key.to_string(), value.clone())),
+            _ => malformed += 1,
+        }
+    }
+    let state = state.ok_or_else(|| {
+        AgentError::Serialization("导出的数据库行中登录状态不是字符串".to_string())
+    })?;
+    let (mut content, mut unmapped) = split_extras(others.into_iter());
+    if malformed > 0 {
+        unmapped.push(format!("{malformed} 个格式不正确的行"));
+    }
+    content.insert(database::AGENT_STATE.to_string(), state);
+    Ok((content, unmapped))
+}
+
+/// 其他工具：登录状态保存在 `state` 等字段中，可能附带邮箱等描述字段
+fn convert_wrapped_state(value: &Value) -> AgentResult<Converted> {
+    let field = foreign_state_field(value)
+        .ok_or_else(|| AgentError::Serialization("未找到登录状态字段".to_string()))?;
+    let object = value.as_object().cloned().unwrap_or_default();
+    let (mut content, unmapped) =
+        split_extras(object.into_iter().filter(|(key, _)| {
+            key != field && !FOREIGN_DESCRIPTIVE_FIELDS.contains(&key.as_str())
+        }));
+    content.insert(database::AGENT_STATE.to_string(), value[field].clone());
+    Ok((content, unmapped))
+}
+
+/// 识别备份格式并转换为当前结构
+///
+/// 转换后的登录状态必须能解码出邮箱；非当前格式的备份补充备份元数据并在备注中记录来源格式
+pub fn convert(value: &Value) -> AgentResult<BackupConversion> {
+    let format = FORMATS
+        .iter()
+        .find(|format| (format.detect)(value))
+        .ok_or_else(|| AgentError::Serialization("无法识别的备份格式".to_string()))?;
+    let (mut content, mut unmapped_fields) = (format.convert)(value)?;
+    unmapped_fields.sort();
+
+    let state = content
+        .get(database::AGENT_STATE)
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    backup::extract_email(state)?;
+
+    if format.name != "current" {
+        let meta = BackupMeta::new(BackupTrigger::Manual)
+            .with_note(Some(format!("从 {} 格式导入", format.name)));
+        content.insert(BACKUP_META_KEY.to_string(), serde_json::to_value(meta)?);
+        tracing::info!(
+            target: "backup::convert",
+            format = format.name,
+            unmapped = unmapped_fields.len(),
+            "已转换备份格式"
+        );
+    }
+
+    Ok(BackupConversion {
+        format: format.name,
+        unmapped_fields,
+        content: SecretJson::new(Value::Object(content)),
+    })
+}
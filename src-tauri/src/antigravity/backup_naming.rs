@@ -0,0 +1,280 @@
+//! 账户备份文件命名
+//! 备份文件名默认是账户邮箱（`{email}.json`），可在设置中改为模板（如 `{alias}-{date}`、
+//! 用 `{hash}` 避免在文件名中暴露邮箱）。实际文件名记录在账户元数据中，
+//! 查找时依次使用元数据记录、`{email}.json`、按备份内容中的邮箱扫描目录
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{AgentError, AgentResult};
+use crate::operation_lock::{self, LockResource};
+
+/// 默认模板（与旧版本的文件名一致）
+pub const DEFAULT_TEMPLATE: &str = "{email}";
+
+/// 模板支持的占位符
+const PLACEHOLDERS: [&str; 5] = ["{email}", "{alias}", "{date}", "{hash}", "{id}"];
+
+/// 校验文件名模板：非空且只包含已知占位符
+pub fn validate_template(template: &str) -> AgentResult<()> {
+    if template.trim().is_empty() {
+        return Err(AgentError::InvalidInput("文件名模板不能为空".to_string()));
+    }
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}').ok_or_else(|| {
+            AgentError::InvalidInput(format!("文件名模板中的占位符未闭合: {template}"))
+        })?;
+        let placeholder = &rest[start..start + end + 1];
+        if !PLACEHOLDERS.contains(&placeholder) {
+            return Err(AgentError::InvalidInput(format!(
+                "未知的占位符 {placeholder}，可用: {}",
+                PLACEHOLDERS.join(" ")
+            )));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    Ok(())
+}
+
+/// 邮箱的稳定哈希（FNV-1a 64 位，不区分大小写），用于不暴露邮箱的文件名
+fn email_hash(email: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in email.trim().to_lowercase().bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+/// 替换文件名中不可用的字符
+fn sanitize_file_stem(stem: &str) -> String {
+    let cleaned: String = stem
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    cleaned
+        .trim_matches(|c: char| c == '.' || c.is_whitespace())
+        .to_string()
+}
+
+/// 按模板生成文件名（不含扩展名）
+fn render(template: &str, email: &str, date: chrono::DateTime<chrono::Local>) -> String {
+    // 只有用到别名或账户 ID 时才需要读取（并可能分配）账户身份
+    let identity = if template.contains("{alias}") || template.contains("{id}") {
+        crate::account_metadata::ensure_identities(&[email.to_string()])
+            .ok()
+            .and_then(|mut ids| ids.pop())
+    } else {
+        None
+    };
+    let alias = identity
+        .as_ref()
+        .and_then(|i| i.alias.clone())
+        .unwrap_or_else(|| email.split('@').next().unwrap_or(email).to_string());
+    let id = identity.map(|i| i.id).unwrap_or_else(|| email_hash(email));
+
+    let stem = sanitize_file_stem(
+        &template
+            .replace("{email}", email)
+            .replace("{alias}", &alias)
+            .replace("{date}", &date.format("%Y%m%d").to_string())
+            .replace("{hash}", &email_hash(email))
+            .replace("{id}", &id),
+    );
+    if stem.is_empty() {
+        email_hash(email)
+    } else {
+        stem
+    }
+}
+
+/// 在目录中选取不冲突的文件名（`name.json`、`name-2.json`……），`own` 为账户当前的文件
+fn unique_file_name(dir: &Path, stem: &str, own: Option<&Path>) -> String {
+    let mut candidate = format!("{stem}.json");
+    let mut index = 2;
+    loop {
+        let path = dir.join(&candidate);
+        if !path.exists() || own == Some(path.as_path()) {
+            return candidate;
+        }
+        candidate = format!("{stem}-{index}.json");
+        index += 1;
+    }
+}
+
+fn current_template() -> String {
+    let settings =
+        crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file());
+    let template = settings.backup_file_name_template;
+    if validate_template(&template).is_ok() {
+        template
+    } else {
+        DEFAULT_TEMPLATE.to_string()
+    }
+}
+
+/// 查找账户已有的备份文件
+pub fn find_backup_file(email: &str) -> Option<PathBuf> {
+    let dir = crate::directories::get_accounts_directory();
+
+    if let Some(name) = crate::account_metadata::get(email).backup_file {
+        let path = dir.join(name);
+        if path.is_file() {
+            return Some(path);
+        }
+    }
+
+    let legacy = dir.join(format!("{email}.json"));
+    if legacy.is_file() {
+        return Some(legacy);
+    }
+
+    // 导入或手动复制的备份可能使用其他文件名，按内容中的邮箱查找
+    fs::read_dir(&dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .find(|path| crate::antigravity::backup::read_backup_email(path).as_deref() == Some(email))
+}
+
+/// 查找账户的备份文件，不存在时返回 `BackupNotFound`
+pub fn require_backup_file(email: &str) -> AgentResult<PathBuf> {
+    find_backup_file(email).ok_or_else(|| AgentError::BackupNotFound(email.to_string()))
+}
+
+/// 写入备份时使用的文件：沿用元数据记录或已有文件，否则按模板生成并记录到元数据
+pub fn backup_file_for_write(email: &str) -> AgentResult<PathBuf> {
+    let dir = crate::directories::get_accounts_directory();
+
+    if let Some(name) = crate::account_metadata::get(email).backup_file {
+        return Ok(dir.join(name));
+    }
+    if let Some(existing) = find_backup_file(email) {
+        return Ok(existing);
+    }
+
+    let stem = render(&current_template(), email, chrono::Local::now());
+    let name = unique_file_name(&dir, &stem, None);
+    crate::account_metadata::update(email, |meta| {
+        meta.backup_file = Some(name.clone());
+        Ok(())
+    })?;
+    Ok(dir.join(name))
+}
+
+/// 重命名的处理状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenameStatus {
+    Renamed,
+    Unchanged,
+    Failed,
+}
+
+/// 单个备份文件的重命名结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupRenameResult {
+    /// 备份中的账户邮箱（无法识别时为 None）
+    pub email: Option<String>,
+    pub from: String,
+    pub to: Option<String>,
+    pub status: RenameStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 备份的创建时间（优先使用备份元数据，旧备份回退到文件修改时间）
+fn backup_created_at(path: &Path) -> chrono::DateTime<chrono::Local> {
+    crate::antigravity::backup::read_backup_time(path).unwrap_or_else(chrono::Local::now)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// 按当前模板重命名所有已有备份，并更新账户元数据中记录的文件名
+pub fn migrate_file_names() -> AgentResult<Vec<BackupRenameResult>> {
+    let _guard = operation_lock::acquire("rename_backups", &[LockResource::BackupDir])?;
+
+    let template = current_template();
+    let dir = crate::directories::get_accounts_directory();
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut results = Vec::new();
+    for path in paths {
+        let from = file_name(&path);
+        let Some(email) = crate::antigravity::backup::read_backup_email(&path) else {
+            results.push(BackupRenameResult {
+                email: None,
+                from,
+                to: None,
+                status: RenameStatus::Failed,
+                error: Some("无法从备份中识别账户邮箱".to_string()),
+            });
+            continue;
+        };
+
+        let stem = render(&template, &email, backup_created_at(&path));
+        let to = unique_file_name(&dir, &stem, Some(&path));
+        let renamed = if to == from {
+            Ok(RenameStatus::Unchanged)
+        } else {
+            fs::rename(&path, dir.join(&to)).map(|_| RenameStatus::Renamed)
+        };
+        let result = renamed.map_err(AgentError::from).and_then(|status| {
+            crate::account_metadata::update(&email, |meta| {
+                meta.backup_file = Some(to.clone());
+                Ok(())
+            })?;
+            Ok(status)
+        });
+
+        results.push(match result {
+            Ok(status) => BackupRenameResult {
+                email: Some(email),
+                from,
+                to: Some(to),
+                status,
+                error: None,
+            },
+            Err(e) => {
+                tracing::warn!(target: "backup::naming", error = %e, "重命名备份文件失败");
+                BackupRenameResult {
+                    email: Some(email),
+                    from,
+                    to: Some(to),
+                    status: RenameStatus::Failed,
+                    error: Some(e.to_string()),
+                }
+            }
+        });
+    }
+
+    let renamed = results
+        .iter()
+        .filter(|r| r.status == RenameStatus::Renamed)
+        .count();
+    tracing::info!(target: "backup::naming", total = results.len(), renamed, "✅ 备份文件重命名完成");
+    crate::audit::record(
+        "backup",
+        "rename_files",
+        results.iter().all(|r| r.status != RenameStatus::Failed),
+        serde_json::json!({ "template": template, "total": results.len(), "renamed": renamed }),
+    );
+    Ok(results)
+}
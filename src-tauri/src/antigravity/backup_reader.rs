@@ -0,0 +1,172 @@
+//! 备份文件的流式读取
+//! 备份中可能附带工作区等体积很大的数据（数百 MB），整体读入再解析容易在内存较小的机器上耗尽内存。
+//! 这里按 JSON 流逐个字段解析顶层对象，只保留需要的字段，其余字段直接跳过不占用内存；
+//! 文件超过上限时不解析直接拒绝；读取保留字段时按已读取的字节数计量，超过上限立即中止，
+//! 不会先把过大的字段整个读入内存
+
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::cell::Cell;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+use crate::error::{AgentError, AgentResult};
+
+/// 单个保留字段的大小上限（登录状态通常只有几 KB）
+const MAX_FIELD_BYTES: u64 = 32 * 1024 * 1024;
+
+/// 备份文件的大小上限（包含跳过的字段）
+const MAX_FILE_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// 读取计量：解析器已读取的字节数，以及当前保留字段允许读到的位置
+#[derive(Default)]
+struct ReadBudget {
+    consumed: Cell<u64>,
+    limit: Cell<Option<u64>>,
+    exceeded: Cell<bool>,
+}
+
+/// 计量读取的字节数，超过当前保留字段的上限时返回错误
+struct BudgetReader<'a, R> {
+    inner: R,
+    budget: &'a ReadBudget,
+}
+
+impl<R: Read> Read for BudgetReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        let consumed = self.budget.consumed.get() + n as u64;
+        self.budget.consumed.set(consumed);
+        if self
+            .budget
+            .limit
+            .get()
+            .is_some_and(|limit| consumed > limit)
+        {
+            self.budget.exceeded.set(true);
+            return Err(io::Error::other("备份字段超过大小上限"));
+        }
+        Ok(n)
+    }
+}
+
+/// 读取统计
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupReadStats {
+    /// 备份文件大小
+    pub file_bytes: u64,
+    /// 保留在内存中的字段大小（近似值）
+    pub retained_bytes: u64,
+    /// 跳过的字段数量
+    pub skipped_keys: usize,
+}
+
+/// 只保留指定字段的顶层对象解析器
+struct SelectFields<'a> {
+    keys: &'a [&'a str],
+    skipped: &'a mut usize,
+    budget: &'a ReadBudget,
+}
+
+impl<'de> DeserializeSeed<'de> for SelectFields<'_> {
+    type Value = Map<String, Value>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SelectFields<'_> {
+    type Value = Map<String, Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("备份 JSON 对象")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut fields = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if self.keys.contains(&key.as_str()) {
+                let start = self.budget.consumed.get();
+                self.budget.limit.set(Some(start + MAX_FIELD_BYTES));
+                let value: Value = map.next_value()?;
+                self.budget.limit.set(None);
+                fields.insert(key, value);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+                *self.skipped += 1;
+            }
+        }
+        Ok(fields)
+    }
+}
+
+/// 字段内容占用的近似字节数
+fn value_bytes(value: &Value) -> u64 {
+    match value {
+        Value::String(s) => s.len() as u64,
+        other => serde_json::to_vec(other).map_or(0, |v| v.len() as u64),
+    }
+}
+
+/// 流式读取备份文件，只返回 `keys` 中列出的顶层字段
+pub fn read_fields(path: &Path, keys: &[&str]) -> AgentResult<(Value, BackupReadStats)> {
+    let file = File::open(path)?;
+    let file_bytes = file.metadata()?.len();
+    if file_bytes > MAX_FILE_BYTES {
+        return Err(AgentError::InvalidBackup(format!(
+            "备份文件过大（{} MB），已拒绝读取",
+            file_bytes / 1024 / 1024
+        )));
+    }
+
+    let budget = ReadBudget::default();
+    let reader = BudgetReader {
+        inner: BufReader::new(file),
+        budget: &budget,
+    };
+
+    let mut skipped_keys = 0;
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let invalid = |e: serde_json::Error| {
+        if budget.exceeded.get() {
+            AgentError::InvalidBackup(format!(
+                "备份字段超过 {} MB，已拒绝读取",
+                MAX_FIELD_BYTES / 1024 / 1024
+            ))
+        } else {
+            AgentError::InvalidBackup(e.to_string())
+        }
+    };
+    let fields = SelectFields {
+        keys,
+        skipped: &mut skipped_keys,
+        budget: &budget,
+    }
+    .deserialize(&mut deserializer)
+    .map_err(invalid)?;
+    deserializer.end().map_err(invalid)?;
+
+    let retained_bytes = fields.values().map(value_bytes).sum();
+
+    let stats = BackupReadStats {
+        file_bytes,
+        retained_bytes,
+        skipped_keys,
+    };
+    tracing::debug!(
+        target: "backup::reader",
+        file_bytes = stats.file_bytes,
+        retained_bytes = stats.retained_bytes,
+        skipped_keys = stats.skipped_keys,
+        "已流式读取备份文件"
+    );
+    Ok((Value::Object(fields), stats))
+}
@@ -0,0 +1,237 @@
+//! 备份文件结构说明
+//! 以机器可读的形式描述账户备份文件：包含哪些键、各个标记键的含义、备份元数据的字段与结构版本，
+//! 并附带 JSON Schema，前端工具与第三方脚本可以据此校验或生成兼容的备份文件，无需阅读源码
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::antigravity::backup::{BackupTrigger, BACKUP_META_KEY};
+use crate::antigravity::{backup_converter, key_mapping};
+use crate::constants::database;
+
+/// 备份文件结构版本（结构不兼容地变化时递增）
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// 键在备份与恢复中的作用
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyRole {
+    /// 账户登录状态，备份文件中必需
+    AgentState,
+    /// 备份元数据
+    Meta,
+    /// 设置中额外备份的键，恢复时原样写回
+    Extra,
+    /// 不在备份文件中，恢复时从数据库删除以便 Antigravity 重新生成
+    RemovedOnRestore,
+    /// 不在备份文件中，清除登录数据后设为 true 以跳过首次启动引导
+    Onboarding,
+    /// 设备标识与存储标记，不允许出现在备份中
+    Denied,
+}
+
+/// 单个键的说明
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaKey {
+    /// 键名（以 `*` 结尾时按前缀匹配）
+    pub key: String,
+    pub role: KeyRole,
+    /// 备份文件中是否必需
+    pub required: bool,
+    pub description: &'static str,
+}
+
+/// 备份元数据字段的说明
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaField {
+    pub name: &'static str,
+    /// JSON 类型，可为空时为 `["string", "null"]`
+    #[serde(rename = "type")]
+    pub json_type: Value,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+/// 备份文件结构
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSchema {
+    pub format_version: u32,
+    /// 备份与恢复涉及的键（额外备份的键取决于当前设置）
+    pub keys: Vec<SchemaKey>,
+    /// 备份元数据（`antigravityAgent.meta`）的字段
+    pub meta_fields: Vec<SchemaField>,
+    /// 备份元数据中 `trigger` 的取值
+    pub triggers: Vec<BackupTrigger>,
+    /// 导入时能识别的格式（`current` 为当前结构，其余在导入时转换）
+    pub import_formats: Vec<&'static str>,
+    /// 当前结构的 JSON Schema（draft 2020-12）
+    pub json_schema: Value,
+}
+
+/// 备份元数据的字段（与 [`crate::antigravity::backup::BackupMeta`] 对应）
+fn meta_fields() -> Vec<SchemaField> {
+    vec![
+        SchemaField {
+            name: "created_at",
+            json_type: json!("string"),
+            required: true,
+            description: "备份时间（RFC 3339）",
+        },
+        SchemaField {
+            name: "trigger",
+            json_type: json!("string"),
+            required: true,
+            description: "备份触发来源，取值见 triggers",
+        },
+        SchemaField {
+            name: "antigravity_version",
+            json_type: json!(["string", "null"]),
+            required: false,
+            description: "备份时已安装的 Antigravity 版本",
+        },
+        SchemaField {
+            name: "platform",
+            json_type: json!("string"),
+            required: true,
+            description: "备份来源平台（windows、macos、linux），恢复到其他平台时据此转换路径",
+        },
+        SchemaField {
+            name: "note",
+            json_type: json!("string"),
+            required: false,
+            description: "备注，没有备注时省略",
+        },
+    ]
+}
+
+/// 备份与恢复涉及的键
+fn schema_keys() -> Vec<SchemaKey> {
+    let effective = key_mapping::effective_keys();
+    let mut keys = vec![
+        SchemaKey {
+            key: database::AGENT_STATE.to_string(),
+            role: KeyRole::AgentState,
+            required: true,
+            description: "Base64 编码的 SessionResponse，包含账户邮箱与登录令牌；备份中固定使用默认键名，恢复时写入键名映射后的键",
+        },
+        SchemaKey {
+            key: BACKUP_META_KEY.to_string(),
+            role: KeyRole::Meta,
+            required: false,
+            description: "标记文件由本程序按当前结构创建；缺少时按旧版本备份处理",
+        },
+    ];
+    keys.extend(effective.extra_backup_keys().iter().map(|key| SchemaKey {
+        key: key.clone(),
+        role: KeyRole::Extra,
+        required: false,
+        description: "ItemTable 中的原始字符串值",
+    }));
+    keys.extend(effective.remove_on_restore.iter().map(|key| SchemaKey {
+        key: key.clone(),
+        role: KeyRole::RemovedOnRestore,
+        required: false,
+        description: "认证状态缓存，恢复后由 Antigravity 重新生成",
+    }));
+    keys.extend(effective.onboarding.map(|key| SchemaKey {
+        key,
+        role: KeyRole::Onboarding,
+        required: false,
+        description: "首次启动引导标记",
+    }));
+    keys.extend(
+        key_mapping::DENIED_KEYS
+            .iter()
+            .filter(|key| **key != BACKUP_META_KEY)
+            .map(|key| SchemaKey {
+                key: key.to_string(),
+                role: KeyRole::Denied,
+                required: false,
+                description: "设备标识或存储标记，写回会造成设备指纹冲突或数据库状态错乱",
+            }),
+    );
+    keys
+}
+
+/// 转义正则表达式中的特殊字符
+fn escape_regex(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| {
+            let escape = r"\.^$|?*+()[]{}".contains(c);
+            escape.then_some('\\').into_iter().chain([c])
+        })
+        .collect()
+}
+
+/// 生成 JSON Schema：登录状态必需，元数据按字段校验，其余键为字符串且不能是禁止的键
+fn json_schema(keys: &[SchemaKey], meta_fields: &[SchemaField]) -> Value {
+    let mut meta_properties = Map::new();
+    for field in meta_fields {
+        meta_properties.insert(
+            field.name.to_string(),
+            json!({ "type": field.json_type, "description": field.description }),
+        );
+    }
+    if let Some(trigger) = meta_properties.get_mut("trigger") {
+        trigger["enum"] = json!(BackupTrigger::ALL);
+    }
+    let meta_required: Vec<&str> = meta_fields
+        .iter()
+        .filter(|field| field.required)
+        .map(|field| field.name)
+        .collect();
+
+    let mut properties = Map::new();
+    properties.insert(
+        database::AGENT_STATE.to_string(),
+        json!({ "type": "string", "contentEncoding": "base64", "minLength": 1 }),
+    );
+    properties.insert(
+        BACKUP_META_KEY.to_string(),
+        json!({
+            "type": "object",
+            "properties": meta_properties,
+            "required": meta_required,
+        }),
+    );
+    for key in keys.iter().filter(|key| key.role == KeyRole::Extra) {
+        properties.insert(key.key.clone(), json!({ "type": "string" }));
+    }
+
+    let denied: Vec<Value> = keys
+        .iter()
+        .filter(|key| key.role == KeyRole::Denied)
+        .map(|key| match key.key.strip_suffix('*') {
+            Some(prefix) => json!({ "pattern": format!("^{}", escape_regex(prefix)) }),
+            None => json!({ "const": key.key }),
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Antigravity Agent 账户备份",
+        "type": "object",
+        "required": [database::AGENT_STATE],
+        "properties": properties,
+        "additionalProperties": { "type": "string" },
+        "propertyNames": { "not": { "anyOf": denied } },
+    })
+}
+
+/// 描述当前的备份文件结构
+pub fn describe() -> BackupSchema {
+    let keys = schema_keys();
+    let meta_fields = meta_fields();
+    let json_schema = json_schema(&keys, &meta_fields);
+    BackupSchema {
+        format_version: BACKUP_FORMAT_VERSION,
+        keys,
+        meta_fields,
+        triggers: BackupTrigger::ALL.to_vec(),
+        import_formats: backup_converter::format_names(),
+        json_schema,
+    }
+}
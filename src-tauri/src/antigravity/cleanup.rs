@@ -5,66 +5,91 @@ use rusqlite::{params, Connection};
 use std::path::Path;
 
 // 导入 platform_utils 模块
+use crate::antigravity::backup::BackupTrigger;
+use crate::error::{AgentError, AgentResult};
 use crate::platform;
 
-fn clear_database(db_path: &Path, db_name: &str) -> Result<usize, String> {
+fn clear_database(db_path: &Path, db_name: &str) -> AgentResult<usize> {
     tracing::info!(target: "cleanup::database", db_name = %db_name, "开始清理数据库");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-
-    // 删除 jetskiStateSync.agentManagerInitState
-    let key = "jetskiStateSync.agentManagerInitState";
-    let rows = conn
-        .execute("DELETE FROM ItemTable WHERE key = ?", [key])
-        .unwrap_or(0);
+    let conn = Connection::open(db_path)?;
+    let keys = crate::antigravity::key_mapping::effective_keys();
 
+    // 删除 jetskiStateSync.agentManagerInitState 与额外备份的键；
     // 根据用户报告, 有些情况不删除 antigravityAuthStatus, Antigravity 不会生成新的
-    let antigravity_auth_status_key = "antigravityAuthStatus";
-    let antigravity_auth_status_rows = conn
-        .execute("DELETE FROM ItemTable WHERE key = ?", [antigravity_auth_status_key])
-        .unwrap_or(0);
+    let mut rows = 0;
+    for key in &keys.remove_on_clear {
+        let deleted = conn
+            .execute("DELETE FROM ItemTable WHERE key = ?", [key])
+            .unwrap_or(0);
+        if deleted > 0 {
+            tracing::debug!(target: "cleanup::database", key = %key, "已删除字段");
+        }
+        rows += deleted;
+    }
 
     // 把 antigravityOnboarding 设置为布尔值 true（写为字符串 "true"） 以跳过首次启动引导
-    let onboarding_key = "antigravityOnboarding";
-    let onboarding_rows = conn
-        .execute(
-            "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
-            params![onboarding_key, "true"],
-        )
-        .unwrap_or(0);
-
-    if rows > 0 {
-        tracing::debug!(target: "cleanup::database", key = %key, "已删除字段");
+    if let Some(onboarding_key) = &keys.onboarding {
+        rows += conn
+            .execute(
+                "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
+                params![onboarding_key, "true"],
+            )
+            .unwrap_or(0);
     }
 
-    Ok(rows + onboarding_rows + antigravity_auth_status_rows)
+    Ok(rows)
 }
 
-pub async fn clear_all_antigravity_data() -> Result<String, String> {
-    tracing::info!(target: "cleanup::main", "开始清除 Antigravity 用户认证数据（保留设备指纹）");
+/// 按设置在清除前备份数据库中登录的账户（`trigger` 记录为备份来源），返回备份文件名
+fn backup_before_clear(db_path: &Path, trigger: BackupTrigger) -> AgentResult<Option<String>> {
+    let settings =
+        crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file());
+    if !settings.backup_before_clear {
+        return Ok(None);
+    }
 
-    let app_data = match platform::get_antigravity_db_path() {
-        Some(p) => p,
-        None => {
-            let possible_paths = platform::get_all_antigravity_db_paths();
-            if possible_paths.is_empty() {
-                return Err("未找到 Antigravity 安装位置".to_string());
-            }
-            possible_paths[0].clone()
-        }
-    };
+    let outcome =
+        crate::antigravity::backup::backup_logged_in_account(db_path, trigger).map_err(|e| {
+            tracing::error!(target: "cleanup::main", error = %e, "清除前备份失败，已中止清除");
+            e
+        })?;
+    Ok(outcome.map(|outcome| {
+        tracing::info!(target: "cleanup::main", email = %outcome.email, changed = outcome.changed, "清除前已备份当前账户");
+        outcome
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or(outcome.email)
+    }))
+}
+
+/// 清除 Antigravity 登录数据
+///
+/// 设置开启时会先备份当前登录的账户（切换账户时来源为 `BeforeSwitch`，单独清除时为 `BeforeClear`）；
+/// 调用方需持有 StateDb 与 BackupDir 操作锁；全程为阻塞 IO，异步调用方应通过 `run_blocking` 调用
+pub fn clear_all_antigravity_data(trigger: BackupTrigger) -> AgentResult<String> {
+    clear_antigravity_data_at(&platform::resolve_antigravity_db_path()?, trigger)
+}
+
+/// 清除指定状态数据库中的登录数据（多个 Antigravity 实例时用于当前使用的安装以外的数据目录）
+///
+/// 调用方需持有 StateDb 与 BackupDir 操作锁
+pub fn clear_antigravity_data_at(app_data: &Path, trigger: BackupTrigger) -> AgentResult<String> {
+    tracing::info!(target: "cleanup::main", "开始清除 Antigravity 用户认证数据（保留设备指纹）");
 
     if !app_data.exists() {
-        return Err(format!(
-            "Antigravity 状态数据库不存在: {}",
-            app_data.display()
-        ));
+        return Err(AgentError::DatabaseNotFound(app_data.to_path_buf()));
     }
 
     let mut msg = String::new();
 
+    if let Some(backup_name) = backup_before_clear(app_data, trigger)? {
+        msg.push_str(&format!("已备份 {}; ", backup_name));
+    }
+
     // 清理主库
     tracing::info!(target: "cleanup::main", "步骤1: 清除 state.vscdb 数据库");
-    match clear_database(&app_data, "state.vscdb") {
+    match clear_database(app_data, "state.vscdb") {
         Ok(c) => {
             tracing::info!(target: "cleanup::main", cleaned_count = %c, "主数据库已清除");
             msg.push_str(&format!("主库清理 {} 项", c));
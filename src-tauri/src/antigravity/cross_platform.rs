@@ -0,0 +1,291 @@
+//! 跨平台备份路径转换
+//! 备份元数据记录了来源平台；在另一个系统上恢复时，登录状态中嵌入的用户目录路径
+//! （如 `C:\Users\alice\...`、`file:///c%3A/Users/alice/...`）会指向不存在的位置。
+//! 恢复前逐字段遍历登录状态的 Protobuf 数据，把用户目录下的路径改写为当前用户目录，
+//! 无法对应的路径（其他盘符、嵌在长文本中的路径）保留原值并给出警告
+
+use base64::Engine;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::constants::database;
+
+/// 报告中最多列出的无法转换条目
+const MAX_UNTRANSLATABLE: usize = 20;
+
+/// 嵌套消息的最大解析深度
+const MAX_DEPTH: usize = 16;
+
+/// 平台转换结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformNormalization {
+    pub source_platform: String,
+    pub target_platform: String,
+    /// 已改写的路径数量
+    pub rewritten: usize,
+    /// 无法转换、保留原值的路径
+    pub untranslatable: Vec<String>,
+}
+
+struct Patterns {
+    windows_home: Regex,
+    windows_home_uri: Regex,
+    windows_any: Regex,
+    unix_home: Regex,
+    unix_home_uri: Regex,
+    unix_any_uri: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| Patterns {
+        windows_home: Regex::new(r"(?i)^[a-z]:[\\/]Users[\\/][^\\/]+(?P<rest>[\\/].*)?$").unwrap(),
+        windows_home_uri: Regex::new(r"(?i)^file:///[a-z](?::|%3a)/Users/[^/]+(?P<rest>/.*)?$")
+            .unwrap(),
+        windows_any: Regex::new(r"(?i)(?:\b[a-z]:\\|file:///[a-z](?::|%3a)/)").unwrap(),
+        unix_home: Regex::new(r"^/(?:home|Users)/[^/]+(?P<rest>/.*)?$").unwrap(),
+        unix_home_uri: Regex::new(r"^file:///(?:home|Users)/[^/]+(?P<rest>/.*)?$").unwrap(),
+        unix_any_uri: Regex::new(r"file:///(?:home|Users)/").unwrap(),
+    })
+}
+
+/// 单个字符串的转换结果
+enum Translation {
+    Unchanged,
+    Rewritten(String),
+    Untranslatable,
+}
+
+struct Translator {
+    source_windows: bool,
+    target_windows: bool,
+    /// 当前用户目录（使用 `/` 分隔）
+    home: String,
+    rewritten: usize,
+    untranslatable: Vec<String>,
+}
+
+impl Translator {
+    fn rest<'a>(caps: &regex::Captures<'a>) -> &'a str {
+        caps.name("rest").map_or("", |m| m.as_str())
+    }
+
+    fn to_path(&self, rest: &str) -> String {
+        let path = format!("{}{}", self.home, rest.replace('\\', "/"));
+        if self.target_windows {
+            path.replace('/', "\\")
+        } else {
+            path
+        }
+    }
+
+    fn to_uri(&self, rest: &str) -> String {
+        let path = format!("{}{}", self.home, rest);
+        if self.target_windows {
+            // VS Code 系编辑器使用小写盘符并转义冒号
+            let mut chars = path.chars();
+            match (chars.next(), path.get(1..2)) {
+                (Some(drive), Some(":")) => {
+                    format!("file:///{}%3A{}", drive.to_ascii_lowercase(), &path[2..])
+                }
+                _ => format!("file:///{}", path.trim_start_matches('/')),
+            }
+        } else {
+            format!("file://{}", path)
+        }
+    }
+
+    fn translate(&self, value: &str) -> Translation {
+        let p = patterns();
+        let (home, home_uri) = if self.source_windows {
+            (&p.windows_home, &p.windows_home_uri)
+        } else {
+            (&p.unix_home, &p.unix_home_uri)
+        };
+
+        if let Some(caps) = home.captures(value) {
+            return Translation::Rewritten(self.to_path(Self::rest(&caps)));
+        }
+        if let Some(caps) = home_uri.captures(value) {
+            return Translation::Rewritten(self.to_uri(Self::rest(&caps)));
+        }
+
+        let foreign = if self.source_windows {
+            &p.windows_any
+        } else {
+            &p.unix_any_uri
+        };
+        if foreign.is_match(value) {
+            Translation::Untranslatable
+        } else {
+            Translation::Unchanged
+        }
+    }
+
+    fn note_untranslatable(&mut self, value: &str) {
+        if self.untranslatable.len() < MAX_UNTRANSLATABLE {
+            self.untranslatable.push(value.chars().take(200).collect());
+        }
+    }
+
+    /// 按 Protobuf 线格式遍历消息并改写字符串字段，数据不是合法消息时返回 None
+    fn rewrite_message(&mut self, buf: &[u8], depth: usize) -> Option<Vec<u8>> {
+        let mut out = Vec::with_capacity(buf.len());
+        let mut pos = 0;
+        while pos < buf.len() {
+            let key_start = pos;
+            let key = read_varint(buf, &mut pos)?;
+            if key >> 3 == 0 {
+                return None;
+            }
+            match key & 0x7 {
+                0 => {
+                    read_varint(buf, &mut pos)?;
+                    out.extend_from_slice(&buf[key_start..pos]);
+                }
+                1 | 5 => {
+                    let width = if key & 0x7 == 1 { 8 } else { 4 };
+                    pos = pos.checked_add(width).filter(|end| *end <= buf.len())?;
+                    out.extend_from_slice(&buf[key_start..pos]);
+                }
+                2 => {
+                    let len = usize::try_from(read_varint(buf, &mut pos)?).ok()?;
+                    let end = pos.checked_add(len).filter(|end| *end <= buf.len())?;
+                    let payload = self.rewrite_payload(&buf[pos..end], depth);
+                    pos = end;
+                    write_varint(&mut out, key);
+                    write_varint(&mut out, payload.len() as u64);
+                    out.extend_from_slice(&payload);
+                }
+                _ => return None,
+            }
+        }
+        Some(out)
+    }
+
+    fn rewrite_payload(&mut self, payload: &[u8], depth: usize) -> Vec<u8> {
+        let text = std::str::from_utf8(payload).ok();
+        if let Some(text) = text {
+            match self.translate(text) {
+                Translation::Rewritten(new) => {
+                    self.rewritten += 1;
+                    return new.into_bytes();
+                }
+                Translation::Untranslatable if !text.contains(['\n', ' ']) => {
+                    // 单独的路径值直接记录，长文本交给下方判断是否为嵌套消息
+                    self.note_untranslatable(text);
+                    return payload.to_vec();
+                }
+                _ => {}
+            }
+        }
+
+        if depth < MAX_DEPTH && !payload.is_empty() {
+            if let Some(nested) = self.rewrite_message(payload, depth + 1) {
+                return nested;
+            }
+        }
+        if let Some(text) = text {
+            if matches!(self.translate(text), Translation::Untranslatable) {
+                self.note_untranslatable(text);
+            }
+        }
+        payload.to_vec()
+    }
+}
+
+fn read_varint(buf: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *buf.get(*pos)?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// 备份元数据中记录的来源平台（旧版本备份没有）
+fn source_platform(account_data: &Value) -> Option<String> {
+    account_data
+        .get(crate::antigravity::backup::BACKUP_META_KEY)?
+        .get("platform")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// 备份来自其他平台时改写登录状态中的用户目录路径
+///
+/// 来源平台未知或与当前平台相同时返回 None，不做任何修改
+pub fn normalize_backup(account_data: &mut Value) -> Option<PlatformNormalization> {
+    let source = source_platform(account_data)?;
+    let target = std::env::consts::OS;
+    if source == target {
+        return None;
+    }
+
+    let home = dirs::home_dir()?;
+    let mut translator = Translator {
+        source_windows: source == "windows",
+        target_windows: target == "windows",
+        home: home_string(&home),
+        rewritten: 0,
+        untranslatable: Vec::new(),
+    };
+
+    let state = account_data.get(database::AGENT_STATE)?.as_str()?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(state)
+        .ok()?;
+    if let Some(rewritten) = translator.rewrite_message(&bytes, 0) {
+        if translator.rewritten > 0 {
+            account_data[database::AGENT_STATE] =
+                Value::String(base64::engine::general_purpose::STANDARD.encode(rewritten));
+        }
+    } else {
+        tracing::warn!(target: "restore::platform", "登录状态不是有效的 Protobuf 数据，跳过路径转换");
+    }
+
+    tracing::info!(
+        target: "restore::platform",
+        source = %source,
+        target_platform = %target,
+        rewritten = translator.rewritten,
+        "已转换跨平台备份中的路径"
+    );
+    if !translator.untranslatable.is_empty() {
+        tracing::warn!(
+            target: "restore::platform",
+            entries = ?translator.untranslatable,
+            "部分路径无法转换到当前平台，已保留原值"
+        );
+    }
+
+    Some(PlatformNormalization {
+        source_platform: source,
+        target_platform: target.to_string(),
+        rewritten: translator.rewritten,
+        untranslatable: translator.untranslatable,
+    })
+}
+
+fn home_string(home: &Path) -> String {
+    home.to_string_lossy()
+        .replace('\\', "/")
+        .trim_end_matches('/')
+        .to_string()
+}
@@ -0,0 +1,217 @@
+//! Antigravity 数据库文件快照
+//! 在恢复、清除等写入数据库的操作前，把 state.vscdb（以及 .backup、-wal）整体复制到带时间戳的
+//! `db-snapshots/` 目录，按键备份不足以还原时可整文件回滚。
+//! 快照记录来源数据库的路径，写入当前使用的安装以外的数据目录（恢复队列）时同样可以回滚
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{AgentError, AgentResult};
+use crate::operation_lock::{self, LockResource};
+use crate::utils::permissions;
+
+/// 快照描述文件名
+const META_FILE: &str = "snapshot.json";
+
+/// 随主库一起快照的附属文件后缀
+const COMPANION_SUFFIXES: [&str; 2] = [".backup", "-wal"];
+
+/// 数据库快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbSnapshot {
+    /// 快照 ID（目录名）
+    pub id: String,
+    pub created_at: String,
+    /// 触发快照的操作，如 `switch_account`
+    pub reason: String,
+    /// 快照中包含的文件名
+    pub files: Vec<String>,
+    pub size_bytes: u64,
+    /// 来源数据库路径（旧快照没有记录，回滚到当前使用的安装）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub db_path: Option<String>,
+}
+
+/// 主库及存在的附属文件
+fn database_files(db_path: &Path) -> Vec<PathBuf> {
+    let mut files = vec![db_path.to_path_buf()];
+    for suffix in COMPANION_SUFFIXES {
+        let mut name = db_path.as_os_str().to_owned();
+        name.push(suffix);
+        let path = PathBuf::from(name);
+        if path.exists() {
+            files.push(path);
+        }
+    }
+    files
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// 复制当前数据库文件到新的快照目录并按保留策略清理旧快照，数据库不存在时返回 None
+pub fn create_snapshot(reason: &str) -> AgentResult<Option<DbSnapshot>> {
+    let snapshot = write_snapshot(reason, &crate::platform::resolve_antigravity_db_path()?)?;
+    prune_snapshots();
+    Ok(snapshot)
+}
+
+fn write_snapshot(reason: &str, db_path: &Path) -> AgentResult<Option<DbSnapshot>> {
+    if !db_path.exists() {
+        tracing::debug!(target: "antigravity::db_snapshot", "数据库不存在，跳过快照");
+        return Ok(None);
+    }
+
+    let now = chrono::Local::now();
+    let id = format!("{}_{}", now.format("%Y%m%d-%H%M%S%.3f"), reason);
+    let snapshots_dir = crate::directories::get_db_snapshots_directory();
+    crate::disk_space::ensure_free_space(&snapshots_dir, "db_snapshot")?;
+    let snapshot_dir = snapshots_dir.join(&id);
+    permissions::create_private_dir(&snapshot_dir)?;
+
+    let mut files = Vec::new();
+    let mut size_bytes = 0;
+    for src in database_files(db_path) {
+        let name = file_name(&src);
+        size_bytes += permissions::copy_private(&src, &snapshot_dir.join(&name))?;
+        files.push(name);
+    }
+
+    let snapshot = DbSnapshot {
+        id,
+        created_at: now.to_rfc3339(),
+        reason: reason.to_string(),
+        files,
+        size_bytes,
+        db_path: Some(db_path.display().to_string()),
+    };
+    permissions::write_private(
+        &snapshot_dir.join(META_FILE),
+        serde_json::to_string_pretty(&snapshot)?,
+    )?;
+    tracing::info!(
+        target: "antigravity::db_snapshot",
+        id = %snapshot.id,
+        size_bytes,
+        "已创建数据库快照"
+    );
+    Ok(Some(snapshot))
+}
+
+/// 写入数据库前按设置创建快照（设置关闭时跳过）
+pub fn snapshot_before(reason: &str) -> AgentResult<()> {
+    snapshot_before_at(reason, &crate::platform::resolve_antigravity_db_path()?)
+}
+
+/// 写入指定数据库前按设置创建快照（设置关闭时跳过）
+pub fn snapshot_before_at(reason: &str, db_path: &Path) -> AgentResult<()> {
+    let settings =
+        crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file());
+    if !settings.db_snapshot_before_write {
+        return Ok(());
+    }
+    let snapshot = write_snapshot(reason, db_path).map_err(|e| {
+        tracing::error!(target: "antigravity::db_snapshot", reason, error = %e, "创建数据库快照失败，已中止操作");
+        e
+    })?;
+    prune_snapshots();
+    if snapshot.is_none() {
+        tracing::debug!(target: "antigravity::db_snapshot", reason, "数据库不存在，未创建快照");
+    }
+    Ok(())
+}
+
+fn read_snapshot(dir: &Path) -> Option<DbSnapshot> {
+    fs::read_to_string(dir.join(META_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// 所有快照（最新的在前）
+pub fn list_snapshots() -> Vec<DbSnapshot> {
+    let mut snapshots: Vec<DbSnapshot> =
+        fs::read_dir(crate::directories::get_db_snapshots_directory())
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|e| e.path().is_dir())
+                    .filter_map(|e| read_snapshot(&e.path()))
+                    .collect()
+            })
+            .unwrap_or_default();
+    snapshots.sort_by(|a, b| b.id.cmp(&a.id));
+    snapshots
+}
+
+/// 按保留策略删除旧快照（见 [`crate::retention`]）
+fn prune_snapshots() {
+    crate::retention::enforce(crate::retention::SnapshotKind::Db);
+}
+
+/// 用快照整体替换当前数据库文件
+///
+/// 必须在使用该数据库的 Antigravity 关闭时执行；替换前会先快照当前文件，便于撤销回滚
+pub fn restore_snapshot(id: &str) -> AgentResult<DbSnapshot> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.contains("..") {
+        return Err(AgentError::InvalidInput(format!("快照 ID 无效: {id}")));
+    }
+
+    let snapshot_dir = crate::directories::get_db_snapshots_directory().join(id);
+    let snapshot = read_snapshot(&snapshot_dir)
+        .ok_or_else(|| AgentError::BackupNotFound(format!("数据库快照 {id}")))?;
+
+    let db_path = match &snapshot.db_path {
+        Some(path) => PathBuf::from(path),
+        None => crate::platform::resolve_antigravity_db_path()?,
+    };
+    match db_path.parent() {
+        Some(data_dir) => {
+            crate::platform::ensure_installs_stopped(&[data_dir.to_path_buf()], false)?
+        }
+        None => crate::platform::ensure_antigravity_stopped(false)?,
+    }
+
+    let _guard = operation_lock::acquire("restore_db_snapshot", &[LockResource::StateDb])?;
+
+    // 回滚完成前不清理旧快照，避免删掉正在使用的快照
+    write_snapshot("before_rollback", &db_path)?;
+
+    let target_dir = db_path
+        .parent()
+        .ok_or(AgentError::AntigravityNotFound)?
+        .to_path_buf();
+    fs::create_dir_all(&target_dir)?;
+
+    // 快照中不存在的附属文件需删除，否则 SQLite 会把旧的 WAL 合并进回滚后的主库
+    for existing in database_files(&db_path).iter().skip(1) {
+        if !snapshot.files.contains(&file_name(existing)) {
+            fs::remove_file(existing)?;
+        }
+    }
+    // 共享内存索引与 WAL 对应，已关闭的数据库可安全删除，SQLite 会重建
+    let mut shm = db_path.as_os_str().to_owned();
+    shm.push("-shm");
+    let shm = PathBuf::from(shm);
+    if shm.exists() {
+        fs::remove_file(&shm)?;
+    }
+    for name in &snapshot.files {
+        fs::copy(snapshot_dir.join(name), target_dir.join(name))?;
+    }
+
+    prune_snapshots();
+
+    tracing::info!(target: "antigravity::db_snapshot", id = %snapshot.id, "✅ 已从快照回滚数据库");
+    crate::audit::record(
+        "db_snapshot",
+        "restore",
+        true,
+        serde_json::json!({ "id": snapshot.id, "files": snapshot.files }),
+    );
+    Ok(snapshot)
+}
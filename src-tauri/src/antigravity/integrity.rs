@@ -0,0 +1,203 @@
+//! Antigravity 数据库完整性检查与修复
+//! Antigravity 崩溃后 state.vscdb 可能损坏导致无法启动：用 `PRAGMA integrity_check` 检测，
+//! 修复时先整体快照原文件，再把仍可读取的行导入新数据库并替换原文件
+
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, Connection, OpenFlags};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{AgentError, AgentResult};
+use crate::operation_lock::{self, LockResource};
+
+/// 最多返回的问题条数
+const MAX_ISSUES: usize = 50;
+
+/// VS Code 系编辑器的 ItemTable 表结构
+const ITEM_TABLE_SCHEMA: &str =
+    "CREATE TABLE IF NOT EXISTS ItemTable (key TEXT UNIQUE ON CONFLICT REPLACE, value BLOB)";
+
+/// 完整性检查结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    pub db_path: String,
+    pub ok: bool,
+    /// `integrity_check` 报告的问题（或无法打开数据库的原因）
+    pub issues: Vec<String>,
+    pub checked_at: String,
+}
+
+/// 修复结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairResult {
+    /// 修复前的数据库快照 ID
+    pub snapshot_id: Option<String>,
+    /// 导入新数据库的行数
+    pub recovered_rows: usize,
+    /// 无法读取而丢弃的行数
+    pub lost_rows: usize,
+    /// 修复后的检查结果
+    pub report: IntegrityReport,
+}
+
+fn run_integrity_check(db_path: &Path) -> rusqlite::Result<Vec<String>> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    rows.take(MAX_ISSUES).collect()
+}
+
+/// 检查指定数据库文件
+fn check_file(db_path: &Path) -> IntegrityReport {
+    let issues = match run_integrity_check(db_path) {
+        Ok(rows) if rows.len() == 1 && rows[0] == "ok" => Vec::new(),
+        Ok(rows) => rows,
+        Err(e) => vec![format!("无法检查数据库: {}", e)],
+    };
+
+    IntegrityReport {
+        db_path: db_path.to_string_lossy().to_string(),
+        ok: issues.is_empty(),
+        issues,
+        checked_at: chrono::Local::now().to_rfc3339(),
+    }
+}
+
+/// 检查 Antigravity 数据库的完整性
+pub fn check_integrity() -> AgentResult<IntegrityReport> {
+    let db_path = crate::platform::resolve_antigravity_db_path()?;
+    if !db_path.exists() {
+        return Err(AgentError::DatabaseNotFound(db_path));
+    }
+
+    let report = check_file(&db_path);
+    if report.ok {
+        tracing::info!(target: "antigravity::integrity", "数据库完整性检查通过");
+    } else {
+        tracing::warn!(target: "antigravity::integrity", issues = report.issues.len(), "数据库完整性检查发现问题");
+    }
+    Ok(report)
+}
+
+/// 读取 ItemTable 中仍可读取的行，返回 (行, 丢弃的行数)
+///
+/// 先整表读取；遇到损坏页时改为按 rowid 逐行读取，跳过无法读取的行
+fn read_recoverable_rows(conn: &Connection) -> (Vec<(String, SqlValue)>, usize) {
+    let full_scan = conn
+        .prepare("SELECT key, value FROM ItemTable")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<rusqlite::Result<Vec<_>>>()
+        });
+    match full_scan {
+        Ok(rows) => return (rows, 0),
+        Err(e) => {
+            tracing::warn!(target: "antigravity::integrity", error = %e, "整表读取失败，改为逐行读取");
+        }
+    }
+
+    let max_rowid: i64 = conn
+        .query_row("SELECT MAX(rowid) FROM ItemTable", [], |row| {
+            row.get::<_, Option<i64>>(0)
+        })
+        .ok()
+        .flatten()
+        .unwrap_or(0);
+
+    let mut rows = Vec::new();
+    let mut lost = 0;
+    let Ok(mut stmt) = conn.prepare("SELECT key, value FROM ItemTable WHERE rowid = ?") else {
+        return (rows, lost);
+    };
+    for rowid in 1..=max_rowid {
+        match stmt.query_row([rowid], |row| Ok((row.get(0)?, row.get(1)?))) {
+            Ok(row) => rows.push(row),
+            Err(rusqlite::Error::QueryReturnedNoRows) => {}
+            Err(_) => lost += 1,
+        }
+    }
+    (rows, lost)
+}
+
+fn companion(db_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = db_path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// 把可读取的行导入新数据库并替换原文件
+///
+/// 必须在 Antigravity 关闭时执行；修复前总会快照原文件（不受快照设置影响）
+pub fn repair() -> AgentResult<RepairResult> {
+    crate::platform::ensure_antigravity_stopped(false)?;
+
+    let db_path = crate::platform::resolve_antigravity_db_path()?;
+    if !db_path.exists() {
+        return Err(AgentError::DatabaseNotFound(db_path));
+    }
+
+    let _guard = operation_lock::acquire("repair_database", &[LockResource::StateDb])?;
+
+    let snapshot = crate::antigravity::db_snapshot::create_snapshot("before_repair")?;
+
+    let (rows, lost_rows) = {
+        let source = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        read_recoverable_rows(&source)
+    };
+    tracing::info!(
+        target: "antigravity::integrity",
+        recovered = rows.len(),
+        lost = lost_rows,
+        "已读取可恢复的数据"
+    );
+
+    let tmp_path = companion(&db_path, ".repair-tmp");
+    if tmp_path.exists() {
+        fs::remove_file(&tmp_path)?;
+    }
+    {
+        let mut conn = Connection::open(&tmp_path)?;
+        conn.execute_batch(ITEM_TABLE_SCHEMA)?;
+        let tx = conn.transaction()?;
+        {
+            let mut insert =
+                tx.prepare("INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)")?;
+            for (key, value) in &rows {
+                insert.execute(params![key, value])?;
+            }
+        }
+        tx.commit()?;
+    }
+
+    // 旧的 WAL 属于损坏的数据库，替换前删除
+    for suffix in ["-wal", "-shm"] {
+        let path = companion(&db_path, suffix);
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+    }
+    fs::rename(&tmp_path, &db_path)?;
+
+    let report = check_file(&db_path);
+    tracing::info!(target: "antigravity::integrity", ok = report.ok, "✅ 数据库修复完成");
+    crate::audit::record(
+        "integrity",
+        "repair",
+        report.ok,
+        serde_json::json!({
+            "snapshotId": snapshot.as_ref().map(|s| &s.id),
+            "recoveredRows": rows.len(),
+            "lostRows": lost_rows,
+        }),
+    );
+
+    Ok(RepairResult {
+        snapshot_id: snapshot.map(|s| s.id),
+        recovered_rows: rows.len(),
+        lost_rows,
+        report,
+    })
+}
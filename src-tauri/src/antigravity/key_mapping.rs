@@ -0,0 +1,224 @@
+//! 数据库键名映射
+//! Antigravity 更新后可能重命名 ItemTable 中的键，编译期常量会导致备份悄悄失效。
+//! 键名在运行时从 `key_mapping.json` 读取（缺失时使用内置默认值），
+//! 并可扫描数据库中疑似认证相关、但不在映射中的键，提示映射可能过期。
+//! 用户还可在设置中额外备份某些键或排除默认处理的键，备份、恢复与清除使用合并后的键集合
+
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::constants::database;
+use crate::error::{AgentError, AgentResult};
+
+/// 疑似认证相关键名包含的片段（小写匹配）
+const AUTH_KEY_HINTS: [&str; 6] = [
+    "auth",
+    "token",
+    "credential",
+    "oauth",
+    "session",
+    "jetskistatesync",
+];
+
+/// 不允许加入备份的键（设备标识与存储标记），恢复时写回会造成设备指纹冲突或数据库状态错乱；
+/// 以 `*` 结尾时按前缀匹配
+pub(crate) const DENIED_KEYS: [&str; 5] = [
+    "telemetry.*",
+    "storage.serviceMachineId",
+    "__$__targetStorageMarker",
+    "__$__isNewStorageMarker",
+    crate::antigravity::backup::BACKUP_META_KEY,
+];
+
+/// 键名是否匹配模式（以 `*` 结尾时按前缀匹配）
+fn matches_pattern(pattern: &str, key: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => key.starts_with(prefix),
+        None => key == pattern,
+    }
+}
+
+/// 键名是否疑似认证相关
+pub(crate) fn looks_like_auth_key(key: &str) -> bool {
+    let lower = key.to_ascii_lowercase();
+    AUTH_KEY_HINTS.iter().any(|hint| lower.contains(hint))
+}
+
+/// 键名映射
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct KeyMapping {
+    /// 账户登录状态（Base64 编码的 SessionResponse）
+    pub agent_state: String,
+    /// 认证状态缓存，恢复或清除账户时删除
+    pub auth_status: String,
+    /// 首次启动引导标记，清除数据后设为 true 以跳过引导
+    pub onboarding: String,
+    /// 与账户无关的已知键，不参与未知键检测；以 `*` 结尾时按前缀匹配
+    pub ignored_keys: Vec<String>,
+}
+
+impl Default for KeyMapping {
+    fn default() -> Self {
+        Self {
+            agent_state: database::AGENT_STATE.to_string(),
+            auth_status: database::AUTH_STATUS.to_string(),
+            onboarding: database::ONBOARDING.to_string(),
+            ignored_keys: Vec::new(),
+        }
+    }
+}
+
+impl KeyMapping {
+    /// 映射中已知的键
+    fn is_known(&self, key: &str) -> bool {
+        key == self.agent_state
+            || key == self.auth_status
+            || key == self.onboarding
+            || self
+                .ignored_keys
+                .iter()
+                .any(|ignored| matches_pattern(ignored, key))
+    }
+}
+
+/// 读取键名映射（配置文件不存在或无效时使用内置默认值，空字段回退到默认键名）
+pub fn load() -> KeyMapping {
+    let path = crate::directories::get_key_mapping_file();
+    let Ok(content) = fs::read_to_string(&path) else {
+        return KeyMapping::default();
+    };
+
+    let mut mapping = match serde_json::from_str::<KeyMapping>(&content) {
+        Ok(mapping) => mapping,
+        Err(e) => {
+            tracing::warn!(target: "antigravity::key_mapping", error = %e, "键名映射文件无效，使用内置默认值");
+            return KeyMapping::default();
+        }
+    };
+
+    let defaults = KeyMapping::default();
+    for (field, default) in [
+        (&mut mapping.agent_state, defaults.agent_state),
+        (&mut mapping.auth_status, defaults.auth_status),
+        (&mut mapping.onboarding, defaults.onboarding),
+    ] {
+        if field.trim().is_empty() {
+            *field = default;
+        }
+    }
+    mapping
+}
+
+/// 合并用户设置后，备份、恢复与清除实际处理的键
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveKeys {
+    /// 备份时从数据库读取并写入备份文件、恢复时写回的键
+    pub backup: Vec<String>,
+    /// 恢复时删除的键（让 Antigravity 重新生成）
+    pub remove_on_restore: Vec<String>,
+    /// 清除登录数据时删除的键
+    pub remove_on_clear: Vec<String>,
+    /// 清除后设为 true 以跳过首次启动引导的键（被排除时为 None）
+    pub onboarding: Option<String>,
+}
+
+impl EffectiveKeys {
+    /// 额外备份的键（账户状态之外）
+    pub fn extra_backup_keys(&self) -> &[String] {
+        &self.backup[1..]
+    }
+}
+
+/// 校验用户设置的额外备份键与排除键
+pub fn validate_overrides(extra_keys: &[String], excluded_keys: &[String]) -> AgentResult<()> {
+    let mapping = load();
+    for key in extra_keys.iter().chain(excluded_keys) {
+        if key.trim().is_empty() || key.trim() != key {
+            return Err(AgentError::InvalidInput(format!("键名 '{key}' 无效")));
+        }
+    }
+    for key in extra_keys {
+        if key.contains('*') {
+            return Err(AgentError::InvalidInput(format!(
+                "额外备份的键 '{key}' 不能使用通配符"
+            )));
+        }
+        if DENIED_KEYS
+            .iter()
+            .any(|denied| matches_pattern(denied, key))
+        {
+            return Err(AgentError::InvalidInput(format!(
+                "键 '{key}' 属于设备标识或存储标记，不能加入备份"
+            )));
+        }
+    }
+    if excluded_keys.contains(&mapping.agent_state) {
+        return Err(AgentError::InvalidInput(format!(
+            "账户状态键 '{}' 是备份必需的，不能排除",
+            mapping.agent_state
+        )));
+    }
+    Ok(())
+}
+
+/// 按键名映射与用户设置计算实际处理的键
+pub fn effective_keys() -> EffectiveKeys {
+    let mapping = load();
+    let settings =
+        crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file());
+    let included = |key: &String| !settings.backup_excluded_keys.contains(key);
+
+    // 设置文件被手动修改时，非法的额外键在这里再过滤一次
+    let mut backup = vec![mapping.agent_state.clone()];
+    for key in &settings.backup_extra_keys {
+        if validate_overrides(std::slice::from_ref(key), &[]).is_ok()
+            && included(key)
+            && !backup.contains(key)
+        {
+            backup.push(key.clone());
+        }
+    }
+
+    let remove_on_restore: Vec<String> = [mapping.auth_status.clone()]
+        .into_iter()
+        .filter(included)
+        .collect();
+    let mut remove_on_clear = backup.clone();
+    remove_on_clear.extend(remove_on_restore.iter().cloned());
+
+    EffectiveKeys {
+        backup,
+        remove_on_restore,
+        remove_on_clear,
+        onboarding: Some(mapping.onboarding).filter(included),
+    }
+}
+
+/// 列出 ItemTable 中疑似认证相关、但不在键名映射中的键
+pub fn detect_unknown_auth_keys() -> AgentResult<Vec<String>> {
+    let db_path = crate::platform::resolve_antigravity_db_path()?;
+    if !db_path.exists() {
+        return Err(AgentError::DatabaseNotFound(db_path));
+    }
+
+    let mapping = load();
+    let conn = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut stmt = conn.prepare("SELECT key FROM ItemTable")?;
+    let keys = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let unknown: Vec<String> = keys
+        .into_iter()
+        .filter(|key| looks_like_auth_key(key))
+        .filter(|key| !mapping.is_known(key))
+        .collect();
+
+    if !unknown.is_empty() {
+        tracing::warn!(target: "antigravity::key_mapping", keys = ?unknown, "发现未在键名映射中的疑似认证键");
+    }
+    Ok(unknown)
+}
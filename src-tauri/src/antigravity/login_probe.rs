@@ -0,0 +1,125 @@
+//! 切换账户后的登录验证
+//! 恢复账户时会删除认证状态缓存，Antigravity 启动后如果接受恢复的凭据会重新写入该缓存；
+//! 如果凭据失效，它会清除登录状态。切换完成后在后台轮询这两个键，
+//! 通过 `account:login-probe` 事件告知前端登录是否真正成功，避免静默登录失败
+
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::Serialize;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// 验证结果事件名
+pub const LOGIN_PROBE_EVENT: &str = "account:login-probe";
+
+/// 轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 最长等待时间（Antigravity 启动并刷新令牌通常在一分钟内完成）
+const PROBE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// 验证结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoginProbeStatus {
+    /// Antigravity 已写回认证状态，登录有效
+    Verified,
+    /// 登录状态被清除（凭据失效）
+    LoggedOut,
+    /// 当前登录的是其他账户
+    Mismatch,
+    /// 超时仍无法确定（例如 Antigravity 未启动）
+    Timeout,
+}
+
+/// 验证结果事件数据
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginProbeEvent {
+    /// 切换到的账户
+    pub email: String,
+    pub status: LoginProbeStatus,
+    /// 当前登录的账户（未登录时为 None）
+    pub current_email: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+/// 读取当前登录账户与认证状态缓存是否存在
+fn read_login_state(db_path: &Path) -> Option<(Option<String>, bool)> {
+    let mapping = crate::antigravity::key_mapping::load();
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY).ok()?;
+    let read = |key: &str| -> Option<String> {
+        conn.query_row("SELECT value FROM ItemTable WHERE key = ?", [key], |row| {
+            row.get(0)
+        })
+        .optional()
+        .ok()
+        .flatten()
+    };
+
+    let email = read(&mapping.agent_state)
+        .and_then(|state| crate::antigravity::backup::extract_email(&state).ok());
+    let auth_cached = read(&mapping.auth_status).is_some_and(|value| !value.trim().is_empty());
+    Some((email, auth_cached))
+}
+
+/// 根据当前登录账户与认证状态缓存判断结果，返回 None 表示尚无结论
+fn evaluate(email: &str, current: Option<&str>, auth_cached: bool) -> Option<LoginProbeStatus> {
+    match current {
+        None => Some(LoginProbeStatus::LoggedOut),
+        Some(current) if current != email => Some(LoginProbeStatus::Mismatch),
+        Some(_) if auth_cached => Some(LoginProbeStatus::Verified),
+        Some(_) => None,
+    }
+}
+
+/// 在后台验证切换后的账户登录是否有效，完成后推送结果事件
+pub fn spawn(app: AppHandle, email: String) {
+    tauri::async_runtime::spawn(async move {
+        let start = Instant::now();
+        let mut current_email = Some(email.clone());
+        let status = loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            let state = tokio::task::spawn_blocking(|| {
+                let db_path = crate::platform::resolve_antigravity_db_path().ok()?;
+                read_login_state(&db_path)
+            })
+            .await
+            .ok()
+            .flatten();
+
+            if let Some((current, auth_cached)) = state {
+                let status = evaluate(&email, current.as_deref(), auth_cached);
+                current_email = current;
+                if let Some(status) = status {
+                    break status;
+                }
+            }
+            if start.elapsed() >= PROBE_TIMEOUT {
+                break LoginProbeStatus::Timeout;
+            }
+        };
+
+        let event = LoginProbeEvent {
+            email,
+            status,
+            current_email,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        };
+        if status == LoginProbeStatus::Verified {
+            tracing::info!(target: "account::login_probe", elapsed_ms = event.elapsed_ms, "切换后的账户登录有效");
+        } else {
+            tracing::warn!(target: "account::login_probe", status = ?status, elapsed_ms = event.elapsed_ms, "切换后的账户登录未通过验证");
+        }
+        crate::audit::record(
+            "account",
+            "login_probe",
+            status == LoginProbeStatus::Verified,
+            serde_json::json!({ "status": status, "elapsedMs": event.elapsed_ms }),
+        );
+        if let Err(e) = app.emit(LOGIN_PROBE_EVENT, &event) {
+            tracing::error!(target: "account::login_probe", error = %e, "推送登录验证结果失败");
+        }
+    });
+}
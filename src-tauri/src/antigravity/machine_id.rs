@@ -0,0 +1,227 @@
+//! Antigravity 设备标识模块
+//! 查看与重新生成 storage.json 中的 telemetry 标识以及 state.vscdb 中的 `storage.serviceMachineId`，
+//! 重置前会自动快照原始值，便于回退
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{AgentError, AgentResult};
+use crate::operation_lock::{self, LockResource};
+use crate::utils::permissions;
+
+/// storage.json 中的 telemetry 标识键
+const KEY_MACHINE_ID: &str = "telemetry.machineId";
+const KEY_MAC_MACHINE_ID: &str = "telemetry.macMachineId";
+const KEY_DEV_DEVICE_ID: &str = "telemetry.devDeviceId";
+const KEY_SQM_ID: &str = "telemetry.sqmId";
+
+/// state.vscdb 中的服务端设备标识键
+const KEY_SERVICE_MACHINE_ID: &str = "storage.serviceMachineId";
+
+/// 设备标识
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MachineIds {
+    pub machine_id: Option<String>,
+    pub mac_machine_id: Option<String>,
+    pub dev_device_id: Option<String>,
+    pub sqm_id: Option<String>,
+    pub service_machine_id: Option<String>,
+}
+
+/// 设备标识查看结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MachineIdReport {
+    /// storage.json 路径（不存在时为 None）
+    pub storage_path: Option<String>,
+    /// state.vscdb 路径（不存在时为 None）
+    pub db_path: Option<String>,
+    pub ids: MachineIds,
+}
+
+/// 设备标识重置结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MachineIdResetResult {
+    pub previous: MachineIds,
+    pub current: MachineIds,
+    /// 重置前的快照目录
+    pub snapshot_dir: String,
+}
+
+fn storage_json_path() -> Option<PathBuf> {
+    crate::platform::get_antigravity_data_dir().map(|dir| dir.join("storage.json"))
+}
+
+fn existing(path: Option<PathBuf>) -> Option<PathBuf> {
+    path.filter(|p| p.exists())
+}
+
+fn read_storage(path: &Path) -> AgentResult<serde_json::Map<String, Value>> {
+    let content = fs::read_to_string(path)?;
+    match serde_json::from_str(&content)? {
+        Value::Object(map) => Ok(map),
+        _ => Err(AgentError::Serialization(
+            "storage.json 顶层不是 JSON 对象".to_string(),
+        )),
+    }
+}
+
+fn read_service_machine_id(db_path: &Path) -> AgentResult<Option<String>> {
+    let conn = Connection::open(db_path)?;
+    Ok(conn
+        .query_row(
+            "SELECT value FROM ItemTable WHERE key = ?",
+            [KEY_SERVICE_MACHINE_ID],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+fn read_ids(storage: Option<&Path>, db: Option<&Path>) -> AgentResult<MachineIds> {
+    let mut ids = MachineIds::default();
+
+    if let Some(path) = storage {
+        let map = read_storage(path)?;
+        let get = |key: &str| map.get(key).and_then(Value::as_str).map(str::to_string);
+        ids.machine_id = get(KEY_MACHINE_ID);
+        ids.mac_machine_id = get(KEY_MAC_MACHINE_ID);
+        ids.dev_device_id = get(KEY_DEV_DEVICE_ID);
+        ids.sqm_id = get(KEY_SQM_ID);
+    }
+
+    if let Some(path) = db {
+        ids.service_machine_id = read_service_machine_id(path)?;
+    }
+
+    Ok(ids)
+}
+
+/// 64 位十六进制（与 VS Code 的 sha256 格式一致）
+fn random_hex64() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// 生成一组新的设备标识
+fn generate_ids() -> MachineIds {
+    MachineIds {
+        machine_id: Some(random_hex64()),
+        mac_machine_id: Some(random_hex64()),
+        dev_device_id: Some(uuid::Uuid::new_v4().to_string()),
+        sqm_id: Some(format!(
+            "{{{}}}",
+            uuid::Uuid::new_v4().to_string().to_uppercase()
+        )),
+        service_machine_id: Some(uuid::Uuid::new_v4().to_string()),
+    }
+}
+
+/// 读取当前设备标识
+pub fn read_machine_ids() -> AgentResult<MachineIdReport> {
+    let storage = existing(storage_json_path());
+    let db = existing(crate::platform::get_antigravity_db_path());
+
+    let ids = read_ids(storage.as_deref(), db.as_deref())?;
+    Ok(MachineIdReport {
+        storage_path: storage.map(|p| p.to_string_lossy().to_string()),
+        db_path: db.map(|p| p.to_string_lossy().to_string()),
+        ids,
+    })
+}
+
+/// 将原始文件与标识写入快照目录
+fn create_snapshot(
+    storage: Option<&Path>,
+    db: Option<&Path>,
+    ids: &MachineIds,
+) -> AgentResult<PathBuf> {
+    let snapshot_dir = crate::directories::get_machine_id_snapshots_directory()
+        .join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string());
+    permissions::create_private_dir(&snapshot_dir)?;
+
+    if let Some(path) = storage {
+        permissions::copy_private(path, &snapshot_dir.join("storage.json"))?;
+    }
+    if let Some(path) = db {
+        permissions::copy_private(path, &snapshot_dir.join("state.vscdb"))?;
+    }
+    permissions::write_private(
+        &snapshot_dir.join("machine_ids.json"),
+        serde_json::to_string_pretty(ids)?,
+    )?;
+
+    Ok(snapshot_dir)
+}
+
+/// 重新生成设备标识
+///
+/// 必须在 Antigravity 关闭时执行，否则 Antigravity 退出时会写回旧值
+pub fn reset_machine_ids() -> AgentResult<MachineIdResetResult> {
+    crate::platform::ensure_antigravity_stopped(false)?;
+
+    let _guard = operation_lock::acquire("reset_machine_ids", &[LockResource::StateDb])?;
+
+    let storage = existing(storage_json_path());
+    let db = existing(crate::platform::get_antigravity_db_path());
+    if storage.is_none() && db.is_none() {
+        return Err(AgentError::AntigravityNotFound);
+    }
+
+    let previous = read_ids(storage.as_deref(), db.as_deref())?;
+    let snapshot_dir = create_snapshot(storage.as_deref(), db.as_deref(), &previous)?;
+    crate::retention::enforce(crate::retention::SnapshotKind::MachineId);
+    tracing::info!(target: "antigravity::machine_id", snapshot = %snapshot_dir.display(), "已快照原设备标识");
+
+    let generated = generate_ids();
+
+    if let Some(path) = &storage {
+        let mut map = read_storage(path)?;
+        for (key, value) in [
+            (KEY_MACHINE_ID, &generated.machine_id),
+            (KEY_MAC_MACHINE_ID, &generated.mac_machine_id),
+            (KEY_DEV_DEVICE_ID, &generated.dev_device_id),
+            (KEY_SQM_ID, &generated.sqm_id),
+        ] {
+            if let Some(value) = value {
+                map.insert(key.to_string(), Value::String(value.clone()));
+            }
+        }
+        fs::write(path, serde_json::to_string_pretty(&Value::Object(map))?)?;
+    }
+
+    if let (Some(path), Some(service_id)) = (&db, &generated.service_machine_id) {
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
+            params![KEY_SERVICE_MACHINE_ID, service_id],
+        )?;
+    }
+
+    let current = read_ids(storage.as_deref(), db.as_deref())?;
+    tracing::info!(target: "antigravity::machine_id", "✅ 设备标识已重新生成");
+
+    crate::audit::record(
+        "machine_id",
+        "reset",
+        true,
+        serde_json::json!({
+            "snapshotDir": snapshot_dir.to_string_lossy(),
+            "previousMachineId": previous.machine_id,
+            "currentMachineId": current.machine_id,
+        }),
+    );
+
+    Ok(MachineIdResetResult {
+        previous,
+        current,
+        snapshot_dir: snapshot_dir.to_string_lossy().to_string(),
+    })
+}
@@ -1,5 +1,21 @@
 pub mod account;
+pub mod backup;
+pub mod backup_converter;
+pub mod backup_naming;
+pub mod backup_reader;
+pub mod backup_schema;
 pub mod cleanup;
+pub mod cross_platform;
+pub mod db_snapshot;
+pub mod integrity;
+pub mod key_mapping;
+pub mod login_probe;
+pub mod machine_id;
 pub mod path_config;
 pub mod restore;
+pub mod restore_queue;
+pub mod sanitize;
 pub mod starter;
+pub mod update_snapshot;
+pub mod version;
+pub mod workspace;
@@ -1,16 +1,22 @@
 //! Antigravity 路径配置管理模块
-//! 负责保存和读取用户自定义的 Antigravity 可执行文件路径
+//! 负责保存和读取用户自定义的 Antigravity 可执行文件路径与数据目录
 
 use crate::directories;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Antigravity 路径配置
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AntigravityPathConfig {
     /// 用户自定义的 Antigravity 可执行文件路径
     pub custom_executable_path: Option<String>,
+    /// 用户自定义的 Antigravity 数据目录（迁移到其他磁盘或使用符号链接时自动检测会失败）
+    #[serde(default)]
+    pub custom_data_dir: Option<String>,
+    /// 存在多个安装时用户选择的安装（数据目录）
+    #[serde(default)]
+    pub selected_install: Option<String>,
 }
 
 /// 获取配置文件路径
@@ -20,6 +26,7 @@ fn get_config_file_path() -> PathBuf {
 
 /// 保存用户自定义可执行文件路径
 pub fn save_custom_executable_path(path: String) -> Result<(), String> {
+    crate::app_settings::ensure_writable()?;
     let config_file = get_config_file_path();
     let mut config = read_config().unwrap_or_default();
 
@@ -30,6 +37,38 @@ pub fn save_custom_executable_path(path: String) -> Result<(), String> {
     Ok(())
 }
 
+/// 保存用户自定义的数据目录（None 清除，恢复自动检测）
+pub fn save_custom_data_dir(path: Option<String>) -> Result<(), String> {
+    crate::app_settings::ensure_writable()?;
+    let config_file = get_config_file_path();
+    let mut config = read_config().unwrap_or_default();
+
+    config.custom_data_dir = path;
+    write_config(&config_file, &config)?;
+
+    tracing::info!(
+        cleared = config.custom_data_dir.is_none(),
+        "✅ 已保存自定义 Antigravity 数据目录"
+    );
+    Ok(())
+}
+
+/// 保存用户选择的安装（None 清除，恢复自动选择）
+pub fn save_selected_install(data_dir: Option<String>) -> Result<(), String> {
+    crate::app_settings::ensure_writable()?;
+    let config_file = get_config_file_path();
+    let mut config = read_config().unwrap_or_default();
+
+    config.selected_install = data_dir;
+    write_config(&config_file, &config)?;
+
+    tracing::info!(
+        cleared = config.selected_install.is_none(),
+        "✅ 已保存选择的 Antigravity 安装"
+    );
+    Ok(())
+}
+
 /// 写入配置到文件
 fn write_config(
     config_file: &std::path::Path,
@@ -64,6 +103,35 @@ pub fn get_custom_executable_path() -> Result<Option<String>, String> {
     Ok(config.custom_executable_path)
 }
 
+/// 从配置文件读取自定义数据目录
+pub fn get_custom_data_dir() -> Result<Option<String>, String> {
+    let config = read_config()?;
+    Ok(config.custom_data_dir)
+}
+
+/// 从配置文件读取用户选择的安装
+pub fn get_selected_install() -> Result<Option<String>, String> {
+    let config = read_config()?;
+    Ok(config.selected_install)
+}
+
+/// 把用户指定的目录解析为存放 state.vscdb 的数据目录
+///
+/// 既可以直接指定 `User/globalStorage`，也可以指定 Antigravity 的根数据目录
+pub fn resolve_data_dir(path: &Path) -> PathBuf {
+    let global_storage = path.join("User").join("globalStorage");
+    if !path.join("state.vscdb").exists() && global_storage.is_dir() {
+        global_storage
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// 验证自定义数据目录（目录需存在）
+pub fn validate_data_dir(path: &str) -> bool {
+    !path.trim().is_empty() && PathBuf::from(path).is_dir()
+}
+
 /// 清除自定义路径配置
 #[allow(dead_code)]
 pub fn clear_custom_path() -> Result<(), String> {
@@ -77,8 +145,144 @@ pub fn clear_custom_path() -> Result<(), String> {
     Ok(())
 }
 
-/// 验证可执行文件路径是否有效
+/// 可执行文件验证结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutableValidation {
+    /// 可以保存为自定义路径（签名问题不影响）
+    pub valid: bool,
+    pub exists: bool,
+    /// 具有可执行权限（.app 包检查其中的主程序）
+    pub executable: bool,
+    /// .app 包结构是否完整（不是 .app 包时为 None）
+    pub bundle_valid: Option<bool>,
+    /// macOS 代码签名是否有效（非 macOS 或无法检查时为 None）
+    pub signature_valid: Option<bool>,
+    /// 从安装目录读取的 Antigravity 版本
+    pub version: Option<String>,
+    /// 发现的问题
+    pub problems: Vec<String>,
+}
+
+fn is_app_bundle(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "app")
+}
+
+/// 文件是否具有可执行权限
+fn is_executable_file(path: &Path) -> bool {
+    if !path.is_file() {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(windows)]
+    {
+        path.extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        true
+    }
+}
+
+/// .app 包中的主程序（Contents/MacOS 下的可执行文件）
+fn bundle_main_executable(bundle: &Path) -> Option<PathBuf> {
+    fs::read_dir(bundle.join("Contents").join("MacOS"))
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| is_executable_file(path))
+}
+
+/// 检查 macOS 代码签名
+#[cfg(target_os = "macos")]
+fn check_signature(path: &Path) -> Option<bool> {
+    std::process::Command::new("codesign")
+        .args(["--verify", "--deep", "--strict"])
+        .arg(path)
+        .output()
+        .ok()
+        .map(|output| output.status.success())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn check_signature(_path: &Path) -> Option<bool> {
+    None
+}
+
+/// 检查路径是否存在、可执行及 .app 包结构（不检查签名与版本）
+fn inspect_executable(path: &Path) -> (bool, bool, Option<bool>, Vec<String>) {
+    let mut problems = Vec::new();
+    let exists = path.exists();
+    if !exists {
+        problems.push("文件不存在".to_string());
+        return (false, false, None, problems);
+    }
+
+    if is_app_bundle(path) {
+        let has_plist = path.join("Contents").join("Info.plist").is_file();
+        let main_executable = bundle_main_executable(path);
+        if !has_plist {
+            problems.push(".app 包缺少 Contents/Info.plist".to_string());
+        }
+        if main_executable.is_none() {
+            problems.push(".app 包的 Contents/MacOS 中没有可执行文件".to_string());
+        }
+        let executable = main_executable.is_some();
+        return (true, executable, Some(has_plist && executable), problems);
+    }
+
+    let executable = is_executable_file(path);
+    if path.is_dir() {
+        problems.push("路径是目录，请选择可执行文件".to_string());
+    } else if !executable {
+        problems.push("文件没有可执行权限".to_string());
+    }
+    (true, executable, None, problems)
+}
+
+/// 完整验证用户选择的可执行文件：存在性、可执行权限、.app 包结构、macOS 签名，并读取版本
+pub fn validate_custom_executable(path: &str) -> ExecutableValidation {
+    let path = PathBuf::from(path);
+    let (exists, executable, bundle_valid, mut problems) = inspect_executable(&path);
+    let valid = exists && executable && bundle_valid != Some(false);
+
+    let signature_valid = valid.then(|| check_signature(&path)).flatten();
+    if signature_valid == Some(false) {
+        problems.push("代码签名无效或未签名".to_string());
+    }
+
+    let version = valid
+        .then(|| crate::antigravity::version::read_version(&path))
+        .flatten();
+    if valid && version.is_none() {
+        problems.push("未能读取版本信息，可能不是 Antigravity".to_string());
+    }
+
+    tracing::debug!(
+        target: "antigravity::path_config",
+        valid,
+        version = ?version,
+        problems = problems.len(),
+        "已验证可执行文件"
+    );
+    ExecutableValidation {
+        valid,
+        exists,
+        executable,
+        bundle_valid,
+        signature_valid,
+        version,
+        problems,
+    }
+}
+
+/// 验证可执行文件路径是否有效（存在、可执行，.app 包结构完整）
 pub fn validate_executable_path(path: &str) -> bool {
-    let path_buf = PathBuf::from(path);
-    path_buf.exists() && path_buf.is_file()
+    let (exists, executable, bundle_valid, _) = inspect_executable(Path::new(path));
+    exists && executable && bundle_valid != Some(false)
 }
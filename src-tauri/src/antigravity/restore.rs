@@ -2,119 +2,379 @@
 // 负责将备份数据恢复到 Antigravity 应用数据库
 
 use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // 导入相关模块
+use crate::antigravity::backup::BACKUP_META_KEY;
+use crate::antigravity::backup_reader::BackupReadStats;
+use crate::antigravity::cross_platform::PlatformNormalization;
+use crate::antigravity::key_mapping::EffectiveKeys;
 use crate::constants::database;
+use crate::error::{AgentError, AgentResult};
+use crate::hooks::HookEvent;
 use crate::platform;
 
+/// 恢复模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreMode {
+    /// 单个键写入失败时记录并继续（默认）
+    #[default]
+    Lenient,
+    /// 任一键失败即中止并回滚该数据库的事务，不再处理后续数据库
+    Strict,
+}
+
+/// 对单个键执行的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAction {
+    Write,
+    Delete,
+}
+
+/// 单个键的处理结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyStatus {
+    Ok,
+    /// 备份中缺少该字段或类型不符
+    Skipped,
+    Failed,
+}
+
+/// 单个键的恢复记录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRestoreResult {
+    pub key: String,
+    pub action: KeyAction,
+    pub status: KeyStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 单个数据库的恢复结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseRestoreReport {
+    /// 数据库文件名，如 `state.vscdb`
+    pub database: String,
+    pub keys: Vec<KeyRestoreResult>,
+    /// 成功写入的键数量
+    pub restored: usize,
+    pub failed: usize,
+    /// 严格模式下因失败回滚了事务
+    pub rolled_back: bool,
+}
+
+impl DatabaseRestoreReport {
+    fn new(database: &str) -> Self {
+        Self {
+            database: database.to_string(),
+            keys: Vec::new(),
+            restored: 0,
+            failed: 0,
+            rolled_back: false,
+        }
+    }
+
+    fn record(&mut self, key: &str, action: KeyAction, result: Result<(), String>) {
+        let (status, error) = match result {
+            Ok(()) => (KeyStatus::Ok, None),
+            Err(e) => (KeyStatus::Failed, Some(e)),
+        };
+        match (status, action) {
+            (KeyStatus::Ok, KeyAction::Write) => self.restored += 1,
+            (KeyStatus::Failed, _) => self.failed += 1,
+            _ => {}
+        }
+        self.keys.push(KeyRestoreResult {
+            key: key.to_string(),
+            action,
+            status,
+            error,
+        });
+    }
+
+    fn skip(&mut self, key: &str, reason: &str) {
+        self.keys.push(KeyRestoreResult {
+            key: key.to_string(),
+            action: KeyAction::Write,
+            status: KeyStatus::Skipped,
+            error: Some(reason.to_string()),
+        });
+    }
+}
+
+/// 账户恢复报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreReport {
+    pub mode: RestoreMode,
+    pub databases: Vec<DatabaseRestoreReport>,
+    /// 已处理的数据库中没有失败的键
+    pub success: bool,
+    /// 简要结果描述
+    pub message: String,
+    /// 备份来自其他平台时的路径转换结果
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<PlatformNormalization>,
+    /// 备份文件大小与实际读入内存的数据量
+    pub source: BackupReadStats,
+}
+
+/// 在单个事务中恢复一个数据库：写回 AGENT_STATE 与额外备份的键，删除 AUTH_STATUS 等需要重新生成的键
+///
+/// 宽松模式下失败的语句不影响其余写入，事务照常提交；严格模式下首个失败即回滚
+fn restore_database(
+    db_path: &Path,
+    db_name: &str,
+    account_data: &Value,
+    keys: &EffectiveKeys,
+    mode: RestoreMode,
+) -> AgentResult<DatabaseRestoreReport> {
+    tracing::info!(target: "restore::database", db_name = %db_name, mode = ?mode, "开始恢复数据库（jetskiStateSync.agentManagerInitState 与额外备份的键，移除 antigravityAuthStatus）");
+    let mut conn = Connection::open(db_path)?;
+    let tx = conn.transaction()?;
+    let mut report = DatabaseRestoreReport::new(db_name);
+
+    // 账户状态在备份文件中固定使用默认键名，写入时使用映射后的键名；额外的键原样写回
+    let agent_state_key = &keys.backup[0];
+    let writes = std::iter::once((database::AGENT_STATE, agent_state_key)).chain(
+        keys.extra_backup_keys()
+            .iter()
+            .map(|key| (key.as_str(), key)),
+    );
+    for (field, db_key) in writes {
+        if mode == RestoreMode::Strict && report.failed > 0 {
+            break;
+        }
+        match account_data.get(field).map(Value::as_str) {
+            Some(Some(val_str)) => {
+                let result = tx
+                    .execute(
+                        "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
+                        params![db_key, val_str],
+                    )
+                    .map(|_| ())
+                    .map_err(|e| e.to_string());
+                match &result {
+                    Ok(()) => {
+                        tracing::debug!(target: "restore::database", key = %db_key, "注入数据成功")
+                    }
+                    Err(e) => {
+                        tracing::error!(target: "restore::database", key = %db_key, error = %e, "写入数据失败")
+                    }
+                }
+                report.record(db_key, KeyAction::Write, result);
+            }
+            Some(None) => {
+                tracing::warn!(target: "restore::database", key = %field, "字段不是字符串类型，跳过");
+                report.skip(db_key, "备份字段不是字符串类型");
+            }
+            None => {
+                tracing::debug!(target: "restore::database", key = %field, "备份中未找到字段，跳过");
+                report.skip(db_key, "备份中未找到字段");
+            }
+        }
+    }
+
+    for key in &keys.remove_on_restore {
+        if mode == RestoreMode::Strict && report.failed > 0 {
+            break;
+        }
+        let result = tx
+            .execute("DELETE FROM ItemTable WHERE key = ?", [key])
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+        match &result {
+            Ok(()) => tracing::debug!(target: "restore::database", key = %key, "已删除字段"),
+            Err(e) => {
+                tracing::warn!(target: "restore::database", key = %key, error = %e, "删除字段失败")
+            }
+        }
+        report.record(key, KeyAction::Delete, result);
+    }
+
+    if mode == RestoreMode::Strict && report.failed > 0 {
+        drop(tx);
+        report.rolled_back = true;
+        tracing::error!(target: "restore::database", db_name = %db_name, "严格模式下出现失败，已回滚事务");
+    } else {
+        tx.commit()?;
+    }
+    Ok(report)
+}
+
+/// 检查状态数据库中登录的账户是否与备份中的账户一致
+///
+/// 不一致时返回 `SessionMismatch`：不先清除就写入会让数据库中混合两个账户的状态。
+/// 当前未登录、或无法识别任一方的邮箱时视为一致
+pub fn check_session(account_file_path: &Path, db_path: &Path) -> AgentResult<()> {
+    let Some(backup) = crate::antigravity::backup::read_backup_email(account_file_path) else {
+        return Ok(());
+    };
+    let Ok((current, _)) = crate::antigravity::backup::read_account_at(db_path) else {
+        return Ok(());
+    };
+    if current.eq_ignore_ascii_case(&backup) {
+        return Ok(());
+    }
+    tracing::warn!(
+        target: "restore",
+        current = %crate::utils::email_mask::mask_email(&current),
+        backup = %crate::utils::email_mask::mask_email(&backup),
+        "当前登录的账户与备份不同"
+    );
+    Err(AgentError::SessionMismatch { current, backup })
+}
+
 /// 恢复 Antigravity 状态（精简版）
 ///
 /// 从账户文件恢复 jetskiStateSync.agentManagerInitState，并删除 antigravityAuthStatus
 ///
 /// # 参数
 /// - `account_file_path`: 账户 JSON 文件的完整路径
+/// - `mode`: 单个键失败时继续（宽松）或回滚中止（严格）
 ///
 /// # 返回
-/// - `Ok(message)`: 成功消息
-/// - `Err(message)`: 错误信息
+/// - `Ok(report)`: 逐键的恢复记录，严格模式下失败时 `success` 为 false
+/// - `Err(error)`: 读取备份或打开数据库失败
 pub async fn save_antigravity_account_to_file(
     account_file_path: PathBuf,
-) -> Result<String, String> {
-    println!("📂 账户文件: {}", account_file_path.display());
+    mode: RestoreMode,
+) -> AgentResult<RestoreReport> {
+    let app_data = platform::resolve_antigravity_db_path()?;
+    // 读取备份、写入数据库与执行钩子都是阻塞操作
+    crate::utils::blocking::run_blocking(move || {
+        restore_account_file_to(&account_file_path, &app_data, mode)
+    })
+    .await
+}
+
+/// 将账户文件恢复到指定的状态数据库（多个 Antigravity 实例时用于当前使用的安装以外的数据目录）
+pub fn restore_account_file_to(
+    account_file_path: &Path,
+    app_data: &Path,
+    mode: RestoreMode,
+) -> AgentResult<RestoreReport> {
+    tracing::info!(target: "restore", file = %account_file_path.display(), "📂 读取账户文件");
 
     if !account_file_path.exists() {
-        return Err(format!("账户文件不存在: {}", account_file_path.display()));
+        return Err(AgentError::BackupNotFound(
+            account_file_path.display().to_string(),
+        ));
     }
 
-    let content = fs::read_to_string(&account_file_path).map_err(|e| e.to_string())?;
-    let account_data: Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    // 只解析恢复需要的字段，备份中附带的其他大体积数据不读入内存
+    let keys = crate::antigravity::key_mapping::effective_keys();
+    let mut fields = vec![database::AGENT_STATE, BACKUP_META_KEY];
+    fields.extend(keys.extra_backup_keys().iter().map(String::as_str));
+    let (account_data, source) =
+        crate::antigravity::backup_reader::read_fields(account_file_path, &fields)?;
+    // 账户状态含访问令牌，恢复结束后清零
+    let mut account_data = crate::utils::secret::SecretJson::new(account_data);
 
-    println!("✅ 账户文件读取成功");
+    tracing::debug!(
+        target: "restore",
+        file_bytes = source.file_bytes,
+        retained_bytes = source.retained_bytes,
+        "✅ 账户文件读取成功"
+    );
 
-    let app_data = match platform::get_antigravity_db_path() {
-        Some(p) => p,
-        None => {
-            let possible_paths = platform::get_all_antigravity_db_paths();
-            if possible_paths.is_empty() {
-                return Err("未找到 Antigravity 安装位置".to_string());
-            }
-            possible_paths[0].clone()
-        }
-    };
+    // 其他平台创建的备份：改写其中的用户目录路径
+    let platform = crate::antigravity::cross_platform::normalize_backup(&mut account_data);
+
+    crate::hooks::run_hooks(
+        HookEvent::PreRestore,
+        serde_json::json!({ "file": account_file_path.to_string_lossy() }),
+    )?;
 
-    // 确保数据库目录存在
+    // 确保数据库目录存在，并留有写入数据库与日志文件的空间
     if let Some(parent) = app_data.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("创建数据库目录失败: {}", e))?;
+        fs::create_dir_all(parent)?;
+        crate::disk_space::ensure_free_space(parent, "restore")?;
     }
 
-    let mut msg = String::new();
+    let mut databases = Vec::new();
 
-    // 内联恢复逻辑：仅写回 AGENT_STATE 并删除 AUTH_STATUS
-    let restore_db = |db_path: &PathBuf, db_name: &str| -> Result<usize, String> {
-        tracing::info!(target: "restore::database", db_name = %db_name, "开始恢复数据库（仅 jetskiStateSync.agentManagerInitState，移除 antigravityAuthStatus）");
-        let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-
-        let mut restored_count = 0;
+    // 恢复主库
+    tracing::info!(target: "restore", "📊 步骤1: 恢复 state.vscdb 数据库");
+    let main = restore_database(app_data, "state.vscdb", &account_data, &keys, mode)?;
+    tracing::info!(target: "restore", restored = main.restored, failed = main.failed, "✅ 主库恢复完成");
+    let mut success = main.failed == 0;
+    databases.push(main);
 
-        if let Some(val) = account_data.get(database::AGENT_STATE) {
-            if let Some(val_str) = val.as_str() {
-                match conn.execute(
-                    "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
-                    params![database::AGENT_STATE, val_str],
-                ) {
-                    Ok(_) => {
-                        tracing::debug!(target: "restore::database", key = %database::AGENT_STATE, "注入数据成功");
-                        restored_count += 1;
-                    }
-                    Err(e) => {
-                        tracing::error!(target: "restore::database", key = %database::AGENT_STATE, error = %e, "写入数据失败");
-                    }
-                }
-            } else {
-                tracing::warn!(target: "restore::database", key = %database::AGENT_STATE, "字段不是字符串类型，跳过");
+    // 恢复账户库（如果有）；严格模式下主库已回滚则不再继续
+    tracing::info!(target: "restore", "💾 步骤2: 恢复 state.vscdb.backup");
+    let backup_db = app_data.with_extension("vscdb.backup");
+    if mode == RestoreMode::Strict && !success {
+        tracing::warn!(target: "restore", "主库恢复失败，跳过账户库");
+    } else if backup_db.exists() {
+        match restore_database(&backup_db, "state.vscdb.backup", &account_data, &keys, mode) {
+            Ok(report) => {
+                tracing::info!(target: "restore", restored = report.restored, failed = report.failed, "✅ 账户库恢复完成");
+                success &= report.failed == 0;
+                databases.push(report);
+            }
+            Err(e) if mode == RestoreMode::Strict => return Err(e),
+            Err(e) => {
+                tracing::warn!(target: "restore", error = %e, "账户库恢复失败（忽略）");
             }
-        } else {
-            tracing::debug!(target: "restore::database", key = %database::AGENT_STATE, "备份中未找到字段，跳过");
-        }
-
-        if let Err(e) = conn.execute(
-            "DELETE FROM ItemTable WHERE key = ?",
-            [database::AUTH_STATUS],
-        ) {
-            tracing::warn!(target: "restore::database", error = %e, "删除 antigravityAuthStatus 失败（忽略）");
-        } else {
-            tracing::debug!(target: "restore::database", "已删除 antigravityAuthStatus");
         }
+    } else {
+        tracing::info!(target: "restore", "ℹ️ 账户数据库不存在，跳过");
+    }
 
-        Ok(restored_count)
+    let msg = databases
+        .iter()
+        .map(|db| {
+            let mut status = format!("{} 恢复 {} 项", db.database, db.restored);
+            if db.failed > 0 {
+                status.push_str(&format!("，失败 {} 项", db.failed));
+            }
+            if db.rolled_back {
+                status.push_str("（已回滚）");
+            }
+            status
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    let message = if success {
+        format!("✅ 恢复成功! {}", msg)
+    } else {
+        format!("❌ 恢复失败: {}", msg)
     };
 
-    // 恢复主库
-    println!("📊 步骤1: 恢复 state.vscdb 数据库");
-    match restore_db(&app_data, "state.vscdb") {
-        Ok(count) => {
-            let status = format!("主库恢复 {} 项", count);
-            println!("  ✅ {}", status);
-            msg.push_str(&status);
+    if success {
+        if let Some(email) = account_data
+            .get(database::AGENT_STATE)
+            .and_then(Value::as_str)
+            .and_then(|state| crate::antigravity::backup::extract_email(state).ok())
+        {
+            crate::account_metadata::touch_recent(&email);
         }
-        Err(e) => return Err(e),
     }
 
-    // 恢复账户库（如果有）
-    println!("💾 步骤2: 恢复 state.vscdb.backup");
-    let backup_db = app_data.with_extension("vscdb.backup");
-    if backup_db.exists() {
-        if let Ok(count) = restore_db(&backup_db, "state.vscdb.backup") {
-            let status = format!("; 账户库恢复 {} 项", count);
-            println!("  ✅ {}", status);
-            msg.push_str(&status);
-        }
-    } else {
-        println!("  ℹ️ 账户数据库不存在，跳过");
-    }
+    crate::hooks::run_hooks(
+        HookEvent::PostRestore,
+        serde_json::json!({
+            "file": account_file_path.to_string_lossy(),
+            "result": message,
+            "success": success,
+        }),
+    )?;
 
-    Ok(format!("✅ 恢复成功! {}", msg))
+    Ok(RestoreReport {
+        mode,
+        databases,
+        success,
+        message,
+        platform,
+        source,
+    })
 }
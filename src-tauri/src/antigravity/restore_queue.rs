@@ -0,0 +1,197 @@
+//! 恢复队列
+//! 同一台机器上运行多个 Antigravity 实例（原生安装、Flatpak、自定义数据目录等）时，
+//! 按顺序把多个账户备份分别恢复到各自的数据目录，逐项返回结果，单项失败不影响后续项目。
+//! 执行前确认每个目标安装都已关闭（或按需关闭），写入每个数据目录前都会为其数据库创建快照
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::antigravity::backup::BackupTrigger;
+use crate::antigravity::restore::{self, RestoreMode, RestoreReport};
+use crate::error::{AgentError, AgentResult};
+use crate::tasks::TaskHandle;
+
+/// 队列中的一项：把哪个备份恢复到哪个数据目录
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreQueueItem {
+    /// 账户名（邮箱）
+    pub account_name: String,
+    /// 目标数据目录（存放 state.vscdb 的目录或 Antigravity 根数据目录），为空时使用当前使用的安装
+    #[serde(default)]
+    pub data_dir: Option<String>,
+    /// 目标数据目录登录了其他账户时先清除其会话（否则该项返回 `SessionMismatch`）
+    #[serde(default)]
+    pub replace_session: bool,
+}
+
+/// 单项的处理状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreQueueStatus {
+    Restored,
+    /// 出错，或严格模式下有键写入失败并已回滚
+    Failed,
+    /// 任务取消后未执行
+    Skipped,
+}
+
+/// 单项的处理结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreQueueItemResult {
+    pub account_name: String,
+    /// 实际写入的状态数据库（无法确定目标时为 None）
+    pub db_path: Option<String>,
+    pub status: RestoreQueueStatus,
+    /// 恢复前是否清除了目标数据目录中其他账户的会话
+    pub replaced_session: bool,
+    pub report: Option<RestoreReport>,
+    pub error: Option<AgentError>,
+}
+
+/// 目标数据目录中的状态数据库（未指定时为当前使用的安装）
+fn target_db_path(data_dir: Option<&str>) -> AgentResult<PathBuf> {
+    let Some(dir) = data_dir.map(str::trim).filter(|dir| !dir.is_empty()) else {
+        return crate::platform::resolve_antigravity_db_path();
+    };
+    let db_path =
+        crate::antigravity::path_config::resolve_data_dir(Path::new(dir)).join("state.vscdb");
+    if !db_path.is_file() {
+        return Err(AgentError::DatabaseNotFound(db_path));
+    }
+    Ok(db_path)
+}
+
+/// 校验队列并解析每一项的目标数据库；同一数据库只能出现一次，否则后面的项会覆盖前面的结果
+fn resolve_targets(items: &[RestoreQueueItem]) -> AgentResult<Vec<AgentResult<PathBuf>>> {
+    if items.is_empty() {
+        return Err(AgentError::InvalidInput("恢复队列为空".to_string()));
+    }
+    let targets: Vec<AgentResult<PathBuf>> = items
+        .iter()
+        .map(|item| target_db_path(item.data_dir.as_deref()))
+        .collect();
+    for (index, target) in targets.iter().enumerate() {
+        let Ok(path) = target else {
+            continue;
+        };
+        if targets[..index]
+            .iter()
+            .any(|other| other.as_ref().is_ok_and(|other| other == path))
+        {
+            return Err(AgentError::InvalidInput(format!(
+                "数据目录 {} 在恢复队列中出现了多次",
+                path.display()
+            )));
+        }
+    }
+    Ok(targets)
+}
+
+/// 恢复单项：确认会话（需要时先清除），为目标数据库创建快照，再写入备份
+fn restore_item(
+    item: &RestoreQueueItem,
+    db_path: &Path,
+    mode: RestoreMode,
+) -> AgentResult<(RestoreReport, bool)> {
+    let account_file = crate::antigravity::backup_naming::require_backup_file(&item.account_name)?;
+    let replace = match restore::check_session(&account_file, db_path) {
+        Err(AgentError::SessionMismatch { .. }) if item.replace_session => true,
+        result => {
+            result?;
+            false
+        }
+    };
+
+    crate::antigravity::db_snapshot::snapshot_before_at("restore_queue", db_path)?;
+    if replace {
+        crate::antigravity::cleanup::clear_antigravity_data_at(
+            db_path,
+            BackupTrigger::BeforeSwitch,
+        )?;
+    }
+    let report = restore::restore_account_file_to(&account_file, db_path, mode)?;
+    Ok((report, replace))
+}
+
+/// 按顺序执行恢复队列（调用方需持有 StateDb 与 BackupDir 操作锁）
+///
+/// 执行前检查所有目标数据目录对应的 Antigravity 进程：`close_antigravity` 为 true 时关闭，
+/// 否则仍在运行时返回 `AntigravityRunning`。任务取消后剩余的项目标记为跳过
+pub fn run(
+    items: &[RestoreQueueItem],
+    mode: RestoreMode,
+    close_antigravity: bool,
+    task: &TaskHandle,
+) -> AgentResult<Vec<RestoreQueueItemResult>> {
+    let targets = resolve_targets(items)?;
+    let data_dirs: Vec<PathBuf> = targets
+        .iter()
+        .filter_map(|target| target.as_ref().ok())
+        .filter_map(|db_path| db_path.parent().map(Path::to_path_buf))
+        .collect();
+    crate::platform::ensure_installs_stopped(&data_dirs, close_antigravity)?;
+    let total = Some(items.len() as u64);
+
+    let mut results = Vec::with_capacity(items.len());
+    for (index, (item, target)) in items.iter().zip(targets).enumerate() {
+        let mut result = RestoreQueueItemResult {
+            account_name: item.account_name.clone(),
+            db_path: target.as_ref().ok().map(|path| path.display().to_string()),
+            status: RestoreQueueStatus::Skipped,
+            replaced_session: false,
+            report: None,
+            error: None,
+        };
+        if task.checkpoint().is_err() {
+            results.push(result);
+            continue;
+        }
+        task.progress(
+            index as u64,
+            total,
+            format!(
+                "正在恢复 {} ({}/{})",
+                item.account_name,
+                index + 1,
+                items.len()
+            ),
+        );
+
+        match target.and_then(|db_path| restore_item(item, &db_path, mode)) {
+            Ok((report, replaced)) => {
+                result.status = if report.success {
+                    RestoreQueueStatus::Restored
+                } else {
+                    RestoreQueueStatus::Failed
+                };
+                result.replaced_session = replaced;
+                result.report = Some(report);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    target: "restore::queue",
+                    account = %crate::utils::email_mask::mask_email(&item.account_name),
+                    error = %e,
+                    "恢复队列中的项目失败"
+                );
+                result.status = RestoreQueueStatus::Failed;
+                result.error = Some(e);
+            }
+        }
+        results.push(result);
+    }
+
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    tracing::info!(
+        target: "restore::queue",
+        total = results.len(),
+        restored = count(RestoreQueueStatus::Restored),
+        failed = count(RestoreQueueStatus::Failed),
+        skipped = count(RestoreQueueStatus::Skipped),
+        "恢复队列执行完成"
+    );
+    task.progress(items.len() as u64, total, "恢复队列执行完成");
+    Ok(results)
+}
@@ -0,0 +1,173 @@
+//! 备份脱敏导出
+//! 生成去除令牌等凭据的备份副本，便于附在问题反馈中或交给他人排查问题。
+//! 副本保留原有结构：账户状态解码后清空令牌再重新编码，其他字段按键名识别凭据并替换
+
+use base64::Engine;
+use prost::Message;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::constants::database;
+use crate::error::{AgentError, AgentResult};
+
+/// 替换凭据后的占位值
+const REDACTED: &str = "[REDACTED]";
+
+/// 脱敏信息在副本中的键名
+const SANITIZED_META_KEY: &str = "antigravityAgent.sanitized";
+
+/// 脱敏导出结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SanitizedExport {
+    /// 导出的副本路径
+    pub path: PathBuf,
+    /// 被替换的字段（账户状态内的字段以 `agentState.` 开头）
+    pub redacted_fields: Vec<String>,
+}
+
+/// 清空账户状态中的令牌与用户标识，返回重新编码后的状态
+fn sanitize_agent_state(state: &str, redacted: &mut Vec<String>) -> AgentResult<String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let bytes = engine.decode(state.trim()).map_err(|e| {
+        AgentError::Serialization(format!("jetskiStateSync Base64 解码失败: {}", e))
+    })?;
+    let mut session = crate::proto::SessionResponse::decode(bytes.as_slice()).map_err(|e| {
+        AgentError::Serialization(format!("jetskiStateSync Protobuf 解码失败: {}", e))
+    })?;
+
+    if let Some(auth) = session.auth.as_mut() {
+        for (name, token) in [
+            ("agentState.auth.accessToken", &mut auth.access_token),
+            ("agentState.auth.idToken", &mut auth.id_token),
+        ] {
+            if !token.is_empty() {
+                *token = REDACTED.to_string();
+                redacted.push(name.to_string());
+            }
+        }
+    }
+    if !session.user_id_raw.is_empty() {
+        session.user_id_raw = REDACTED.as_bytes().to_vec();
+        redacted.push("agentState.userId".to_string());
+    }
+
+    Ok(engine.encode(session.encode_to_vec()))
+}
+
+/// 键名是否疑似凭据
+fn is_secret_key(key: &str) -> bool {
+    let lower = key.to_ascii_lowercase();
+    crate::antigravity::key_mapping::looks_like_auth_key(key)
+        || ["secret", "password", "cookie", "apikey", "api_key"]
+            .iter()
+            .any(|hint| lower.contains(hint))
+}
+
+/// 递归替换疑似凭据的字段，保留其余结构
+fn redact_value(path: &str, value: &mut Value, redacted: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                let child_path = format!("{path}.{key}");
+                if is_secret_key(key) && !child.is_null() {
+                    *child = Value::String(REDACTED.to_string());
+                    redacted.push(child_path);
+                } else {
+                    redact_value(&child_path, child, redacted);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                redact_value(&format!("{path}[{index}]"), item, redacted);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 生成去除凭据的备份内容
+fn sanitize_backup(mut content: Value) -> AgentResult<(Value, Vec<String>)> {
+    let Some(map) = content.as_object_mut() else {
+        return Err(AgentError::Serialization(
+            "备份文件格式无效：顶层不是对象".to_string(),
+        ));
+    };
+
+    let mut redacted = Vec::new();
+    for (key, value) in map.iter_mut() {
+        match value {
+            Value::String(state) if key == database::AGENT_STATE => {
+                *state = sanitize_agent_state(state, &mut redacted)?;
+            }
+            // 备份元数据不含凭据
+            _ if key == crate::antigravity::backup::BACKUP_META_KEY => {}
+            _ if is_secret_key(key) => {
+                *value = Value::String(REDACTED.to_string());
+                redacted.push(key.clone());
+            }
+            // 额外备份的键常以 JSON 字符串保存，解析后逐字段处理
+            Value::String(raw) => {
+                if let Ok(mut parsed @ (Value::Object(_) | Value::Array(_))) =
+                    serde_json::from_str::<Value>(raw)
+                {
+                    let before = redacted.len();
+                    redact_value(key, &mut parsed, &mut redacted);
+                    if redacted.len() > before {
+                        *raw = parsed.to_string();
+                    }
+                }
+            }
+            other => redact_value(key, other, &mut redacted),
+        }
+    }
+
+    map.insert(
+        SANITIZED_META_KEY.to_string(),
+        serde_json::json!({
+            "sanitizedAt": chrono::Local::now().to_rfc3339(),
+            "redactedFields": redacted,
+        }),
+    );
+    Ok((content, redacted))
+}
+
+/// 导出指定账户备份的脱敏副本
+pub fn export_sanitized_backup(name: &str) -> AgentResult<SanitizedExport> {
+    let source = crate::antigravity::backup_naming::require_backup_file(name)?;
+    let content: Value = serde_json::from_str(&fs::read_to_string(&source)?)?;
+    let (sanitized, redacted_fields) = sanitize_backup(content)?;
+
+    let dir = crate::directories::get_sanitized_exports_directory();
+    fs::create_dir_all(&dir)?;
+    let stem = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "backup".to_string());
+    let path = dir.join(format!(
+        "{}-sanitized-{}.json",
+        stem,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::write(&path, serde_json::to_string_pretty(&sanitized)?)?;
+
+    tracing::info!(
+        target: "backup::sanitize",
+        path = %path.display(),
+        redacted = redacted_fields.len(),
+        "✅ 已导出脱敏备份"
+    );
+    crate::audit::record(
+        "backup",
+        "export_sanitized",
+        true,
+        serde_json::json!({ "file": path.to_string_lossy(), "redacted": redacted_fields.len() }),
+    );
+    Ok(SanitizedExport {
+        path,
+        redacted_fields,
+    })
+}
@@ -2,55 +2,168 @@
 ///
 /// 提供跨平台的 Antigravity 应用程序启动功能
 /// 支持 Windows、macOS 和 Linux 系统
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
-/// 启动 Antigravity 应用程序（主入口函数）
-///
-/// # 返回值
+use crate::account_metadata::LaunchProfile;
+use crate::platform::launched::track;
+use crate::proxy::ProxySettings;
+
+/// 启动前发现 Antigravity 已在运行时的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchPolicy {
+    /// 切换到已运行的实例，不启动新实例（仅 macOS 能激活窗口）
+    #[default]
+    Focus,
+    /// 拒绝启动并提示
+    Refuse,
+    /// 仍然启动新实例
+    NewInstance,
+}
+
+/// 启动选项：账户启动配置或全局默认值
+struct LaunchOptions {
+    proxy: ProxySettings,
+    /// 传给 Antigravity 的参数
+    args: Vec<String>,
+}
+
+impl LaunchOptions {
+    /// `reopen_workspace`：重启前打开的工作区（启动配置中指定了工作区时忽略）
+    fn new(profile: Option<&LaunchProfile>, reopen_workspace: Option<&Path>) -> Self {
+        let mut args = profile.map(LaunchProfile::app_args).unwrap_or_default();
+        if let Some(workspace) = reopen_workspace {
+            if profile.is_none_or(|p| p.workspace.is_none()) {
+                args.push(workspace.to_string_lossy().to_string());
+            }
+        }
+
+        Self {
+            proxy: profile
+                .and_then(|p| p.proxy.clone())
+                .unwrap_or_else(crate::proxy::load),
+            args,
+        }
+    }
+
+    /// `open` 命令需要通过 `--args` 转发参数
+    #[cfg(target_os = "macos")]
+    fn open_args(&self) -> Vec<String> {
+        if self.args.is_empty() {
+            Vec::new()
+        } else {
+            std::iter::once("--args".to_string())
+                .chain(self.args.iter().cloned())
+                .collect()
+        }
+    }
+}
+
+/// 创建启动命令，并注入代理设置中的环境变量
 ///
-/// * `Ok(String)` - 启动成功，返回成功消息
-/// * `Err(String)` - 启动失败，返回错误信息
+/// 注意：macOS 上通过 `open` 启动的应用由 LaunchServices 拉起，不会继承这些环境变量
+fn launch_command<S: AsRef<std::ffi::OsStr>>(program: S, options: &LaunchOptions) -> Command {
+    let mut command = Command::new(program);
+    options.proxy.apply_to_command(&mut command);
+    command
+}
+
+/// 启动 Antigravity 应用程序（主入口函数）
 ///
-/// # 示例
+/// * `profile` - 账户启动配置（为 None 时使用全局默认值）
+/// * `reopen_workspace` - 重启前打开的工作区（见 `workspace::capture_for_restart`）
 ///
-/// ```rust
-/// match antigravity_starter::start_antigravity() {
-///     Ok(msg) => println!("启动成功: {}", msg),
-///     Err(e) => println!("启动失败: {}", e),
-/// }
-/// ```
-pub fn start_antigravity() -> Result<String, String> {
+/// 启动成功或按启动策略无需启动时返回提示消息，失败时返回错误信息
+pub fn start_antigravity_with_profile(
+    profile: Option<&LaunchProfile>,
+    reopen_workspace: Option<&Path>,
+) -> Result<String, String> {
+    if let Some(message) = check_running_instance()? {
+        return Ok(message);
+    }
+
+    let options = LaunchOptions::new(profile, reopen_workspace);
+
     // 优先使用用户配置的可执行文件路径
     if let Ok(Some(custom_exec)) = crate::antigravity::path_config::get_custom_executable_path() {
         let path = PathBuf::from(&custom_exec);
         if path.exists() && path.is_file() {
-            tracing::info!("📁 使用自定义 Antigravity 可执行文件: {}", custom_exec);
-            return try_start_from_path(&path)
+            tracing::info!(target: "antigravity::starter", path = %custom_exec, "使用自定义 Antigravity 可执行文件");
+            return try_start_from_path(&path, &options)
                 .map_err(|e| format!("无法启动自定义 Antigravity: {}. 请检查路径是否正确", e));
         } else {
-            tracing::warn!("⚠️ 自定义可执行文件路径无效: {}", custom_exec);
+            tracing::warn!(target: "antigravity::starter", path = %custom_exec, "自定义可执行文件路径无效");
         }
     }
 
+    // 其次使用用户选择的安装
+    if let Some(path) = crate::platform::installs::selected_executable() {
+        tracing::info!(target: "antigravity::starter", path = %path.display(), "使用所选安装的 Antigravity 可执行文件");
+        return try_start_from_path(&path, &options);
+    }
+
     // 回退到自动检测
     match std::env::consts::OS {
-        "windows" => start_antigravity_windows(),
-        "macos" => start_antigravity_macos(),
-        "linux" => start_antigravity_linux(),
+        "windows" => start_antigravity_windows(&options),
+        "macos" => start_antigravity_macos(&options),
+        "linux" => start_antigravity_linux(&options),
         _ => Err("不支持的操作系统".to_string()),
     }
 }
 
+/// 按启动策略处理已运行的实例
+///
+/// 返回 `Some(消息)` 表示不需要启动新实例
+fn check_running_instance() -> Result<Option<String>, String> {
+    let policy =
+        crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file())
+            .launch_policy;
+
+    match policy {
+        LaunchPolicy::NewInstance => Ok(None),
+        _ if !crate::platform::is_antigravity_running() => Ok(None),
+        LaunchPolicy::Refuse => {
+            tracing::warn!(target: "antigravity::starter", "Antigravity 已在运行，按启动策略拒绝启动");
+            Err("Antigravity 已在运行，未启动新实例（可在设置中修改启动策略）".to_string())
+        }
+        LaunchPolicy::Focus => Ok(Some(focus_running_instance())),
+    }
+}
+
+/// 切换到已运行的 Antigravity 窗口
+fn focus_running_instance() -> String {
+    #[cfg(target_os = "macos")]
+    {
+        // 不带 -n 的 open -a 只会激活已运行的应用
+        match Command::new("open").args(["-a", "Antigravity"]).status() {
+            Ok(status) if status.success() => {
+                tracing::info!(target: "antigravity::starter", "Antigravity 已在运行，已切换到现有窗口");
+                return "Antigravity 已在运行，已切换到现有窗口".to_string();
+            }
+            Ok(status) => {
+                tracing::warn!(target: "antigravity::starter", status = %status, "激活 Antigravity 窗口失败")
+            }
+            Err(e) => {
+                tracing::warn!(target: "antigravity::starter", error = %e, "激活 Antigravity 窗口失败")
+            }
+        }
+    }
+
+    tracing::info!(target: "antigravity::starter", "Antigravity 已在运行，跳过启动");
+    "Antigravity 已在运行，未启动新实例".to_string()
+}
+
 /// 在 Windows 平台启动 Antigravity
-fn start_antigravity_windows() -> Result<String, String> {
+fn start_antigravity_windows(options: &LaunchOptions) -> Result<String, String> {
     let mut errors = Vec::new();
     let antigravity_paths = crate::path_utils::AppPaths::antigravity_executable_paths();
 
     // 尝试所有推测的路径
     for path in &antigravity_paths {
         if path.exists() {
-            match try_start_from_path(path) {
+            match try_start_from_path(path, options) {
                 Ok(_) => {
                     return Ok("Antigravity 已启动".to_string());
                 }
@@ -65,7 +178,7 @@ fn start_antigravity_windows() -> Result<String, String> {
 
     // 尝试从系统 PATH 启动命令
     let commands = vec!["Antigravity", "antigravity"];
-    match try_start_from_commands(commands) {
+    match try_start_from_commands(commands, options) {
         Ok(msg) => Ok(msg),
         Err(e) => {
             errors.push(e);
@@ -78,14 +191,14 @@ fn start_antigravity_windows() -> Result<String, String> {
 }
 
 /// 在 macOS 平台启动 Antigravity
-fn start_antigravity_macos() -> Result<String, String> {
+fn start_antigravity_macos(options: &LaunchOptions) -> Result<String, String> {
     let mut errors = Vec::new();
     let antigravity_paths = crate::path_utils::AppPaths::antigravity_executable_paths();
 
     // 尝试所有推测的路径
     for path in &antigravity_paths {
         if path.exists() {
-            match try_start_from_path(path) {
+            match try_start_from_path(path, options) {
                 Ok(_) => {
                     return Ok("Antigravity 已启动".to_string());
                 }
@@ -100,7 +213,7 @@ fn start_antigravity_macos() -> Result<String, String> {
 
     // 尝试系统 PATH 命令
     let commands = vec!["Antigravity", "antigravity"];
-    match try_start_from_commands(commands) {
+    match try_start_from_commands(commands, options) {
         Ok(msg) => Ok(msg),
         Err(e) => {
             errors.push(e);
@@ -122,14 +235,15 @@ fn start_antigravity_macos() -> Result<String, String> {
 }
 
 /// 在 Linux 平台启动 Antigravity
-fn start_antigravity_linux() -> Result<String, String> {
+fn start_antigravity_linux(options: &LaunchOptions) -> Result<String, String> {
     let antigravity_path = std::path::PathBuf::from("/usr/share/antigravity/antigravity");
 
     if !antigravity_path.exists() {
         return Err("Antigravity 未安装。请先安装 Antigravity 应用。".to_string());
     }
 
-    let mut cmd = std::process::Command::new(&antigravity_path);
+    let mut cmd = launch_command(&antigravity_path, options);
+    cmd.args(&options.args);
 
     // 设置桌面环境变量
     cmd.env("XDG_SESSION_TYPE", "wayland");
@@ -146,14 +260,17 @@ fn start_antigravity_linux() -> Result<String, String> {
         cmd.env("XAUTHORITY", xauthority);
     }
 
-    match cmd.spawn() {
+    match cmd
+        .spawn()
+        .map(|child| track(child, antigravity_path.display().to_string()))
+    {
         Ok(_) => Ok("Antigravity 已启动".to_string()),
         Err(e) => Err(format!("启动 Antigravity 失败: {}", e)),
     }
 }
 
 /// 尝试从指定路径启动应用程序
-fn try_start_from_path(path: &PathBuf) -> Result<String, String> {
+fn try_start_from_path(path: &PathBuf, options: &LaunchOptions) -> Result<String, String> {
     // macOS 需要特殊处理：使用 open 命令启动 .app 应用
     #[cfg(target_os = "macos")]
     {
@@ -165,12 +282,14 @@ fn try_start_from_path(path: &PathBuf) -> Result<String, String> {
         };
 
         // 方法1: 尝试不带 -n 参数的 open 命令（更兼容）
-        match Command::new("open")
+        match launch_command("open", options)
             .arg("-g") // 在后台启动应用
             .arg(&app_bundle_path)
+            .args(options.open_args())
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null())
             .spawn()
+            .map(|child| track(child, "open"))
         {
             Ok(_) => {
                 return Ok("Antigravity 已启动".to_string());
@@ -181,10 +300,12 @@ fn try_start_from_path(path: &PathBuf) -> Result<String, String> {
                 for exec_name in &exec_names {
                     let exec_path = app_bundle_path.join("Contents/MacOS").join(exec_name);
                     if exec_path.exists() {
-                        match Command::new(&exec_path)
+                        match launch_command(&exec_path, options)
+                            .args(&options.args)
                             .stdout(std::process::Stdio::null())
                             .stderr(std::process::Stdio::null())
                             .spawn()
+                            .map(|child| track(child, exec_path.display().to_string()))
                         {
                             Ok(_) => {
                                 return Ok("Antigravity 已启动".to_string());
@@ -197,11 +318,13 @@ fn try_start_from_path(path: &PathBuf) -> Result<String, String> {
                 }
 
                 // 方法3: 最后尝试不带任何参数的 open 命令
-                match Command::new("open")
+                match launch_command("open", options)
                     .arg(&app_bundle_path)
+                    .args(options.open_args())
                     .stdout(std::process::Stdio::null())
                     .stderr(std::process::Stdio::null())
                     .spawn()
+                    .map(|child| track(child, "open"))
                 {
                     Ok(_) => {
                         return Ok("Antigravity 已启动".to_string());
@@ -220,20 +343,24 @@ fn try_start_from_path(path: &PathBuf) -> Result<String, String> {
         // Windows：重定向输出到 null 设备
         #[cfg(target_os = "windows")]
         {
-            Command::new(path)
+            launch_command(path, options)
+                .args(&options.args)
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
                 .spawn()
+                .map(|child| track(child, path.display().to_string()))
                 .map_err(|e| format!("启动失败: {}", e))?;
         }
 
         // Linux：重定向输出到 null 设备
         #[cfg(target_os = "linux")]
         {
-            Command::new(path)
+            launch_command(path, options)
+                .args(&options.args)
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
                 .spawn()
+                .map(|child| track(child, path.display().to_string()))
                 .map_err(|e| format!("启动失败: {}", e))?;
         }
 
@@ -242,14 +369,16 @@ fn try_start_from_path(path: &PathBuf) -> Result<String, String> {
 }
 
 /// 尝试从系统命令启动应用程序（静默启动）
-fn try_start_from_commands(commands: Vec<&str>) -> Result<String, String> {
+fn try_start_from_commands(commands: Vec<&str>, options: &LaunchOptions) -> Result<String, String> {
     let mut errors = Vec::new();
 
     for cmd in commands {
-        match Command::new(cmd)
+        match launch_command(cmd, options)
+            .args(&options.args)
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null())
             .spawn()
+            .map(|child| track(child, cmd))
         {
             Ok(_) => {
                 return Ok("Antigravity 已启动".to_string());
@@ -263,15 +392,28 @@ fn try_start_from_commands(commands: Vec<&str>) -> Result<String, String> {
     Err(format!("所有命令尝试失败: {}", errors.join(", ")))
 }
 
-/// 检测 Antigravity 可执行文件路径（不启动，只检测）
+/// 检测 Antigravity 可执行文件路径（不启动，只检测；使用缓存的检测结果）
 pub fn detect_antigravity_executable() -> Option<PathBuf> {
-    tracing::info!("🔍 开始自动检测 Antigravity 可执行文件...");
+    crate::platform::platform_detection().executable.clone()
+}
+
+/// 遍历候选安装位置查找 Antigravity 可执行文件
+pub(crate) fn scan_antigravity_executable() -> Option<PathBuf> {
+    tracing::info!(target: "antigravity::starter", "开始自动检测 Antigravity 可执行文件");
+
+    // WSL 模式下启动 Windows 上的 Antigravity
+    if crate::platform::wsl::windows_mode_enabled() {
+        if let Some(path) = crate::platform::wsl::windows_executable() {
+            tracing::info!(target: "antigravity::starter", path = %path.display(), "WSL 模式：找到 Windows 上的 Antigravity");
+            return Some(path);
+        }
+    }
 
     let paths = crate::path_utils::AppPaths::antigravity_executable_paths();
 
     let result = paths.into_iter().find(|p| {
         if p.exists() {
-            tracing::info!("✅ 找到 Antigravity 可执行文件: {}", p.display());
+            tracing::info!(target: "antigravity::starter", path = %p.display(), "找到 Antigravity 可执行文件");
             true
         } else {
             false
@@ -279,7 +421,7 @@ pub fn detect_antigravity_executable() -> Option<PathBuf> {
     });
 
     if result.is_none() {
-        tracing::warn!("⚠️ 未能自动检测到 Antigravity 可执行文件");
+        tracing::warn!(target: "antigravity::starter", "未能自动检测到 Antigravity 可执行文件");
     }
 
     result
@@ -0,0 +1,183 @@
+//! Antigravity 版本更新快照模块
+//! 记录上次检测到的 Antigravity 版本，版本变化时自动快照当前账户与用户设置，便于排查更新引起的问题
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+use tokio::time::{interval, Duration};
+
+use crate::antigravity::backup::{self, BackupMeta, BackupTrigger};
+use crate::error::AgentResult;
+use crate::utils::permissions;
+
+/// 版本变化事件名
+pub const VERSION_CHANGED_EVENT: &str = "antigravity:version-changed";
+
+/// 版本检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// 随账户一同快照的 Antigravity 用户配置文件
+const USER_SETTINGS_FILES: &[&str] = &["settings.json", "keybindings.json"];
+
+/// 版本记录文件内容
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct VersionRecord {
+    last_version: Option<String>,
+    checked_at: Option<String>,
+}
+
+/// 版本更新快照结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSnapshot {
+    pub previous_version: String,
+    pub current_version: String,
+    /// 快照目录
+    pub snapshot_dir: String,
+    /// 快照中包含的账户邮箱（未登录时为 None）
+    pub email: Option<String>,
+}
+
+fn read_record(path: &Path) -> VersionRecord {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_record(path: &Path, version: &str) -> AgentResult<()> {
+    let record = VersionRecord {
+        last_version: Some(version.to_string()),
+        checked_at: Some(chrono::Local::now().to_rfc3339()),
+    };
+    fs::write(path, serde_json::to_string_pretty(&record)?)?;
+    Ok(())
+}
+
+/// 文件名中不允许出现的字符替换为 `_`
+fn sanitize_for_path(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Antigravity 用户配置目录（globalStorage 的上一级 User 目录）
+fn antigravity_user_dir() -> Option<PathBuf> {
+    crate::platform::get_antigravity_data_dir().and_then(|dir| dir.parent().map(Path::to_path_buf))
+}
+
+/// 将当前账户与用户设置写入快照目录
+fn create_snapshot(previous: &str, current: &str) -> AgentResult<UpdateSnapshot> {
+    let snapshots_dir = crate::directories::get_update_snapshots_directory();
+    crate::disk_space::ensure_free_space(&snapshots_dir, "update_snapshot")?;
+    let snapshot_dir = snapshots_dir.join(format!(
+        "{}_{}_to_{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        sanitize_for_path(previous),
+        sanitize_for_path(current)
+    ));
+    permissions::create_private_dir(&snapshot_dir)?;
+
+    // 1. 账户状态（未登录时跳过）
+    let email = match backup::read_current_account() {
+        Ok((email, agent_state)) => {
+            let mut meta = BackupMeta::new(BackupTrigger::VersionChange);
+            // 数据库中的账户状态仍来自更新前的版本
+            meta.antigravity_version = Some(previous.to_string());
+            let extras = crate::platform::resolve_antigravity_db_path()
+                .and_then(|db_path| backup::read_extra_values(&db_path))
+                .unwrap_or_default();
+            let content = backup::build_backup_content(agent_state.expose(), &extras, &meta);
+            permissions::write_private(
+                &snapshot_dir.join(format!("{email}.json")),
+                backup::serialize_backup_content(&content)?.as_bytes(),
+            )?;
+            Some(email)
+        }
+        Err(e) => {
+            tracing::warn!(target: "antigravity::update_snapshot", error = %e, "未能读取当前账户，快照中不包含账户数据");
+            None
+        }
+    };
+
+    // 2. 用户设置
+    if let Some(user_dir) = antigravity_user_dir() {
+        for name in USER_SETTINGS_FILES {
+            let src = user_dir.join(name);
+            if src.is_file() {
+                if let Err(e) = permissions::copy_private(&src, &snapshot_dir.join(name)) {
+                    tracing::warn!(target: "antigravity::update_snapshot", file = %name, error = %e, "复制用户设置失败");
+                }
+            }
+        }
+    }
+
+    Ok(UpdateSnapshot {
+        previous_version: previous.to_string(),
+        current_version: current.to_string(),
+        snapshot_dir: snapshot_dir.to_string_lossy().to_string(),
+        email,
+    })
+}
+
+/// 检查 Antigravity 版本是否变化，变化时创建快照
+///
+/// 首次检测只记录版本，不创建快照
+pub fn check_version_and_snapshot() -> AgentResult<Option<UpdateSnapshot>> {
+    let Some(current) = crate::antigravity::version::detect_antigravity_version() else {
+        return Ok(None);
+    };
+
+    let record_path = crate::directories::get_antigravity_version_file();
+    let record = read_record(&record_path);
+
+    let snapshot = match record.last_version {
+        Some(previous) if previous != current => {
+            tracing::info!(
+                target: "antigravity::update_snapshot",
+                previous = %previous,
+                current = %current,
+                "📦 检测到 Antigravity 版本变化，创建快照"
+            );
+            let snapshot = create_snapshot(&previous, &current)?;
+            crate::retention::enforce(crate::retention::SnapshotKind::Update);
+            Some(snapshot)
+        }
+        _ => None,
+    };
+
+    write_record(&record_path, &current)?;
+    Ok(snapshot)
+}
+
+/// 启动后台版本检查（启动时立即检查一次，之后定期检查）
+pub fn spawn_version_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = interval(CHECK_INTERVAL);
+        loop {
+            ticker.tick().await;
+
+            match tokio::task::spawn_blocking(check_version_and_snapshot).await {
+                Ok(Ok(Some(snapshot))) => {
+                    if let Err(e) = app.emit(VERSION_CHANGED_EVENT, &snapshot) {
+                        tracing::error!(target: "antigravity::update_snapshot", "推送版本变化事件失败: {}", e);
+                    }
+                }
+                Ok(Ok(None)) => {}
+                Ok(Err(e)) => {
+                    tracing::warn!(target: "antigravity::update_snapshot", error = %e, "版本检查失败")
+                }
+                Err(e) => {
+                    tracing::error!(target: "antigravity::update_snapshot", error = %e, "版本检查任务异常")
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,57 @@
+//! Antigravity 版本检测模块
+//! 从安装目录中的 `resources/app/package.json` 读取已安装的 Antigravity 版本
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 向上查找安装根目录的最大层数
+const MAX_ANCESTOR_DEPTH: usize = 3;
+
+/// 获取 Antigravity 可执行文件路径（优先使用用户自定义路径）
+fn resolve_executable() -> Option<PathBuf> {
+    crate::antigravity::path_config::get_custom_executable_path()
+        .ok()
+        .flatten()
+        .map(PathBuf::from)
+        .filter(|p| p.exists())
+        .or_else(crate::antigravity::starter::detect_antigravity_executable)
+}
+
+/// 在安装目录中查找 package.json
+///
+/// - Windows/Linux: `<安装目录>/resources/app/package.json`
+/// - macOS: `Antigravity.app/Contents/Resources/app/package.json`
+fn find_package_json(executable: &Path) -> Option<PathBuf> {
+    // Linux 下 /usr/bin/antigravity 通常是指向安装目录的符号链接
+    let executable = fs::canonicalize(executable).unwrap_or_else(|_| executable.to_path_buf());
+
+    executable
+        .ancestors()
+        .take(MAX_ANCESTOR_DEPTH + 1)
+        .flat_map(|base| {
+            [
+                base.join("resources").join("app").join("package.json"),
+                base.join("Contents")
+                    .join("Resources")
+                    .join("app")
+                    .join("package.json"),
+            ]
+        })
+        .find(|p| p.is_file())
+}
+
+/// 读取指定可执行文件所属安装的 Antigravity 版本
+pub fn read_version(executable: &Path) -> Option<String> {
+    let package_json = find_package_json(executable)?;
+
+    let content = fs::read_to_string(&package_json).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+    value.get("version")?.as_str().map(str::to_string)
+}
+
+/// 检测已安装的 Antigravity 版本
+pub fn detect_antigravity_version() -> Option<String> {
+    let version = read_version(&resolve_executable()?)?;
+    tracing::debug!(target: "antigravity::version", version = %version, "检测到 Antigravity 版本");
+    Some(version)
+}
@@ -0,0 +1,52 @@
+//! 重启时恢复打开的工作区
+//! 关闭 Antigravity 前从状态数据库的最近打开列表中读取上次打开的文件夹或工作区文件，
+//! 重新启动时作为参数传回，避免重启后停留在欢迎页。远程工作区（非 file:// 地址）不恢复
+
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// 最近打开列表在 ItemTable 中的键
+const RECENTLY_OPENED_KEY: &str = "history.recentlyOpenedPathsList";
+
+/// 读取最近打开的文件夹或工作区文件
+pub fn last_opened_workspace() -> Option<PathBuf> {
+    let db_path = crate::platform::resolve_antigravity_db_path().ok()?;
+    let conn = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY).ok()?;
+    let raw: String = conn
+        .query_row(
+            "SELECT value FROM ItemTable WHERE key = ?1",
+            [RECENTLY_OPENED_KEY],
+            |row| row.get(0),
+        )
+        .optional()
+        .ok()
+        .flatten()?;
+    let list: Value = serde_json::from_str(&raw).ok()?;
+
+    list["entries"].as_array()?.iter().find_map(|entry| {
+        let uri = entry["folderUri"]
+            .as_str()
+            .or_else(|| entry["workspace"]["configPath"].as_str())?;
+        let path = tauri::Url::parse(uri).ok()?.to_file_path().ok()?;
+        path.exists().then_some(path)
+    })
+}
+
+/// 重启流程中关闭 Antigravity 前调用：设置开启时返回需要重新打开的工作区
+pub fn capture_for_restart() -> Option<PathBuf> {
+    let settings =
+        crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file());
+    if !settings.reopen_workspace_on_restart {
+        return None;
+    }
+
+    let workspace = last_opened_workspace();
+    match &workspace {
+        Some(path) => {
+            tracing::info!(target: "antigravity::workspace", path = %path.display(), "已记录重启前打开的工作区")
+        }
+        None => tracing::debug!(target: "antigravity::workspace", "未找到可恢复的工作区"),
+    }
+    workspace
+}
@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use tauri::AppHandle;
 
+use crate::error::{AgentError, AgentResult};
+
 /// 应用程序设置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -16,6 +20,70 @@ pub struct AppSettings {
     pub debug_mode: bool,
     /// 隐私模式：用户信息打码（邮箱/用户名）
     pub private_mode: bool,
+    /// 是否已完成首次运行引导
+    pub setup_completed: bool,
+    /// 检测到 Antigravity 内登录账户变化时自动备份新账户
+    pub auto_backup_on_account_change: bool,
+    /// 是否启用本地 HTTP 控制接口
+    pub http_api_enabled: bool,
+    /// 本地 HTTP 控制接口端口
+    pub http_api_port: u16,
+    /// 本地 HTTP 控制接口访问令牌（首次启用时随机生成）
+    pub http_api_token: Option<String>,
+    /// 是否在后台定期检查 Agent 更新
+    pub auto_check_updates: bool,
+    /// 自定义更新源地址（为空时使用 tauri.conf.json 中的 GitHub Releases 地址）
+    pub update_endpoint: Option<String>,
+    /// 代理设置（用于启动 Antigravity 与 Agent 自身的网络请求）
+    pub proxy: crate::proxy::ProxySettings,
+    /// 只读模式：拒绝所有修改 Antigravity 数据库、账户备份与设置的操作（用于演示或共享电脑）
+    pub read_only_mode: bool,
+    /// 仅托盘运行时隐藏 Dock 图标（仅 macOS）
+    pub hide_dock_icon: bool,
+    /// 清除登录数据等危险操作需要先申请确认令牌
+    pub confirm_dangerous_operations: bool,
+    /// 恢复、清除数据库前整体复制 state.vscdb 到 db-snapshots 目录
+    pub db_snapshot_before_write: bool,
+    /// 清除登录数据前先备份当前登录的账户
+    pub backup_before_clear: bool,
+    /// 日志、托盘与导出中邮箱的打码风格
+    pub email_mask_style: crate::utils::email_mask::EmailMaskStyle,
+    /// 新备份文件的命名模板（支持 `{email}` `{alias}` `{date}` `{hash}` `{id}`）
+    pub backup_file_name_template: String,
+    /// 当前账户超过多少天未备份时提醒（0 表示不提醒）
+    pub backup_stale_days: u32,
+    /// 日志信息、备份列表与审计记录中时间的显示语言与时区
+    pub time_display: crate::utils::time_format::TimeDisplaySettings,
+    /// 启动 Antigravity 时发现已有实例在运行的处理策略
+    pub launch_policy: crate::antigravity::starter::LaunchPolicy,
+    /// 重启 Antigravity（切换账户、登录新账户等）后重新打开之前的工作区
+    pub reopen_workspace_on_restart: bool,
+    /// 关闭 Antigravity 后等待进程退出、数据库解锁的超时时间
+    pub wait_settings: crate::platform::wait::WaitSettings,
+    /// 切换账户后验证 Antigravity 是否接受恢复的登录状态
+    pub verify_login_after_switch: bool,
+    /// 托盘菜单等后端文案的界面语言
+    pub language: crate::utils::i18n::Language,
+    /// 选择过「不再询问」的原生确认对话框
+    pub skipped_confirm_prompts: Vec<crate::dialog::ConfirmPrompt>,
+    /// 运行在 WSL 中时操作 Windows 上的 Antigravity
+    pub wsl_windows_mode: bool,
+    /// 除账户状态外额外备份的数据库键
+    pub backup_extra_keys: Vec<String>,
+    /// 备份、恢复与清除时不处理的键
+    pub backup_excluded_keys: Vec<String>,
+    /// 定时任务的运行条件（如等待系统空闲）
+    pub scheduler: crate::scheduler::SchedulerSettings,
+    /// 使用统计（默认关闭）
+    pub telemetry: crate::telemetry::TelemetrySettings,
+    /// 备份、快照、恢复与写日志前要求的最小磁盘剩余空间（MB，0 表示不检查）
+    pub min_free_disk_mb: u64,
+    /// 界面主题（浅色、深色或跟随系统）
+    pub theme: crate::appearance::ThemePreference,
+    /// 强调色、界面密度与字号缩放
+    pub ui_preferences: crate::appearance::UiPreferences,
+    /// 快照保留策略
+    pub retention_policy: crate::retention::RetentionPolicy,
 }
 
 fn default_private_mode() -> bool {
@@ -29,6 +97,39 @@ impl Default for AppSettings {
             silent_start_enabled: false,
             debug_mode: false,
             private_mode: default_private_mode(),
+            setup_completed: false,
+            auto_backup_on_account_change: false,
+            http_api_enabled: false,
+            http_api_port: crate::http_api::DEFAULT_PORT,
+            http_api_token: None,
+            auto_check_updates: true,
+            update_endpoint: None,
+            proxy: crate::proxy::ProxySettings::default(),
+            read_only_mode: false,
+            hide_dock_icon: false,
+            confirm_dangerous_operations: true,
+            db_snapshot_before_write: true,
+            backup_before_clear: true,
+            email_mask_style: Default::default(),
+            backup_file_name_template: crate::antigravity::backup_naming::DEFAULT_TEMPLATE
+                .to_string(),
+            backup_stale_days: 7,
+            time_display: Default::default(),
+            launch_policy: Default::default(),
+            reopen_workspace_on_restart: true,
+            wait_settings: Default::default(),
+            verify_login_after_switch: true,
+            language: Default::default(),
+            skipped_confirm_prompts: Vec::new(),
+            wsl_windows_mode: false,
+            backup_extra_keys: Vec::new(),
+            backup_excluded_keys: Vec::new(),
+            scheduler: Default::default(),
+            telemetry: Default::default(),
+            min_free_disk_mb: crate::disk_space::DEFAULT_MIN_FREE_MB,
+            theme: Default::default(),
+            ui_preferences: Default::default(),
+            retention_policy: Default::default(),
         }
     }
 }
@@ -44,6 +145,31 @@ pub fn load_settings_from_disk(config_path: &PathBuf) -> AppSettings {
     }
 }
 
+/// 先写入同目录的临时文件再重命名，避免写入中断或并发读取时得到半个文件
+fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    {
+        let mut file = crate::utils::permissions::private_file_options().open(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp_path);
+    })
+}
+
+/// 只读模式下返回 `AgentError::ReadOnly`
+///
+/// 直接读取设置文件，命令行与 HTTP 控制接口同样受限
+pub fn ensure_writable() -> AgentResult<()> {
+    if load_settings_from_disk(&crate::directories::get_app_settings_file()).read_only_mode {
+        tracing::warn!(target: "app_settings::read_only", "只读模式已开启，拒绝修改操作");
+        Err(AgentError::ReadOnly)
+    } else {
+        Ok(())
+    }
+}
+
 impl AppSettings {
     /// 验证设置的有效性，确保不会出现危险的配置组合
     pub fn validate(&mut self) -> bool {
@@ -64,10 +190,22 @@ impl AppSettings {
     }
 }
 
+/// 把设置应用到进程内的全局状态（邮箱脱敏、时间格式、界面语言、最小剩余磁盘空间）
+///
+/// 界面模式由 [`AppSettingsManager::new`] 调用；命令行模式不创建设置管理器，启动时直接调用
+pub fn apply_globals(settings: &AppSettings) {
+    crate::utils::email_mask::set_style(settings.email_mask_style);
+    crate::utils::time_format::set_settings(settings.time_display);
+    crate::utils::i18n::set_language(settings.language);
+    crate::disk_space::set_min_free_mb(settings.min_free_disk_mb);
+}
+
 /// 应用程序设置管理器
 pub struct AppSettingsManager {
     settings: Mutex<AppSettings>,
     config_path: PathBuf,
+    /// 内存中有尚未写入磁盘的修改（上次写入失败）
+    unsaved: AtomicBool,
 }
 
 impl AppSettingsManager {
@@ -87,9 +225,12 @@ impl AppSettingsManager {
             );
         }
 
+        apply_globals(&settings);
+
         Self {
             settings: Mutex::new(settings),
             config_path,
+            unsaved: AtomicBool::new(false),
         }
     }
 
@@ -98,8 +239,25 @@ impl AppSettingsManager {
         self.settings.lock().unwrap().clone()
     }
 
-    /// 更新设置
-    pub fn update_settings<F>(&self, update_fn: F) -> Result<(), String>
+    /// 更新设置（只读模式下拒绝）
+    pub fn update_settings<F>(&self, update_fn: F) -> AgentResult<()>
+    where
+        F: FnOnce(&mut AppSettings),
+    {
+        if self.settings.lock().unwrap().read_only_mode {
+            return Err(AgentError::ReadOnly);
+        }
+        self.write_settings(update_fn)
+    }
+
+    /// 开启或关闭只读模式（只读模式下唯一允许的设置修改）
+    pub fn set_read_only_mode(&self, enabled: bool) -> AgentResult<()> {
+        self.write_settings(|settings| settings.read_only_mode = enabled)?;
+        tracing::info!(target: "app_settings::read_only", enabled, "只读模式已切换");
+        Ok(())
+    }
+
+    fn write_settings<F>(&self, update_fn: F) -> AgentResult<()>
     where
         F: FnOnce(&mut AppSettings),
     {
@@ -123,16 +281,34 @@ impl AppSettingsManager {
             );
         }
 
-        // 保存到文件
-        let json = serde_json::to_string_pretty(&*settings)
-            .map_err(|e| format!("序列化设置失败: {}", e))?;
+        let result = self.persist(&settings);
+        self.unsaved.store(result.is_err(), Ordering::Relaxed);
+        result.map_err(AgentError::Io)
+    }
+
+    /// 保存到文件
+    fn persist(&self, settings: &AppSettings) -> Result<(), String> {
+        let json =
+            serde_json::to_string_pretty(settings).map_err(|e| format!("序列化设置失败: {}", e))?;
 
         if let Some(parent) = self.config_path.parent() {
             fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
         }
 
-        fs::write(&self.config_path, json).map_err(|e| format!("写入设置文件失败: {}", e))?;
+        write_atomic(&self.config_path, json.as_bytes())
+            .map_err(|e| format!("写入设置文件失败: {}", e))?;
 
         Ok(())
     }
+
+    /// 把上次写入失败的设置重新写入磁盘（退出前调用，没有未保存的修改时不写入）
+    pub fn flush(&self) -> Result<(), String> {
+        if !self.unsaved.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let settings = self.settings.lock().unwrap();
+        self.persist(&settings)?;
+        self.unsaved.store(false, Ordering::Relaxed);
+        Ok(())
+    }
 }
@@ -0,0 +1,221 @@
+//! 外观主题
+//! 保存用户选择的主题（浅色、深色或跟随系统），启动时应用到窗口。
+//! 跟随系统时监听主窗口的系统主题变化，通过 `appearance:theme-changed` 事件通知前端，
+//! 并切换托盘图标的深浅色版本。
+//! 强调色、界面密度与字号缩放同样保存在设置中，修改后推送给所有窗口
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager, Theme};
+
+use crate::error::{AgentError, AgentResult};
+use crate::window::state_manager::MAIN_WINDOW;
+
+/// 主题变化事件名
+pub const THEME_CHANGED_EVENT: &str = "appearance:theme-changed";
+
+/// 用户选择的主题
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreference {
+    Light,
+    Dark,
+    /// 跟随系统
+    #[default]
+    System,
+}
+
+/// 实际生效的主题
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EffectiveTheme {
+    Light,
+    Dark,
+}
+
+impl From<Theme> for EffectiveTheme {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Dark => Self::Dark,
+            _ => Self::Light,
+        }
+    }
+}
+
+/// 当前主题（`appearance:theme-changed` 事件负载）
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeState {
+    pub preference: ThemePreference,
+    pub effective: EffectiveTheme,
+}
+
+/// 当前生效的是否为深色主题（托盘图标据此选择版本）
+static DARK: AtomicBool = AtomicBool::new(false);
+
+/// 当前生效的是否为深色主题
+pub fn is_dark() -> bool {
+    DARK.load(Ordering::Relaxed)
+}
+
+fn preference(app: &AppHandle) -> ThemePreference {
+    app.state::<crate::app_settings::AppSettingsManager>()
+        .get_settings()
+        .theme
+}
+
+/// 主窗口报告的主题（跟随系统时即系统主题），无法获取时按浅色处理
+fn window_theme(app: &AppHandle) -> EffectiveTheme {
+    app.get_webview_window(MAIN_WINDOW)
+        .and_then(|window| window.theme().ok())
+        .map_or(EffectiveTheme::Light, EffectiveTheme::from)
+}
+
+/// 当前主题
+pub fn current(app: &AppHandle) -> ThemeState {
+    let preference = preference(app);
+    ThemeState {
+        preference,
+        effective: match preference {
+            ThemePreference::Light => EffectiveTheme::Light,
+            ThemePreference::Dark => EffectiveTheme::Dark,
+            ThemePreference::System => window_theme(app),
+        },
+    }
+}
+
+/// 记录生效的主题，变化时切换托盘图标；随后通知前端
+fn publish(app: &AppHandle, state: ThemeState) {
+    let dark = state.effective == EffectiveTheme::Dark;
+    if DARK.swap(dark, Ordering::Relaxed) != dark {
+        crate::system_tray::refresh_tray_icon(app);
+    }
+    if let Err(e) = app.emit(THEME_CHANGED_EVENT, state) {
+        tracing::warn!(target: "appearance", error = %e, "推送主题变化事件失败");
+    }
+}
+
+/// 应用主题偏好：设置窗口主题（跟随系统时取消覆盖），更新托盘图标并通知前端
+pub fn apply(app: &AppHandle, preference: ThemePreference) -> ThemeState {
+    app.set_theme(match preference {
+        ThemePreference::Light => Some(Theme::Light),
+        ThemePreference::Dark => Some(Theme::Dark),
+        ThemePreference::System => None,
+    });
+    let state = current(app);
+    tracing::info!(target: "appearance", preference = ?state.preference, effective = ?state.effective, "已应用主题");
+    publish(app, state);
+    state
+}
+
+/// 启动时应用保存的主题，并监听系统主题变化
+pub fn init(app: &AppHandle) {
+    let saved = preference(app);
+    if saved != ThemePreference::System {
+        app.set_theme(Some(match saved {
+            ThemePreference::Dark => Theme::Dark,
+            _ => Theme::Light,
+        }));
+    }
+    DARK.store(
+        current(app).effective == EffectiveTheme::Dark,
+        Ordering::Relaxed,
+    );
+
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+    let handle = app.clone();
+    window.on_window_event(move |event| {
+        let tauri::WindowEvent::ThemeChanged(theme) = event else {
+            return;
+        };
+        // 用户指定了主题时不跟随系统
+        if preference(&handle) != ThemePreference::System {
+            return;
+        }
+        let effective = EffectiveTheme::from(*theme);
+        tracing::info!(target: "appearance", effective = ?effective, "系统主题已变化");
+        publish(
+            &handle,
+            ThemeState {
+                preference: ThemePreference::System,
+                effective,
+            },
+        );
+    });
+}
+
+/// 界面偏好变化事件名
+pub const UI_PREFERENCES_CHANGED_EVENT: &str = "appearance:ui-preferences-changed";
+
+/// 字号缩放范围
+const FONT_SCALE_RANGE: std::ops::RangeInclusive<f64> = 0.8..=1.5;
+
+/// 界面密度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Density {
+    #[default]
+    Comfortable,
+    /// 紧凑模式：减小列表与卡片的间距
+    Compact,
+}
+
+/// 界面偏好（所有窗口共用，修改后通过 `appearance:ui-preferences-changed` 事件同步）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct UiPreferences {
+    /// 强调色（`#RRGGBB`）
+    pub accent_color: String,
+    pub density: Density,
+    /// 字号缩放比例（0.8-1.5）
+    pub font_scale: f64,
+}
+
+impl Default for UiPreferences {
+    fn default() -> Self {
+        Self {
+            accent_color: "#3B82F6".to_string(),
+            density: Density::default(),
+            font_scale: 1.0,
+        }
+    }
+}
+
+impl UiPreferences {
+    /// 校验界面偏好，强调色统一为大写
+    pub fn normalize(mut self) -> AgentResult<Self> {
+        let hex = self.accent_color.trim().trim_start_matches('#');
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(AgentError::InvalidInput(format!(
+                "强调色格式无效（应为 #RRGGBB）: {}",
+                self.accent_color
+            )));
+        }
+        self.accent_color = format!("#{}", hex.to_ascii_uppercase());
+
+        if !FONT_SCALE_RANGE.contains(&self.font_scale) {
+            return Err(AgentError::InvalidInput(format!(
+                "字号缩放比例应在 {}-{} 之间",
+                FONT_SCALE_RANGE.start(),
+                FONT_SCALE_RANGE.end()
+            )));
+        }
+        Ok(self)
+    }
+}
+
+/// 通知所有窗口界面偏好已变化
+pub fn publish_ui_preferences(app: &AppHandle, preferences: &UiPreferences) {
+    tracing::info!(
+        target: "appearance",
+        accent_color = %preferences.accent_color,
+        density = ?preferences.density,
+        font_scale = preferences.font_scale,
+        "界面偏好已更新"
+    );
+    if let Err(e) = app.emit(UI_PREFERENCES_CHANGED_EVENT, preferences) {
+        tracing::warn!(target: "appearance", error = %e, "推送界面偏好变化事件失败");
+    }
+}
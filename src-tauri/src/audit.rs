@@ -0,0 +1,106 @@
+//! 审计日志
+//! 以 JSON Lines 格式记录关键操作（钩子执行、备份、恢复等），便于事后追溯
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::utils::time_format::FormattedTime;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::sync::Mutex;
+
+/// 单个审计日志文件的最大大小，超过后轮转为 `.1`
+const MAX_FILE_SIZE: u64 = 5 * 1024 * 1024;
+
+/// 串行化写入，避免多个线程同时追加导致行交错
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 审计日志条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// 记录时间（RFC 3339）
+    pub timestamp: String,
+    /// 分类，如 `hook`
+    pub category: String,
+    /// 具体操作
+    pub action: String,
+    pub success: bool,
+    /// 附加信息
+    pub detail: Value,
+    /// 所属操作的 ID（在任务中记录时）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    /// 格式化后的记录时间（读取时生成，不写入文件）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<FormattedTime>,
+}
+
+/// 追加一条审计日志（写入失败只记录警告，不影响调用方）
+pub fn record(category: &str, action: &str, success: bool, detail: Value) {
+    let entry = AuditEntry {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        category: category.to_string(),
+        action: action.to_string(),
+        success,
+        detail,
+        operation_id: crate::tasks::current_operation_id(),
+        time: None,
+    };
+
+    if let Err(e) = append(&entry) {
+        tracing::warn!(target: "audit", error = %e, "写入审计日志失败");
+    }
+}
+
+fn append(entry: &AuditEntry) -> std::io::Result<()> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = crate::directories::get_audit_log_file();
+
+    if fs::metadata(&path).is_ok_and(|m| m.len() > MAX_FILE_SIZE) {
+        fs::rename(&path, path.with_extension("jsonl.1"))?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    let line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    writeln!(file, "{}", line)
+}
+
+/// 读取指定操作的审计日志（按时间顺序，包含已轮转的旧文件）
+pub fn read_for_operation(operation_id: &str) -> Vec<AuditEntry> {
+    let path = crate::directories::get_audit_log_file();
+    [path.with_extension("jsonl.1"), path]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+                .filter(|entry| entry.operation_id.as_deref() == Some(operation_id))
+                .collect::<Vec<_>>()
+        })
+        .map(|mut entry| {
+            entry.time = crate::utils::time_format::format_rfc3339(&entry.timestamp);
+            entry
+        })
+        .collect()
+}
+
+/// 读取最近的审计日志（最新的在前）
+pub fn read_recent(limit: usize) -> Vec<AuditEntry> {
+    let path = crate::directories::get_audit_log_file();
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+
+    content
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+        .take(limit)
+        .map(|mut entry| {
+            entry.time = crate::utils::time_format::format_rfc3339(&entry.timestamp);
+            entry
+        })
+        .collect()
+}
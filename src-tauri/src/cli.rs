@@ -0,0 +1,257 @@
+//! 命令行（无界面）模式
+//! 复用后端模块执行备份、切换、列出、恢复等操作，不启动 Tauri 窗口，结果以 JSON 输出到标准输出
+//!
+//! ```text
+//! antigravity-agent list
+//! antigravity-agent backup [--email <email>] [--note <备注>]
+//! antigravity-agent backup-all
+//! antigravity-agent switch --email <email>
+//! antigravity-agent restore <file>
+//! antigravity-agent restart
+//! ```
+
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+
+use crate::antigravity::backup::{self, BackupTrigger};
+use crate::error::{AgentError, AgentResult};
+use crate::tasks::TaskHandle;
+
+/// 命令行子命令
+#[derive(Debug)]
+pub enum CliCommand {
+    /// 列出所有账户备份
+    List,
+    /// 备份当前登录账户（可选校验邮箱）
+    Backup {
+        email: Option<String>,
+        note: Option<String>,
+    },
+    /// 刷新所有已备份账户中当前登录账户的备份
+    BackupAll,
+    /// 切换到指定账户
+    Switch { email: String },
+    /// 从指定备份文件恢复并切换账户
+    Restore { file: PathBuf },
+    /// 重启 Antigravity
+    Restart,
+    /// 显示帮助
+    Help,
+}
+
+/// 命令行输出
+#[derive(Serialize)]
+struct CliOutput<'a> {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a AgentError>,
+}
+
+const USAGE: &str = "用法:
+  antigravity-agent list                      列出所有账户备份
+  antigravity-agent backup [--email <邮箱>] [--note <备注>]
+                                              备份当前登录账户
+  antigravity-agent backup-all                刷新所有已备份账户
+  antigravity-agent switch --email <邮箱>     切换到指定账户
+  antigravity-agent restore <文件>            从备份文件恢复并切换账户
+  antigravity-agent restart                   重启 Antigravity";
+
+/// 解析子命令的 `--name <值>` 参数
+///
+/// 出现不在 `allowed` 中的参数，或参数缺少值时返回 InvalidInput
+fn parse_flags<'a>(
+    subcommand: &str,
+    rest: &[&'a String],
+    allowed: &[&str],
+) -> AgentResult<Vec<(&'a str, String)>> {
+    let mut flags = Vec::new();
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        if !allowed.contains(&arg.as_str()) {
+            return Err(AgentError::InvalidInput(format!(
+                "{} 不支持参数 {}",
+                subcommand, arg
+            )));
+        }
+        let value = iter
+            .next()
+            .filter(|v| !v.starts_with("--"))
+            .ok_or_else(|| AgentError::InvalidInput(format!("{} 需要参数值", arg)))?;
+        flags.push((arg.as_str(), value.to_string()));
+    }
+    Ok(flags)
+}
+
+fn flag_value(flags: &[(&str, String)], name: &str) -> Option<String> {
+    flags
+        .iter()
+        .find(|(flag, _)| *flag == name)
+        .map(|(_, value)| value.clone())
+}
+
+/// 解析命令行参数
+///
+/// 第一个参数不是已知子命令时返回 None，按正常 GUI 模式启动；
+/// 参数缺少值、出现未知参数或多余参数时返回 InvalidInput
+pub fn parse_args(args: &[String]) -> Option<AgentResult<CliCommand>> {
+    // 跳过全局参数（如 --portable）
+    let mut iter = args.iter().filter(|a| a.as_str() != "--portable");
+    let subcommand = iter.next()?;
+    let rest: Vec<&String> = iter.collect();
+    let name = subcommand.as_str();
+
+    let command = match name {
+        "list" => parse_flags(name, &rest, &[]).map(|_| CliCommand::List),
+        "backup" => {
+            parse_flags(name, &rest, &["--email", "--note"]).map(|flags| CliCommand::Backup {
+                email: flag_value(&flags, "--email"),
+                note: flag_value(&flags, "--note"),
+            })
+        }
+        "backup-all" => parse_flags(name, &rest, &[]).map(|_| CliCommand::BackupAll),
+        "switch" => parse_flags(name, &rest, &["--email"]).and_then(|flags| {
+            flag_value(&flags, "--email")
+                .map(|email| CliCommand::Switch { email })
+                .ok_or_else(|| AgentError::InvalidInput("switch 需要 --email <邮箱>".to_string()))
+        }),
+        "restore" => match rest.as_slice() {
+            [file] if !file.starts_with('-') => Ok(CliCommand::Restore {
+                file: PathBuf::from(file.as_str()),
+            }),
+            [flag] => Err(AgentError::InvalidInput(format!(
+                "restore 不支持参数 {}",
+                flag
+            ))),
+            [] => Err(AgentError::InvalidInput(
+                "restore 需要备份文件路径".to_string(),
+            )),
+            _ => Err(AgentError::InvalidInput(
+                "restore 只接受一个备份文件路径".to_string(),
+            )),
+        },
+        "restart" => parse_flags(name, &rest, &[]).map(|_| CliCommand::Restart),
+        "help" | "--help" | "-h" => Ok(CliCommand::Help),
+        _ => return None,
+    };
+
+    Some(command)
+}
+
+/// 备份当前账户
+fn backup_current(expected_email: Option<String>, note: Option<String>) -> AgentResult<Value> {
+    if let Some(expected) = expected_email {
+        let (current, _) = backup::read_current_account()?;
+        if !current.eq_ignore_ascii_case(&expected) {
+            return Err(AgentError::InvalidInput(format!(
+                "当前登录账户为 {}，与指定的 {} 不一致",
+                current, expected
+            )));
+        }
+    }
+
+    let outcome = backup::smart_backup_antigravity_account(BackupTrigger::Manual, note)?;
+    Ok(serde_json::json!({
+        "email": outcome.email,
+        "file": outcome.path.to_string_lossy(),
+        "changed": outcome.changed,
+    }))
+}
+
+async fn execute(command: CliCommand) -> AgentResult<Value> {
+    match command {
+        CliCommand::List => Ok(Value::Array(backup::list_backups()?)),
+        CliCommand::Backup { email, note } => backup_current(email, note),
+        CliCommand::BackupAll => Ok(serde_json::to_value(backup::backup_all_known_accounts()?)?),
+        CliCommand::Switch { email } => {
+            let account_file = crate::antigravity::backup_naming::require_backup_file(&email)?;
+            let message =
+                crate::commands::switch_to_account_file(account_file, &TaskHandle::detached())
+                    .await?;
+            Ok(serde_json::json!({ "message": message }))
+        }
+        CliCommand::Restore { file } => {
+            let message =
+                crate::commands::switch_to_account_file(file, &TaskHandle::detached()).await?;
+            Ok(serde_json::json!({ "message": message }))
+        }
+        CliCommand::Restart => {
+            let workspace = crate::antigravity::workspace::capture_for_restart();
+            match crate::platform::kill_antigravity_processes() {
+                Ok(_) | Err(AgentError::ProcessNotFound) => {}
+                Err(e) => return Err(e),
+            }
+            // 等待进程完全退出
+            let wait_settings = crate::platform::wait::load_settings();
+            crate::platform::wait::wait_after_kill(&wait_settings).await?;
+            let message = crate::antigravity::starter::start_antigravity_with_profile(
+                None,
+                workspace.as_deref(),
+            )?;
+            Ok(serde_json::json!({ "message": message }))
+        }
+        CliCommand::Help => Ok(Value::String(USAGE.to_string())),
+    }
+}
+
+/// Windows 发布版使用 GUI 子系统，没有控制台；命令行模式下连接到启动它的终端，
+/// 否则标准输出中的结果不会显示（输出已被重定向到文件或管道时保持不变）
+#[cfg(windows)]
+pub fn attach_parent_console() {
+    use windows::Win32::System::Console::{
+        AttachConsole, GetStdHandle, ATTACH_PARENT_PROCESS, STD_OUTPUT_HANDLE,
+    };
+
+    unsafe {
+        if GetStdHandle(STD_OUTPUT_HANDLE).is_ok() {
+            return;
+        }
+        // 从资源管理器等没有控制台的进程启动时连接失败，忽略即可
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// 执行命令行子命令，返回进程退出码
+pub fn run(command: AgentResult<CliCommand>) -> i32 {
+    let result = match command {
+        Ok(command) => {
+            tracing::info!(target: "cli", command = ?command, "执行命令行子命令");
+            match tokio::runtime::Runtime::new() {
+                Ok(runtime) => runtime.block_on(execute(command)),
+                Err(e) => Err(AgentError::Internal(format!("创建异步运行时失败: {}", e))),
+            }
+        }
+        Err(e) => Err(e),
+    };
+
+    let (output, code) = match &result {
+        Ok(data) => (
+            CliOutput {
+                ok: true,
+                data: Some(data.clone()),
+                error: None,
+            },
+            0,
+        ),
+        Err(e) => {
+            tracing::error!(target: "cli", error = %e, "命令行子命令执行失败");
+            (
+                CliOutput {
+                    ok: false,
+                    data: None,
+                    error: Some(e),
+                },
+                1,
+            )
+        }
+    };
+
+    match serde_json::to_string_pretty(&output) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("输出结果失败: {}", e),
+    }
+
+    code
+}
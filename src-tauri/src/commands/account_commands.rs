@@ -1,12 +1,15 @@
 //! 账户基础命令：查询、备份、恢复、切换、清理
 
 use crate::antigravity::account::decode_jetski_state_proto;
-use base64::Engine;
-use prost::Message;
+use crate::antigravity::restore::{RestoreMode, RestoreReport};
+use crate::error::{AgentError, AgentResult};
+use crate::operation_lock::{self, LockResource};
+use crate::tasks::TaskHandle;
 use rusqlite::{Connection, OptionalExtension};
 use serde_json::{from_str, Value};
 use std::fs;
-use tauri::State;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
 use tracing::instrument;
 
 /// 获取所有 Antigravity 账户（解码 jetskiStateSync.agentManagerInitState，返回完整 SessionResponse JSON）
@@ -77,7 +80,7 @@ pub async fn get_antigravity_accounts(
         }
 
         // 按文件修改时间排序（最新的在前），仅返回解码后的对象
-        accounts.sort_by(|a, b| b.0.cmp(&a.0));
+        accounts.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
         let decoded_only: Vec<Value> = accounts.into_iter().map(|(_, decoded)| decoded).collect();
 
         tracing::debug!("🎉 成功加载 {} 个账户", decoded_only.len());
@@ -144,8 +147,8 @@ pub async fn get_current_antigravity_account_info() -> Result<Value, String> {
         // jetski 状态（可选）
         let jetski_state: Option<String> = conn
             .query_row(
-                "SELECT value FROM ItemTable WHERE key = 'jetskiStateSync.agentManagerInitState'",
-                [],
+                "SELECT value FROM ItemTable WHERE key = ?",
+                [crate::antigravity::key_mapping::load().agent_state],
                 |row| row.get(0),
             )
             .optional()
@@ -182,91 +185,28 @@ pub async fn get_current_antigravity_account_info() -> Result<Value, String> {
     }
 }
 
-/// 备份当前 Antigravity 账户
+/// 备份当前 Antigravity 账户（可附加备注，便于日后区分备份）
 #[tauri::command]
 #[instrument]
-pub async fn save_antigravity_current_account() -> Result<String, String> {
+pub async fn save_antigravity_current_account(note: Option<String>) -> AgentResult<String> {
     tracing::info!("📥 开始保存 jetskiStateSync.agentManagerInitState");
 
     let start_time = std::time::Instant::now();
 
     let result = async {
-        // 尝试获取 Antigravity 状态数据库路径
-        let app_data = match crate::platform::get_antigravity_db_path() {
-            Some(path) => path,
-            None => {
-                // 如果主路径不存在，尝试其他可能的位置
-                let possible_paths = crate::platform::get_all_antigravity_db_paths();
-                if possible_paths.is_empty() {
-                    return Err("未找到Antigravity安装位置".to_string());
-                }
-                possible_paths[0].clone()
-            }
-        };
-
-        if !app_data.exists() {
-            return Err(format!(
-                "Antigravity 状态数据库文件不存在: {}",
-                app_data.display()
-            ));
-        }
-
-        // 连接到 SQLite 数据库并获取认证信息
-        let conn = Connection::open(&app_data)
-            .map_err(|e| format!("连接数据库失败 ({}): {}", app_data.display(), e))?;
-
-        // jetski 状态（必需）
-        let jetski_state: String = conn
-            .query_row(
-                "SELECT value FROM ItemTable WHERE key = 'jetskiStateSync.agentManagerInitState'",
-                [],
-                |row| row.get(0),
+        let outcome = crate::utils::blocking::run_blocking(move || {
+            crate::antigravity::backup::smart_backup_antigravity_account(
+                crate::antigravity::backup::BackupTrigger::Manual,
+                note,
             )
-            .optional()
-            .map_err(|e| format!("查询 jetskiStateSync.agentManagerInitState 失败: {}", e))?
-            .ok_or_else(|| "未找到 jetskiStateSync.agentManagerInitState".to_string())?;
-
-        // 从 jetski proto 解码邮箱（仅用于文件名）
-        let bytes = base64::engine::general_purpose::STANDARD
-            .decode(jetski_state.trim())
-            .map_err(|e| format!("jetskiStateSync Base64 解码失败: {}", e))?;
-        let msg = crate::proto::SessionResponse::decode(bytes.as_slice())
-            .map_err(|e| format!("jetskiStateSync Protobuf 解码失败: {}", e))?;
-
-        let email = msg
-            .context
-            .as_ref()
-            .and_then(|c| {
-                if c.email.is_empty() {
-                    None
-                } else {
-                    Some(c.email.as_str())
-                }
-            })
-            .ok_or_else(|| "jetskiStateSync 中未找到邮箱字段，无法确定备份文件名".to_string())?;
-
-        // 直接保存原始字符串，不解码，文件名与原逻辑保持：{email}.json
-        let accounts_dir = crate::directories::get_accounts_directory();
-        if let Err(e) = std::fs::create_dir_all(&accounts_dir) {
-            return Err(format!("创建账户目录失败: {}", e));
-        }
-
-        let account_file = accounts_dir.join(format!("{email}.json"));
-        let content = serde_json::json!({
-            "jetskiStateSync.agentManagerInitState": jetski_state
-        });
-        std::fs::write(
-            &account_file,
-            serde_json::to_string_pretty(&content).unwrap(),
-        )
-        .map_err(|e| format!("写入 jetski 状态失败: {}", e))?;
+        })
+        .await?;
 
         let message = format!(
             "已保存 jetskiStateSync.agentManagerInitState 到 {}",
-            account_file.display()
+            outcome.path.display()
         );
-        tracing::info!(file = %account_file.display(), "✅ 保存 jetski 状态完成");
-        Ok(message)
+        Ok::<_, AgentError>(message)
     }
     .await;
 
@@ -292,80 +232,306 @@ pub async fn save_antigravity_current_account() -> Result<String, String> {
     }
 }
 
-/// 清除所有 Antigravity 数据
+/// 列出所有账户备份摘要（含备份元数据与令牌过期状态）
 #[tauri::command]
-pub async fn clear_all_antigravity_data() -> Result<String, String> {
-    crate::antigravity::cleanup::clear_all_antigravity_data().await
+pub async fn list_backup_summaries() -> AgentResult<Vec<Value>> {
+    crate::log_async_command!("list_backup_summaries", async {
+        crate::utils::blocking::run_blocking(crate::antigravity::backup::list_backups).await
+    })
 }
 
-/// 恢复 Antigravity 账户
+/// 按邮箱、别名、备注与备份来源筛选账户备份
 #[tauri::command]
-pub async fn restore_antigravity_account(account_name: String) -> Result<String, String> {
-    tracing::debug!(target: "account::restore", account_name = %account_name, "调用 restore_antigravity_account");
+pub async fn search_backups(
+    query: crate::antigravity::backup::BackupSearchQuery,
+) -> AgentResult<Vec<Value>> {
+    crate::log_async_command!("search_backups", async {
+        crate::utils::blocking::run_blocking(move || {
+            crate::antigravity::backup::search_backups(&query)
+        })
+        .await
+    })
+}
 
-    // 1. 构建备份文件路径
-    let accounts_dir = crate::directories::get_accounts_directory();
-    let account_file = accounts_dir.join(format!("{account_name}.json"));
+/// 描述账户备份文件的结构（包含的键、元数据字段、结构版本与 JSON Schema）
+#[tauri::command]
+pub async fn describe_backup_schema() -> AgentResult<crate::antigravity::backup_schema::BackupSchema>
+{
+    crate::log_async_command!("describe_backup_schema", async {
+        crate::utils::blocking::run_blocking(|| Ok(crate::antigravity::backup_schema::describe()))
+            .await
+    })
+}
 
-    // 2. 调用统一的恢复函数
-    crate::antigravity::restore::save_antigravity_account_to_file(account_file).await
+/// 批量刷新所有已备份账户，返回每个账户的处理结果
+#[tauri::command]
+pub async fn backup_all_known_accounts(
+) -> AgentResult<Vec<crate::antigravity::backup::AccountBackupResult>> {
+    crate::log_async_command!("backup_all_known_accounts", async {
+        crate::utils::blocking::run_blocking(crate::antigravity::backup::backup_all_known_accounts)
+            .await
+    })
 }
 
-/// 切换到 Antigravity 账户（调用 restore_antigravity_account）
+/// 清除所有 Antigravity 数据（需要先通过 `request_dangerous_operation("logout_all")` 获取确认令牌）
+///
+/// Antigravity 正在运行时返回 `AntigravityRunning`，`close_antigravity` 为 true 时先关闭它
 #[tauri::command]
-pub async fn switch_to_antigravity_account(account_name: String) -> Result<String, String> {
-    crate::log_async_command!("switch_to_antigravity_account", async {
-        // 1. 关闭 Antigravity 进程 (如果存在)
-        let kill_result = match crate::platform::kill_antigravity_processes() {
-            Ok(result) => {
-                if result.contains("not found") || result.contains("未找到") {
-                    tracing::debug!(target: "account::switch::step1", "Antigravity 进程未运行，跳过关闭步骤");
-                    "Antigravity 进程未运行".to_string()
-                } else {
-                    tracing::debug!(target: "account::switch::step1", result = %result, "进程关闭完成");
-                    result
-                }
-            }
-            Err(e) => {
-                if e.contains("not found") || e.contains("未找到") {
-                    tracing::debug!(target: "account::switch::step1", "Antigravity 进程未运行，跳过关闭步骤");
-                    "Antigravity 进程未运行".to_string()
-                } else {
-                    tracing::error!(target: "account::switch::step1", error = %e, "关闭进程时发生错误");
-                    return Err(format!("关闭进程时发生错误: {}", e));
-                }
-            }
-        };
+pub async fn clear_all_antigravity_data(
+    confirmation_token: Option<String>,
+    close_antigravity: Option<bool>,
+) -> AgentResult<String> {
+    crate::platform::ensure_antigravity_stopped(close_antigravity.unwrap_or(false))?;
+    let _guard = operation_lock::acquire(
+        "clear_data",
+        &[LockResource::StateDb, LockResource::BackupDir],
+    )?;
+    // 前置条件都满足后再消费令牌，避免进程未关闭、只读模式或锁冲突时令牌被白白作废
+    crate::confirmation::consume(
+        crate::confirmation::DangerousOperation::LogoutAll,
+        confirmation_token.as_deref(),
+    )?;
+    crate::antigravity::db_snapshot::snapshot_before("clear_data")?;
+    crate::utils::blocking::run_blocking(|| {
+        crate::antigravity::cleanup::clear_all_antigravity_data(
+            crate::antigravity::backup::BackupTrigger::BeforeClear,
+        )
+    })
+    .await
+}
 
-        // 等待一秒确保进程完全关闭
-        tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+/// 恢复 Antigravity 账户
+///
+/// Antigravity 正在运行时返回 `AntigravityRunning`，`close_antigravity` 为 true 时先关闭它；
+/// `strict` 为 true 时任一键写入失败即回滚，结果见返回报告的 `success`。
+/// Antigravity 当前登录了其他账户时返回 `SessionMismatch`，`replace_session` 为 true 时先清除当前会话再恢复
+#[tauri::command]
+pub async fn restore_antigravity_account(
+    app: AppHandle,
+    account_name: String,
+    task_id: Option<String>,
+    close_antigravity: Option<bool>,
+    strict: Option<bool>,
+    replace_session: Option<bool>,
+) -> AgentResult<RestoreReport> {
+    tracing::debug!(target: "account::restore", account_name = %account_name, "调用 restore_antigravity_account");
 
-        // 2. 清除原来的数据库
-        clear_all_antigravity_data().await?;
-        tracing::warn!(target: "account::switch::step2", "Antigravity 数据库清除完成");
+    crate::tasks::run_task(&app, task_id, "restore_account", |task| async move {
+        task.record_target(&account_name);
 
-        // 3. 恢复指定账户到 Antigravity 数据库
-        let restore_result = restore_antigravity_account(account_name.clone()).await?;
-        tracing::debug!(target: "account::switch::step3", result = %restore_result, "账户数据恢复完成");
+        // 1. 构建备份文件路径
+        let account_file = crate::antigravity::backup_naming::require_backup_file(&account_name)?;
 
-        // 等待一秒确保数据库操作完成
-        tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+        // 2. 确认 Antigravity 已关闭，否则写入会在其退出时被覆盖
+        task.progress(0, Some(1), "正在检查 Antigravity 进程");
+        crate::platform::ensure_antigravity_stopped(close_antigravity.unwrap_or(false))?;
 
-        // 4. 重新启动 Antigravity 进程
-        let start_result = crate::antigravity::starter::start_antigravity();
-        let start_message = match start_result {
-            Ok(result) => {
-                tracing::debug!(target: "account::switch::step4", result = %result, "Antigravity 启动成功");
-                result
-            }
-            Err(e) => {
-                tracing::warn!(target: "account::switch::step4", error = %e, "Antigravity 启动失败");
-                format!("启动失败: {}", e)
+        // 3. 确认当前登录的是同一账户，替换会话时先清除（清除前会备份当前账户）
+        let replace_session = replace_session.unwrap_or(false);
+        task.record_option("replaceSession", serde_json::Value::Bool(replace_session));
+        task.record_option("strict", serde_json::Value::Bool(strict.unwrap_or(false)));
+        let resources: &[LockResource] = if replace_session {
+            &[LockResource::StateDb, LockResource::BackupDir]
+        } else {
+            &[LockResource::StateDb]
+        };
+        let _guard = operation_lock::acquire("restore_account", resources)?;
+        task.checkpoint()?;
+        let db_path = crate::platform::resolve_antigravity_db_path()?;
+        let mismatch = match crate::antigravity::restore::check_session(&account_file, &db_path) {
+            Err(AgentError::SessionMismatch { .. }) if replace_session => true,
+            result => {
+                result?;
+                false
             }
         };
+        crate::antigravity::db_snapshot::snapshot_before("restore_account")?;
+        if mismatch {
+            task.progress(0, Some(1), "正在清除当前会话");
+            crate::utils::blocking::run_blocking(|| {
+                crate::antigravity::cleanup::clear_all_antigravity_data(
+                    crate::antigravity::backup::BackupTrigger::BeforeSwitch,
+                )
+            })
+            .await?;
+        }
 
-        let final_message = format!("{} -> {} -> {}", kill_result, restore_result, start_message);
+        // 4. 调用统一的恢复函数
+        task.progress(0, Some(1), "正在恢复账户数据");
+        let mode = if strict.unwrap_or(false) {
+            RestoreMode::Strict
+        } else {
+            RestoreMode::Lenient
+        };
+        let report =
+            crate::antigravity::restore::save_antigravity_account_to_file(account_file, mode)
+                .await?;
+        task.progress(1, Some(1), report.message.clone());
+        Ok(report)
+    })
+    .await
+}
 
-        Ok(final_message)
+/// 按顺序把多个备份恢复到各自的 Antigravity 数据目录（多实例），逐项返回结果
+///
+/// Antigravity 正在运行时返回 `AntigravityRunning`，`close_antigravity` 为 true 时先关闭它；
+/// `strict` 为 true 时单项中任一键写入失败即回滚该项
+#[tauri::command]
+pub async fn run_restore_queue(
+    app: AppHandle,
+    items: Vec<crate::antigravity::restore_queue::RestoreQueueItem>,
+    task_id: Option<String>,
+    close_antigravity: Option<bool>,
+    strict: Option<bool>,
+) -> AgentResult<Vec<crate::antigravity::restore_queue::RestoreQueueItemResult>> {
+    crate::log_async_command!("run_restore_queue", async {
+        crate::tasks::run_task(&app, task_id, "restore_queue", |task| async move {
+            let mode = if strict.unwrap_or(false) {
+                RestoreMode::Strict
+            } else {
+                RestoreMode::Lenient
+            };
+            crate::utils::blocking::run_blocking(move || {
+                let _guard = operation_lock::acquire(
+                    "restore_queue",
+                    &[LockResource::StateDb, LockResource::BackupDir],
+                )?;
+                crate::antigravity::restore_queue::run(
+                    &items,
+                    mode,
+                    close_antigravity.unwrap_or(false),
+                    &task,
+                )
+            })
+            .await
+        })
+        .await
     })
 }
+
+/// 切换到 Antigravity 账户（调用 restore_antigravity_account）
+#[tauri::command]
+pub async fn switch_to_antigravity_account(
+    app: AppHandle,
+    account_name: String,
+    task_id: Option<String>,
+) -> AgentResult<String> {
+    crate::log_async_command!("switch_to_antigravity_account", async {
+        let account_file = crate::antigravity::backup_naming::require_backup_file(&account_name)?;
+        let email = crate::antigravity::backup::read_backup_email(&account_file);
+        let message = crate::tasks::run_task(&app, task_id, "switch_account", |task| async move {
+            task.record_target(&account_name);
+            switch_to_account_file(account_file, &task).await
+        })
+        .await?;
+
+        // 后台验证 Antigravity 是否接受恢复的登录状态
+        let verify = app
+            .state::<crate::app_settings::AppSettingsManager>()
+            .get_settings()
+            .verify_login_after_switch;
+        if let Some(email) = email.filter(|_| verify) {
+            crate::antigravity::login_probe::spawn(app.clone(), email);
+        }
+        Ok(message)
+    })
+}
+
+/// 使用指定的账户备份文件切换账户：关闭进程 -> 清除数据库 -> 恢复 -> 重新启动
+///
+/// 只在清除数据库之前响应取消，避免留下未登录的中间状态
+pub async fn switch_to_account_file(
+    account_file: PathBuf,
+    task: &TaskHandle,
+) -> AgentResult<String> {
+    const TOTAL_STEPS: Option<u64> = Some(4);
+
+    let _guard = operation_lock::acquire(
+        "switch_account",
+        &[LockResource::StateDb, LockResource::BackupDir],
+    )?;
+
+    // 1. 关闭 Antigravity 进程 (如果存在)
+    task.checkpoint()?;
+    task.progress(0, TOTAL_STEPS, "正在关闭 Antigravity");
+    let workspace = crate::antigravity::workspace::capture_for_restart();
+    let kill_result = match crate::platform::kill_antigravity_processes() {
+        Ok(report) => {
+            tracing::debug!(target: "account::switch::step1", result = %report, "进程关闭完成");
+            report.to_string()
+        }
+        Err(AgentError::ProcessNotFound) => {
+            tracing::debug!(target: "account::switch::step1", "Antigravity 进程未运行，跳过关闭步骤");
+            "Antigravity 进程未运行".to_string()
+        }
+        Err(e) => {
+            tracing::error!(target: "account::switch::step1", error = %e, "关闭进程时发生错误");
+            return Err(e);
+        }
+    };
+
+    // 等待进程退出、数据库解锁
+    let wait_settings = crate::platform::wait::load_settings();
+    let stop_wait = crate::platform::wait::wait_after_kill(&wait_settings).await?;
+
+    // 2. 清除原来的数据库
+    task.checkpoint()?;
+    task.progress(1, TOTAL_STEPS, "正在清除 Antigravity 数据");
+    crate::antigravity::db_snapshot::snapshot_before("switch_account")?;
+    crate::utils::blocking::run_blocking(|| {
+        crate::antigravity::cleanup::clear_all_antigravity_data(
+            crate::antigravity::backup::BackupTrigger::BeforeSwitch,
+        )
+    })
+    .await?;
+    tracing::warn!(target: "account::switch::step2", "Antigravity 数据库清除完成");
+
+    // 3. 恢复指定账户到 Antigravity 数据库
+    task.progress(2, TOTAL_STEPS, "正在恢复账户数据");
+    let restore_result = crate::antigravity::restore::save_antigravity_account_to_file(
+        account_file.clone(),
+        RestoreMode::Lenient,
+    )
+    .await?
+    .message;
+    tracing::debug!(target: "account::switch::step3", result = %restore_result, "账户数据恢复完成");
+
+    // 等待数据库写入完成后再启动
+    let restore_wait = crate::platform::wait::wait_for_antigravity_db(&wait_settings).await;
+
+    // 4. 重新启动 Antigravity 进程（账户设置了启动配置时使用该配置）
+    task.progress(3, TOTAL_STEPS, "正在启动 Antigravity");
+    let launch_profile = crate::antigravity::backup::read_backup_email(&account_file)
+        .and_then(|email| crate::account_metadata::launch_profile(&email));
+    if launch_profile.is_some() {
+        tracing::info!(target: "account::switch::step4", "使用账户启动配置启动 Antigravity");
+    }
+    let start_result = crate::antigravity::starter::start_antigravity_with_profile(
+        launch_profile.as_ref(),
+        workspace.as_deref(),
+    );
+    let start_message = match start_result {
+        Ok(result) => {
+            tracing::debug!(target: "account::switch::step4", result = %result, "Antigravity 启动成功");
+            result
+        }
+        Err(e) => {
+            tracing::warn!(target: "account::switch::step4", error = %e, "Antigravity 启动失败");
+            format!("启动失败: {}", e)
+        }
+    };
+
+    let final_message = format!(
+        "{}（{}） -> {}（{}） -> {}",
+        kill_result, stop_wait, restore_result, restore_wait, start_message
+    );
+
+    crate::hooks::run_hooks_async(
+        crate::hooks::HookEvent::PostSwitch,
+        serde_json::json!({ "file": account_file.to_string_lossy(), "result": final_message }),
+    )
+    .await?;
+
+    task.progress(4, TOTAL_STEPS, "切换完成");
+    Ok(final_message)
+}
@@ -1,18 +1,24 @@
 //! 账户备份/导入导出与加解密命令
 
+use crate::antigravity::backup_naming::BackupRenameResult;
+use crate::error::{AgentError, AgentResult};
 use crate::log_async_command;
+use crate::operation_lock::{self, LockResource};
+use crate::utils::blocking::run_blocking;
+use crate::utils::permissions;
+use crate::utils::secret::{SecretJson, SecretString};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 use std::fs;
 use std::time::SystemTime;
-use tauri::State;
+use tauri::{AppHandle, State};
+use zeroize::Zeroizing;
 
 /// 备份数据收集结构
 #[derive(Serialize, Deserialize, Debug)]
 pub struct AccountExportedData {
     filename: String,
     #[serde(rename = "content")]
-    content: Value,
+    content: SecretJson,
     #[serde(rename = "timestamp")]
     timestamp: u64,
 }
@@ -23,6 +29,19 @@ pub struct RestoreResult {
     #[serde(rename = "restoredCount")]
     restored_count: u32,
     failed: Vec<FailedAccountExportedData>,
+    /// 从旧版本或其他工具的格式转换后导入的备份
+    #[serde(default)]
+    converted: Vec<ConvertedAccountExportedData>,
+}
+
+/// 转换格式后导入的备份
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ConvertedAccountExportedData {
+    filename: String,
+    format: String,
+    /// 未能对应到当前结构、没有写入的字段
+    #[serde(rename = "unmappedFields")]
+    unmapped_fields: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -34,36 +53,55 @@ pub struct FailedAccountExportedData {
 /// 收集所有账户文件的完整内容, 用于导出
 #[tauri::command]
 pub async fn collect_account_contents(
+    app: AppHandle,
     state: State<'_, crate::AppState>,
-) -> Result<Vec<AccountExportedData>, String> {
-    let mut backups_with_content = Vec::new();
-
+    task_id: Option<String>,
+) -> AgentResult<Vec<AccountExportedData>> {
     // 读取Antigravity账户目录中的JSON文件
     let antigravity_dir = state.config_dir.join("antigravity-accounts");
 
-    if !antigravity_dir.exists() {
-        return Ok(backups_with_content);
-    }
+    crate::tasks::run_task(&app, task_id, "export_accounts", |task| async move {
+        let mut backups_with_content = Vec::new();
 
-    for entry in fs::read_dir(&antigravity_dir).map_err(|e| format!("读取用户目录失败: {}", e))?
-    {
-        let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
-        let path = entry.path();
+        let paths: Vec<_> = run_blocking(move || {
+            if !antigravity_dir.exists() {
+                return Ok(Vec::new());
+            }
+            Ok(fs::read_dir(&antigravity_dir)?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect())
+        })
+        .await?;
+        let count = paths.len() as u64;
+        let total = Some(count);
+
+        for (index, path) in paths.into_iter().enumerate() {
+            task.checkpoint()?;
 
-        if path.extension().is_some_and(|ext| ext == "json") {
             let filename = path
                 .file_name()
                 .and_then(|name| name.to_str())
                 .map(|s| s.to_string())
                 .unwrap_or_default();
 
+            task.progress(
+                index as u64,
+                total,
+                format!(
+                    "正在读取 {}",
+                    crate::utils::email_mask::mask_email(&filename)
+                ),
+            );
+
             if filename.is_empty() {
                 continue;
             }
 
-            match fs::read_to_string(&path).map_err(|e| format!("读取文件失败 {}: {}", filename, e))
-            {
-                Ok(content) => match serde_json::from_str::<serde_json::Value>(&content) {
+            let content = run_blocking(move || Ok(fs::read_to_string(&path).map(Zeroizing::new)))
+                .await?;
+            match content {
+                Ok(content) => match serde_json::from_str::<SecretJson>(&content) {
                     Ok(json_value) => {
                         backups_with_content.push(AccountExportedData {
                             filename,
@@ -75,99 +113,170 @@ pub async fn collect_account_contents(
                         });
                     }
                     Err(e) => {
-                        tracing::warn!(target: "backup::scan", filename = %filename, error = %e, "跳过损坏的备份文件");
+                        tracing::warn!(target: "backup::scan", filename = %crate::utils::email_mask::mask_email(&filename), error = %e, "跳过损坏的备份文件");
                     }
                 },
                 Err(_) => {
-                    tracing::warn!(target: "backup::scan", filename = %filename, "跳过无法读取的文件");
+                    tracing::warn!(target: "backup::scan", filename = %crate::utils::email_mask::mask_email(&filename), "跳过无法读取的文件");
                 }
             }
         }
-    }
 
-    Ok(backups_with_content)
+        task.progress(
+            count,
+            total,
+            format!("已读取 {} 个账户", backups_with_content.len()),
+        );
+        Ok(backups_with_content)
+    })
+    .await
 }
 
 /// 恢复备份文件到本地
 #[tauri::command]
 pub async fn restore_backup_files(
+    app: AppHandle,
     account_file_data: Vec<AccountExportedData>,
     state: State<'_, crate::AppState>,
-) -> Result<RestoreResult, String> {
-    let mut results = RestoreResult {
-        restored_count: 0,
-        failed: Vec::new(),
-    };
-
+    task_id: Option<String>,
+) -> AgentResult<RestoreResult> {
     // 获取目标目录
     let antigravity_dir = state.config_dir.join("antigravity-accounts");
 
-    // 确保目录存在
-    if let Err(e) = fs::create_dir_all(&antigravity_dir) {
-        return Err(format!("创建目录失败: {}", e));
-    }
+    crate::tasks::run_task(&app, task_id, "import_accounts", |task| async move {
+        let mut results = RestoreResult {
+            restored_count: 0,
+            failed: Vec::new(),
+            converted: Vec::new(),
+        };
+
+        let _guard = operation_lock::acquire("import_accounts", &[LockResource::BackupDir])?;
+
+        // 确保目录存在
+        let dir = antigravity_dir.clone();
+        run_blocking(move || {
+            permissions::create_private_dir(&dir)?;
+            crate::disk_space::ensure_free_space(&dir, "import_accounts")
+        })
+        .await?;
+
+        let count = account_file_data.len() as u64;
+        let total = Some(count);
+
+        // 遍历每个备份（已写入的文件保留，取消只影响剩余文件）
+        for (index, account_file) in account_file_data.into_iter().enumerate() {
+            task.checkpoint()?;
+            task.progress(
+                index as u64,
+                total,
+                format!("正在写入 {}", account_file.filename),
+            );
+
+            // 旧版本或其他工具的备份先转换为当前格式
+            let conversion =
+                match crate::antigravity::backup_converter::convert(&account_file.content) {
+                    Ok(conversion) => conversion,
+                    Err(e) => {
+                        tracing::warn!(target: "backup::import", filename = %crate::utils::email_mask::mask_email(&account_file.filename), error = %e, "无法识别备份格式，跳过导入");
+                        results.failed.push(FailedAccountExportedData {
+                            filename: account_file.filename,
+                            error: e.to_string(),
+                        });
+                        continue;
+                    }
+                };
+            let mut filename = account_file.filename;
+            if !conversion.is_native() {
+                if !filename.ends_with(".json") {
+                    filename.push_str(".json");
+                }
+                results.converted.push(ConvertedAccountExportedData {
+                    filename: filename.clone(),
+                    format: conversion.format.to_string(),
+                    unmapped_fields: conversion.unmapped_fields.clone(),
+                });
+            }
 
-    // 遍历每个备份
-    for account_file in account_file_data {
-        let file_path = antigravity_dir.join(&account_file.filename);
+            let file_path = antigravity_dir.join(&filename);
+            let content = Zeroizing::new(
+                serde_json::to_string_pretty(&conversion.content).unwrap_or_default(),
+            );
 
-        match fs::write(
-            &file_path,
-            serde_json::to_string_pretty(&account_file.content).unwrap_or_default(),
-        )
-        .map_err(|e| format!("写入文件失败: {}", e))
-        {
-            Ok(_) => {
-                results.restored_count += 1;
-            }
-            Err(e) => {
-                results.failed.push(FailedAccountExportedData {
-                    filename: account_file.filename,
-                    error: e,
-                });
+            match run_blocking(move || {
+                Ok(permissions::write_private(&file_path, content.as_bytes()))
+            })
+            .await?
+            .map_err(|e| format!("写入文件失败: {}", e))
+            {
+                Ok(_) => {
+                    results.restored_count += 1;
+                }
+                Err(e) => {
+                    results.failed.push(FailedAccountExportedData { filename, error: e });
+                }
             }
         }
-    }
 
-    Ok(results)
+        task.progress(
+            count,
+            total,
+            format!("已导入 {} 个账户", results.restored_count),
+        );
+        Ok(results)
+    })
+    .await
 }
 
 /// 删除指定备份
 #[tauri::command]
-pub async fn delete_backup(
-    name: String,
-    state: State<'_, crate::AppState>,
-) -> Result<String, String> {
+pub async fn delete_backup(name: String, state: State<'_, crate::AppState>) -> AgentResult<String> {
+    let _guard = operation_lock::acquire("delete_backup", &[LockResource::BackupDir])?;
     // 只删除Antigravity账户JSON文件
     let antigravity_dir = state.config_dir.join("antigravity-accounts");
-    let antigravity_file = antigravity_dir.join(format!("{}.json", name));
+    let antigravity_file = crate::antigravity::backup_naming::find_backup_file(&name)
+        .unwrap_or_else(|| antigravity_dir.join(format!("{}.json", name)));
 
     if antigravity_file.exists() {
-        fs::remove_file(&antigravity_file).map_err(|e| format!("删除用户文件失败: {}", e))?;
+        fs::remove_file(&antigravity_file)?;
+        if crate::account_metadata::get(&name).backup_file.is_some() {
+            crate::account_metadata::update(&name, |meta| {
+                meta.backup_file = None;
+                Ok(())
+            })?;
+        }
+        crate::account_avatar::remove(&name);
         Ok(format!("删除用户成功: {}", name))
     } else {
-        Err("用户文件不存在".to_string())
+        Err(AgentError::BackupNotFound(name))
     }
 }
 
+/// 按当前命名模板重命名所有已有备份，并更新账户元数据中记录的文件名
+#[tauri::command]
+pub async fn migrate_backup_file_names() -> AgentResult<Vec<BackupRenameResult>> {
+    crate::log_async_command!("migrate_backup_file_names", async {
+        crate::antigravity::backup_naming::migrate_file_names()
+    })
+}
+
 /// 清空所有备份
 #[tauri::command]
-pub async fn clear_all_backups(state: State<'_, crate::AppState>) -> Result<String, String> {
+pub async fn clear_all_backups(state: State<'_, crate::AppState>) -> AgentResult<String> {
+    let _guard = operation_lock::acquire("clear_backups", &[LockResource::BackupDir])?;
     let antigravity_dir = state.config_dir.join("antigravity-accounts");
 
     if antigravity_dir.exists() {
         // 读取目录中的所有文件
         let mut deleted_count = 0;
-        for entry in
-            fs::read_dir(&antigravity_dir).map_err(|e| format!("读取用户目录失败: {}", e))?
-        {
-            let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
+        for entry in fs::read_dir(&antigravity_dir)? {
+            let entry = entry?;
             let path = entry.path();
 
             // 只删除 JSON 文件
             if path.extension().is_some_and(|ext| ext == "json") {
-                fs::remove_file(&path)
-                    .map_err(|e| format!("删除文件 {} 失败: {}", path.display(), e))?;
+                fs::remove_file(&path).map_err(|e| {
+                    AgentError::Io(format!("删除文件 {} 失败: {}", path.display(), e))
+                })?;
                 deleted_count += 1;
             }
         }
@@ -181,148 +290,138 @@ pub async fn clear_all_backups(state: State<'_, crate::AppState>) -> Result<Stri
     }
 }
 
-/// 加密配置数据（用于账户导出）
+/// 加密配置数据（用于账户导出），密码强度不足时拒绝
 #[tauri::command]
-pub async fn encrypt_config_data(json_data: String, password: String) -> Result<String, String> {
+pub async fn encrypt_config_data(
+    json_data: SecretString,
+    password: SecretString,
+) -> Result<String, String> {
     log_async_command!("encrypt_config_data", async {
-        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
-
-        if password.is_empty() {
-            return Err("密码不能为空".to_string());
-        }
-
-        let password_bytes = password.as_bytes();
-        let mut result = Vec::new();
-
-        // XOR 加密
-        for (i, byte) in json_data.as_bytes().iter().enumerate() {
-            let key_byte = password_bytes[i % password_bytes.len()];
-            result.push(byte ^ key_byte);
-        }
-
-        // Base64 编码
-        let encoded = BASE64.encode(&result);
-
-        Ok(encoded)
+        crate::utils::blocking::run_blocking(move || {
+            crate::utils::config_crypto::encrypt(json_data.expose(), password.expose())
+        })
+        .await
+        .map_err(|e| e.to_string())
     })
 }
 
-/// 解密配置数据（用于账户导入）
+/// 解密配置数据（用于账户导入），本次运行中连续输错密码后需等待一段时间才能重试
 #[tauri::command]
 pub async fn decrypt_config_data(
     encrypted_data: String,
-    password: String,
-) -> Result<String, String> {
+    password: SecretString,
+) -> Result<SecretString, String> {
     log_async_command!("decrypt_config_data", async {
-        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
-
-        if password.is_empty() {
-            return Err("密码不能为空".to_string());
-        }
-
-        let decoded = BASE64
-            .decode(encrypted_data)
-            .map_err(|_| "Base64 解码失败".to_string())?;
-
-        let password_bytes = password.as_bytes();
-        let mut result = Vec::new();
-
-        for (i, byte) in decoded.iter().enumerate() {
-            let key_byte = password_bytes[i % password_bytes.len()];
-            result.push(byte ^ key_byte);
-        }
-
-        let decrypted =
-            String::from_utf8(result).map_err(|_| "解密失败，数据可能已损坏".to_string())?;
-
-        Ok(decrypted)
+        crate::utils::blocking::run_blocking(move || {
+            crate::utils::config_crypto::decrypt(&encrypted_data, password.expose())
+        })
+        .await
+        .map_err(|e| e.to_string())
     })
 }
 
 /// 备份并重启 Antigravity（迁移自 process_commands）
 #[tauri::command]
-pub async fn sign_in_new_antigravity_account() -> Result<String, String> {
-    println!("🔄 开始执行 sign_in_new_antigravity_account 命令");
+pub async fn sign_in_new_antigravity_account() -> AgentResult<String> {
+    tracing::info!(target: "account::sign_in", "开始登录新账户流程");
 
     // 1. 关闭进程 (如果存在)
-    println!("🛑 步骤1: 检查并关闭 Antigravity 进程");
+    tracing::debug!(target: "account::sign_in::step1", "检查并关闭 Antigravity 进程");
+    let workspace = crate::antigravity::workspace::capture_for_restart();
     let kill_result = match crate::platform::kill_antigravity_processes() {
-        Ok(result) => {
-            if result.contains("not found") || result.contains("未找到") {
-                println!("ℹ️ Antigravity 进程未运行，跳过关闭步骤");
-                "Antigravity 进程未运行".to_string()
-            } else {
-                println!("✅ 进程关闭结果: {}", result);
-                result
-            }
+        Ok(report) => {
+            tracing::debug!(target: "account::sign_in::step1", result = %report, "进程关闭完成");
+            report.to_string()
         }
-        Err(e) => {
-            if e.contains("not found") || e.contains("未找到") {
-                println!("ℹ️ Antigravity 进程未运行，跳过关闭步骤");
-                "Antigravity 进程未运行".to_string()
-            } else {
-                return Err(format!("关闭进程时发生错误: {}", e));
-            }
+        Err(AgentError::ProcessNotFound) => {
+            tracing::debug!(target: "account::sign_in::step1", "Antigravity 进程未运行，跳过关闭步骤");
+            "Antigravity 进程未运行".to_string()
         }
+        Err(e) => return Err(e),
     };
 
-    // 等待500ms确保进程完全关闭（缩短等待时间避免前端超时）
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    // 等待进程退出、数据库解锁
+    let wait_settings = crate::platform::wait::load_settings();
+    let stop_wait = crate::platform::wait::wait_after_kill(&wait_settings).await?;
 
     // 2. 备份当前账户信息（直接调用 save_antigravity_current_account）
-    println!("💾 步骤2: 调用 save_antigravity_current_account 备份当前账户信息");
-    let backup_info = match crate::commands::save_antigravity_current_account().await {
+    tracing::debug!(target: "account::sign_in::step2", "备份当前账户信息");
+    let backup_info = match crate::commands::save_antigravity_current_account(Some(
+        "自动：登录新账户前".to_string(),
+    ))
+    .await
+    {
         Ok(msg) => {
-            println!("✅ 备份完成: {}", msg);
+            tracing::debug!(target: "account::sign_in::step2", result = %msg, "备份完成");
             Some(msg)
         }
         Err(e) => {
-            println!("⚠️ 备份失败: {}", e);
+            tracing::warn!(target: "account::sign_in::step2", error = %e, "备份失败，继续登录流程");
             None
         }
     };
 
     // 3. 清除 Antigravity 所有数据 (彻底注销)
-    println!("🗑️ 步骤3: 清除所有 Antigravity 数据 (彻底注销)");
-    match crate::antigravity::cleanup::clear_all_antigravity_data().await {
+    let _guard = operation_lock::acquire(
+        "sign_in_new_account",
+        &[LockResource::StateDb, LockResource::BackupDir],
+    )?;
+    tracing::debug!(target: "account::sign_in::step3", "清除所有 Antigravity 数据");
+    crate::antigravity::db_snapshot::snapshot_before("sign_in_new_account")?;
+    match crate::utils::blocking::run_blocking(|| {
+        crate::antigravity::cleanup::clear_all_antigravity_data(
+            crate::antigravity::backup::BackupTrigger::BeforeSwitch,
+        )
+    })
+    .await
+    {
         Ok(result) => {
-            println!("✅ 清除完成: {}", result);
+            tracing::debug!(target: "account::sign_in::step3", result = %result, "清除完成");
         }
         Err(e) => {
             // 清除失败可能是因为数据库本来就是空的，这是正常情况
-            println!("ℹ️ 清除数据时出现: {}（可能数据库本来就是空的）", e);
+            tracing::info!(target: "account::sign_in::step3", error = %e, "清除数据未完成（可能数据库本来就是空的）");
         }
     }
 
-    // 等待300ms确保操作完成（缩短等待时间避免前端超时）
-    tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+    // 等待数据库写入完成后再启动
+    let clear_wait = crate::platform::wait::wait_for_antigravity_db(&wait_settings).await;
 
     // 4. 重新启动进程
-    println!("🚀 步骤4: 重新启动 Antigravity");
-    let start_result = crate::antigravity::starter::start_antigravity();
+    tracing::debug!(target: "account::sign_in::step4", "重新启动 Antigravity");
+    let start_result =
+        crate::antigravity::starter::start_antigravity_with_profile(None, workspace.as_deref());
     let start_message = match start_result {
         Ok(result) => {
-            println!("✅ 启动结果: {}", result);
+            tracing::debug!(target: "account::sign_in::step4", result = %result, "Antigravity 启动成功");
             result
         }
         Err(e) => {
-            println!("⚠️ 启动失败: {}", e);
+            tracing::warn!(target: "account::sign_in::step4", error = %e, "Antigravity 启动失败");
             format!("启动失败: {}", e)
         }
     };
 
     let final_message = if let Some(backup_message) = backup_info {
         format!(
-            "{} -> 已备份: {} -> 已清除账户数据 -> {}",
-            kill_result, backup_message, start_message
+            "{}（{}） -> 已备份: {} -> 已清除账户数据（{}） -> {}",
+            kill_result, stop_wait, backup_message, clear_wait, start_message
         )
     } else {
         format!(
-            "{} -> 未检测到登录用户（跳过备份） -> 已清除账户数据 -> {}",
-            kill_result, start_message
+            "{}（{}） -> 未检测到登录用户（跳过备份） -> 已清除账户数据（{}） -> {}",
+            kill_result, stop_wait, clear_wait, start_message
         )
     };
-    println!("🎉 所有操作完成: {}", final_message);
+    tracing::info!(target: "account::sign_in", result = %final_message, "登录新账户流程完成");
 
     Ok(final_message)
 }
+
+/// 导出账户备份的脱敏副本（令牌等凭据替换为占位值，结构保持不变），用于问题反馈或协助排查
+#[tauri::command]
+pub async fn export_sanitized_backup(
+    name: String,
+) -> AgentResult<crate::antigravity::sanitize::SanitizedExport> {
+    run_blocking(move || crate::antigravity::sanitize::export_sanitized_backup(&name)).await
+}
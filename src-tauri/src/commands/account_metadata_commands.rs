@@ -0,0 +1,94 @@
+//! 账户元数据命令
+
+use tauri::{AppHandle, Manager};
+
+use crate::account_avatar::AccountAvatar;
+use crate::account_metadata::{LaunchProfile, RecentAccount};
+use crate::app_settings::AppSettingsManager;
+use crate::error::AgentResult;
+
+/// 获取账户的启动配置（未设置时返回 None）
+#[tauri::command]
+pub async fn get_account_launch_profile(email: String) -> AgentResult<Option<LaunchProfile>> {
+    Ok(crate::account_metadata::launch_profile(&email))
+}
+
+/// 保存账户的启动配置（传入 None 时清除，恢复使用全局默认值）
+#[tauri::command]
+pub async fn save_account_launch_profile(
+    email: String,
+    profile: Option<LaunchProfile>,
+) -> AgentResult<Option<LaunchProfile>> {
+    crate::log_async_command!("save_account_launch_profile", async {
+        if let Some(profile) = &profile {
+            profile.validate()?;
+        }
+        let metadata = crate::account_metadata::update(&email, |meta| {
+            meta.launch_profile = profile;
+            Ok(())
+        })?;
+        Ok(metadata.launch_profile)
+    })
+}
+
+/// 设置账户别名（传入 None 或空字符串时清除），返回保存后的别名
+#[tauri::command]
+pub async fn save_account_alias(
+    email: String,
+    alias: Option<String>,
+) -> AgentResult<Option<String>> {
+    crate::log_async_command!("save_account_alias", async {
+        let alias = alias
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty());
+        let metadata = crate::account_metadata::update(&email, |meta| {
+            meta.alias = alias;
+            Ok(())
+        })?;
+        Ok(metadata.alias)
+    })
+}
+
+/// 最近使用的账户（置顶在前，其余按切换、恢复、备份的先后排序），`limit` 为空时返回全部
+#[tauri::command]
+pub async fn get_recent_accounts(limit: Option<usize>) -> AgentResult<Vec<RecentAccount>> {
+    crate::log_async_command!("get_recent_accounts", async {
+        let mut accounts = crate::account_metadata::recent_accounts(
+            &crate::antigravity::backup::list_backup_emails(),
+        );
+        if let Some(limit) = limit {
+            accounts.truncate(limit);
+        }
+        Ok(accounts)
+    })
+}
+
+/// 置顶或取消置顶账户，返回保存后的状态
+#[tauri::command]
+pub async fn set_account_pinned(email: String, pinned: bool) -> AgentResult<bool> {
+    crate::log_async_command!("set_account_pinned", async {
+        let metadata = crate::account_metadata::update(&email, |meta| {
+            meta.pinned = pinned;
+            Ok(())
+        })?;
+        crate::platform::jump_list::refresh_jump_list();
+        Ok(metadata.pinned)
+    })
+}
+
+/// 获取缓存的账户头像（不访问网络，没有缓存时只返回首字母）
+#[tauri::command]
+pub async fn get_account_avatar(email: String) -> AgentResult<AccountAvatar> {
+    crate::utils::blocking::run_blocking(move || crate::account_avatar::get(&email)).await
+}
+
+/// 下载并缓存账户头像（使用全局代理设置），下载失败时返回已有的缓存
+#[tauri::command]
+pub async fn cache_account_avatar(
+    app: AppHandle,
+    email: String,
+    url: String,
+) -> AgentResult<AccountAvatar> {
+    let settings = app.state::<AppSettingsManager>().get_settings();
+    crate::account_avatar::cache(&email, &url, &settings.proxy).await
+}
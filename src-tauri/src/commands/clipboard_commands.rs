@@ -0,0 +1,72 @@
+//! 剪贴板命令
+//! 复制账户邮箱、备份文件路径或诊断摘要，托盘与快捷操作也可直接调用
+
+use serde::Deserialize;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::error::{AgentError, AgentResult};
+
+/// 要复制的内容
+#[derive(Debug, Clone, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum ClipboardContent {
+    /// 当前在 Antigravity 中登录的账户邮箱
+    ActiveAccountEmail,
+    /// 指定账户的邮箱
+    AccountEmail { email: String },
+    /// 指定账户的备份文件路径
+    BackupPath { email: String },
+    /// 诊断摘要（已脱敏）
+    DiagnosticSummary,
+}
+
+/// 生成要复制的文本
+pub async fn clipboard_text(app: &AppHandle, content: ClipboardContent) -> AgentResult<String> {
+    match content {
+        ClipboardContent::ActiveAccountEmail => {
+            crate::utils::blocking::run_blocking(|| {
+                crate::antigravity::backup::read_current_account().map(|(email, _)| email)
+            })
+            .await
+        }
+        ClipboardContent::AccountEmail { email } => {
+            if email.trim().is_empty() {
+                return Err(AgentError::InvalidInput("邮箱不能为空".to_string()));
+            }
+            Ok(email)
+        }
+        ClipboardContent::BackupPath { email } => {
+            crate::utils::blocking::run_blocking(move || {
+                crate::antigravity::backup_naming::require_backup_file(&email)
+                    .map(|path| path.to_string_lossy().into_owned())
+            })
+            .await
+        }
+        ClipboardContent::DiagnosticSummary => crate::health_check::diagnostic_summary(app).await,
+    }
+}
+
+/// 复制内容到剪贴板，返回复制的文本
+///
+/// `clear_after_secs` 不为空时到时自动清除剪贴板（剪贴板已被替换为其他内容时保留）
+#[tauri::command]
+pub async fn copy_to_clipboard(
+    app: AppHandle,
+    content: ClipboardContent,
+    clear_after_secs: Option<u64>,
+) -> AgentResult<String> {
+    let text = clipboard_text(&app, content).await?;
+    crate::utils::clipboard::copy(
+        text.clone(),
+        clear_after_secs
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs),
+    )
+    .await?;
+    Ok(text)
+}
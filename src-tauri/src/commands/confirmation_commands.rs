@@ -0,0 +1,14 @@
+//! 危险操作确认命令
+
+use crate::confirmation::{ConfirmationToken, DangerousOperation};
+use crate::error::AgentResult;
+
+/// 申请危险操作的确认令牌（60 秒内有效，只能使用一次）
+#[tauri::command]
+pub async fn request_dangerous_operation(
+    operation: DangerousOperation,
+) -> AgentResult<ConfirmationToken> {
+    crate::log_async_command!("request_dangerous_operation", async {
+        Ok(crate::confirmation::request(operation))
+    })
+}
@@ -0,0 +1,17 @@
+//! 崩溃报告命令
+
+use crate::crash_report::CrashReport;
+use crate::error::AgentResult;
+
+/// 获取最近一次崩溃报告（没有时返回 None）
+#[tauri::command]
+pub async fn get_last_crash_report() -> AgentResult<Option<CrashReport>> {
+    Ok(crate::crash_report::last_report())
+}
+
+/// 记录前端错误边界捕获的异常，返回报告文件路径
+#[tauri::command]
+pub async fn report_frontend_crash(message: String, stack: Option<String>) -> AgentResult<String> {
+    let path = crate::crash_report::record_frontend_crash(message, stack)?;
+    Ok(path.to_string_lossy().to_string())
+}
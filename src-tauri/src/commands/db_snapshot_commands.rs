@@ -0,0 +1,20 @@
+//! 数据库文件快照命令
+
+use crate::antigravity::db_snapshot::{self, DbSnapshot};
+use crate::error::AgentResult;
+
+/// 列出所有数据库文件快照（最新的在前）
+#[tauri::command]
+pub async fn list_db_snapshots() -> AgentResult<Vec<DbSnapshot>> {
+    crate::log_async_command!("list_db_snapshots", async {
+        Ok(db_snapshot::list_snapshots())
+    })
+}
+
+/// 用指定快照整体回滚 Antigravity 数据库（需先关闭 Antigravity）
+#[tauri::command]
+pub async fn restore_db_snapshot(id: String) -> AgentResult<DbSnapshot> {
+    crate::log_async_command!("restore_db_snapshot", async {
+        db_snapshot::restore_snapshot(&id)
+    })
+}
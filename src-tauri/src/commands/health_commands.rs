@@ -0,0 +1,24 @@
+//! 健康检查命令
+//! 为前端诊断（Doctor）页面提供环境检查结果
+
+use tauri::AppHandle;
+
+use crate::error::AgentResult;
+
+/// 执行健康检查
+#[tauri::command]
+pub async fn run_health_check(app: AppHandle) -> AgentResult<crate::health_check::HealthReport> {
+    crate::log_async_command!("run_health_check", async {
+        crate::health_check::run(&app).await
+    })
+}
+
+/// 将配置目录、备份与快照中权限过宽的文件收紧为仅当前用户可访问（Windows 下不做处理）
+#[tauri::command]
+pub async fn fix_permissions() -> Result<crate::utils::permissions::PermissionFixReport, String> {
+    crate::log_async_command!("fix_permissions", async {
+        crate::utils::blocking::run_blocking(|| Ok(crate::utils::permissions::fix()))
+            .await
+            .map_err(|e| e.to_string())
+    })
+}
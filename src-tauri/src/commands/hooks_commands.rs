@@ -0,0 +1,31 @@
+//! 生命周期钩子与审计日志命令
+
+use crate::audit::AuditEntry;
+use crate::error::AgentResult;
+use crate::hooks::HooksConfig;
+
+/// 审计日志默认返回条数
+const DEFAULT_AUDIT_LIMIT: usize = 200;
+
+/// 获取钩子配置
+#[tauri::command]
+pub async fn get_hooks_config() -> AgentResult<HooksConfig> {
+    Ok(crate::hooks::load_config())
+}
+
+/// 保存钩子配置
+#[tauri::command]
+pub async fn save_hooks_config(config: HooksConfig) -> AgentResult<HooksConfig> {
+    crate::log_async_command!("save_hooks_config", async {
+        crate::hooks::save_config(&config)?;
+        Ok(config)
+    })
+}
+
+/// 获取最近的审计日志（最新的在前）
+#[tauri::command]
+pub async fn get_audit_log(limit: Option<usize>) -> AgentResult<Vec<AuditEntry>> {
+    Ok(crate::audit::read_recent(
+        limit.unwrap_or(DEFAULT_AUDIT_LIMIT),
+    ))
+}
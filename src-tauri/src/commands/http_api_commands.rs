@@ -0,0 +1,88 @@
+//! 本地 HTTP 控制接口命令
+//! 负责开启/关闭接口、查询状态以及重新生成访问令牌
+
+use crate::app_settings::AppSettingsManager;
+use crate::error::AgentResult;
+use crate::http_api::{generate_token, HttpApiServer};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+/// HTTP 控制接口状态
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpApiStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+    pub token: Option<String>,
+}
+
+fn current_status(app: &AppHandle) -> HttpApiStatus {
+    let settings = app.state::<AppSettingsManager>().get_settings();
+    let server = app.state::<HttpApiServer>();
+
+    HttpApiStatus {
+        enabled: settings.http_api_enabled,
+        running: server.running_port().is_some(),
+        port: settings.http_api_port,
+        token: settings.http_api_token,
+    }
+}
+
+/// 按当前设置启动或停止服务
+pub fn apply_http_api_settings(app: &AppHandle) -> AgentResult<()> {
+    let settings = app.state::<AppSettingsManager>().get_settings();
+    let server = app.state::<HttpApiServer>();
+
+    match (settings.http_api_enabled, settings.http_api_token) {
+        (true, Some(token)) => server.start(settings.http_api_port, token),
+        _ => {
+            server.stop();
+            Ok(())
+        }
+    }
+}
+
+/// 获取 HTTP 控制接口状态
+#[tauri::command]
+pub async fn get_http_api_status(app: AppHandle) -> AgentResult<HttpApiStatus> {
+    Ok(current_status(&app))
+}
+
+/// 开启/关闭 HTTP 控制接口
+#[tauri::command]
+pub async fn save_http_api_state(
+    app: AppHandle,
+    enabled: bool,
+    port: Option<u16>,
+) -> AgentResult<HttpApiStatus> {
+    crate::log_async_command!("save_http_api_state", async {
+        let settings_manager = app.state::<AppSettingsManager>();
+        settings_manager.update_settings(|settings| {
+            settings.http_api_enabled = enabled;
+            if let Some(port) = port {
+                settings.http_api_port = port;
+            }
+            if enabled && settings.http_api_token.is_none() {
+                settings.http_api_token = Some(generate_token());
+            }
+        })?;
+
+        apply_http_api_settings(&app)?;
+        Ok(current_status(&app))
+    })
+}
+
+/// 重新生成访问令牌（服务运行中时自动重启以使新令牌生效）
+#[tauri::command]
+pub async fn regenerate_http_api_token(app: AppHandle) -> AgentResult<HttpApiStatus> {
+    crate::log_async_command!("regenerate_http_api_token", async {
+        let settings_manager = app.state::<AppSettingsManager>();
+        settings_manager.update_settings(|settings| {
+            settings.http_api_token = Some(generate_token());
+        })?;
+
+        apply_http_api_settings(&app)?;
+        Ok(current_status(&app))
+    })
+}
@@ -0,0 +1,18 @@
+//! 数据库完整性命令
+
+use crate::antigravity::integrity::{self, IntegrityReport, RepairResult};
+use crate::error::AgentResult;
+
+/// 检查 Antigravity 数据库完整性（`PRAGMA integrity_check`）
+#[tauri::command]
+pub async fn check_database_integrity() -> AgentResult<IntegrityReport> {
+    crate::log_async_command!("check_database_integrity", async {
+        integrity::check_integrity()
+    })
+}
+
+/// 修复损坏的 Antigravity 数据库（需先关闭 Antigravity，修复前自动快照）
+#[tauri::command]
+pub async fn repair_database() -> AgentResult<RepairResult> {
+    crate::log_async_command!("repair_database", async { integrity::repair() })
+}
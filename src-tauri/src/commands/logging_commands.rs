@@ -1,8 +1,11 @@
 /// 日志和加密命令
 /// 负责日志管理、文件写入、数据加密解密等功能
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
 
+use crate::utils::time_format::FormattedTime;
+
 /// 写入文本文件
 /// 将文本内容写入指定路径的文件
 #[tauri::command]
@@ -23,94 +26,29 @@ pub async fn write_text_file(path: String, content: String) -> Result<String, St
 }
 
 /// 写入前端日志
-/// 将前端日志条目写入到后端日志系统
+/// 将前端日志条目写入到后端日志系统（与批量写入共用限流）
 #[tauri::command]
 pub async fn write_frontend_log(log_entry: serde_json::Value) -> Result<(), String> {
-    use tracing::{debug, error, info, warn};
-
-    // level: 'info' | 'warn' | 'error' | 'debug'
-    let level_str = log_entry
-        .get("level")
-        .and_then(|v| v.as_str())
-        .unwrap_or("info");
-
-    // message
-    let message = log_entry
-        .get("message")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-
-    // details（前端会把对象 JSON.stringify 成字符串）
-    let details = log_entry.get("details").and_then(|v| v.as_str());
-
-    // module：优先顶层；否则尝试从 details(JSON) 提取 module 字段
-    let module = log_entry
-        .get("module")
-        .and_then(|v| v.as_str())
-        .map(ToOwned::to_owned)
-        .or_else(|| {
-            let details_str = details?;
-            let details_json = serde_json::from_str::<serde_json::Value>(details_str).ok()?;
-            details_json
-                .get("module")
-                .and_then(|v| v.as_str())
-                .map(ToOwned::to_owned)
-        })
-        .unwrap_or_else(|| "frontend".to_string());
-
-    let full_message = if message.is_empty() {
-        format!("[{}]", module)
-    } else {
-        format!("[{}] {}", module, message)
-    };
-
-    // tracing 的 target 需要静态字符串；统一用 "frontend" 方便过滤（RUST_LOG / Debug Mode）
-    match level_str {
-        "error" => match details {
-            Some(details) => error!(
-                target: "frontend",
-                module = module.as_str(),
-                details = %details,
-                "{}",
-                full_message
-            ),
-            None => error!(target: "frontend", module = module.as_str(), "{}", full_message),
-        },
-        "warn" => match details {
-            Some(details) => warn!(
-                target: "frontend",
-                module = module.as_str(),
-                details = %details,
-                "{}",
-                full_message
-            ),
-            None => warn!(target: "frontend", module = module.as_str(), "{}", full_message),
-        },
-        "debug" => match details {
-            Some(details) => debug!(
-                target: "frontend",
-                module = module.as_str(),
-                details = %details,
-                "{}",
-                full_message
-            ),
-            None => debug!(target: "frontend", module = module.as_str(), "{}", full_message),
-        },
-        _ => match details {
-            Some(details) => info!(
-                target: "frontend",
-                module = module.as_str(),
-                details = %details,
-                "{}",
-                full_message
-            ),
-            None => info!(target: "frontend", module = module.as_str(), "{}", full_message),
-        },
-    }
-
+    crate::frontend_log::submit(std::slice::from_ref(&log_entry));
     Ok(())
 }
 
+/// 批量写入前端日志
+/// 按会话限流，超出部分丢弃并写入汇总；被限流时返回建议的暂停时长
+#[tauri::command]
+pub async fn write_frontend_logs(
+    entries: Vec<serde_json::Value>,
+) -> Result<crate::frontend_log::FrontendLogBatchResult, String> {
+    Ok(crate::frontend_log::submit(&entries))
+}
+
+/// 获取各前端会话的日志统计（提交、写入与丢弃数量）
+#[tauri::command]
+pub async fn get_frontend_log_stats(
+) -> Result<Vec<crate::frontend_log::FrontendLogSessionStats>, String> {
+    Ok(crate::frontend_log::stats())
+}
+
 /// 获取日志目录路径
 #[tauri::command]
 pub async fn get_log_directory_path() -> Result<String, String> {
@@ -121,12 +59,197 @@ pub async fn get_log_directory_path() -> Result<String, String> {
     })
 }
 
+/// 日志文件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFileKind {
+    /// 正在写入的日志（最新的按日滚动文件）
+    Current,
+    /// 往日滚动生成的日志
+    Rotated,
+    /// 目录中的其他文件（手动备份、归档或旧版本遗留的日志）
+    Backup,
+}
+
+/// 日志文件信息
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFileInfo {
+    pub name: String,
+    /// 绝对路径
+    pub path: String,
+    pub kind: LogFileKind,
+    pub size_bytes: u64,
+    pub modified: Option<FormattedTime>,
+}
+
+/// 日志目录信息
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogInfo {
+    /// 日志目录的绝对路径
+    pub directory: String,
+    /// 正在写入的日志文件的绝对路径
+    pub current_file: Option<String>,
+    pub total_bytes: u64,
+    /// 日志文件（最新修改的在前）
+    pub files: Vec<LogFileInfo>,
+}
+
+/// 是否为按日滚动生成的日志文件名（`antigravity-agent.2024-01-15`）
+fn is_rolling_log(name: &str) -> bool {
+    name.strip_prefix(crate::utils::sanitizing_layer::LOG_FILE_PREFIX)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .is_some_and(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok())
+}
+
+/// 获取日志目录信息（各文件的绝对路径、类型、大小与按设置格式化的修改时间）
+#[tauri::command]
+pub async fn get_log_info() -> Result<LogInfo, String> {
+    crate::log_async_command!("get_log_info", async {
+        let log_dir = crate::directories::get_log_directory();
+        // 便携模式下配置目录可能是相对路径，返回给前端前转为绝对路径（不使用 canonicalize，避免 Windows 上的 `\\?\` 前缀）
+        let log_dir = std::path::absolute(&log_dir).unwrap_or(log_dir);
+
+        let mut files: Vec<(Option<std::time::SystemTime>, LogFileInfo)> = fs::read_dir(&log_dir)
+            .map_err(|e| format!("读取日志目录失败: {}", e))?
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let meta = entry.metadata().ok().filter(|m| m.is_file())?;
+                let modified = meta.modified().ok();
+                let name = entry.file_name().to_string_lossy().to_string();
+                let kind = if is_rolling_log(&name) {
+                    LogFileKind::Rotated
+                } else {
+                    LogFileKind::Backup
+                };
+                let info = LogFileInfo {
+                    path: entry.path().display().to_string(),
+                    name,
+                    kind,
+                    size_bytes: meta.len(),
+                    modified: modified.map(|t| {
+                        crate::utils::time_format::format_time(
+                            &chrono::DateTime::<chrono::Local>::from(t),
+                        )
+                    }),
+                };
+                Some((modified, info))
+            })
+            .collect();
+        files.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        let mut files: Vec<LogFileInfo> = files.into_iter().map(|(_, info)| info).collect();
+        // 滚动日志按 UTC 日期命名，以最近修改的一个作为当前日志，不依赖时区推算文件名
+        let current_file = files
+            .iter_mut()
+            .find(|f| f.kind == LogFileKind::Rotated)
+            .map(|f| {
+                f.kind = LogFileKind::Current;
+                f.path.clone()
+            });
+        Ok(LogInfo {
+            directory: log_dir.display().to_string(),
+            current_file,
+            total_bytes: files.iter().map(|f| f.size_bytes).sum(),
+            files,
+        })
+    })
+}
+
+/// 按操作 ID 查询时最多扫描的日志文件数（最新的若干个）
+const OPERATION_TRACE_MAX_FILES: usize = 7;
+
+/// 按操作 ID 查询时最多返回的日志行数
+const OPERATION_TRACE_MAX_LINES: usize = 2000;
+
+/// 一次操作的全部记录
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationTrace {
+    pub operation_id: String,
+    /// 后端与前端日志中属于该操作的行（按时间顺序）
+    pub log_lines: Vec<String>,
+    /// 超出行数上限，只保留了最早的部分
+    pub truncated: bool,
+    pub audit: Vec<crate::audit::AuditEntry>,
+}
+
+/// 在最近的日志文件中查找属于指定操作的行
+fn collect_operation_lines(operation_id: &str) -> (Vec<String>, bool) {
+    let needle = format!("operation_id={operation_id}");
+    let log_dir = crate::directories::get_log_directory();
+    let mut files: Vec<_> = fs::read_dir(&log_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| {
+                    entry
+                        .file_name()
+                        .to_string_lossy()
+                        .starts_with(crate::utils::sanitizing_layer::LOG_FILE_PREFIX)
+                })
+                .filter_map(|entry| {
+                    let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+                    Some((modified, entry.path()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    files.truncate(OPERATION_TRACE_MAX_FILES);
+
+    let mut lines = Vec::new();
+    // 从旧到新读取，保证结果按时间顺序
+    for (_, path) in files.iter().rev() {
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        for line in content.lines().filter(|line| line.contains(&needle)) {
+            if lines.len() >= OPERATION_TRACE_MAX_LINES {
+                return (lines, true);
+            }
+            lines.push(line.to_string());
+        }
+    }
+    (lines, false)
+}
+
+/// 按操作 ID（即任务 ID）汇总前后端日志与审计记录，用于排查某次失败的恢复、导入等操作
+#[tauri::command]
+pub async fn get_operation_trace(
+    operation_id: String,
+) -> crate::error::AgentResult<OperationTrace> {
+    crate::log_async_command!("get_operation_trace", async {
+        let operation_id = operation_id.trim().to_string();
+        if operation_id.is_empty() || operation_id.chars().any(char::is_whitespace) {
+            return Err(crate::error::AgentError::InvalidInput(
+                "操作 ID 无效".to_string(),
+            ));
+        }
+
+        crate::utils::blocking::run_blocking(move || {
+            let (log_lines, truncated) = collect_operation_lines(&operation_id);
+            let audit = crate::audit::read_for_operation(&operation_id);
+            Ok(OperationTrace {
+                operation_id,
+                log_lines,
+                truncated,
+                audit,
+            })
+        })
+        .await
+    })
+}
+
 /// 打开日志目录
 /// 在系统文件管理器中打开日志目录（例如 Windows 资源管理器 / macOS Finder / Linux 文件管理器）
 #[tauri::command]
 pub async fn open_log_directory() -> Result<(), String> {
     crate::log_async_command!("open_log_directory", async {
         let log_dir = crate::directories::get_log_directory();
+        // 尚未写入日志时目录可能不存在
+        fs::create_dir_all(&log_dir).map_err(|e| format!("创建日志目录失败: {}", e))?;
 
         tauri_plugin_opener::open_path(&log_dir, None::<&str>)
             .map_err(|e| format!("打开日志目录失败: {}", e))?;
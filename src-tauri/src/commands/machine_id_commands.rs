@@ -0,0 +1,25 @@
+//! Antigravity 设备标识命令
+
+use crate::antigravity::machine_id::{self, MachineIdReport, MachineIdResetResult};
+use crate::error::{AgentError, AgentResult};
+
+/// 查看 Antigravity 当前的设备标识
+#[tauri::command]
+pub async fn get_machine_ids() -> AgentResult<MachineIdReport> {
+    crate::log_async_command!("get_machine_ids", async { machine_id::read_machine_ids() })
+}
+
+/// 重新生成 Antigravity 设备标识（会先快照原始值）
+///
+/// `confirmed` 必须为 true，防止误调用
+#[tauri::command]
+pub async fn reset_machine_ids(confirmed: bool) -> AgentResult<MachineIdResetResult> {
+    crate::log_async_command!("reset_machine_ids", async {
+        if !confirmed {
+            return Err(AgentError::InvalidInput(
+                "重置设备标识需要用户确认".to_string(),
+            ));
+        }
+        machine_id::reset_machine_ids()
+    })
+}
@@ -0,0 +1,10 @@
+//! 命令性能统计命令
+
+use crate::error::AgentResult;
+use crate::metrics::MetricsSnapshot;
+
+/// 获取各命令的调用次数、失败次数与耗时分布
+#[tauri::command]
+pub async fn get_metrics_snapshot() -> AgentResult<MetricsSnapshot> {
+    Ok(crate::metrics::snapshot())
+}
@@ -24,14 +24,99 @@ pub mod settings_commands;
 
 // 数据库监控命令
 pub mod db_monitor_commands;
+
+// 健康检查命令
+pub mod health_commands;
+
+// 本地 HTTP 控制接口命令
+pub mod http_api_commands;
+
+// 生命周期钩子与审计日志命令
+pub mod hooks_commands;
+
+// Agent 自更新命令
+pub mod updater_commands;
+
+// Antigravity 设备标识命令
+pub mod machine_id_commands;
+
+// 代理设置命令
+pub mod proxy_commands;
+
+// 长耗时任务命令
+pub mod task_commands;
+
+// 操作锁查询命令
+pub mod operation_commands;
+
+// 崩溃报告命令
+pub mod crash_commands;
+
+// 启动性能统计命令
+pub mod startup_commands;
+
+// 命令性能统计命令
+pub mod metrics_commands;
+
+// 账户元数据命令
+pub mod account_metadata_commands;
+
+// 危险操作确认命令
+pub mod confirmation_commands;
+
+// 数据库文件快照命令
+pub mod db_snapshot_commands;
+
+// 数据库完整性命令
+pub mod integrity_commands;
+
+// 存储占用命令
+pub mod storage_commands;
+
+// 状态订阅命令
+pub mod state_commands;
+
+// 剪贴板命令
+pub mod clipboard_commands;
+
+// 定时任务命令
+pub mod scheduler_commands;
+
+// 使用统计命令
+pub mod telemetry_commands;
+
+// 账户配额命令
+pub mod quota_commands;
 // 语言服务器相关命令（在 src/language_server 下）
 
 // 重新导出所有命令，保持与 main.rs 的兼容性
 pub use account_commands::*;
 pub use account_manage_commands::*;
+pub use account_metadata_commands::*;
+pub use clipboard_commands::*;
+pub use confirmation_commands::*;
+pub use crash_commands::*;
 pub use db_monitor_commands::*;
+pub use db_snapshot_commands::*;
+pub use health_commands::*;
+pub use hooks_commands::*;
+pub use http_api_commands::*;
+pub use integrity_commands::*;
 pub use logging_commands::*;
+pub use machine_id_commands::*;
+pub use metrics_commands::*;
+pub use operation_commands::*;
 pub use platform_commands::*;
 pub use process_commands::*;
+pub use proxy_commands::*;
+pub use quota_commands::*;
+pub use scheduler_commands::*;
 pub use settings_commands::*;
+pub use startup_commands::*;
+pub use state_commands::*;
+pub use storage_commands::*;
+pub use task_commands::*;
+pub use telemetry_commands::*;
 pub use tray_commands::*;
+pub use updater_commands::*;
+pub use window_commands::*;
@@ -0,0 +1,10 @@
+//! 操作锁查询命令
+
+use crate::error::AgentResult;
+use crate::operation_lock::ActiveOperation;
+
+/// 获取当前正在进行的破坏性操作（切换、恢复、清除、备份等）
+#[tauri::command]
+pub async fn get_active_operations() -> AgentResult<Vec<ActiveOperation>> {
+    Ok(crate::operation_lock::active_operations())
+}
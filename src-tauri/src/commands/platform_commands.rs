@@ -11,20 +11,38 @@ pub async fn get_platform_info() -> Result<Value, String> {
     let family = std::env::consts::FAMILY;
 
     let antigravity_available = crate::platform::is_antigravity_available();
-    let antigravity_paths = crate::platform::get_all_antigravity_db_paths();
+    let detection = crate::platform::platform_detection();
+    let tray = crate::system_tray::tray_capability();
 
     Ok(serde_json::json!({
         "os": os_type,
         "arch": arch,
         "family": family,
         "antigravity_available": antigravity_available,
-        "antigravity_paths": antigravity_paths.iter().map(|p| p.to_string_lossy()).collect::<Vec<_>>(),
+        "antigravity_paths": detection.db_paths.iter().map(|p| p.to_string_lossy()).collect::<Vec<_>>(),
         "config_dir": dirs::config_dir().map(|p| p.to_string_lossy().to_string()),
         "data_dir": dirs::data_dir().map(|p| p.to_string_lossy().to_string()),
-        "home_dir": dirs::home_dir().map(|p| p.to_string_lossy().to_string())
+        "home_dir": dirs::home_dir().map(|p| p.to_string_lossy().to_string()),
+        "portable_mode": crate::directories::is_portable_mode(),
+        "tray_supported": tray.supported,
+        "tray_unsupported_reason": tray.reason,
+        "agent_data_dir": crate::directories::get_config_directory().to_string_lossy(),
+        "install_flavor": detection.flavor,
+        "wsl": crate::platform::wsl::is_wsl(),
+        "detected_at": detection.detected_at
     }))
 }
 
+/// 重新检测 Antigravity 安装位置（丢弃缓存的数据库路径与可执行文件检测结果）
+#[tauri::command]
+pub async fn refresh_platform_detection() -> Result<crate::platform::PlatformDetection, String> {
+    let detection =
+        crate::utils::blocking::run_blocking(|| Ok(crate::platform::refresh_platform_detection()))
+            .await
+            .map_err(|e| e.to_string())?;
+    Ok((*detection).clone())
+}
+
 /// 查找 Antigravity 安装位置
 #[tauri::command]
 pub async fn find_antigravity_installations() -> Result<Vec<String>, String> {
@@ -43,6 +61,20 @@ pub async fn validate_antigravity_executable(path: String) -> Result<bool, Strin
     ))
 }
 
+/// 完整验证 Antigravity 可执行文件（存在性、可执行权限、.app 包结构、macOS 签名与版本），保存前调用
+#[tauri::command]
+pub async fn validate_custom_executable(
+    path: String,
+) -> Result<crate::antigravity::path_config::ExecutableValidation, String> {
+    crate::utils::blocking::run_blocking(move || {
+        Ok(crate::antigravity::path_config::validate_custom_executable(
+            &path,
+        ))
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
 /// 检测 Antigravity 安装状态（数据库路径）
 #[tauri::command]
 pub async fn detect_antigravity_installation() -> Result<serde_json::Value, String> {
@@ -54,8 +86,12 @@ pub async fn detect_antigravity_installation() -> Result<serde_json::Value, Stri
                 .map(|p| p.to_string_lossy().to_string())
                 .unwrap_or_default();
 
-            println!("📁 检测到 Antigravity 数据库: {}", db_path.display());
-            println!("📂 Antigravity 数据目录: {}", data_dir);
+            tracing::info!(
+                target: "platform::detect",
+                db_path = %db_path.display(),
+                data_dir = %data_dir,
+                "检测到 Antigravity 数据库"
+            );
 
             return Ok(serde_json::json!({
                 "found": true,
@@ -66,7 +102,7 @@ pub async fn detect_antigravity_installation() -> Result<serde_json::Value, Stri
     }
 
     // 未找到
-    println!("⚠️ 未找到 Antigravity 数据库");
+    tracing::warn!(target: "platform::detect", "未找到 Antigravity 数据库");
     Ok(serde_json::json!({
         "found": false,
         "path": null,
@@ -94,7 +130,7 @@ pub async fn detect_antigravity_executable() -> Result<serde_json::Value, String
     // 3. 尝试自动检测
     let detected_path = crate::antigravity::starter::detect_antigravity_executable();
     if let Some(exec_path) = detected_path {
-        println!("✅ 检测到 Antigravity 可执行文件: {}", exec_path.display());
+        tracing::info!(target: "platform::detect", path = %exec_path.display(), "检测到 Antigravity 可执行文件");
 
         return Ok(serde_json::json!({
             "found": true,
@@ -104,7 +140,7 @@ pub async fn detect_antigravity_executable() -> Result<serde_json::Value, String
     }
 
     // 4. 未找到
-    println!("⚠️ 未找到 Antigravity 可执行文件，启动功能可能不可用");
+    tracing::warn!(target: "platform::detect", "未找到 Antigravity 可执行文件，启动功能可能不可用");
     Ok(serde_json::json!({
         "found": false,
         "path": null,
@@ -116,8 +152,13 @@ pub async fn detect_antigravity_executable() -> Result<serde_json::Value, String
 #[tauri::command]
 pub async fn save_antigravity_executable(path: String) -> Result<String, String> {
     // 1. 验证路径有效性
-    if !crate::antigravity::path_config::validate_executable_path(&path) {
-        return Err(format!("路径无效：文件 '{}' 不存在或不是可执行文件", path));
+    let validation = crate::antigravity::path_config::validate_custom_executable(&path);
+    if !validation.valid {
+        return Err(format!(
+            "路径无效：'{}' {}",
+            path,
+            validation.problems.join("；")
+        ));
     }
 
     // 2. 保存路径到配置
@@ -126,12 +167,82 @@ pub async fn save_antigravity_executable(path: String) -> Result<String, String>
     Ok(format!("已保存 Antigravity 可执行文件路径: {}", path))
 }
 
+/// 保存用户自定义的 Antigravity 数据目录（传入 None 恢复自动检测），保存后重新检测安装位置
+#[tauri::command]
+pub async fn save_antigravity_data_dir(path: Option<String>) -> Result<String, String> {
+    let path = path.filter(|p| !p.trim().is_empty());
+    if let Some(ref dir) = path {
+        if !crate::antigravity::path_config::validate_data_dir(dir) {
+            return Err(format!("路径无效：目录 '{}' 不存在", dir));
+        }
+    }
+
+    crate::antigravity::path_config::save_custom_data_dir(path.clone())?;
+    crate::utils::blocking::run_blocking(|| Ok(crate::platform::refresh_platform_detection()))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if std::env::var_os(crate::platform::DATA_DIR_ENV).is_some() {
+        tracing::warn!(
+            target: "platform::detect",
+            env = crate::platform::DATA_DIR_ENV,
+            "已设置数据目录环境变量，自定义数据目录暂不生效"
+        );
+    }
+
+    Ok(match path {
+        Some(dir) => format!("已保存 Antigravity 数据目录: {}", dir),
+        None => "已恢复自动检测 Antigravity 数据目录".to_string(),
+    })
+}
+
+/// 列出检测到的所有 Antigravity 安装（数据目录、可执行文件与版本）
+#[tauri::command]
+pub async fn list_antigravity_installs(
+) -> Result<Vec<crate::platform::installs::AntigravityInstall>, String> {
+    crate::utils::blocking::run_blocking(|| Ok(crate::platform::installs::list_installs()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 选择要使用的 Antigravity 安装（传入数据目录，None 恢复自动选择）
+#[tauri::command]
+pub async fn select_antigravity_install(data_dir: Option<String>) -> Result<String, String> {
+    let data_dir = data_dir.filter(|dir| !dir.trim().is_empty());
+    if let Some(ref dir) = data_dir {
+        let dir = std::path::PathBuf::from(dir);
+        let installs =
+            crate::utils::blocking::run_blocking(|| Ok(crate::platform::installs::list_installs()))
+                .await
+                .map_err(|e| e.to_string())?;
+        if !installs.iter().any(|install| install.data_dir == dir) {
+            return Err(format!("未检测到数据目录为 '{}' 的安装", dir.display()));
+        }
+    }
+
+    crate::antigravity::path_config::save_selected_install(data_dir.clone())?;
+    crate::utils::blocking::run_blocking(|| Ok(crate::platform::refresh_platform_detection()))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(match data_dir {
+        Some(dir) => format!("已切换到 Antigravity 安装: {}", dir),
+        None => "已恢复自动选择 Antigravity 安装".to_string(),
+    })
+}
+
 /// 获取当前配置的路径
 #[tauri::command]
 pub async fn get_current_paths() -> Result<serde_json::Value, String> {
     let exec_path = crate::antigravity::path_config::get_custom_executable_path().unwrap_or(None);
+    let data_dir = crate::antigravity::path_config::get_custom_data_dir().unwrap_or(None);
+    let data_dir_env = std::env::var(crate::platform::DATA_DIR_ENV)
+        .ok()
+        .filter(|value| !value.is_empty());
 
     Ok(serde_json::json!({
-        "executablePath": exec_path
+        "executablePath": exec_path,
+        "dataDir": data_dir,
+        "dataDirEnv": data_dir_env
     }))
 }
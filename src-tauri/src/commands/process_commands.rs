@@ -3,3 +3,17 @@
 pub async fn is_antigravity_running() -> bool {
     crate::platform::is_antigravity_running()
 }
+
+/// 获取 Agent 启动且仍在运行的 Antigravity 进程
+#[tauri::command]
+pub async fn get_launched_processes() -> Vec<crate::platform::launched::LaunchedProcess> {
+    crate::platform::launched::launched_processes()
+}
+
+/// 列出关闭 Antigravity 时会被关闭的进程（只查询，不关闭）
+#[tauri::command]
+pub async fn list_matching_processes() -> Result<Vec<crate::platform::MatchedProcess>, String> {
+    crate::utils::blocking::run_blocking(|| Ok(crate::platform::list_matching_processes()))
+        .await
+        .map_err(|e| e.to_string())
+}
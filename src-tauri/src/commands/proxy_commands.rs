@@ -0,0 +1,44 @@
+//! 代理设置命令
+
+use crate::app_settings::AppSettingsManager;
+use crate::error::AgentResult;
+use crate::proxy::{ProxySettings, ProxyTestResult};
+use tauri::{AppHandle, Manager};
+
+/// 获取代理设置
+#[tauri::command]
+pub async fn get_proxy_settings(app: AppHandle) -> AgentResult<ProxySettings> {
+    Ok(app.state::<AppSettingsManager>().get_settings().proxy)
+}
+
+/// 保存代理设置
+#[tauri::command]
+pub async fn save_proxy_settings(
+    app: AppHandle,
+    settings: ProxySettings,
+) -> AgentResult<ProxySettings> {
+    crate::log_async_command!("save_proxy_settings", async {
+        settings.validate()?;
+        app.state::<AppSettingsManager>()
+            .update_settings(|s| s.proxy = settings.clone())?;
+        Ok(settings)
+    })
+}
+
+/// 测试代理连接（可传入未保存的设置）
+#[tauri::command]
+pub async fn test_proxy_connection(
+    app: AppHandle,
+    settings: Option<ProxySettings>,
+    target: Option<String>,
+) -> AgentResult<ProxyTestResult> {
+    crate::log_async_command!("test_proxy_connection", async {
+        let settings =
+            settings.unwrap_or_else(|| app.state::<AppSettingsManager>().get_settings().proxy);
+        tokio::task::spawn_blocking(move || {
+            crate::proxy::test_connection(&settings, target.as_deref())
+        })
+        .await
+        .map_err(|e| crate::error::AgentError::Internal(format!("代理测试任务异常: {}", e)))?
+    })
+}
@@ -0,0 +1,22 @@
+//! 账户配额命令
+
+use crate::error::AgentResult;
+use crate::quota_history::{QuotaComparison, QuotaSample};
+use crate::utils::blocking::run_blocking;
+
+/// 记录获取到的账户配额（前端每次刷新配额后调用）
+#[tauri::command]
+pub async fn record_account_quota(email: String, samples: Vec<QuotaSample>) -> AgentResult<()> {
+    run_blocking(move || crate::quota_history::record(&email, &samples)).await
+}
+
+/// 比较账户最近一次记录的剩余配额，返回排序结果与推荐切换的账户
+#[tauri::command]
+pub async fn compare_accounts_quota(
+    emails: Vec<String>,
+    model: Option<String>,
+) -> AgentResult<QuotaComparison> {
+    crate::log_async_command!("compare_accounts_quota", async {
+        run_blocking(move || crate::quota_history::compare(&emails, model.as_deref())).await
+    })
+}
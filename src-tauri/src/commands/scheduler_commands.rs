@@ -0,0 +1,94 @@
+//! 定时任务命令
+
+use serde::Serialize;
+
+use crate::error::AgentResult;
+use crate::scheduler::{JobInput, JobStatus, RunRecord, Schedule};
+
+/// 默认预览的运行次数
+const DEFAULT_PREVIEW_COUNT: usize = 5;
+
+/// 预览运行次数的上限
+const MAX_PREVIEW_COUNT: usize = 50;
+
+/// 运行历史默认返回的条数
+const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+/// Cron 表达式的校验结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CronValidation {
+    pub valid: bool,
+    /// 无效的原因
+    pub error: Option<String>,
+    /// 接下来几次运行时间（RFC 3339）
+    pub next_runs: Vec<String>,
+}
+
+fn preview(schedule: &Schedule, count: Option<usize>) -> Vec<String> {
+    let count = count
+        .unwrap_or(DEFAULT_PREVIEW_COUNT)
+        .min(MAX_PREVIEW_COUNT);
+    schedule
+        .upcoming(chrono::Local::now(), count)
+        .into_iter()
+        .map(|time| time.to_rfc3339())
+        .collect()
+}
+
+/// 校验 Cron 表达式，有效时附带接下来几次运行时间
+#[tauri::command]
+pub async fn validate_cron(
+    expression: String,
+    count: Option<usize>,
+) -> AgentResult<CronValidation> {
+    let schedule = Schedule::Cron { expression };
+    Ok(match schedule.validate() {
+        Ok(()) => CronValidation {
+            valid: true,
+            error: None,
+            next_runs: preview(&schedule, count),
+        },
+        Err(e) => CronValidation {
+            valid: false,
+            error: Some(e.to_string()),
+            next_runs: Vec::new(),
+        },
+    })
+}
+
+/// 预览计划接下来几次运行时间（间隔计划从当前时间起算）
+#[tauri::command]
+pub async fn preview_schedule(
+    schedule: Schedule,
+    count: Option<usize>,
+) -> AgentResult<Vec<String>> {
+    schedule.validate()?;
+    Ok(preview(&schedule, count))
+}
+
+/// 获取所有定时任务
+#[tauri::command]
+pub async fn list_scheduled_jobs() -> AgentResult<Vec<JobStatus>> {
+    Ok(crate::scheduler::list_jobs())
+}
+
+/// 新建或修改定时任务（按名称匹配）
+#[tauri::command]
+pub async fn save_scheduled_job(job: JobInput) -> AgentResult<JobStatus> {
+    crate::scheduler::save_job(job)
+}
+
+/// 删除定时任务
+#[tauri::command]
+pub async fn delete_scheduled_job(name: String) -> AgentResult<()> {
+    crate::scheduler::delete_job(&name)
+}
+
+/// 获取定时任务的运行历史（最新的在前）
+#[tauri::command]
+pub async fn get_scheduler_history(limit: Option<usize>) -> AgentResult<Vec<RunRecord>> {
+    Ok(crate::scheduler::history(
+        limit.unwrap_or(DEFAULT_HISTORY_LIMIT),
+    ))
+}
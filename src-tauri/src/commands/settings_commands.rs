@@ -3,24 +3,6 @@
 
 use tauri::{AppHandle, Manager};
 
-/// 保存系统托盘状态
-#[tauri::command]
-pub async fn save_system_tray_state(app: AppHandle, enabled: bool) -> Result<bool, String> {
-    crate::log_async_command!("save_system_tray_state", async {
-        let system_tray = app.state::<crate::system_tray::SystemTrayManager>();
-
-        if enabled {
-            system_tray.enable(&app)?;
-        } else {
-            system_tray.disable(&app)?;
-        }
-
-        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
-        let settings = settings_manager.get_settings();
-        Ok(settings.system_tray_enabled)
-    })
-}
-
 /// 保存静默启动状态
 #[tauri::command]
 pub async fn save_silent_start_state(app: AppHandle, enabled: bool) -> Result<bool, String> {
@@ -66,6 +48,435 @@ pub async fn save_debug_mode_state(app: AppHandle, enabled: bool) -> Result<bool
     })
 }
 
+/// 保存账户变化自动备份状态
+#[tauri::command]
+pub async fn save_auto_backup_on_account_change_state(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!("save_auto_backup_on_account_change_state", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.auto_backup_on_account_change = enabled;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.auto_backup_on_account_change)
+    })
+}
+
+/// 保存隐藏 Dock 图标状态（仅 macOS，托盘启用时生效）
+#[tauri::command]
+pub async fn save_hide_dock_icon_state(app: AppHandle, enabled: bool) -> Result<bool, String> {
+    crate::log_async_command!("save_hide_dock_icon_state", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.hide_dock_icon = enabled;
+        })?;
+        app.state::<crate::system_tray::SystemTrayManager>()
+            .apply_dock_visibility(&app);
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.hide_dock_icon)
+    })
+}
+
+/// 保存危险操作二次确认状态
+#[tauri::command]
+pub async fn save_confirm_dangerous_operations_state(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!("save_confirm_dangerous_operations_state", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.confirm_dangerous_operations = enabled;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.confirm_dangerous_operations)
+    })
+}
+
+/// 保存写入前数据库文件快照状态
+#[tauri::command]
+pub async fn save_db_snapshot_before_write_state(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!("save_db_snapshot_before_write_state", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.db_snapshot_before_write = enabled;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.db_snapshot_before_write)
+    })
+}
+
+/// 保存清除数据前自动备份状态
+#[tauri::command]
+pub async fn save_backup_before_clear_state(app: AppHandle, enabled: bool) -> Result<bool, String> {
+    crate::log_async_command!("save_backup_before_clear_state", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.backup_before_clear = enabled;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.backup_before_clear)
+    })
+}
+
+/// 保存邮箱打码风格（立即作用于后续日志、托盘菜单与导出进度）
+#[tauri::command]
+pub async fn save_email_mask_style(
+    app: AppHandle,
+    style: crate::utils::email_mask::EmailMaskStyle,
+) -> Result<crate::utils::email_mask::EmailMaskStyle, String> {
+    crate::log_async_command!("save_email_mask_style", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.email_mask_style = style;
+        })?;
+        crate::utils::email_mask::set_style(style);
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.email_mask_style)
+    })
+}
+
+/// 保存备份文件命名模板（只影响新账户的备份，已有文件需调用 `migrate_backup_file_names` 重命名）
+#[tauri::command]
+pub async fn save_backup_file_name_template(
+    app: AppHandle,
+    template: String,
+) -> Result<String, String> {
+    crate::log_async_command!("save_backup_file_name_template", async {
+        let template = template.trim().to_string();
+        crate::antigravity::backup_naming::validate_template(&template)
+            .map_err(|e| e.to_string())?;
+
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+        settings_manager.update_settings(|settings| {
+            settings.backup_file_name_template = template;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.backup_file_name_template)
+    })
+}
+
+/// 保存备份过期提醒天数（0 表示不提醒）
+#[tauri::command]
+pub async fn save_backup_stale_days(app: AppHandle, days: u32) -> Result<u32, String> {
+    crate::log_async_command!("save_backup_stale_days", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.backup_stale_days = days;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.backup_stale_days)
+    })
+}
+
+/// 保存时间显示设置（立即作用于之后返回的日志信息、备份列表与审计记录）
+#[tauri::command]
+pub async fn save_time_display_settings(
+    app: AppHandle,
+    time_display: crate::utils::time_format::TimeDisplaySettings,
+) -> Result<crate::utils::time_format::TimeDisplaySettings, String> {
+    crate::log_async_command!("save_time_display_settings", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.time_display = time_display;
+        })?;
+        crate::utils::time_format::set_settings(time_display);
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.time_display)
+    })
+}
+
+/// 保存启动策略（启动 Antigravity 时发现已有实例在运行的处理方式）
+#[tauri::command]
+pub async fn save_launch_policy(
+    app: AppHandle,
+    policy: crate::antigravity::starter::LaunchPolicy,
+) -> Result<crate::antigravity::starter::LaunchPolicy, String> {
+    crate::log_async_command!("save_launch_policy", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.launch_policy = policy;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.launch_policy)
+    })
+}
+
+/// 保存重启后是否重新打开之前的工作区
+#[tauri::command]
+pub async fn save_reopen_workspace_on_restart(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<bool, String> {
+    crate::log_async_command!("save_reopen_workspace_on_restart", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.reopen_workspace_on_restart = enabled;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.reopen_workspace_on_restart)
+    })
+}
+
+/// 保存等待超时设置（关闭 Antigravity 后等待进程退出、数据库解锁的最长时间）
+#[tauri::command]
+pub async fn save_wait_settings(
+    app: AppHandle,
+    wait_settings: crate::platform::wait::WaitSettings,
+) -> Result<crate::platform::wait::WaitSettings, String> {
+    crate::log_async_command!("save_wait_settings", async {
+        wait_settings.validate()?;
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.wait_settings = wait_settings;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.wait_settings)
+    })
+}
+
+/// 保存切换账户后是否验证登录状态
+#[tauri::command]
+pub async fn save_verify_login_after_switch(app: AppHandle, enabled: bool) -> Result<bool, String> {
+    crate::log_async_command!("save_verify_login_after_switch", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.verify_login_after_switch = enabled;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.verify_login_after_switch)
+    })
+}
+
+/// 保存界面语言（立即按新语言重建托盘菜单与提示）
+#[tauri::command]
+pub async fn save_language(
+    app: AppHandle,
+    language: crate::utils::i18n::Language,
+) -> Result<crate::utils::i18n::Language, String> {
+    crate::log_async_command!("save_language", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.language = language;
+        })?;
+        crate::utils::i18n::set_language(language);
+        crate::system_tray::refresh_tray_language(&app)?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.language)
+    })
+}
+
+/// 保存不再询问的原生确认对话框（传入空列表恢复全部确认）
+#[tauri::command]
+pub async fn save_skipped_confirm_prompts(
+    app: AppHandle,
+    prompts: Vec<crate::dialog::ConfirmPrompt>,
+) -> Result<Vec<crate::dialog::ConfirmPrompt>, String> {
+    crate::log_async_command!("save_skipped_confirm_prompts", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.skipped_confirm_prompts = prompts;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.skipped_confirm_prompts)
+    })
+}
+
+/// 保存 WSL 模式（运行在 WSL 中时操作 Windows 上的 Antigravity），保存后重新检测安装位置
+#[tauri::command]
+pub async fn save_wsl_windows_mode(app: AppHandle, enabled: bool) -> Result<bool, String> {
+    crate::log_async_command!("save_wsl_windows_mode", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.wsl_windows_mode = enabled;
+        })?;
+        crate::utils::blocking::run_blocking(|| Ok(crate::platform::refresh_platform_detection()))
+            .await?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.wsl_windows_mode)
+    })
+}
+
+/// 保存额外备份的键与排除的键（不允许加入设备标识等危险键，也不能排除账户状态键），返回合并后实际处理的键
+#[tauri::command]
+pub async fn save_backup_key_overrides(
+    app: AppHandle,
+    extra_keys: Vec<String>,
+    excluded_keys: Vec<String>,
+) -> Result<crate::antigravity::key_mapping::EffectiveKeys, String> {
+    crate::log_async_command!("save_backup_key_overrides", async {
+        crate::antigravity::key_mapping::validate_overrides(&extra_keys, &excluded_keys)
+            .map_err(|e| e.to_string())?;
+
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+        settings_manager.update_settings(|settings| {
+            settings.backup_extra_keys = extra_keys;
+            settings.backup_excluded_keys = excluded_keys;
+        })?;
+
+        Ok(crate::antigravity::key_mapping::effective_keys())
+    })
+}
+
+/// 保存定时任务设置（系统空闲多久后才运行备份等耗时任务）
+#[tauri::command]
+pub async fn save_scheduler_settings(
+    app: AppHandle,
+    scheduler_settings: crate::scheduler::SchedulerSettings,
+) -> Result<crate::scheduler::SchedulerSettings, String> {
+    crate::log_async_command!("save_scheduler_settings", async {
+        scheduler_settings.validate()?;
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.scheduler = scheduler_settings;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.scheduler)
+    })
+}
+
+/// 保存使用统计设置（关闭时清除已收集的数据）
+#[tauri::command]
+pub async fn save_telemetry_settings(
+    app: AppHandle,
+    telemetry_settings: crate::telemetry::TelemetrySettings,
+) -> Result<crate::telemetry::TelemetrySettings, String> {
+    crate::log_async_command!("save_telemetry_settings", async {
+        telemetry_settings.validate()?;
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.telemetry = telemetry_settings;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        crate::telemetry::apply_settings(&settings.telemetry);
+        Ok(settings.telemetry)
+    })
+}
+
+/// 保存最小磁盘剩余空间（MB，0 表示不检查），立即作用于之后的备份、快照、恢复与日志写入
+#[tauri::command]
+pub async fn save_min_free_disk_mb(app: AppHandle, mb: u64) -> Result<u64, String> {
+    crate::log_async_command!("save_min_free_disk_mb", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.min_free_disk_mb = mb;
+        })?;
+        crate::disk_space::set_min_free_mb(mb);
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.min_free_disk_mb)
+    })
+}
+
+/// 获取界面主题（用户选择的主题与实际生效的主题）
+#[tauri::command]
+pub async fn get_theme_state(app: AppHandle) -> Result<crate::appearance::ThemeState, String> {
+    crate::log_async_command!("get_theme_state", async {
+        Ok::<_, String>(crate::appearance::current(&app))
+    })
+}
+
+/// 保存界面主题，立即应用到窗口与托盘图标（变化通过 `appearance:theme-changed` 事件推送）
+#[tauri::command]
+pub async fn save_theme_preference(
+    app: AppHandle,
+    theme: crate::appearance::ThemePreference,
+) -> Result<crate::appearance::ThemeState, String> {
+    crate::log_async_command!("save_theme_preference", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.theme = theme;
+        })?;
+
+        Ok(crate::appearance::apply(&app, theme))
+    })
+}
+
+/// 获取界面偏好（强调色、界面密度与字号缩放）
+#[tauri::command]
+pub async fn get_ui_preferences(
+    app: AppHandle,
+) -> Result<crate::appearance::UiPreferences, String> {
+    crate::log_async_command!("get_ui_preferences", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+        Ok::<_, String>(settings_manager.get_settings().ui_preferences)
+    })
+}
+
+/// 保存界面偏好，通过 `appearance:ui-preferences-changed` 事件同步到所有窗口
+#[tauri::command]
+pub async fn set_ui_preferences(
+    app: AppHandle,
+    ui_preferences: crate::appearance::UiPreferences,
+) -> Result<crate::appearance::UiPreferences, String> {
+    crate::log_async_command!("set_ui_preferences", async {
+        let ui_preferences = ui_preferences.normalize()?;
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.ui_preferences = ui_preferences;
+        })?;
+
+        let settings = settings_manager.get_settings();
+        crate::appearance::publish_ui_preferences(&app, &settings.ui_preferences);
+        Ok(settings.ui_preferences)
+    })
+}
+
+/// 保存只读模式状态
+#[tauri::command]
+pub async fn save_read_only_mode_state(app: AppHandle, enabled: bool) -> Result<bool, String> {
+    crate::log_async_command!("save_read_only_mode_state", async {
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+        settings_manager.set_read_only_mode(enabled)?;
+
+        let settings = settings_manager.get_settings();
+        Ok(settings.read_only_mode)
+    })
+}
+
 /// 获取所有应用设置
 #[tauri::command]
 pub async fn get_all_settings(app: AppHandle) -> Result<serde_json::Value, String> {
@@ -77,7 +488,45 @@ pub async fn get_all_settings(app: AppHandle) -> Result<serde_json::Value, Strin
             "system_tray_enabled": settings.system_tray_enabled,
             "silent_start_enabled": settings.silent_start_enabled,
             "debugMode": settings.debug_mode,
-            "privateMode": settings.private_mode
+            "privateMode": settings.private_mode,
+            "setupCompleted": settings.setup_completed,
+            "autoBackupOnAccountChange": settings.auto_backup_on_account_change,
+            "autoCheckUpdates": settings.auto_check_updates,
+            "updateEndpoint": settings.update_endpoint,
+            "readOnlyMode": settings.read_only_mode,
+            "hideDockIcon": settings.hide_dock_icon,
+            "confirmDangerousOperations": settings.confirm_dangerous_operations,
+            "dbSnapshotBeforeWrite": settings.db_snapshot_before_write,
+            "backupBeforeClear": settings.backup_before_clear,
+            "emailMaskStyle": settings.email_mask_style,
+            "backupFileNameTemplate": settings.backup_file_name_template,
+            "backupStaleDays": settings.backup_stale_days,
+            "timeDisplay": settings.time_display,
+            "launchPolicy": settings.launch_policy,
+            "reopenWorkspaceOnRestart": settings.reopen_workspace_on_restart,
+            "waitSettings": settings.wait_settings,
+            "verifyLoginAfterSwitch": settings.verify_login_after_switch,
+            "language": settings.language,
+            "skippedConfirmPrompts": settings.skipped_confirm_prompts,
+            "wslWindowsMode": settings.wsl_windows_mode,
+            "backupExtraKeys": settings.backup_extra_keys,
+            "backupExcludedKeys": settings.backup_excluded_keys,
+            "schedulerSettings": settings.scheduler,
+            "telemetry": settings.telemetry,
+            "minFreeDiskMb": settings.min_free_disk_mb,
+            "theme": settings.theme,
+            "uiPreferences": settings.ui_preferences,
+            "retentionPolicy": settings.retention_policy
         }))
     })
 }
+
+/// 执行首次运行引导：检测环境、写入默认设置并返回检测报告
+#[tauri::command]
+pub async fn run_first_time_setup(
+    app: AppHandle,
+) -> Result<crate::first_run::SetupReport, crate::error::AgentError> {
+    crate::log_async_command!("run_first_time_setup", async {
+        crate::first_run::run(&app)
+    })
+}
@@ -0,0 +1,10 @@
+//! 启动性能统计命令
+
+use crate::error::AgentResult;
+use crate::startup_metrics::StartupMetrics;
+
+/// 获取启动各阶段耗时
+#[tauri::command]
+pub async fn get_startup_metrics() -> AgentResult<StartupMetrics> {
+    Ok(crate::startup_metrics::snapshot())
+}
@@ -0,0 +1,40 @@
+//! 状态订阅与首页概览命令
+
+use serde::Serialize;
+
+use crate::dashboard::DashboardSummary;
+use crate::error::AgentResult;
+use crate::state_bus::AgentStateSnapshot;
+
+/// 订阅信息：监听的事件名与当前快照
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSubscription {
+    pub event: &'static str,
+    pub snapshot: AgentStateSnapshot,
+}
+
+/// 获取完整状态快照（用于前端初始化）
+#[tauri::command]
+pub async fn get_full_state() -> AgentResult<AgentStateSnapshot> {
+    crate::log_async_command!("get_full_state", async { Ok(crate::state_bus::snapshot()) })
+}
+
+/// 订阅状态变化：返回需要监听的事件名与当前快照，之后的变化通过事件推送
+#[tauri::command]
+pub async fn subscribe_state() -> AgentResult<StateSubscription> {
+    crate::log_async_command!("subscribe_state", async {
+        Ok(StateSubscription {
+            event: crate::state_bus::AGENT_STATE_EVENT,
+            snapshot: crate::state_bus::snapshot(),
+        })
+    })
+}
+
+/// 获取首页概览（当前账户、进程状态、备份情况与待处理提醒）
+#[tauri::command]
+pub async fn get_dashboard_summary() -> AgentResult<DashboardSummary> {
+    crate::log_async_command!("get_dashboard_summary", async {
+        crate::utils::blocking::run_blocking(|| Ok(crate::dashboard::collect())).await
+    })
+}
@@ -0,0 +1,41 @@
+//! 存储占用命令
+
+use tauri::{AppHandle, Manager};
+
+use crate::error::AgentResult;
+use crate::retention::{RetentionPlan, RetentionPolicy};
+use crate::storage_stats::StorageStats;
+
+/// 统计账户备份与快照的存储占用
+#[tauri::command]
+pub async fn get_backup_storage_stats() -> AgentResult<StorageStats> {
+    crate::log_async_command!("get_backup_storage_stats", async {
+        crate::utils::blocking::run_blocking(|| Ok(crate::storage_stats::collect())).await
+    })
+}
+
+/// 模拟按保留策略清理快照，返回将被删除的快照与可回收的空间（不删除任何文件）
+#[tauri::command]
+pub async fn simulate_retention(policy: RetentionPolicy) -> AgentResult<RetentionPlan> {
+    crate::log_async_command!("simulate_retention", async {
+        crate::utils::blocking::run_blocking(move || crate::retention::simulate(&policy)).await
+    })
+}
+
+/// 保存快照保留策略并立即按新策略清理，返回实际删除的快照
+#[tauri::command]
+pub async fn save_retention_policy(
+    app: AppHandle,
+    policy: RetentionPolicy,
+) -> AgentResult<RetentionPlan> {
+    crate::log_async_command!("save_retention_policy", async {
+        policy.validate()?;
+        let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+
+        settings_manager.update_settings(|settings| {
+            settings.retention_policy = policy.clone();
+        })?;
+
+        crate::utils::blocking::run_blocking(move || crate::retention::apply(&policy)).await
+    })
+}
@@ -0,0 +1,106 @@
+//! 长耗时任务命令
+
+use crate::error::{AgentError, AgentResult};
+use crate::task_journal::InterruptedTask;
+use crate::tasks::{TaskInfo, TaskManager};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+/// 获取正在运行的任务
+#[tauri::command]
+pub async fn list_tasks(tasks: State<'_, TaskManager>) -> AgentResult<Vec<TaskInfo>> {
+    Ok(tasks.list())
+}
+
+/// 取消任务（任务会在下一个安全检查点停止）
+#[tauri::command]
+pub async fn cancel_task(id: String, tasks: State<'_, TaskManager>) -> AgentResult<()> {
+    if tasks.cancel(&id) {
+        Ok(())
+    } else {
+        Err(AgentError::InvalidInput(format!(
+            "任务 {id} 不存在或已结束"
+        )))
+    }
+}
+
+/// 中断任务的处理方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterruptedTaskAction {
+    /// 重新执行同一操作
+    Resume,
+    /// 回滚到操作开始后创建的数据库快照
+    Rollback,
+    /// 忽略（不做任何修改）
+    Dismiss,
+}
+
+/// 获取上次运行时中断、尚未处理的任务
+#[tauri::command]
+pub async fn list_interrupted_tasks() -> AgentResult<Vec<InterruptedTask>> {
+    crate::utils::blocking::run_blocking(|| Ok(crate::task_journal::interrupted())).await
+}
+
+/// 处理中断的任务，成功后从任务日志中移除
+#[tauri::command]
+pub async fn resolve_interrupted_task(
+    app: AppHandle,
+    id: String,
+    action: InterruptedTaskAction,
+) -> AgentResult<String> {
+    let task = crate::task_journal::find_interrupted(&id)?;
+    tracing::info!(target: "tasks", task_id = %id, kind = %task.entry.kind, action = ?action, "处理中断的任务");
+
+    let message = match action {
+        InterruptedTaskAction::Resume => {
+            let target = task.entry.target.clone().filter(|_| task.resumable);
+            let Some(account_name) = target else {
+                return Err(AgentError::InvalidInput(format!(
+                    "任务类型 {} 不支持继续执行",
+                    task.entry.kind
+                )));
+            };
+            if task.entry.kind == "restore_account" {
+                // 沿用中断前的选项：选择了替换会话时，数据库中可能已经没有原来的会话
+                let option = |key: &str| {
+                    task.entry
+                        .options
+                        .get(key)
+                        .and_then(serde_json::Value::as_bool)
+                };
+                crate::commands::restore_antigravity_account(
+                    app,
+                    account_name,
+                    None,
+                    Some(true),
+                    option("strict"),
+                    option("replaceSession"),
+                )
+                .await?
+                .message
+            } else {
+                crate::commands::switch_to_antigravity_account(app, account_name, None).await?
+            }
+        }
+        InterruptedTaskAction::Rollback => {
+            let Some(snapshot_id) = task.rollback_snapshot.clone() else {
+                return Err(AgentError::InvalidInput(
+                    "没有该任务开始后创建的数据库快照，无法回滚".to_string(),
+                ));
+            };
+            let snapshot = crate::antigravity::db_snapshot::restore_snapshot(&snapshot_id)?;
+            format!("已回滚到数据库快照 {}", snapshot.id)
+        }
+        InterruptedTaskAction::Dismiss => "已忽略中断的任务".to_string(),
+    };
+
+    crate::task_journal::dismiss(&id);
+    crate::audit::record(
+        "tasks",
+        "resolve_interrupted",
+        true,
+        serde_json::json!({ "kind": task.entry.kind, "action": action }),
+    );
+    Ok(message)
+}
@@ -0,0 +1,38 @@
+//! 使用统计命令
+
+use tauri::{AppHandle, Manager};
+
+use crate::app_settings::AppSettingsManager;
+use crate::error::AgentResult;
+use crate::telemetry::{TelemetryReport, TelemetrySummary};
+
+/// 获取本地汇总的使用统计（按功能与按天）
+#[tauri::command]
+pub async fn get_telemetry_summary(app: AppHandle) -> AgentResult<TelemetrySummary> {
+    let mode = app
+        .state::<AppSettingsManager>()
+        .get_settings()
+        .telemetry
+        .mode;
+    Ok(crate::telemetry::summary(mode))
+}
+
+/// 预览将要发送的使用统计（仅共享模式），确认后以返回的 ID 调用 `send_telemetry_report`
+#[tauri::command]
+pub async fn preview_telemetry_report(app: AppHandle) -> AgentResult<TelemetryReport> {
+    let settings = app.state::<AppSettingsManager>().get_settings();
+    crate::telemetry::prepare_report(&settings.telemetry)
+}
+
+/// 发送用户预览并确认过的使用统计（内容与预览完全一致）
+#[tauri::command]
+pub async fn send_telemetry_report(app: AppHandle, id: String) -> AgentResult<()> {
+    let settings = app.state::<AppSettingsManager>().get_settings();
+    crate::telemetry::send_report(&id, &settings.proxy).await
+}
+
+/// 清除本地收集的使用统计
+#[tauri::command]
+pub async fn clear_telemetry_data() -> AgentResult<()> {
+    crate::utils::blocking::run_blocking(crate::telemetry::clear).await
+}
@@ -1,3 +1,4 @@
+use crate::error::AgentResult;
 use crate::system_tray::{update_tray_menu, SystemTrayManager};
 use tauri::Manager;
 
@@ -6,14 +7,22 @@ use tauri::Manager;
 pub async fn update_tray_menu_command(
     app: tauri::AppHandle,
     accounts: Vec<String>,
-) -> Result<String, String> {
+) -> AgentResult<String> {
     update_tray_menu(&app, accounts)?;
     Ok("托盘菜单已更新".to_string())
 }
 
+/// 启用或禁用系统托盘，返回变更后的启用状态（变化通过 `tray:state-changed` 事件推送）
+#[tauri::command]
+pub async fn set_system_tray_enabled(app: tauri::AppHandle, enabled: bool) -> AgentResult<bool> {
+    crate::log_async_command!("set_system_tray_enabled", async {
+        app.state::<SystemTrayManager>().set_enabled(&app, enabled)
+    })
+}
+
 /// 最小化到托盘
 #[tauri::command]
-pub async fn minimize_to_tray(app: tauri::AppHandle) -> Result<String, String> {
+pub async fn minimize_to_tray(app: tauri::AppHandle) -> AgentResult<String> {
     let system_tray = app.state::<SystemTrayManager>();
     system_tray.minimize_to_tray(&app)?;
     Ok("已最小化到托盘".to_string())
@@ -21,7 +30,7 @@ pub async fn minimize_to_tray(app: tauri::AppHandle) -> Result<String, String> {
 
 /// 从托盘恢复
 #[tauri::command]
-pub async fn restore_from_tray(app: tauri::AppHandle) -> Result<String, String> {
+pub async fn restore_from_tray(app: tauri::AppHandle) -> AgentResult<String> {
     let system_tray = app.state::<SystemTrayManager>();
     system_tray.restore_from_tray(&app)?;
     Ok("已恢复窗口".to_string())
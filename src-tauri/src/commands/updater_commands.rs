@@ -0,0 +1,57 @@
+//! Agent 自更新命令
+
+use crate::app_settings::AppSettingsManager;
+use crate::error::AgentResult;
+use crate::state_bus::StateUpdate;
+use crate::system_tray::TooltipUpdate;
+use crate::updater::UpdateInfo;
+use tauri::{AppHandle, Manager};
+
+/// 检查 Agent 是否有可用更新
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> AgentResult<Option<UpdateInfo>> {
+    crate::log_async_command!("check_for_updates", async {
+        let info = crate::updater::check_for_updates(&app).await?;
+        let version = info.as_ref().map(|i| i.version.clone());
+        crate::system_tray::update_tray_tooltip(
+            &app,
+            TooltipUpdate::UpdateAvailable(version.clone()),
+        );
+        crate::state_bus::publish(StateUpdate::UpdateAvailable(version));
+        Ok(info)
+    })
+}
+
+/// 下载、校验签名并安装更新，完成后自动重启
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> AgentResult<()> {
+    crate::log_async_command!("install_update", async {
+        crate::updater::install_update(&app).await
+    })
+}
+
+/// 保存更新设置
+#[tauri::command]
+pub async fn save_update_settings(
+    app: AppHandle,
+    auto_check: bool,
+    endpoint: Option<String>,
+) -> AgentResult<()> {
+    crate::log_async_command!("save_update_settings", async {
+        let endpoint = endpoint
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        if let Some(url) = &endpoint {
+            tauri::Url::parse(url).map_err(|e| {
+                crate::error::AgentError::InvalidInput(format!("更新源地址无效: {}", e))
+            })?;
+        }
+
+        app.state::<AppSettingsManager>()
+            .update_settings(|settings| {
+                settings.auto_check_updates = auto_check;
+                settings.update_endpoint = endpoint;
+            })?;
+        Ok(())
+    })
+}
@@ -1,5 +1,35 @@
 //! 窗口状态管理命令
-//! 负责窗口位置、大小、状态等信息的保存和加载
-//!
-//! 注意：窗口状态管理已迁移到 window_event_handler.rs 中的自动处理
-//! 此模块保留以备将来需要手动窗口状态管理时使用
+//! 窗口位置、大小的保存与恢复由 window 模块自动处理；
+//! 这里提供手动调整：按布局预设摆放窗口，或在保存的位置出错时重置
+
+use crate::error::{AgentError, AgentResult};
+use crate::window::presets::{self, WindowPreset};
+use crate::window::state_manager::{WindowState, MAIN_WINDOW};
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+/// 按 label 查找窗口（未指定时为主窗口）
+fn find_window(app: &AppHandle, label: Option<String>) -> AgentResult<WebviewWindow> {
+    let label = label.unwrap_or_else(|| MAIN_WINDOW.to_string());
+    app.get_webview_window(&label)
+        .ok_or_else(|| AgentError::InvalidInput(format!("窗口 {label} 不存在")))
+}
+
+/// 按布局预设（left-half、right-half、centered-small、centered-large）调整窗口（默认主窗口），返回调整后的窗口状态
+#[tauri::command]
+pub async fn apply_window_preset(
+    app: AppHandle,
+    preset: WindowPreset,
+    label: Option<String>,
+) -> AgentResult<WindowState> {
+    crate::log_async_command!("apply_window_preset", async {
+        presets::apply_preset(&find_window(&app, label)?, preset).await
+    })
+}
+
+/// 清除保存的窗口状态，窗口（默认主窗口）恢复默认大小并居中
+#[tauri::command]
+pub async fn reset_window_state(app: AppHandle, label: Option<String>) -> AgentResult<WindowState> {
+    crate::log_async_command!("reset_window_state", async {
+        presets::reset(&find_window(&app, label)?).await
+    })
+}
@@ -0,0 +1,100 @@
+//! 危险操作二次确认
+//! 前端先调用 `request_dangerous_operation` 获取短时有效的一次性令牌，再把令牌传给危险命令，
+//! 防止单个误触或前端缺陷直接清除登录凭据。可在设置中关闭
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::{AgentError, AgentResult};
+
+/// 令牌有效期
+const TOKEN_TTL: Duration = Duration::from_secs(60);
+
+/// 需要二次确认的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DangerousOperation {
+    /// 清除 Antigravity 全部登录数据（`clear_all_antigravity_data`）
+    LogoutAll,
+}
+
+impl DangerousOperation {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::LogoutAll => "清除所有 Antigravity 数据",
+        }
+    }
+}
+
+/// 确认令牌
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmationToken {
+    pub token: String,
+    pub operation: DangerousOperation,
+    pub expires_in_secs: u64,
+}
+
+struct PendingToken {
+    token: String,
+    operation: DangerousOperation,
+    expires_at: Instant,
+}
+
+static PENDING: Mutex<Vec<PendingToken>> = Mutex::new(Vec::new());
+
+/// 签发确认令牌
+pub fn request(operation: DangerousOperation) -> ConfirmationToken {
+    let token = uuid::Uuid::new_v4().to_string();
+    let now = Instant::now();
+
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    pending.retain(|p| p.expires_at > now);
+    pending.push(PendingToken {
+        token: token.clone(),
+        operation,
+        expires_at: now + TOKEN_TTL,
+    });
+
+    tracing::info!(target: "confirmation", operation = ?operation, "已签发危险操作确认令牌");
+    ConfirmationToken {
+        token,
+        operation,
+        expires_in_secs: TOKEN_TTL.as_secs(),
+    }
+}
+
+/// 校验并消费令牌（一次性），设置中关闭二次确认时直接放行
+pub fn consume(operation: DangerousOperation, token: Option<&str>) -> AgentResult<()> {
+    let settings =
+        crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file());
+    if !settings.confirm_dangerous_operations {
+        return Ok(());
+    }
+
+    let now = Instant::now();
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    pending.retain(|p| p.expires_at > now);
+
+    let Some(token) = token.map(str::trim).filter(|t| !t.is_empty()) else {
+        return Err(AgentError::ConfirmationRequired(format!(
+            "「{}」需要先申请确认令牌",
+            operation.label()
+        )));
+    };
+    let Some(index) = pending
+        .iter()
+        .position(|p| p.operation == operation && p.token == token)
+    else {
+        tracing::warn!(target: "confirmation", operation = ?operation, "确认令牌无效或已过期");
+        return Err(AgentError::ConfirmationRequired(format!(
+            "「{}」的确认令牌无效或已过期，请重新确认",
+            operation.label()
+        )));
+    };
+
+    pending.remove(index);
+    tracing::info!(target: "confirmation", operation = ?operation, "危险操作已确认");
+    Ok(())
+}
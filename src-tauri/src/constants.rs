@@ -1,8 +1,13 @@
 /// 数据库字段常量
+///
+/// 读写 Antigravity 数据库时的键名以 `antigravity::key_mapping` 为准，这里是内置默认值
 pub mod database {
     /// 认证状态
     pub const AUTH_STATUS: &str = "antigravityAuthStatus";
 
-    /// Agent 状态同步
+    /// Agent 状态同步（同时也是备份文件中保存账户状态的字段名）
     pub const AGENT_STATE: &str = "jetskiStateSync.agentManagerInitState";
+
+    /// 首次启动引导标记
+    pub const ONBOARDING: &str = "antigravityOnboarding";
 }
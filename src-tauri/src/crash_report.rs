@@ -0,0 +1,177 @@
+//! 崩溃报告
+//! 捕获后端 panic 与前端渲染异常，将错误信息、调用栈、最近的日志与平台信息写入配置目录，
+//! 便于用户反馈"静默退出"类问题
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+
+use crate::error::AgentResult;
+
+/// 最多保留的崩溃报告数量
+const MAX_REPORTS: usize = 20;
+
+/// 附带的日志行数
+const LOG_TAIL_LINES: usize = 200;
+
+/// 崩溃来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CrashSource {
+    Backend,
+    Frontend,
+}
+
+/// 平台信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashPlatform {
+    pub os: String,
+    pub arch: String,
+    pub agent_version: String,
+    pub portable: bool,
+}
+
+/// 崩溃报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub timestamp: String,
+    pub source: CrashSource,
+    pub message: String,
+    /// panic 位置（文件:行:列）
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: String,
+    /// 最近的日志（已脱敏）
+    pub log_tail: Vec<String>,
+    pub platform: CrashPlatform,
+}
+
+fn platform_info() -> CrashPlatform {
+    CrashPlatform {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        agent_version: env!("CARGO_PKG_VERSION").to_string(),
+        portable: crate::directories::is_portable_mode(),
+    }
+}
+
+/// 读取最新日志文件的末尾若干行
+fn read_log_tail() -> Vec<String> {
+    let log_dir = crate::directories::get_log_directory();
+    let latest = fs::read_dir(&log_dir).ok().and_then(|entries| {
+        entries
+            .filter_map(Result::ok)
+            .filter(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .starts_with(crate::utils::sanitizing_layer::LOG_FILE_PREFIX)
+            })
+            .max_by_key(|e| e.metadata().and_then(|m| m.modified()).ok())
+    });
+
+    let Some(file) = latest.and_then(|e| fs::File::open(e.path()).ok()) else {
+        return Vec::new();
+    };
+
+    let lines: Vec<String> = BufReader::new(file).lines().map_while(Result::ok).collect();
+    let start = lines.len().saturating_sub(LOG_TAIL_LINES);
+    lines[start..].to_vec()
+}
+
+/// 写入崩溃报告并清理过旧的报告
+fn write_report(report: &CrashReport) -> AgentResult<PathBuf> {
+    let dir = crate::directories::get_crash_reports_directory();
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!(
+        "crash-{}.json",
+        chrono::Local::now().format("%Y%m%d-%H%M%S%.3f")
+    ));
+    fs::write(&path, serde_json::to_string_pretty(report)?)?;
+
+    let mut files = report_files();
+    if files.len() > MAX_REPORTS {
+        files.sort();
+        for old in &files[..files.len() - MAX_REPORTS] {
+            let _ = fs::remove_file(old);
+        }
+    }
+
+    Ok(path)
+}
+
+/// 所有崩溃报告文件
+pub fn report_files() -> Vec<PathBuf> {
+    fs::read_dir(crate::directories::get_crash_reports_directory())
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|e| e.path())
+                .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 安装 panic 钩子（保留默认钩子的输出）
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "未知 panic".to_string());
+
+        let report = CrashReport {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            source: CrashSource::Backend,
+            message,
+            location: info
+                .location()
+                .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            thread: std::thread::current().name().map(str::to_string),
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+            log_tail: read_log_tail(),
+            platform: platform_info(),
+        };
+
+        match write_report(&report) {
+            Ok(path) => {
+                tracing::error!(target: "crash_report", path = %path.display(), message = %report.message, "程序发生 panic，已写入崩溃报告")
+            }
+            Err(e) => tracing::error!(target: "crash_report", error = %e, "写入崩溃报告失败"),
+        }
+
+        default_hook(info);
+    }));
+}
+
+/// 记录前端渲染异常
+pub fn record_frontend_crash(message: String, stack: Option<String>) -> AgentResult<PathBuf> {
+    let report = CrashReport {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        source: CrashSource::Frontend,
+        message,
+        location: None,
+        thread: None,
+        backtrace: stack.unwrap_or_default(),
+        log_tail: read_log_tail(),
+        platform: platform_info(),
+    };
+    let path = write_report(&report)?;
+    tracing::error!(target: "crash_report", path = %path.display(), "前端发生未捕获异常，已写入崩溃报告");
+    Ok(path)
+}
+
+/// 读取最近一次崩溃报告
+pub fn last_report() -> Option<CrashReport> {
+    let latest = report_files().into_iter().max()?;
+    fs::read_to_string(latest)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
@@ -0,0 +1,114 @@
+//! 首页概览
+//! 把首页需要的当前账户、进程状态、备份情况、进行中的操作与待处理提醒汇总为一次返回，
+//! 前端无需分别调用多个命令。优先使用状态总线中的快照，尚未检测过的项目在这里直接读取
+
+use serde::Serialize;
+
+use crate::utils::time_format::FormattedTime;
+
+/// 待处理提醒
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardWarning {
+    /// 稳定的提醒代码（前端据此分支处理）
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// 首页概览
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardSummary {
+    /// 当前登录的账户（未登录时为 None）
+    pub active_account: Option<String>,
+    /// 当前账户的别名
+    pub active_alias: Option<String>,
+    pub antigravity_running: bool,
+    /// Agent 启动且仍在运行的 Antigravity 进程 PID
+    pub launched_pids: Vec<u32>,
+    /// 当前账户最近一次成功备份的时间
+    pub last_backup_at: Option<FormattedTime>,
+    /// 已备份的账户数量
+    pub backup_count: usize,
+    /// 进行中的操作名
+    pub active_operations: Vec<String>,
+    /// 可用的 Agent 新版本
+    pub update_available: Option<String>,
+    pub warnings: Vec<DashboardWarning>,
+}
+
+/// 直接读取当前登录的账户
+fn read_active_account() -> Option<String> {
+    let db_path = crate::platform::resolve_antigravity_db_path().ok()?;
+    let state = crate::antigravity::backup::read_agent_state(&db_path)
+        .ok()
+        .flatten()?;
+    crate::antigravity::backup::extract_email(state.expose()).ok()
+}
+
+/// 汇总首页概览（涉及文件与数据库读取，需在阻塞线程中调用）
+pub fn collect() -> DashboardSummary {
+    let snapshot = crate::state_bus::snapshot();
+    let settings =
+        crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file());
+
+    let active_account = if snapshot.account_checked {
+        snapshot.active_account
+    } else {
+        read_active_account()
+    };
+    let antigravity_running = snapshot
+        .antigravity_running
+        .unwrap_or_else(crate::platform::is_antigravity_running);
+
+    let last_backup = active_account
+        .as_deref()
+        .and_then(crate::antigravity::backup::last_backup_time);
+    let active_alias = active_account
+        .as_deref()
+        .and_then(|email| crate::account_metadata::get(email).alias);
+
+    let mut warnings = Vec::new();
+    if !crate::platform::is_antigravity_available() {
+        warnings.push(DashboardWarning {
+            code: "antigravity_not_found",
+            message: "未找到 Antigravity 安装位置".to_string(),
+        });
+    }
+    if settings.read_only_mode {
+        warnings.push(DashboardWarning {
+            code: "read_only_mode",
+            message: "只读模式已开启，无法修改账户、备份或设置".to_string(),
+        });
+    }
+    if active_account.is_some() && settings.backup_stale_days > 0 {
+        let stale_days = settings.backup_stale_days;
+        match last_backup {
+            None => warnings.push(DashboardWarning {
+                code: "backup_missing",
+                message: "当前账户尚未备份".to_string(),
+            }),
+            Some(time)
+                if chrono::Local::now() - time > chrono::Duration::days(i64::from(stale_days)) =>
+            {
+                warnings.push(DashboardWarning {
+                    code: "backup_stale",
+                    message: format!("当前账户已超过 {} 天未备份", stale_days),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    DashboardSummary {
+        active_account,
+        active_alias,
+        antigravity_running,
+        launched_pids: snapshot.launched_pids,
+        last_backup_at: last_backup.map(|time| crate::utils::time_format::format_time(&time)),
+        backup_count: crate::antigravity::backup::list_backup_emails().len(),
+        active_operations: snapshot.active_operations,
+        update_available: snapshot.update_available,
+        warnings,
+    }
+}
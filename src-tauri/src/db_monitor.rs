@@ -105,6 +105,11 @@ impl DatabaseMonitor {
         *self.is_running.lock().await = false;
     }
 
+    /// 监控是否正在运行
+    pub async fn is_running(&self) -> bool {
+        *self.is_running.lock().await
+    }
+
     /// 获取完整数据库数据
     async fn get_complete_data() -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         // 检测数据库路径
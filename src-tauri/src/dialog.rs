@@ -0,0 +1,100 @@
+//! 原生确认对话框
+//! 托盘中的危险操作（任务进行中退出、清除全部数据）在主窗口隐藏时也需要确认，
+//! 这里通过 dialog 插件弹出系统对话框。每类确认可选择「不再询问」，记录在设置中
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{
+    DialogExt, MessageDialogButtons, MessageDialogKind, MessageDialogResult,
+};
+
+use crate::utils::i18n::t;
+
+/// 需要原生确认的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmPrompt {
+    /// 恢复、切换等任务进行中时退出应用
+    QuitWhileBusy,
+    /// 从托盘清除 Antigravity 全部登录数据
+    ClearAllData,
+}
+
+impl ConfirmPrompt {
+    fn message_key(&self) -> &'static str {
+        match self {
+            Self::QuitWhileBusy => "dialog.quit_while_busy",
+            Self::ClearAllData => "dialog.clear_all_data",
+        }
+    }
+}
+
+/// 用户在对话框中的选择
+enum Choice {
+    Proceed,
+    ProceedAndRemember,
+    Cancel,
+}
+
+/// 弹出确认对话框并等待用户选择（阻塞当前线程，不能在主线程调用）
+fn show(app: &AppHandle, prompt: ConfirmPrompt) -> Choice {
+    let proceed = t("dialog.proceed");
+    let remember = t("dialog.proceed_remember");
+    let result = app
+        .dialog()
+        .message(t(prompt.message_key()))
+        .title("Antigravity Agent")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::YesNoCancelCustom(
+            proceed.to_string(),
+            remember.to_string(),
+            t("dialog.cancel").to_string(),
+        ))
+        .blocking_show_with_result();
+
+    // 不同平台对自定义按钮返回按钮文本或对应的标准结果
+    match result {
+        MessageDialogResult::Yes | MessageDialogResult::Ok => Choice::Proceed,
+        MessageDialogResult::No => Choice::ProceedAndRemember,
+        MessageDialogResult::Custom(label) if label == proceed => Choice::Proceed,
+        MessageDialogResult::Custom(label) if label == remember => Choice::ProceedAndRemember,
+        _ => Choice::Cancel,
+    }
+}
+
+/// 请求用户确认操作，返回是否继续
+///
+/// 之前选择过「不再询问」的操作直接放行；选择「不再询问」时写入设置
+pub async fn confirm(app: &AppHandle, prompt: ConfirmPrompt) -> bool {
+    let settings_manager = app.state::<crate::app_settings::AppSettingsManager>();
+    if settings_manager
+        .get_settings()
+        .skipped_confirm_prompts
+        .contains(&prompt)
+    {
+        tracing::debug!(target: "dialog", prompt = ?prompt, "已设置不再询问，跳过确认");
+        return true;
+    }
+
+    let handle = app.clone();
+    let choice = tokio::task::spawn_blocking(move || show(&handle, prompt))
+        .await
+        .unwrap_or(Choice::Cancel);
+
+    match choice {
+        Choice::Proceed => true,
+        Choice::ProceedAndRemember => {
+            if let Err(e) = settings_manager.update_settings(|settings| {
+                settings.skipped_confirm_prompts.push(prompt);
+            }) {
+                tracing::warn!(target: "dialog", prompt = ?prompt, error = %e, "保存不再询问设置失败");
+            }
+            tracing::info!(target: "dialog", prompt = ?prompt, "已确认，之后不再询问");
+            true
+        }
+        Choice::Cancel => {
+            tracing::info!(target: "dialog", prompt = ?prompt, "用户取消操作");
+            false
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! 账户备份目录的跨进程锁
+//! 操作锁只在进程内互斥，而命令行模式（跳转列表、脚本）会以独立进程写入备份目录。
+//! 获取 `BackupDir` 操作锁时同时在备份目录中创建 `.agent.lock`（记录进程号、操作名与时间），
+//! 持有进程已退出、锁文件属于本进程遗留或超过时限的锁视为失效，自动清理后重新获取。
+//! 锁文件由写好内容的临时文件硬链接而成，出现时内容已经完整；
+//! 清理时先把锁文件原子地重命名到一旁再核对内容，多个进程同时清理时不会删掉其他进程刚获取的锁
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::{AgentError, AgentResult};
+
+/// 超过该时长的锁视为失效（正常操作远小于此值）
+const STALE_AFTER: chrono::Duration = chrono::Duration::minutes(30);
+
+/// 无法解析的锁文件在修改后这段时间内仍视为有效（不支持硬链接时，其他进程可能正在写入内容）
+const UNPARSEABLE_GRACE: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// 锁文件内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LockInfo {
+    pid: u32,
+    operation: String,
+    started_at: String,
+}
+
+/// 锁文件守卫，离开作用域时删除锁文件
+pub struct DirLock {
+    path: PathBuf,
+}
+
+impl Drop for DirLock {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            tracing::warn!(target: "dir_lock", error = %e, "删除备份目录锁文件失败");
+        }
+    }
+}
+
+fn parse_info(content: Option<&str>) -> Option<LockInfo> {
+    serde_json::from_str(content?).ok()
+}
+
+fn process_alive(pid: u32) -> bool {
+    let mut system = sysinfo::System::new();
+    system.refresh_process(sysinfo::Pid::from_u32(pid))
+}
+
+/// 锁是否已失效；无法解析的锁文件（写入中途崩溃）超过一段时间后也视为失效
+fn is_stale(path: &Path, info: Option<&LockInfo>) -> bool {
+    let Some(info) = info else {
+        return fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_none_or(|age| age > UNPARSEABLE_GRACE);
+    };
+    // 进程内互斥由操作锁保证，本进程号的锁文件只能是之前遗留的
+    if info.pid == std::process::id() || !process_alive(info.pid) {
+        return true;
+    }
+    chrono::DateTime::parse_from_rfc3339(&info.started_at)
+        .map(|started| chrono::Local::now().fixed_offset() - started > STALE_AFTER)
+        .unwrap_or(true)
+}
+
+/// 清理失效的锁文件：先原子地重命名到一旁（同一个文件只有一个进程能移走），
+/// 内容与判断失效时读到的不同（其他进程已清理并重新获取了锁）时放回原处
+fn remove_stale(path: &Path, seen: Option<&str>) -> AgentResult<()> {
+    let aside = path.with_extension(format!("lock.stale-{}", uuid::Uuid::new_v4()));
+    match fs::rename(path, &aside) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    }
+
+    let moved = fs::read_to_string(&aside).ok();
+    if moved.as_deref() != seen {
+        // 硬链接在目标已存在时失败，不会覆盖更新的锁文件
+        if let Err(e) = fs::hard_link(&aside, path) {
+            tracing::warn!(target: "dir_lock", error = %e, "放回其他进程的备份目录锁失败");
+        }
+    }
+    if let Err(e) = fs::remove_file(&aside) {
+        tracing::warn!(target: "dir_lock", error = %e, "删除失效的备份目录锁失败");
+    }
+    Ok(())
+}
+
+/// 创建锁文件，已存在时返回 `AlreadyExists`
+///
+/// 先写入临时文件再硬链接到锁文件的位置，锁文件出现时内容已经完整；
+/// 文件系统不支持硬链接时退回到直接创建后写入
+fn create_lock(path: &Path, content: &str) -> std::io::Result<()> {
+    let tmp = path.with_extension(format!("lock.tmp-{}", uuid::Uuid::new_v4()));
+    fs::write(&tmp, content)?;
+    let linked = fs::hard_link(&tmp, path);
+    if let Err(e) = fs::remove_file(&tmp) {
+        tracing::warn!(target: "dir_lock", error = %e, "删除备份目录锁的临时文件失败");
+    }
+    match linked {
+        Err(e) if e.kind() != std::io::ErrorKind::AlreadyExists => {
+            tracing::debug!(target: "dir_lock", error = %e, "无法硬链接锁文件，改为直接创建");
+            let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+            if let Err(e) = file.write_all(content.as_bytes()) {
+                let _ = fs::remove_file(path);
+                return Err(e);
+            }
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// 获取备份目录锁，被其他存活的 Agent 进程持有时返回 `OperationInProgress`
+pub fn acquire(operation: &str) -> AgentResult<DirLock> {
+    let path = crate::directories::get_backup_dir_lock_file();
+    let info = LockInfo {
+        pid: std::process::id(),
+        operation: operation.to_string(),
+        started_at: chrono::Local::now().to_rfc3339(),
+    };
+
+    // 清理失效锁后最多重试一次，避免两个进程同时清理时无限循环
+    let info = serde_json::to_string(&info)?;
+    for attempt in 0..2 {
+        match create_lock(&path, &info) {
+            Ok(()) => return Ok(DirLock { path }),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                let content = fs::read_to_string(&path).ok();
+                let holder = parse_info(content.as_deref());
+                if attempt == 0 && is_stale(&path, holder.as_ref()) {
+                    tracing::warn!(
+                        target: "dir_lock",
+                        holder = ?holder,
+                        "清理失效的备份目录锁"
+                    );
+                    remove_stale(&path, content.as_deref())?;
+                    continue;
+                }
+
+                let holder = holder.map_or_else(
+                    || "另一个 Agent 进程".to_string(),
+                    |h| format!("另一个 Agent 进程（PID {}）的「{}」", h.pid, h.operation),
+                );
+                tracing::warn!(target: "dir_lock", requested = operation, holder = %holder, "备份目录被其他进程占用");
+                return Err(AgentError::OperationInProgress(format!(
+                    "账户备份目录正在被{holder}使用，请稍后再试"
+                )));
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Err(AgentError::OperationInProgress(
+        "账户备份目录正在被其他进程使用，请稍后再试".to_string(),
+    ))
+}
@@ -3,34 +3,64 @@
 use std::fs;
 use std::io;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 use serde_json::{self, Value};
 use tracing::{info, warn};
 
-/// 获取应用主配置目录
-/// 所有配置、日志、数据都统一存放在用户主目录的 .antigravity-agent 下
-#[cfg(windows)]
-pub fn get_config_directory() -> PathBuf {
-    let config_dir = dirs::home_dir()
-        .expect("Home directory not found")
-        .join(".antigravity-agent");
+/// 便携模式标记文件名（与可执行文件位于同一目录）
+const PORTABLE_FLAG_FILE: &str = "portable.flag";
 
-    // 确保目录存在
-    if let Err(e) = fs::create_dir_all(&config_dir) {
-        eprintln!("警告：无法创建配置目录 {}: {}", config_dir.display(), e);
-    }
+/// 便携模式命令行参数
+const PORTABLE_CLI_FLAG: &str = "--portable";
 
-    config_dir
+/// 便携模式数据目录名（与可执行文件位于同一目录）
+const PORTABLE_DATA_DIR_NAME: &str = "data";
+
+/// 便携模式检测结果缓存（进程生命周期内只检测一次）
+static PORTABLE_DATA_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// 获取便携模式数据目录
+///
+/// 当可执行文件同级存在 `portable.flag`，或启动参数包含 `--portable` 时启用便携模式，
+/// 此时所有配置、日志、备份都存放在可执行文件同级的 `data/` 目录下。
+pub fn get_portable_data_directory() -> Option<PathBuf> {
+    PORTABLE_DATA_DIR
+        .get_or_init(detect_portable_data_directory)
+        .clone()
+}
+
+/// 是否处于便携模式
+pub fn is_portable_mode() -> bool {
+    get_portable_data_directory().is_some()
+}
+
+fn detect_portable_data_directory() -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|p| p.to_path_buf()))?;
+
+    let has_cli_flag = std::env::args().any(|arg| arg == PORTABLE_CLI_FLAG);
+    let has_flag_file = exe_dir.join(PORTABLE_FLAG_FILE).is_file();
+
+    if has_cli_flag || has_flag_file {
+        Some(exe_dir.join(PORTABLE_DATA_DIR_NAME))
+    } else {
+        None
+    }
 }
 
 /// 获取应用主配置目录
-#[cfg(not(windows))]
+/// 所有配置、日志、数据都统一存放在用户主目录的 .antigravity-agent 下；
+/// 便携模式下改为可执行文件同级的 data 目录
 pub fn get_config_directory() -> PathBuf {
-    let config_dir = dirs::home_dir()
-        .expect("Home directory not found")
-        .join(".antigravity-agent");
+    let config_dir = get_portable_data_directory().unwrap_or_else(|| {
+        dirs::home_dir()
+            .expect("Home directory not found")
+            .join(".antigravity-agent")
+    });
 
-    // 确保目录存在
-    if let Err(e) = fs::create_dir_all(&config_dir) {
+    // 确保目录存在且仅当前用户可访问（日志目录位于配置目录下，此时日志系统尚未初始化，只能输出到 stderr）
+    if let Err(e) = crate::utils::permissions::create_private_dir(&config_dir) {
         eprintln!("警告：无法创建配置目录 {}: {}", config_dir.display(), e);
     }
 
@@ -53,9 +83,9 @@ pub fn get_log_directory() -> PathBuf {
 pub fn get_accounts_directory() -> PathBuf {
     let accounts_dir = get_config_directory().join("antigravity-accounts");
 
-    // 确保目录存在
-    if let Err(e) = fs::create_dir_all(&accounts_dir) {
-        eprintln!("警告：无法创建账户目录 {}: {}", accounts_dir.display(), e);
+    // 确保目录存在（备份包含登录令牌，仅当前用户可访问）
+    if let Err(e) = crate::utils::permissions::create_private_dir(&accounts_dir) {
+        tracing::warn!(target: "directories", path = %accounts_dir.display(), error = %e, "无法创建账户目录");
     }
 
     accounts_dir
@@ -66,7 +96,12 @@ pub fn get_app_settings_file() -> PathBuf {
     get_config_directory().join("app_settings.json")
 }
 
-/// 获取窗口状态文件路径
+/// 获取 Agent 内部数据库路径
+pub fn get_agent_db_file() -> PathBuf {
+    get_config_directory().join("agent.db")
+}
+
+/// 获取旧版本窗口状态文件路径（已迁移到 agent.db）
 pub fn get_window_state_file() -> PathBuf {
     get_config_directory().join("window_state.json")
 }
@@ -76,6 +111,81 @@ pub fn get_antigravity_path_file() -> PathBuf {
     get_config_directory().join("antigravity_path.json")
 }
 
+/// 获取 Antigravity 版本记录文件路径
+pub fn get_antigravity_version_file() -> PathBuf {
+    get_config_directory().join("antigravity_version.json")
+}
+
+/// 获取 Antigravity 版本更新快照目录
+pub fn get_update_snapshots_directory() -> PathBuf {
+    get_config_directory().join("update-snapshots")
+}
+
+/// 获取设备标识重置前的快照目录
+pub fn get_machine_id_snapshots_directory() -> PathBuf {
+    get_config_directory().join("machine-id-snapshots")
+}
+
+/// 获取 Antigravity 数据库文件快照目录
+pub fn get_db_snapshots_directory() -> PathBuf {
+    get_config_directory().join("db-snapshots")
+}
+
+/// 获取脱敏备份的导出目录
+pub fn get_sanitized_exports_directory() -> PathBuf {
+    get_config_directory().join("sanitized-exports")
+}
+
+/// 获取崩溃报告目录
+pub fn get_crash_reports_directory() -> PathBuf {
+    get_config_directory().join("crash-reports")
+}
+
+/// 获取生命周期钩子配置文件路径
+pub fn get_hooks_config_file() -> PathBuf {
+    get_config_directory().join("hooks.json")
+}
+
+/// 获取旧版本账户元数据文件路径（已迁移到 agent.db）
+pub fn get_account_metadata_file() -> PathBuf {
+    get_config_directory().join("account_metadata.json")
+}
+
+/// 获取数据库键名映射文件路径
+pub fn get_key_mapping_file() -> PathBuf {
+    get_config_directory().join("key_mapping.json")
+}
+
+/// 获取账户备份目录的跨进程锁文件路径
+pub fn get_backup_dir_lock_file() -> PathBuf {
+    get_accounts_directory().join(".agent.lock")
+}
+
+/// 获取任务日志文件路径（记录运行中的任务，用于发现中断的操作）
+pub fn get_task_journal_file() -> PathBuf {
+    get_config_directory().join("task_journal.json")
+}
+
+/// 获取定时任务文件路径（任务配置与运行历史）
+pub fn get_scheduler_file() -> PathBuf {
+    get_config_directory().join("scheduled_jobs.json")
+}
+
+/// 获取使用统计文件路径（开启使用统计后本地汇总的匿名计数）
+pub fn get_telemetry_file() -> PathBuf {
+    get_config_directory().join("telemetry.json")
+}
+
+/// 获取审计日志文件路径
+pub fn get_audit_log_file() -> PathBuf {
+    get_config_directory().join("audit.jsonl")
+}
+
+/// 获取账户头像缓存目录
+pub fn get_avatars_directory() -> PathBuf {
+    get_config_directory().join("avatars")
+}
+
 /// 在应用启动时检查并迁移旧账户目录到新路径。
 /// 当前为空实现，后续补充实际迁移逻辑。
 pub fn migrate_legacy_accounts_if_needed() -> io::Result<()> {
@@ -87,6 +197,12 @@ pub fn migrate_legacy_accounts_if_needed() -> io::Result<()> {
         new_config_dir.display()
     );
 
+    // 便携模式下不读取系统配置目录，避免把本机数据带到 U 盘上
+    if is_portable_mode() {
+        info!(target: "app::startup", "便携模式已启用，跳过旧账户目录检测");
+        return Ok(());
+    }
+
     // 旧账户目录（Roaming 配置目录下）
     let Some(config_dir) = dirs::config_dir() else {
         info!(target: "app::startup", "未找到系统配置目录 (dirs::config_dir)，跳过旧账户目录检测");
@@ -111,15 +227,14 @@ pub fn migrate_legacy_accounts_if_needed() -> io::Result<()> {
     for entry in read_dir {
         let entry = entry?;
         let path = entry.path();
-        if path.is_file() {
-            if path
+        if path.is_file()
+            && path
                 .extension()
                 .and_then(|s| s.to_str())
                 .map(|s| s.eq_ignore_ascii_case("json"))
                 .unwrap_or(false)
-            {
-                legacy_files.push(path);
-            }
+        {
+            legacy_files.push(path);
         }
     }
     info!(
@@ -164,7 +279,9 @@ pub fn migrate_legacy_accounts_if_needed() -> io::Result<()> {
                                 } else {
                                     match serde_json::to_string_pretty(&filtered_value) {
                                         Ok(serialized) => {
-                                            if let Err(e) = fs::write(&new_path, serialized) {
+                                            if let Err(e) = crate::utils::permissions::write_private(
+                                                &new_path, serialized,
+                                            ) {
                                                 warn!(
                                                     target: "app::startup",
                                                     "写入新文件失败: {}，错误: {}",
@@ -0,0 +1,136 @@
+//! 磁盘空间检查
+//! 创建备份、数据库快照、版本更新快照与恢复账户前检查目标所在磁盘的剩余空间，
+//! 低于设置的下限时返回 `INSUFFICIENT_DISK_SPACE` 并推送 `disk:low-space` 事件，而不是写出被截断的文件。
+//! 日志写入器定期检查日志目录，空间不足时暂停写入日志文件
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{AgentError, AgentResult};
+
+/// 磁盘空间不足事件名
+pub const LOW_DISK_SPACE_EVENT: &str = "disk:low-space";
+
+/// 默认的最小剩余空间（MB）
+pub const DEFAULT_MIN_FREE_MB: u64 = 200;
+
+/// 同一目录重复推送空间不足事件的最短间隔
+const NOTIFY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 当前生效的最小剩余空间（MB，0 表示不检查）
+static MIN_FREE_MB: AtomicU64 = AtomicU64::new(DEFAULT_MIN_FREE_MB);
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// 最近一次推送事件的目录与时间
+static LAST_NOTIFIED: Mutex<Option<(PathBuf, Instant)>> = Mutex::new(None);
+
+/// 空间不足事件内容
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LowDiskSpace {
+    pub path: String,
+    /// 被拦截的操作，如 `backup`、`restore`
+    pub operation: String,
+    pub available_bytes: u64,
+    pub required_bytes: u64,
+}
+
+/// 绑定应用句柄（在 setup 中调用一次），用于推送空间不足事件
+pub fn init(app: &AppHandle) {
+    let _ = APP_HANDLE.set(app.clone());
+}
+
+/// 更新最小剩余空间设置
+pub fn set_min_free_mb(mb: u64) {
+    MIN_FREE_MB.store(mb, Ordering::Relaxed);
+}
+
+/// 当前要求的最小剩余空间（字节），不检查时为 None
+fn required_bytes() -> Option<u64> {
+    match MIN_FREE_MB.load(Ordering::Relaxed) {
+        0 => None,
+        mb => Some(mb.saturating_mul(1024 * 1024)),
+    }
+}
+
+/// 路径所在磁盘的剩余空间（路径尚不存在时按最近的已存在上级目录计算），无法获取时返回 None
+pub fn available_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let resolved = existing
+        .canonicalize()
+        .unwrap_or_else(|_| existing.to_path_buf());
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| resolved.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// 剩余空间是否低于设置的下限（无法获取剩余空间时视为充足，不输出日志，可在日志写入器中调用）
+pub fn is_low(path: &Path) -> bool {
+    required_bytes()
+        .zip(available_space(path))
+        .is_some_and(|(required, available)| available < required)
+}
+
+/// 写入前检查目标目录的剩余空间，不足时推送事件并返回 `InsufficientDiskSpace`
+pub fn ensure_free_space(path: &Path, operation: &str) -> AgentResult<()> {
+    let Some(required) = required_bytes() else {
+        return Ok(());
+    };
+    let Some(available) = available_space(path) else {
+        tracing::debug!(target: "disk_space", path = %path.display(), "无法获取磁盘剩余空间，跳过检查");
+        return Ok(());
+    };
+    if available >= required {
+        return Ok(());
+    }
+
+    tracing::warn!(
+        target: "disk_space",
+        path = %path.display(),
+        operation,
+        available_bytes = available,
+        required_bytes = required,
+        "磁盘剩余空间不足，已中止操作"
+    );
+    notify(LowDiskSpace {
+        path: path.display().to_string(),
+        operation: operation.to_string(),
+        available_bytes: available,
+        required_bytes: required,
+    });
+    Err(AgentError::InsufficientDiskSpace {
+        path: path.to_path_buf(),
+        available,
+        required,
+    })
+}
+
+/// 推送空间不足事件（同一目录一分钟内只推送一次）
+fn notify(event: LowDiskSpace) {
+    let Some(app) = APP_HANDLE.get() else {
+        return;
+    };
+    {
+        let mut last = LAST_NOTIFIED.lock().unwrap_or_else(|e| e.into_inner());
+        let path = PathBuf::from(&event.path);
+        if last
+            .as_ref()
+            .is_some_and(|(p, at)| *p == path && at.elapsed() < NOTIFY_INTERVAL)
+        {
+            return;
+        }
+        *last = Some((path, Instant::now()));
+    }
+    if let Err(e) = app.emit(LOW_DISK_SPACE_EVENT, &event) {
+        tracing::warn!(target: "disk_space", error = %e, "推送磁盘空间不足事件失败");
+    }
+}
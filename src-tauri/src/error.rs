@@ -0,0 +1,221 @@
+//! 统一错误类型
+//! 命令返回稳定的错误码，序列化为 `{ code, message, details }`，前端无需再匹配中文文本
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Agent 命令错误
+#[derive(Debug, Clone)]
+pub enum AgentError {
+    /// 未找到 Antigravity 安装位置
+    AntigravityNotFound,
+    /// 当前运行环境无法访问 Antigravity 数据目录（如在 WSL 中运行）
+    DataDirUnreachable(String),
+    /// Antigravity 状态数据库文件不存在
+    DatabaseNotFound(PathBuf),
+    /// 数据库读写失败
+    Database(String),
+    /// 当前没有已登录的账户
+    NotLoggedIn(String),
+    /// 备份文件不存在
+    BackupNotFound(String),
+    /// 备份文件内容无效
+    InvalidBackup(String),
+    /// Antigravity 当前登录的账户与要恢复的备份不同，直接写入会混合两个账户的状态
+    SessionMismatch { current: String, backup: String },
+    /// 未找到 Antigravity 进程
+    ProcessNotFound,
+    /// Antigravity 正在运行，写入数据库会在其退出时被覆盖
+    AntigravityRunning,
+    /// 关闭 Antigravity 进程失败
+    ProcessKillFailed(String),
+    /// 系统托盘操作失败
+    Tray(String),
+    /// 读写系统剪贴板失败
+    Clipboard(String),
+    /// 生命周期钩子执行失败（中止了操作）
+    Hook(String),
+    /// 检查或安装 Agent 更新失败
+    Update(String),
+    /// 网络请求失败
+    Network(String),
+    /// 任务已被用户取消
+    Cancelled,
+    /// 同一资源上已有操作在进行
+    OperationInProgress(String),
+    /// 只读模式下拒绝修改操作
+    ReadOnly,
+    /// 危险操作缺少有效的确认令牌
+    ConfirmationRequired(String),
+    /// 目标磁盘剩余空间低于设置的下限
+    InsufficientDiskSpace {
+        path: PathBuf,
+        available: u64,
+        required: u64,
+    },
+    /// 文件读写失败
+    Io(String),
+    /// 序列化/反序列化失败
+    Serialization(String),
+    /// 参数无效
+    InvalidInput(String),
+    /// 其他内部错误
+    Internal(String),
+}
+
+/// 命令结果类型
+pub type AgentResult<T> = Result<T, AgentError>;
+
+impl AgentError {
+    /// 稳定的错误码（前端据此分支处理）
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::AntigravityNotFound => "ANTIGRAVITY_NOT_FOUND",
+            Self::DataDirUnreachable(_) => "DATA_DIR_UNREACHABLE",
+            Self::DatabaseNotFound(_) => "DATABASE_NOT_FOUND",
+            Self::Database(_) => "DATABASE_ERROR",
+            Self::NotLoggedIn(_) => "NOT_LOGGED_IN",
+            Self::BackupNotFound(_) => "BACKUP_NOT_FOUND",
+            Self::InvalidBackup(_) => "INVALID_BACKUP",
+            Self::SessionMismatch { .. } => "SESSION_MISMATCH",
+            Self::ProcessNotFound => "PROCESS_NOT_FOUND",
+            Self::AntigravityRunning => "ANTIGRAVITY_RUNNING",
+            Self::ProcessKillFailed(_) => "PROCESS_KILL_FAILED",
+            Self::Tray(_) => "TRAY_ERROR",
+            Self::Clipboard(_) => "CLIPBOARD_ERROR",
+            Self::Hook(_) => "HOOK_FAILED",
+            Self::Update(_) => "UPDATE_FAILED",
+            Self::Network(_) => "NETWORK_ERROR",
+            Self::Cancelled => "TASK_CANCELLED",
+            Self::OperationInProgress(_) => "OPERATION_IN_PROGRESS",
+            Self::ReadOnly => "READ_ONLY_MODE",
+            Self::ConfirmationRequired(_) => "CONFIRMATION_REQUIRED",
+            Self::InsufficientDiskSpace { .. } => "INSUFFICIENT_DISK_SPACE",
+            Self::Io(_) => "IO_ERROR",
+            Self::Serialization(_) => "SERIALIZATION_ERROR",
+            Self::InvalidInput(_) => "INVALID_INPUT",
+            Self::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    /// 附加的结构化信息
+    pub fn details(&self) -> Option<Value> {
+        match self {
+            Self::DatabaseNotFound(path) => Some(serde_json::json!({
+                "path": path.to_string_lossy(),
+            })),
+            Self::BackupNotFound(name) => Some(serde_json::json!({ "name": name })),
+            Self::SessionMismatch { current, backup } => Some(serde_json::json!({
+                "currentEmail": current,
+                "backupEmail": backup,
+            })),
+            Self::InsufficientDiskSpace {
+                path,
+                available,
+                required,
+            } => Some(serde_json::json!({
+                "path": path.to_string_lossy(),
+                "availableBytes": available,
+                "requiredBytes": required,
+            })),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AgentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AntigravityNotFound => write!(f, "未找到 Antigravity 安装位置"),
+            Self::DataDirUnreachable(reason) => {
+                write!(f, "无法访问 Antigravity 数据目录: {}", reason)
+            }
+            Self::DatabaseNotFound(path) => {
+                write!(f, "Antigravity 状态数据库不存在: {}", path.display())
+            }
+            Self::Database(e) => write!(f, "数据库操作失败: {}", e),
+            Self::NotLoggedIn(e) => write!(f, "未检测到已登录的账户: {}", e),
+            Self::BackupNotFound(name) => write!(f, "备份文件不存在: {}", name),
+            Self::InvalidBackup(e) => write!(f, "备份文件无效: {}", e),
+            Self::SessionMismatch { current, backup } => write!(
+                f,
+                "Antigravity 当前登录的是 {}，与要恢复的账户 {} 不同，请确认替换当前会话",
+                current, backup
+            ),
+            Self::ProcessNotFound => write!(f, "未找到Antigravity进程"),
+            Self::AntigravityRunning => write!(f, "Antigravity 正在运行，请先关闭后再试"),
+            Self::ProcessKillFailed(e) => write!(f, "关闭进程时发生错误: {}", e),
+            Self::Tray(e) => write!(f, "系统托盘操作失败: {}", e),
+            Self::Clipboard(e) => write!(f, "剪贴板操作失败: {}", e),
+            Self::Hook(e) => write!(f, "钩子执行失败: {}", e),
+            Self::Update(e) => write!(f, "更新失败: {}", e),
+            Self::Network(e) => write!(f, "网络请求失败: {}", e),
+            Self::Cancelled => write!(f, "任务已取消"),
+            Self::OperationInProgress(e) => write!(f, "已有操作正在进行: {}", e),
+            Self::ReadOnly => write!(f, "只读模式已开启，无法修改账户、备份或设置"),
+            Self::ConfirmationRequired(e) => write!(f, "需要二次确认: {}", e),
+            Self::InsufficientDiskSpace {
+                path,
+                available,
+                required,
+            } => write!(
+                f,
+                "磁盘剩余空间不足（{} 所在磁盘剩余 {} MB，至少需要 {} MB）",
+                path.display(),
+                available / (1024 * 1024),
+                required / (1024 * 1024)
+            ),
+            Self::Io(e) => write!(f, "文件操作失败: {}", e),
+            Self::Serialization(e) => write!(f, "数据序列化失败: {}", e),
+            Self::InvalidInput(e) => write!(f, "参数无效: {}", e),
+            Self::Internal(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for AgentError {}
+
+impl Serialize for AgentError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AgentError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("details", &self.details())?;
+        state.end()
+    }
+}
+
+/// 兼容尚未迁移、仍返回 String 错误的模块
+impl From<String> for AgentError {
+    fn from(e: String) -> Self {
+        Self::Internal(e)
+    }
+}
+
+/// 供仍返回 String 错误的命令调用已迁移的模块
+impl From<AgentError> for String {
+    fn from(e: AgentError) -> Self {
+        e.to_string()
+    }
+}
+
+impl From<rusqlite::Error> for AgentError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Database(e.to_string())
+    }
+}
+
+impl From<std::io::Error> for AgentError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AgentError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Serialization(e.to_string())
+    }
+}
@@ -0,0 +1,168 @@
+//! 首次运行引导
+//! 一次性完成环境检测（安装位置、数据目录、运行中的进程），给出默认配置建议并写入初始设置，
+//! 避免各模块在不同时机各自猜测路径
+
+use crate::app_settings::AppSettingsManager;
+use crate::error::AgentResult;
+use crate::system_tray::SystemTrayManager;
+use serde::Serialize;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+/// 环境检测结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupDetection {
+    /// Antigravity 用户数据目录
+    pub antigravity_data_dir: Option<String>,
+    /// Antigravity 状态数据库路径
+    pub antigravity_db_path: Option<String>,
+    /// 状态数据库是否存在
+    pub antigravity_db_exists: bool,
+    /// Antigravity 可执行文件路径（优先使用用户自定义路径）
+    pub antigravity_executable: Option<String>,
+    /// 可执行文件路径是否来自用户自定义配置
+    pub executable_is_custom: bool,
+    /// Antigravity 是否正在运行
+    pub antigravity_running: bool,
+    /// Agent 数据目录
+    pub agent_data_dir: String,
+    /// 是否处于便携模式
+    pub portable_mode: bool,
+}
+
+/// 建议的默认配置
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupDefaults {
+    /// 启用系统托盘
+    pub system_tray_enabled: bool,
+    /// 静默启动
+    pub silent_start_enabled: bool,
+    /// 开机自启（当前版本尚不支持，仅作为建议项返回）
+    pub autostart_enabled: bool,
+    /// 账户备份目录
+    pub backup_dir: String,
+}
+
+/// 首次运行报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupReport {
+    /// 本次是否为首次运行（首次运行才会写入默认设置）
+    pub first_run: bool,
+    /// 是否已将默认设置写入配置文件
+    pub settings_applied: bool,
+    pub detection: SetupDetection,
+    pub defaults: SetupDefaults,
+    /// 需要用户关注的问题
+    pub warnings: Vec<String>,
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+/// 检测当前运行环境
+pub fn detect_environment() -> SetupDetection {
+    let data_dir = crate::platform::get_antigravity_data_dir();
+    let db_path = crate::platform::resolve_antigravity_db_path().ok();
+    let db_exists = db_path.as_ref().is_some_and(|p| p.exists());
+
+    let custom_exec = crate::antigravity::path_config::get_custom_executable_path()
+        .unwrap_or(None)
+        .filter(|p| crate::antigravity::path_config::validate_executable_path(p));
+    let executable_is_custom = custom_exec.is_some();
+    let executable = custom_exec.or_else(|| {
+        crate::antigravity::starter::detect_antigravity_executable().map(|p| path_to_string(&p))
+    });
+
+    SetupDetection {
+        antigravity_data_dir: data_dir.as_deref().map(path_to_string),
+        antigravity_db_path: db_path.as_deref().map(path_to_string),
+        antigravity_db_exists: db_exists,
+        antigravity_executable: executable,
+        executable_is_custom,
+        antigravity_running: crate::platform::is_antigravity_running(),
+        agent_data_dir: path_to_string(&crate::directories::get_config_directory()),
+        portable_mode: crate::directories::is_portable_mode(),
+    }
+}
+
+/// 生成默认配置建议
+fn propose_defaults() -> SetupDefaults {
+    SetupDefaults {
+        system_tray_enabled: crate::system_tray::tray_capability().supported,
+        silent_start_enabled: false,
+        autostart_enabled: false,
+        backup_dir: path_to_string(&crate::directories::get_accounts_directory()),
+    }
+}
+
+/// 执行首次运行引导
+///
+/// 已完成过引导时只返回检测结果，不会覆盖用户已有的设置
+pub fn run(app: &AppHandle) -> AgentResult<SetupReport> {
+    let settings_manager = app.state::<AppSettingsManager>();
+    let setup_completed = settings_manager.get_settings().setup_completed;
+    // 旧版本升级的用户已有设置文件，视为已完成引导，不覆盖其配置
+    let has_existing_settings = crate::directories::get_app_settings_file().exists();
+    let first_run = !setup_completed && !has_existing_settings;
+
+    let detection = detect_environment();
+    let defaults = propose_defaults();
+
+    let mut warnings = Vec::new();
+    if !detection.antigravity_db_exists {
+        warnings.push("未找到 Antigravity 状态数据库，请确认已安装并至少登录过一次".to_string());
+    }
+    if detection.antigravity_executable.is_none() {
+        warnings.push("未检测到 Antigravity 可执行文件，请在设置中手动指定路径".to_string());
+    }
+    if detection.antigravity_running {
+        warnings.push("Antigravity 正在运行，切换账户时会自动关闭".to_string());
+    }
+
+    let mut settings_applied = false;
+    if first_run {
+        tracing::info!(target: "app::first_run", "首次运行，写入默认设置");
+
+        settings_manager.update_settings(|s| {
+            s.silent_start_enabled = defaults.silent_start_enabled;
+        })?;
+
+        if defaults.system_tray_enabled {
+            let system_tray = app.state::<SystemTrayManager>();
+            if let Err(e) = system_tray.enable(app) {
+                tracing::warn!(target: "app::first_run", error = %e, "启用系统托盘失败");
+                warnings.push(format!("启用系统托盘失败: {}", e));
+            }
+        }
+
+        settings_manager.update_settings(|s| s.setup_completed = true)?;
+        settings_applied = true;
+    } else {
+        if !setup_completed {
+            settings_manager.update_settings(|s| s.setup_completed = true)?;
+        }
+        tracing::debug!(target: "app::first_run", "已完成首次运行引导，仅返回检测结果");
+    }
+
+    tracing::info!(
+        target: "app::first_run",
+        first_run,
+        db_exists = detection.antigravity_db_exists,
+        executable_found = detection.antigravity_executable.is_some(),
+        running = detection.antigravity_running,
+        warnings = warnings.len(),
+        "首次运行检测完成"
+    );
+
+    Ok(SetupReport {
+        first_run,
+        settings_applied,
+        detection,
+        defaults,
+        warnings,
+    })
+}
@@ -0,0 +1,307 @@
+//! 前端日志写入
+//! 前端日志按批次通过 `write_frontend_logs` 提交，减少 IPC 往返。
+//! 按会话（页面加载）限流：每秒超出上限的条目直接丢弃，并在下一个统计窗口写入一条汇总，
+//! 避免异常页面每秒刷出数千条日志撑满日志文件；各会话的收发数量可通过 `get_frontend_log_stats` 查看
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 限流统计窗口
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// 每个会话每个窗口最多写入的条目数
+const MAX_ENTRIES_PER_WINDOW: u32 = 200;
+
+/// 单个批次最多处理的条目数，超出部分计为丢弃
+const MAX_BATCH_SIZE: usize = 1000;
+
+/// 会话空闲超过该时长后移除统计
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// 未携带会话 ID 的日志归入的会话
+const UNKNOWN_SESSION: &str = "unknown";
+
+struct SessionState {
+    window_start: Instant,
+    window_count: u32,
+    window_dropped: u32,
+    received: u64,
+    written: u64,
+    dropped: u64,
+    peak_per_second: u32,
+    first_seen: chrono::DateTime<chrono::Local>,
+    last_seen: Instant,
+}
+
+impl SessionState {
+    fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            window_count: 0,
+            window_dropped: 0,
+            received: 0,
+            written: 0,
+            dropped: 0,
+            peak_per_second: 0,
+            first_seen: chrono::Local::now(),
+            last_seen: now,
+        }
+    }
+
+    /// 进入新窗口时返回上一窗口丢弃的条目数
+    fn roll_window(&mut self, now: Instant) -> Option<u32> {
+        if now.duration_since(self.window_start) < WINDOW {
+            return None;
+        }
+        let dropped = std::mem::take(&mut self.window_dropped);
+        self.window_start = now;
+        self.window_count = 0;
+        (dropped > 0).then_some(dropped)
+    }
+
+    /// 记录一条日志，返回是否允许写入
+    fn admit(&mut self) -> bool {
+        self.received += 1;
+        let rate = self.window_count + self.window_dropped + 1;
+        self.peak_per_second = self.peak_per_second.max(rate);
+        if self.window_count < MAX_ENTRIES_PER_WINDOW {
+            self.window_count += 1;
+            self.written += 1;
+            true
+        } else {
+            self.window_dropped += 1;
+            self.dropped += 1;
+            false
+        }
+    }
+
+    /// 当前窗口剩余时间（已被限流时）
+    fn throttled_for(&self, now: Instant) -> Option<Duration> {
+        (self.window_count >= MAX_ENTRIES_PER_WINDOW)
+            .then(|| WINDOW.saturating_sub(now.duration_since(self.window_start)))
+    }
+}
+
+static SESSIONS: Mutex<BTreeMap<String, SessionState>> = Mutex::new(BTreeMap::new());
+
+/// 批量写入结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontendLogBatchResult {
+    pub accepted: usize,
+    pub dropped: usize,
+    /// 会话已被限流时，建议前端暂停提交的时长（毫秒）
+    pub retry_after_ms: Option<u64>,
+}
+
+/// 单个会话的日志统计
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontendLogSessionStats {
+    pub session_id: String,
+    pub received: u64,
+    pub written: u64,
+    pub dropped: u64,
+    /// 单个窗口（1 秒）内的最高提交数
+    pub peak_per_second: u32,
+    pub first_seen: String,
+}
+
+fn session_id(entry: &Value) -> &str {
+    entry
+        .get("sessionId")
+        .and_then(Value::as_str)
+        .filter(|id| !id.is_empty())
+        .unwrap_or(UNKNOWN_SESSION)
+}
+
+fn log_dropped_summary(session: &str, dropped: u32) {
+    tracing::warn!(
+        target: "frontend",
+        session = session,
+        dropped = dropped,
+        "[frontend] 前端日志过多，已丢弃 {} 条（每秒最多写入 {} 条）",
+        dropped,
+        MAX_ENTRIES_PER_WINDOW
+    );
+}
+
+/// 提交一批前端日志：按会话限流后写入日志系统
+pub fn submit(entries: &[Value]) -> FrontendLogBatchResult {
+    let now = Instant::now();
+    let (batch, overflow) = entries.split_at(entries.len().min(MAX_BATCH_SIZE));
+    let mut accepted = Vec::with_capacity(batch.len());
+    let mut summaries = Vec::new();
+    let mut retry_after = None;
+
+    {
+        let mut sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+        sessions.retain(|id, state| {
+            let active = now.duration_since(state.last_seen) < SESSION_IDLE_TIMEOUT;
+            if !active && state.window_dropped > 0 {
+                summaries.push((id.clone(), state.window_dropped));
+            }
+            active
+        });
+
+        for entry in batch {
+            let id = session_id(entry);
+            let state = sessions
+                .entry(id.to_string())
+                .or_insert_with(|| SessionState::new(now));
+            state.last_seen = now;
+            if let Some(dropped) = state.roll_window(now) {
+                summaries.push((id.to_string(), dropped));
+            }
+            if state.admit() {
+                accepted.push(entry);
+            }
+        }
+
+        if let Some(first) = overflow.first() {
+            let id = session_id(first);
+            let state = sessions
+                .entry(id.to_string())
+                .or_insert_with(|| SessionState::new(now));
+            let count = overflow.len() as u64;
+            state.received += count;
+            state.dropped += count;
+            summaries.push((id.to_string(), overflow.len() as u32));
+        }
+
+        if let Some(id) = batch.first().map(session_id) {
+            retry_after = sessions.get(id).and_then(|state| state.throttled_for(now));
+        }
+    }
+
+    for (session, dropped) in summaries {
+        log_dropped_summary(&session, dropped);
+    }
+    for entry in &accepted {
+        emit(entry);
+    }
+
+    FrontendLogBatchResult {
+        accepted: accepted.len(),
+        dropped: entries.len() - accepted.len(),
+        retry_after_ms: retry_after.map(|d| d.as_millis() as u64),
+    }
+}
+
+/// 各会话的日志统计（最近活跃的在前）
+pub fn stats() -> Vec<FrontendLogSessionStats> {
+    let sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut items: Vec<(Instant, FrontendLogSessionStats)> = sessions
+        .iter()
+        .map(|(id, state)| {
+            (
+                state.last_seen,
+                FrontendLogSessionStats {
+                    session_id: id.clone(),
+                    received: state.received,
+                    written: state.written,
+                    dropped: state.dropped,
+                    peak_per_second: state.peak_per_second,
+                    first_seen: state.first_seen.to_rfc3339(),
+                },
+            )
+        })
+        .collect();
+    items.sort_by_key(|(last_seen, _)| std::cmp::Reverse(*last_seen));
+    items.into_iter().map(|(_, stats)| stats).collect()
+}
+
+/// 将单条前端日志写入日志系统
+fn emit(log_entry: &Value) {
+    use tracing::{debug, error, info, warn};
+
+    // level: 'info' | 'warn' | 'error' | 'debug'
+    let level_str = log_entry
+        .get("level")
+        .and_then(|v| v.as_str())
+        .unwrap_or("info");
+
+    // message
+    let message = log_entry
+        .get("message")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    // details（前端会把对象 JSON.stringify 成字符串）
+    let details = log_entry.get("details").and_then(|v| v.as_str());
+
+    // module：优先顶层；否则尝试从 details(JSON) 提取 module 字段
+    let module = log_entry
+        .get("module")
+        .and_then(|v| v.as_str())
+        .map(ToOwned::to_owned)
+        .or_else(|| {
+            let details_str = details?;
+            let details_json = serde_json::from_str::<serde_json::Value>(details_str).ok()?;
+            details_json
+                .get("module")
+                .and_then(|v| v.as_str())
+                .map(ToOwned::to_owned)
+        })
+        .unwrap_or_else(|| "frontend".to_string());
+
+    // 携带操作 ID 的日志写入同名的 operation span，与后端日志按同一 ID 关联
+    let _operation = log_entry
+        .get("operationId")
+        .and_then(|v| v.as_str())
+        .filter(|id| !id.is_empty())
+        .map(|id| tracing::info_span!("operation", operation_id = %id).entered());
+
+    let full_message = if message.is_empty() {
+        format!("[{}]", module)
+    } else {
+        format!("[{}] {}", module, message)
+    };
+
+    // tracing 的 target 需要静态字符串；统一用 "frontend" 方便过滤（RUST_LOG / Debug Mode）
+    match level_str {
+        "error" => match details {
+            Some(details) => error!(
+                target: "frontend",
+                module = module.as_str(),
+                details = %details,
+                "{}",
+                full_message
+            ),
+            None => error!(target: "frontend", module = module.as_str(), "{}", full_message),
+        },
+        "warn" => match details {
+            Some(details) => warn!(
+                target: "frontend",
+                module = module.as_str(),
+                details = %details,
+                "{}",
+                full_message
+            ),
+            None => warn!(target: "frontend", module = module.as_str(), "{}", full_message),
+        },
+        "debug" => match details {
+            Some(details) => debug!(
+                target: "frontend",
+                module = module.as_str(),
+                details = %details,
+                "{}",
+                full_message
+            ),
+            None => debug!(target: "frontend", module = module.as_str(), "{}", full_message),
+        },
+        _ => match details {
+            Some(details) => info!(
+                target: "frontend",
+                module = module.as_str(),
+                details = %details,
+                "{}",
+                full_message
+            ),
+            None => info!(target: "frontend", module = module.as_str(), "{}", full_message),
+        },
+    }
+}
@@ -0,0 +1,685 @@
+//! 健康检查（Doctor）
+//! 逐项检查运行环境，返回 pass/warn/fail 结果及修复建议，供前端诊断页面展示；
+//! 启动时另外检查当前登录账户与备份是否一致，未备份或备份过期时推送 `account:backup-consistency` 提醒
+
+use crate::account_watcher::AccountWatcher;
+use crate::app_settings::AppSettingsManager;
+use crate::db_monitor::DatabaseMonitor;
+use crate::error::AgentResult;
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 启动时备份一致性提醒事件名
+pub const BACKUP_CONSISTENCY_EVENT: &str = "account:backup-consistency";
+
+/// 启动检查结果推迟推送的时间（等待前端注册事件监听）
+const STARTUP_NOTIFY_DELAY: Duration = Duration::from_secs(3);
+
+/// 检查结果状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// 单项检查结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheckItem {
+    /// 检查项标识（稳定，供前端匹配）
+    pub id: &'static str,
+    /// 检查项名称
+    pub label: &'static str,
+    pub status: HealthStatus,
+    /// 检查结果说明
+    pub message: String,
+    /// 修复建议
+    pub suggestion: Option<String>,
+}
+
+impl HealthCheckItem {
+    fn pass(id: &'static str, label: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            id,
+            label,
+            status: HealthStatus::Pass,
+            message: message.into(),
+            suggestion: None,
+        }
+    }
+
+    fn warn(
+        id: &'static str,
+        label: &'static str,
+        message: impl Into<String>,
+        suggestion: impl Into<String>,
+    ) -> Self {
+        Self {
+            id,
+            label,
+            status: HealthStatus::Warn,
+            message: message.into(),
+            suggestion: Some(suggestion.into()),
+        }
+    }
+
+    fn fail(
+        id: &'static str,
+        label: &'static str,
+        message: impl Into<String>,
+        suggestion: impl Into<String>,
+    ) -> Self {
+        Self {
+            id,
+            label,
+            status: HealthStatus::Fail,
+            message: message.into(),
+            suggestion: Some(suggestion.into()),
+        }
+    }
+}
+
+/// 健康检查报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    /// 整体状态（取所有检查项中最差的结果）
+    pub overall: HealthStatus,
+    pub checks: Vec<HealthCheckItem>,
+}
+
+/// 当前登录账户与备份的一致性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupConsistency {
+    /// Antigravity 未登录
+    NotLoggedIn,
+    /// 当前账户已有未过期的备份
+    Consistent,
+    /// 当前账户从未备份
+    NeverBackedUp,
+    /// 当前账户的备份超过设置的提醒天数
+    Stale,
+}
+
+/// 备份一致性检查结果（`account:backup-consistency` 事件负载）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupConsistencyReport {
+    pub status: BackupConsistency,
+    /// 当前登录的账户
+    pub email: Option<String>,
+    /// 当前账户最近一次成功备份的时间
+    pub last_backup_at: Option<String>,
+    /// 设置的提醒天数（0 表示不检查是否过期）
+    pub stale_days: u32,
+}
+
+/// 读取当前登录账户：优先使用认证状态缓存中的邮箱，缓存不存在时从登录状态解码
+fn read_live_email() -> Option<String> {
+    let db_path = crate::platform::resolve_antigravity_db_path().ok()?;
+    let auth_status_key = crate::antigravity::key_mapping::load().auth_status;
+    let from_auth_status = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .ok()
+        .and_then(|conn| {
+            conn.query_row(
+                "SELECT value FROM ItemTable WHERE key = ?",
+                [&auth_status_key],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .ok()
+            .flatten()
+        })
+        .and_then(|value| serde_json::from_str::<serde_json::Value>(&value).ok())
+        .and_then(|value| value["email"].as_str().map(str::to_string))
+        .filter(|email| !email.is_empty());
+
+    from_auth_status.or_else(|| {
+        crate::antigravity::backup::read_current_account()
+            .ok()
+            .map(|(email, _)| email)
+    })
+}
+
+/// 比较当前登录账户与其最近一次备份
+pub fn backup_consistency() -> BackupConsistencyReport {
+    let stale_days =
+        crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file())
+            .backup_stale_days;
+    let email = read_live_email();
+    let last_backup = email
+        .as_deref()
+        .and_then(crate::antigravity::backup::last_backup_time);
+
+    let status = match (&email, last_backup) {
+        (None, _) => BackupConsistency::NotLoggedIn,
+        (Some(_), None) => BackupConsistency::NeverBackedUp,
+        (Some(_), Some(time))
+            if stale_days > 0
+                && chrono::Local::now() - time > chrono::Duration::days(i64::from(stale_days)) =>
+        {
+            BackupConsistency::Stale
+        }
+        (Some(_), Some(_)) => BackupConsistency::Consistent,
+    };
+
+    BackupConsistencyReport {
+        status,
+        email,
+        last_backup_at: last_backup.map(|time| time.to_rfc3339()),
+        stale_days,
+    }
+}
+
+/// 启动时检查备份一致性，需要提醒时稍后推送事件（由账户变化监听在首次读取登录状态时调用）
+///
+/// 返回需要提醒的账户，供监听器避免重复推送备份过期提醒
+pub fn spawn_startup_consistency_check(app: &AppHandle) -> Option<String> {
+    let report = backup_consistency();
+    if !matches!(
+        report.status,
+        BackupConsistency::NeverBackedUp | BackupConsistency::Stale
+    ) {
+        tracing::debug!(target: "app::health_check", status = ?report.status, "启动时备份一致性检查通过");
+        return None;
+    }
+
+    tracing::warn!(
+        target: "app::health_check",
+        status = ?report.status,
+        last_backup_at = ?report.last_backup_at,
+        "当前登录账户未备份或备份已过期"
+    );
+    let email = report.email.clone();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_NOTIFY_DELAY).await;
+        if let Err(e) = app.emit(BACKUP_CONSISTENCY_EVENT, &report) {
+            tracing::error!(target: "app::health_check", error = %e, "推送备份一致性提醒失败");
+        }
+    });
+    email
+}
+
+/// 检查目录是否可写（写入并删除一个临时文件）
+fn check_dir_writable(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let probe = dir.join(".health_check_probe");
+    fs::write(&probe, b"ok").map_err(|e| e.to_string())?;
+    fs::remove_file(&probe).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn check_executable() -> HealthCheckItem {
+    const ID: &str = "antigravity_executable";
+    const LABEL: &str = "Antigravity 可执行文件";
+
+    let custom = crate::antigravity::path_config::get_custom_executable_path().unwrap_or(None);
+    if let Some(path) = custom {
+        return if crate::antigravity::path_config::validate_executable_path(&path) {
+            HealthCheckItem::pass(ID, LABEL, format!("使用自定义路径: {}", path))
+        } else {
+            HealthCheckItem::fail(
+                ID,
+                LABEL,
+                format!("自定义路径无效: {}", path),
+                "在设置中重新选择 Antigravity 可执行文件",
+            )
+        };
+    }
+
+    match crate::antigravity::starter::detect_antigravity_executable() {
+        Some(path) => HealthCheckItem::pass(ID, LABEL, format!("已检测到: {}", path.display())),
+        None => HealthCheckItem::warn(
+            ID,
+            LABEL,
+            "未能自动检测到可执行文件",
+            "确认 Antigravity 已安装，或在设置中手动指定可执行文件路径",
+        ),
+    }
+}
+
+fn check_data_dir() -> HealthCheckItem {
+    const ID: &str = "antigravity_data_dir";
+    const LABEL: &str = "Antigravity 数据目录";
+
+    match crate::platform::get_antigravity_data_dir() {
+        Some(dir) if dir.is_dir() => match fs::read_dir(&dir) {
+            Ok(_) => HealthCheckItem::pass(ID, LABEL, dir.display().to_string()),
+            Err(e) => HealthCheckItem::fail(
+                ID,
+                LABEL,
+                format!("无法访问 {}: {}", dir.display(), e),
+                "检查该目录的访问权限",
+            ),
+        },
+        Some(dir) => HealthCheckItem::fail(
+            ID,
+            LABEL,
+            format!("目录不存在: {}", dir.display()),
+            "启动 Antigravity 并登录一次以生成数据目录，或检查自定义的数据目录是否正确",
+        ),
+        None => HealthCheckItem::fail(
+            ID,
+            LABEL,
+            "无法确定数据目录位置",
+            "确认 Antigravity 已安装；数据目录不在默认位置时可在设置中指定，或设置 ANTIGRAVITY_AGENT_DATA_DIR 环境变量",
+        ),
+    }
+}
+
+fn check_state_db() -> HealthCheckItem {
+    const ID: &str = "state_db";
+    const LABEL: &str = "state.vscdb 数据库";
+
+    let db_path = match crate::platform::resolve_antigravity_db_path() {
+        Ok(path) => path,
+        Err(e) => {
+            return HealthCheckItem::fail(ID, LABEL, e.to_string(), "确认 Antigravity 已安装")
+        }
+    };
+
+    if !db_path.exists() {
+        return HealthCheckItem::fail(
+            ID,
+            LABEL,
+            format!("数据库不存在: {}", db_path.display()),
+            "启动 Antigravity 并登录一次以生成数据库",
+        );
+    }
+
+    let result =
+        Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY).and_then(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM ItemTable", [], |row| {
+                row.get::<_, i64>(0)
+            })
+        });
+
+    match result {
+        Ok(count) => HealthCheckItem::pass(ID, LABEL, format!("可读取，共 {} 条记录", count)),
+        Err(e) => HealthCheckItem::fail(
+            ID,
+            LABEL,
+            format!("读取失败: {}", e),
+            "关闭 Antigravity 后重试；若仍失败，数据库可能已损坏",
+        ),
+    }
+}
+
+fn check_state_db_integrity() -> HealthCheckItem {
+    const ID: &str = "state_db_integrity";
+    const LABEL: &str = "数据库完整性";
+
+    match crate::antigravity::integrity::check_integrity() {
+        Ok(report) if report.ok => HealthCheckItem::pass(ID, LABEL, "integrity_check 通过"),
+        Ok(report) => HealthCheckItem::fail(
+            ID,
+            LABEL,
+            format!(
+                "发现 {} 个问题: {}",
+                report.issues.len(),
+                report.issues.first().cloned().unwrap_or_default()
+            ),
+            "关闭 Antigravity 后执行数据库修复（修复前会自动快照原文件）",
+        ),
+        // 数据库不存在等情况已由 state_db 检查项报告
+        Err(e) => HealthCheckItem::warn(
+            ID,
+            LABEL,
+            format!("未检查: {}", e),
+            "确认 Antigravity 已安装",
+        ),
+    }
+}
+
+fn check_key_mapping() -> HealthCheckItem {
+    const ID: &str = "key_mapping";
+    const LABEL: &str = "数据库键名映射";
+
+    match crate::antigravity::key_mapping::detect_unknown_auth_keys() {
+        Ok(keys) if keys.is_empty() => {
+            HealthCheckItem::pass(ID, LABEL, "未发现映射之外的认证相关键")
+        }
+        Ok(keys) => HealthCheckItem::warn(
+            ID,
+            LABEL,
+            format!("发现 {} 个未知的疑似认证键: {}", keys.len(), keys.join(", ")),
+            "若 Antigravity 更新后备份为空，请在数据目录的 key_mapping.json 中更新键名；无关的键可加入 ignoredKeys",
+        ),
+        Err(e) => HealthCheckItem::warn(ID, LABEL, format!("未检查: {}", e), "确认 Antigravity 已安装"),
+    }
+}
+
+fn check_effective_keys() -> HealthCheckItem {
+    const ID: &str = "effective_keys";
+    const LABEL: &str = "备份处理的键";
+
+    let keys = crate::antigravity::key_mapping::effective_keys();
+    let message = format!(
+        "备份: {}；恢复时删除: {}；清除时删除: {}",
+        keys.backup.join(", "),
+        if keys.remove_on_restore.is_empty() {
+            "无".to_string()
+        } else {
+            keys.remove_on_restore.join(", ")
+        },
+        keys.remove_on_clear.join(", ")
+    );
+    let settings =
+        crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file());
+    if settings.backup_excluded_keys.is_empty() {
+        HealthCheckItem::pass(ID, LABEL, message)
+    } else {
+        HealthCheckItem::warn(
+            ID,
+            LABEL,
+            format!(
+                "{}（已排除: {}）",
+                message,
+                settings.backup_excluded_keys.join(", ")
+            ),
+            "排除默认处理的键后，切换账户可能不完整；遇到问题时请先在设置中清空排除列表",
+        )
+    }
+}
+
+fn check_writable_dir(id: &'static str, label: &'static str, dir: &Path) -> HealthCheckItem {
+    match check_dir_writable(dir) {
+        Ok(()) => HealthCheckItem::pass(id, label, dir.display().to_string()),
+        Err(e) => HealthCheckItem::fail(
+            id,
+            label,
+            format!("{} 不可写: {}", dir.display(), e),
+            "检查磁盘空间和目录权限",
+        ),
+    }
+}
+
+fn check_permissions() -> HealthCheckItem {
+    const ID: &str = "config_permissions";
+    const LABEL: &str = "配置目录权限";
+
+    if cfg!(not(unix)) {
+        return HealthCheckItem::pass(ID, LABEL, "由用户目录的访问控制保护");
+    }
+    let issues = crate::utils::permissions::audit();
+    match issues.first() {
+        None => HealthCheckItem::pass(ID, LABEL, "配置目录与备份仅当前用户可访问"),
+        Some(first) => HealthCheckItem::warn(
+            ID,
+            LABEL,
+            format!(
+                "{} 个文件或目录可被其他用户访问（如 {}，权限 {}）",
+                issues.len(),
+                first.path,
+                first.mode
+            ),
+            "执行修复权限，将其收紧为仅当前用户可访问（目录 700、文件 600）",
+        ),
+    }
+}
+
+fn check_tray(app: &AppHandle) -> HealthCheckItem {
+    const ID: &str = "system_tray";
+    const LABEL: &str = "系统托盘";
+
+    let capability = crate::system_tray::tray_capability();
+    if !capability.supported {
+        return HealthCheckItem::warn(
+            ID,
+            LABEL,
+            capability
+                .reason
+                .clone()
+                .unwrap_or_else(|| "当前系统不支持系统托盘".to_string()),
+            "安装 libayatana-appindicator 并启用桌面的 AppIndicator/StatusNotifier 支持后重启应用",
+        );
+    }
+
+    let enabled = app
+        .state::<AppSettingsManager>()
+        .get_settings()
+        .system_tray_enabled;
+
+    match (enabled, app.tray_by_id("main").is_some()) {
+        (true, true) => HealthCheckItem::pass(ID, LABEL, "托盘已启用"),
+        (true, false) => HealthCheckItem::fail(
+            ID,
+            LABEL,
+            "设置已启用托盘，但托盘图标未创建",
+            "在设置中关闭后重新开启系统托盘，或重启应用",
+        ),
+        (false, _) => HealthCheckItem::warn(
+            ID,
+            LABEL,
+            "托盘未启用",
+            "启用系统托盘后可在后台快速切换账户",
+        ),
+    }
+}
+
+async fn check_monitor(app: &AppHandle) -> HealthCheckItem {
+    const ID: &str = "db_monitor";
+    const LABEL: &str = "后台监控";
+
+    let monitor = app.state::<Arc<DatabaseMonitor>>();
+    if monitor.is_running().await {
+        HealthCheckItem::pass(ID, LABEL, "数据库监控运行中")
+    } else {
+        HealthCheckItem::warn(ID, LABEL, "数据库监控未运行", "重启应用以恢复后台监控")
+    }
+}
+
+fn check_scheduler() -> HealthCheckItem {
+    const ID: &str = "scheduler";
+    const LABEL: &str = "定时任务";
+
+    let Some(last_tick) = crate::scheduler::last_tick() else {
+        return HealthCheckItem::warn(
+            ID,
+            LABEL,
+            "定时任务调度尚未运行",
+            "刚启动时请稍后再检查，持续出现时重启应用",
+        );
+    };
+    let stalled_after = chrono::Duration::from_std(crate::scheduler::TICK_INTERVAL * 3)
+        .unwrap_or_else(|_| chrono::Duration::minutes(2));
+    let since = chrono::Local::now() - last_tick;
+    if since > stalled_after {
+        return HealthCheckItem::fail(
+            ID,
+            LABEL,
+            format!("定时任务调度已 {} 秒未响应", since.num_seconds()),
+            "重启应用以恢复定时任务",
+        );
+    }
+
+    let jobs = crate::scheduler::list_jobs();
+    let enabled: Vec<_> = jobs.iter().filter(|status| status.job.enabled).collect();
+    if enabled.is_empty() {
+        return HealthCheckItem::pass(ID, LABEL, "调度运行中，没有启用的定时任务");
+    }
+
+    // 每个任务最近一次运行失败时提醒
+    let history = crate::scheduler::history(usize::MAX);
+    let failed: Vec<&str> = enabled
+        .iter()
+        .filter(|status| {
+            history
+                .iter()
+                .find(|record| record.job == status.job.name)
+                .is_some_and(|record| record.outcome == crate::scheduler::RunOutcome::Failed)
+        })
+        .map(|status| status.job.name.as_str())
+        .collect();
+    if !failed.is_empty() {
+        return HealthCheckItem::warn(
+            ID,
+            LABEL,
+            format!("定时任务最近一次运行失败: {}", failed.join("、")),
+            "在定时任务页面查看运行历史中的错误信息",
+        );
+    }
+
+    let next_run = enabled
+        .iter()
+        .filter_map(|status| status.job.next_run())
+        .min()
+        .map_or_else(
+            || "未知".to_string(),
+            |time| time.format("%Y-%m-%d %H:%M").to_string(),
+        );
+    HealthCheckItem::pass(
+        ID,
+        LABEL,
+        format!(
+            "调度运行中，已启用 {} 个任务，下次运行于 {}",
+            enabled.len(),
+            next_run
+        ),
+    )
+}
+
+fn check_backup_consistency() -> HealthCheckItem {
+    const ID: &str = "backup_consistency";
+    const LABEL: &str = "当前账户备份";
+
+    let report = backup_consistency();
+    match report.status {
+        BackupConsistency::NotLoggedIn => {
+            HealthCheckItem::pass(ID, LABEL, "Antigravity 未登录，无需备份")
+        }
+        BackupConsistency::Consistent => HealthCheckItem::pass(
+            ID,
+            LABEL,
+            format!("最近备份于 {}", report.last_backup_at.unwrap_or_default()),
+        ),
+        BackupConsistency::NeverBackedUp => HealthCheckItem::warn(
+            ID,
+            LABEL,
+            "当前登录的账户尚未备份",
+            "立即备份当前账户，以便之后切换回来",
+        ),
+        BackupConsistency::Stale => HealthCheckItem::warn(
+            ID,
+            LABEL,
+            format!("当前账户已超过 {} 天未备份", report.stale_days),
+            "立即备份当前账户，避免恢复时使用过期的登录状态",
+        ),
+    }
+}
+
+async fn check_account_watcher(app: &AppHandle) -> HealthCheckItem {
+    const ID: &str = "account_watcher";
+    const LABEL: &str = "账户变化监听";
+
+    let watcher = app.state::<Arc<AccountWatcher>>();
+    if watcher.is_running().await {
+        HealthCheckItem::pass(ID, LABEL, "正在监听 Antigravity 登录状态")
+    } else {
+        HealthCheckItem::warn(
+            ID,
+            LABEL,
+            "账户变化监听未运行",
+            "重启应用以恢复账户变化监听",
+        )
+    }
+}
+
+/// 执行全部健康检查
+///
+/// 完整性检查、进程扫描与目录读写探测都是阻塞操作，放到阻塞线程池执行
+pub async fn run(app: &AppHandle) -> AgentResult<HealthReport> {
+    let monitor = check_monitor(app).await;
+    let account_watcher = check_account_watcher(app).await;
+    let app = app.clone();
+    let checks = crate::utils::blocking::run_blocking(move || {
+        Ok(vec![
+            check_executable(),
+            check_data_dir(),
+            check_state_db(),
+            check_state_db_integrity(),
+            check_key_mapping(),
+            check_effective_keys(),
+            check_backup_consistency(),
+            check_writable_dir(
+                "backup_dir",
+                "账户备份目录",
+                &crate::directories::get_accounts_directory(),
+            ),
+            check_writable_dir(
+                "log_dir",
+                "日志目录",
+                &crate::directories::get_log_directory(),
+            ),
+            check_permissions(),
+            check_tray(&app),
+            monitor,
+            check_scheduler(),
+            account_watcher,
+        ])
+    })
+    .await?;
+
+    let overall = if checks.iter().any(|c| c.status == HealthStatus::Fail) {
+        HealthStatus::Fail
+    } else if checks.iter().any(|c| c.status == HealthStatus::Warn) {
+        HealthStatus::Warn
+    } else {
+        HealthStatus::Pass
+    };
+
+    tracing::info!(
+        target: "app::health_check",
+        overall = ?overall,
+        failed = checks.iter().filter(|c| c.status == HealthStatus::Fail).count(),
+        warned = checks.iter().filter(|c| c.status == HealthStatus::Warn).count(),
+        "健康检查完成"
+    );
+
+    Ok(HealthReport { overall, checks })
+}
+
+/// 纯文本的诊断摘要（版本、平台与各检查项结果），邮箱与用户目录已脱敏，可直接粘贴到问题反馈中
+pub async fn diagnostic_summary(app: &AppHandle) -> AgentResult<String> {
+    let report = run(app).await?;
+    let (detection, version) = crate::utils::blocking::run_blocking(|| {
+        Ok((
+            crate::platform::platform_detection(),
+            crate::antigravity::version::detect_antigravity_version(),
+        ))
+    })
+    .await?;
+
+    let mut lines = vec![
+        format!("Antigravity Agent v{}", env!("CARGO_PKG_VERSION")),
+        format!(
+            "平台: {} {}（安装方式: {:?}）",
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            detection.flavor
+        ),
+        format!(
+            "Antigravity 版本: {}",
+            version.unwrap_or_else(|| "未知".to_string())
+        ),
+        format!("整体状态: {:?}", report.overall),
+    ];
+    for check in &report.checks {
+        lines.push(format!(
+            "- [{:?}] {}: {}",
+            check.status, check.label, check.message
+        ));
+    }
+    Ok(crate::utils::log_sanitizer::sanitize_log_message(
+        &lines.join("\n"),
+    ))
+}
@@ -0,0 +1,394 @@
+//! 生命周期钩子
+//! 用户可以为备份、恢复、关闭进程、切换账户等事件注册脚本；
+//! Agent 通过 stdin 传入 JSON 数据、强制超时，并将执行结果写入审计日志。
+//! `pre-*` 钩子以非零状态退出或超时时会中止对应操作。
+//! 超时时结束脚本及其启动的全部子进程（Unix 上为进程组，Windows 上为作业对象）。
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::io::{Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::{AgentError, AgentResult};
+
+/// 默认超时（秒）
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// 允许的最大超时（秒）
+const MAX_TIMEOUT_SECS: u64 = 600;
+
+/// 审计日志中保留的输出长度上限
+const MAX_OUTPUT_LEN: usize = 4096;
+
+/// 脚本退出后等待输出读取完成的时间（脚本留下的后台进程可能一直占着输出管道）
+const OUTPUT_GRACE: Duration = Duration::from_secs(2);
+
+/// 钩子事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HookEvent {
+    PreBackup,
+    PostBackup,
+    PreRestore,
+    PostRestore,
+    PreKill,
+    PostSwitch,
+}
+
+impl HookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PreBackup => "pre-backup",
+            Self::PostBackup => "post-backup",
+            Self::PreRestore => "pre-restore",
+            Self::PostRestore => "post-restore",
+            Self::PreKill => "pre-kill",
+            Self::PostSwitch => "post-switch",
+        }
+    }
+
+    /// `pre-*` 钩子失败时中止操作
+    fn aborts_on_failure(&self) -> bool {
+        matches!(self, Self::PreBackup | Self::PreRestore | Self::PreKill)
+    }
+}
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_TIMEOUT_SECS
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 钩子定义
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookDefinition {
+    pub event: HookEvent,
+    /// 可执行文件路径
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// 钩子配置（hooks.json）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HooksConfig {
+    #[serde(default)]
+    pub hooks: Vec<HookDefinition>,
+}
+
+/// 读取钩子配置（文件不存在或无效时返回空配置）
+pub fn load_config() -> HooksConfig {
+    let path = crate::directories::get_hooks_config_file();
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!(target: "hooks", error = %e, "钩子配置解析失败，忽略所有钩子");
+            HooksConfig::default()
+        }),
+        Err(_) => HooksConfig::default(),
+    }
+}
+
+/// 校验并保存钩子配置
+pub fn save_config(config: &HooksConfig) -> AgentResult<()> {
+    crate::app_settings::ensure_writable()?;
+
+    for hook in &config.hooks {
+        if hook.command.trim().is_empty() {
+            return Err(AgentError::InvalidInput(format!(
+                "{} 钩子的命令不能为空",
+                hook.event.as_str()
+            )));
+        }
+        if hook.timeout_secs == 0 || hook.timeout_secs > MAX_TIMEOUT_SECS {
+            return Err(AgentError::InvalidInput(format!(
+                "钩子超时时间需在 1-{} 秒之间",
+                MAX_TIMEOUT_SECS
+            )));
+        }
+    }
+
+    let path = crate::directories::get_hooks_config_file();
+    fs::write(&path, serde_json::to_string_pretty(config)?)?;
+    Ok(())
+}
+
+/// 单次钩子执行结果
+struct HookRun {
+    exit_code: Option<i32>,
+    timed_out: bool,
+    stdout: String,
+    stderr: String,
+    duration: Duration,
+}
+
+impl HookRun {
+    fn succeeded(&self) -> bool {
+        !self.timed_out && self.exit_code == Some(0)
+    }
+}
+
+fn truncate(mut s: String) -> String {
+    if s.len() > MAX_OUTPUT_LEN {
+        let mut end = MAX_OUTPUT_LEN;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        s.truncate(end);
+        s.push('…');
+    }
+    s
+}
+
+fn spawn_reader<R: Read + Send + 'static>(mut reader: R) -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = reader.read_to_end(&mut buf);
+        let _ = tx.send(String::from_utf8_lossy(&buf).to_string());
+    });
+    rx
+}
+
+/// Windows 作业对象：脚本启动的子进程自动加入同一作业，超时时一并结束
+#[cfg(windows)]
+struct Job(windows::Win32::Foundation::HANDLE);
+
+#[cfg(windows)]
+impl Job {
+    fn assign(child: &Child) -> Option<Self> {
+        use std::os::windows::io::AsRawHandle;
+        use windows::core::PCWSTR;
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::System::JobObjects::{AssignProcessToJobObject, CreateJobObjectW};
+
+        unsafe {
+            let job = Self(CreateJobObjectW(None, PCWSTR::null()).ok()?);
+            AssignProcessToJobObject(job.0, HANDLE(child.as_raw_handle())).ok()?;
+            Some(job)
+        }
+    }
+
+    fn terminate(&self) {
+        unsafe {
+            let _ = windows::Win32::System::JobObjects::TerminateJobObject(self.0, 1);
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for Job {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = windows::Win32::Foundation::CloseHandle(self.0);
+        }
+    }
+}
+
+/// 钩子脚本及其启动的子进程
+struct HookProcess {
+    child: Child,
+    #[cfg(windows)]
+    job: Option<Job>,
+}
+
+impl HookProcess {
+    fn spawn(command: &mut Command) -> std::io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            // 脚本作为新进程组的组长，超时时可以结束整个进程组
+            command.process_group(0);
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            // CREATE_NO_WINDOW：不弹出控制台窗口
+            command.creation_flags(0x0800_0000);
+        }
+
+        let child = command.spawn()?;
+        Ok(Self {
+            #[cfg(windows)]
+            job: Job::assign(&child),
+            child,
+        })
+    }
+
+    /// 结束脚本及其启动的全部子进程
+    fn kill_tree(&mut self) {
+        #[cfg(unix)]
+        unsafe {
+            // 进程组 ID 与组长的 PID 相同
+            libc::kill(-(self.child.id() as libc::pid_t), libc::SIGKILL);
+        }
+        #[cfg(windows)]
+        if let Some(job) = &self.job {
+            job.terminate();
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn run_single(hook: &HookDefinition, input: &str) -> std::io::Result<HookRun> {
+    let start = Instant::now();
+
+    let mut command = Command::new(&hook.command);
+    command
+        .args(&hook.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut process = HookProcess::spawn(&mut command)?;
+
+    if let Some(mut stdin) = process.child.stdin.take() {
+        // 在单独的线程中写入，脚本不读取 stdin 时也不会阻塞超时检查；写入失败不视为错误
+        let input = input.to_string();
+        thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        });
+    }
+
+    let stdout = process.child.stdout.take().map(spawn_reader);
+    let stderr = process.child.stderr.take().map(spawn_reader);
+
+    let timeout = Duration::from_secs(hook.timeout_secs);
+    let mut timed_out = false;
+    let status = loop {
+        match process.child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) => {}
+            Err(e) => {
+                process.kill_tree();
+                return Err(e);
+            }
+        }
+        if start.elapsed() >= timeout {
+            timed_out = true;
+            process.kill_tree();
+            break None;
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+
+    // 脚本留下的后台进程仍占着输出管道时不再等待，只保留已经读取完成的输出
+    let deadline = Instant::now() + OUTPUT_GRACE;
+    let collect = |rx: Option<mpsc::Receiver<String>>| {
+        rx.and_then(|rx| {
+            rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                .ok()
+        })
+        .map(truncate)
+        .unwrap_or_default()
+    };
+
+    Ok(HookRun {
+        exit_code: status.and_then(|s| s.code()),
+        timed_out,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+        duration: start.elapsed(),
+    })
+}
+
+/// 在异步上下文中执行指定事件的全部钩子：脚本放到阻塞线程池运行，不占用异步运行时的工作线程
+pub async fn run_hooks_async(event: HookEvent, payload: Value) -> AgentResult<()> {
+    crate::utils::blocking::run_blocking(move || run_hooks(event, payload)).await
+}
+
+/// 同步调用方位于异步运行时的工作线程上时（异步命令中直接调用的备份、恢复等），
+/// 先把该线程上的其他任务交给别的线程，脚本运行期间不阻塞它们
+fn block_in_place<T>(f: impl FnOnce() -> T) -> T {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+/// 执行指定事件的全部钩子
+///
+/// `payload` 会与事件名、时间一起作为 JSON 写入脚本的 stdin；异步代码中使用 [`run_hooks_async`]
+pub fn run_hooks(event: HookEvent, payload: Value) -> AgentResult<()> {
+    let config = load_config();
+    let hooks: Vec<_> = config
+        .hooks
+        .iter()
+        .filter(|h| h.enabled && h.event == event)
+        .collect();
+
+    if hooks.is_empty() {
+        return Ok(());
+    }
+
+    let input = serde_json::json!({
+        "event": event.as_str(),
+        "timestamp": chrono::Local::now().to_rfc3339(),
+        "data": payload,
+    })
+    .to_string();
+
+    for hook in hooks {
+        tracing::info!(target: "hooks", event = event.as_str(), command = %hook.command, "执行钩子");
+
+        let (success, detail, error) = match block_in_place(|| run_single(hook, &input)) {
+            Ok(run) => {
+                let success = run.succeeded();
+                let error = if run.timed_out {
+                    Some(format!("超时（{} 秒）", hook.timeout_secs))
+                } else if !success {
+                    Some(format!("退出码 {:?}", run.exit_code))
+                } else {
+                    None
+                };
+                let detail = serde_json::json!({
+                    "command": hook.command,
+                    "args": hook.args,
+                    "exitCode": run.exit_code,
+                    "timedOut": run.timed_out,
+                    "durationMs": run.duration.as_millis() as u64,
+                    "stdout": run.stdout,
+                    "stderr": run.stderr,
+                });
+                (success, detail, error)
+            }
+            Err(e) => {
+                let detail = serde_json::json!({
+                    "command": hook.command,
+                    "args": hook.args,
+                    "error": e.to_string(),
+                });
+                (false, detail, Some(format!("启动失败: {}", e)))
+            }
+        };
+
+        crate::audit::record("hook", event.as_str(), success, detail);
+
+        if let Some(error) = error {
+            tracing::warn!(target: "hooks", event = event.as_str(), command = %hook.command, error = %error, "钩子执行失败");
+            if event.aborts_on_failure() {
+                return Err(AgentError::Hook(format!(
+                    "{} 钩子 {} {}",
+                    event.as_str(),
+                    hook.command,
+                    error
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
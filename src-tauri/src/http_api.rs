@@ -0,0 +1,222 @@
+//! 本地 HTTP 控制接口
+//! 仅监听 127.0.0.1，使用随机 Bearer Token 鉴权，供外部工具（脚本、Stream Deck 插件等）控制 Agent
+//!
+//! - `GET  /status`   运行状态与当前账户
+//! - `GET  /accounts` 账户备份列表
+//! - `POST /backup`   备份当前账户
+//! - `POST /switch`   切换账户，请求体 `{"email": "..."}`
+
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::antigravity::backup::{self, BackupTrigger};
+use crate::error::{AgentError, AgentResult};
+
+/// 默认监听端口
+pub const DEFAULT_PORT: u16 = 47890;
+
+/// 生成新的访问令牌
+pub fn generate_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+struct RunningServer {
+    server: Arc<Server>,
+    handle: JoinHandle<()>,
+    port: u16,
+}
+
+/// HTTP 控制接口管理器
+pub struct HttpApiServer {
+    running: Mutex<Option<RunningServer>>,
+}
+
+impl HttpApiServer {
+    pub fn new() -> Self {
+        Self {
+            running: Mutex::new(None),
+        }
+    }
+
+    /// 当前监听端口（未运行时为 None）
+    pub fn running_port(&self) -> Option<u16> {
+        self.running.lock().unwrap().as_ref().map(|r| r.port)
+    }
+
+    /// 启动服务（已运行时先停止）
+    pub fn start(&self, port: u16, token: String) -> AgentResult<()> {
+        self.stop();
+
+        let server = Server::http(("127.0.0.1", port))
+            .map_err(|e| AgentError::Internal(format!("启动 HTTP 控制接口失败: {}", e)))?;
+        let server = Arc::new(server);
+
+        let worker = server.clone();
+        let handle = std::thread::spawn(move || {
+            for request in worker.incoming_requests() {
+                handle_request(request, &token);
+            }
+            tracing::info!(target: "http_api", "HTTP 控制接口已停止");
+        });
+
+        tracing::info!(target: "http_api", port, "🌐 HTTP 控制接口已启动");
+        *self.running.lock().unwrap() = Some(RunningServer {
+            server,
+            handle,
+            port,
+        });
+        Ok(())
+    }
+
+    /// 停止服务
+    pub fn stop(&self) {
+        if let Some(running) = self.running.lock().unwrap().take() {
+            running.server.unblock();
+            let _ = running.handle.join();
+        }
+    }
+}
+
+/// 常量时间比较，避免通过响应时间推测令牌
+fn token_matches(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn is_authorized(request: &Request, token: &str) -> bool {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .and_then(|h| h.value.as_str().strip_prefix("Bearer "))
+        .is_some_and(|provided| token_matches(provided.trim(), token))
+}
+
+fn json_response(status: u16, body: &Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    let header = Header::from_bytes("Content-Type", "application/json; charset=utf-8")
+        .expect("静态响应头必然有效");
+    Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header)
+}
+
+fn error_status(error: &AgentError) -> u16 {
+    match error {
+        AgentError::InvalidInput(_) | AgentError::InvalidBackup(_) => 400,
+        AgentError::BackupNotFound(_) => 404,
+        AgentError::NotLoggedIn(_)
+        | AgentError::OperationInProgress(_)
+        | AgentError::AntigravityRunning => 409,
+        AgentError::ReadOnly | AgentError::ConfirmationRequired(_) => 403,
+        _ => 500,
+    }
+}
+
+fn current_status() -> AgentResult<Value> {
+    let current_email = backup::read_current_account().ok().map(|(email, _)| email);
+    Ok(serde_json::json!({
+        "agentVersion": env!("CARGO_PKG_VERSION"),
+        "antigravityRunning": crate::platform::is_antigravity_running(),
+        "antigravityVersion": crate::antigravity::version::detect_antigravity_version(),
+        "currentEmail": current_email,
+    }))
+}
+
+/// 备份当前账户，请求体可选 `{ "note": "..." }`
+fn backup_account(request: &mut Request) -> AgentResult<Value> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .read_to_string(&mut body)
+        .map_err(|e| AgentError::InvalidInput(format!("读取请求体失败: {}", e)))?;
+
+    let note = if body.trim().is_empty() {
+        None
+    } else {
+        let payload: Value = serde_json::from_str(&body)
+            .map_err(|e| AgentError::InvalidInput(format!("请求体不是有效的 JSON: {}", e)))?;
+        payload
+            .get("note")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+
+    let outcome = backup::smart_backup_antigravity_account(BackupTrigger::Manual, note)?;
+    Ok(serde_json::json!({
+        "email": outcome.email,
+        "file": outcome.path.to_string_lossy(),
+        "changed": outcome.changed,
+    }))
+}
+
+fn switch_account(request: &mut Request) -> AgentResult<Value> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .read_to_string(&mut body)
+        .map_err(|e| AgentError::InvalidInput(format!("读取请求体失败: {}", e)))?;
+
+    let payload: Value = serde_json::from_str(&body)
+        .map_err(|e| AgentError::InvalidInput(format!("请求体不是有效的 JSON: {}", e)))?;
+    let email = payload
+        .get("email")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| AgentError::InvalidInput("缺少 email 字段".to_string()))?;
+
+    let account_file = crate::antigravity::backup_naming::require_backup_file(email)?;
+
+    let message = tauri::async_runtime::block_on(crate::commands::switch_to_account_file(
+        account_file,
+        &crate::tasks::TaskHandle::detached(),
+    ))?;
+    Ok(serde_json::json!({ "message": message }))
+}
+
+fn handle_request(mut request: Request, token: &str) {
+    let method = request.method().clone();
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+
+    let response = if !is_authorized(&request, token) {
+        tracing::warn!(target: "http_api", method = %method, path = %path, "拒绝未授权的请求");
+        json_response(
+            401,
+            &serde_json::json!({ "ok": false, "error": { "code": "UNAUTHORIZED", "message": "未授权" } }),
+        )
+    } else {
+        tracing::info!(target: "http_api", method = %method, path = %path, "处理 HTTP 控制请求");
+
+        let result = match (&method, path.as_str()) {
+            (Method::Get, "/status") => Some(current_status()),
+            (Method::Get, "/accounts") => Some(backup::list_backups().map(Value::Array)),
+            (Method::Post, "/backup") => Some(backup_account(&mut request)),
+            (Method::Post, "/switch") => Some(switch_account(&mut request)),
+            _ => None,
+        };
+
+        match result {
+            Some(Ok(data)) => json_response(200, &serde_json::json!({ "ok": true, "data": data })),
+            Some(Err(e)) => {
+                tracing::warn!(target: "http_api", path = %path, error = %e, "HTTP 控制请求失败");
+                json_response(
+                    error_status(&e),
+                    &serde_json::json!({ "ok": false, "error": e }),
+                )
+            }
+            None => json_response(
+                404,
+                &serde_json::json!({ "ok": false, "error": { "code": "NOT_FOUND", "message": "接口不存在" } }),
+            ),
+        }
+    };
+
+    if let Err(e) = request.respond(response) {
+        tracing::warn!(target: "http_api", error = %e, "发送 HTTP 响应失败");
+    }
+}
@@ -7,14 +7,42 @@ use tracing_subscriber::filter::LevelFilter;
 use tracing_subscriber::{prelude::*, EnvFilter};
 
 // Modules
+mod account_avatar;
+mod account_metadata;
+mod account_watcher;
+mod agent_db;
 mod antigravity;
 mod app_settings;
-mod config_manager;
+mod appearance;
+mod audit;
+mod cli;
+mod confirmation;
 mod constants;
+mod crash_report;
+mod dashboard;
+mod dialog;
+mod dir_lock;
 mod directories;
+mod disk_space;
+mod error;
+mod first_run;
+mod frontend_log;
+mod health_check;
+mod hooks;
+mod http_api;
+mod metrics;
+mod operation_lock;
 mod platform;
 mod proto;
+mod proxy;
+mod quota_history;
+mod retention;
+mod scheduler;
 mod system_tray;
+mod task_journal;
+mod tasks;
+mod telemetry;
+mod updater;
 mod utils;
 mod window;
 
@@ -22,7 +50,11 @@ mod commands;
 mod db_monitor;
 mod path_utils;
 mod setup;
+mod shutdown;
+mod startup_metrics;
 mod state;
+mod state_bus;
+mod storage_stats;
 
 // Re-export AppState for compatibility with other modules
 pub use state::{AntigravityAccount, AppState, ProfileInfo};
@@ -31,7 +63,9 @@ pub use state::{AntigravityAccount, AppState, ProfileInfo};
 use crate::commands::*;
 
 /// 初始化双层日志系统（控制台 + 文件）
-fn init_tracing() -> WorkerGuard {
+///
+/// 命令行模式下关闭控制台输出，避免干扰标准输出中的 JSON 结果
+fn init_tracing(console: bool) -> WorkerGuard {
     let app_settings_path = crate::directories::get_app_settings_file();
     let settings = crate::app_settings::load_settings_from_disk(&app_settings_path);
 
@@ -59,14 +93,14 @@ fn init_tracing() -> WorkerGuard {
     // 设置控制台和文件双层输出
     tracing_subscriber::registry()
         .with(env_filter)
-        .with(
+        .with(console.then(|| {
             tracing_subscriber::fmt::layer()
                 .with_writer(std::io::stdout) // 控制台输出，不脱敏
                 .with_target(false)
                 .compact()
                 .with_ansi(true) // 控制台启用颜色
-                .with_filter(LevelFilter::INFO),
-        )
+                .with_filter(LevelFilter::INFO)
+        }))
         .with(
             tracing_subscriber::fmt::layer()
                 .with_writer(non_blocking) // 文件输出，自动脱敏
@@ -80,20 +114,52 @@ fn init_tracing() -> WorkerGuard {
 }
 
 fn main() {
+    startup_metrics::mark_process_start();
+
+    // 命令行模式：识别到子命令时不启动窗口，执行完毕后直接退出
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(command) = cli::parse_args(&args) {
+        #[cfg(windows)]
+        cli::attach_parent_console();
+        // 日志写入前应用脱敏方式等设置
+        app_settings::apply_globals(&app_settings::load_settings_from_disk(
+            &directories::get_app_settings_file(),
+        ));
+        let guard = init_tracing(false);
+        crash_report::install_panic_hook();
+        let code = cli::run(command);
+        drop(guard);
+        std::process::exit(code);
+    }
+
     // 初始化双层日志系统（控制台 + 文件）
-    let _guard = init_tracing();
+    let _guard = {
+        let _phase = startup_metrics::phase("init_tracing");
+        init_tracing(true)
+    };
+    crash_report::install_panic_hook();
 
     tracing::info!(target: "app::startup", "🚀 启动 Antigravity Agent");
     tracing::info!(target: "app::startup", "📝 日志系统已初始化（控制台 + 文件）");
     tracing::info!(target: "app::startup", "📁 日志目录: {}", crate::directories::get_log_directory().display());
+    if crate::directories::is_portable_mode() {
+        tracing::info!(
+            target: "app::startup",
+            "💼 便携模式已启用，数据目录: {}",
+            crate::directories::get_config_directory().display()
+        );
+    }
 
     // 记录系统启动信息
     crate::utils::tracing_config::log_system_info();
 
     // 阻塞主线程执行一次账户目录迁移检查
-    match crate::directories::migrate_legacy_accounts_if_needed() {
-        Ok(()) => tracing::info!(target: "app::startup", "📦 账户目录迁移检查完成"),
-        Err(e) => tracing::error!(target: "app::startup", "⚠️ 账户目录迁移检查失败: {}", e),
+    {
+        let _phase = startup_metrics::phase("migrate_accounts");
+        match crate::directories::migrate_legacy_accounts_if_needed() {
+            Ok(()) => tracing::info!(target: "app::startup", "📦 账户目录迁移检查完成"),
+            Err(e) => tracing::error!(target: "app::startup", "⚠️ 账户目录迁移检查失败: {}", e),
+        }
     }
 
     tauri::Builder::default()
@@ -106,19 +172,46 @@ fn main() {
         .plugin(tauri_plugin_http::init())
         .manage(AppState::default())
         .setup(|app| setup::init(app))
+        .on_page_load(|_, payload| {
+            if payload.event() == tauri::webview::PageLoadEvent::Finished {
+                startup_metrics::mark_window_ready();
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             collect_account_contents,
+            export_sanitized_backup,
+            copy_to_clipboard,
+            // 定时任务命令
+            validate_cron,
+            preview_schedule,
+            list_scheduled_jobs,
+            save_scheduled_job,
+            delete_scheduled_job,
+            get_scheduler_history,
+            // 使用统计命令
+            get_telemetry_summary,
+            preview_telemetry_report,
+            send_telemetry_report,
+            clear_telemetry_data,
             restore_backup_files,
             delete_backup,
             clear_all_backups,
+            migrate_backup_file_names,
             // 账户基础命令
             get_antigravity_accounts,
             get_current_antigravity_account_info,
             save_antigravity_current_account,
+            backup_all_known_accounts,
+            list_backup_summaries,
+            search_backups,
+            describe_backup_schema,
             restore_antigravity_account,
+            run_restore_queue,
             switch_to_antigravity_account,
             clear_all_antigravity_data,
             is_antigravity_running,
+            get_launched_processes,
+            list_matching_processes,
             sign_in_new_antigravity_account,
             // 平台支持命令
             get_platform_info,
@@ -128,27 +221,134 @@ fn main() {
             detect_antigravity_installation,
             // 可执行文件路径相关
             validate_antigravity_executable,
+            validate_custom_executable,
+            list_antigravity_installs,
+            select_antigravity_install,
             detect_antigravity_executable,
+            refresh_platform_detection,
             save_antigravity_executable,
+            save_antigravity_data_dir,
             minimize_to_tray,
             restore_from_tray,
+            // 窗口布局
+            apply_window_preset,
+            reset_window_state,
             update_tray_menu_command,
-            save_system_tray_state,
+            set_system_tray_enabled,
             save_silent_start_state,
             save_private_mode_state,
             save_debug_mode_state,
+            save_auto_backup_on_account_change_state,
+            save_read_only_mode_state,
+            save_hide_dock_icon_state,
+            save_confirm_dangerous_operations_state,
+            save_db_snapshot_before_write_state,
+            save_backup_before_clear_state,
+            save_email_mask_style,
+            save_backup_file_name_template,
+            save_backup_stale_days,
+            save_time_display_settings,
+            save_launch_policy,
+            save_reopen_workspace_on_restart,
+            save_wait_settings,
+            save_verify_login_after_switch,
+            save_language,
+            save_skipped_confirm_prompts,
+            save_wsl_windows_mode,
+            save_backup_key_overrides,
+            save_scheduler_settings,
+            save_telemetry_settings,
+            save_min_free_disk_mb,
+            get_theme_state,
+            save_theme_preference,
+            get_ui_preferences,
+            set_ui_preferences,
             get_all_settings,
+            run_first_time_setup,
             // 数据库监控命令
             is_database_monitoring_running,
             start_database_monitoring,
             stop_database_monitoring,
+            // 健康检查
+            run_health_check,
+            fix_permissions,
+            // 本地 HTTP 控制接口
+            get_http_api_status,
+            save_http_api_state,
+            regenerate_http_api_token,
+            // 生命周期钩子与审计日志
+            get_hooks_config,
+            save_hooks_config,
+            get_audit_log,
+            // Agent 自更新
+            check_for_updates,
+            install_update,
+            save_update_settings,
+            // Antigravity 设备标识
+            get_machine_ids,
+            reset_machine_ids,
+            // 代理设置
+            get_proxy_settings,
+            save_proxy_settings,
+            test_proxy_connection,
+            // 长耗时任务
+            list_tasks,
+            cancel_task,
+            list_interrupted_tasks,
+            resolve_interrupted_task,
+            // 操作锁
+            get_active_operations,
+            // 崩溃报告
+            get_last_crash_report,
+            report_frontend_crash,
+            // 启动性能统计
+            get_startup_metrics,
+            // 命令性能统计
+            get_metrics_snapshot,
+            // 危险操作确认
+            request_dangerous_operation,
+            // 数据库文件快照
+            list_db_snapshots,
+            restore_db_snapshot,
+            // 数据库完整性
+            check_database_integrity,
+            repair_database,
+            // 存储占用
+            get_backup_storage_stats,
+            simulate_retention,
+            save_retention_policy,
+            // 状态订阅
+            get_full_state,
+            subscribe_state,
+            get_dashboard_summary,
+            // 账户启动配置
+            get_account_launch_profile,
+            save_account_launch_profile,
+            save_account_alias,
+            get_recent_accounts,
+            set_account_pinned,
+            get_account_avatar,
+            cache_account_avatar,
+            // 账户配额
+            record_account_quota,
+            compare_accounts_quota,
             decrypt_config_data,
             encrypt_config_data,
             write_text_file,
             write_frontend_log,
+            write_frontend_logs,
+            get_frontend_log_stats,
+            get_operation_trace,
             get_log_directory_path,
+            get_log_info,
             open_log_directory,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            // 托盘菜单退出、关闭最后一个窗口或系统关机：窗口销毁前保存状态
+            tauri::RunEvent::ExitRequested { .. } => shutdown::flush_state(app, "exit_requested"),
+            tauri::RunEvent::Exit => shutdown::flush_state(app, "exit"),
+            _ => {}
+        });
 }
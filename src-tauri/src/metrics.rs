@@ -0,0 +1,120 @@
+//! 命令性能统计
+//! 按 Tauri 命令记录调用次数、失败次数与耗时分布（由 `log_async_command!` 自动上报），
+//! 通过 `get_metrics_snapshot` 命令查看
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 耗时分布桶的上界（毫秒），超过最后一个上界的计入溢出桶
+const BUCKET_BOUNDS_MS: [u64; 9] = [10, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+#[derive(Default)]
+struct CommandStats {
+    calls: u64,
+    errors: u64,
+    total_ms: f64,
+    max_ms: f64,
+    buckets: [u64; BUCKET_BOUNDS_MS.len() + 1],
+}
+
+struct Registry {
+    since: Option<String>,
+    commands: BTreeMap<&'static str, CommandStats>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    since: None,
+    commands: BTreeMap::new(),
+});
+
+/// 耗时分布桶
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyBucket {
+    /// 桶上界（毫秒），溢出桶为 None
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+/// 单个命令的统计
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandMetrics {
+    pub command: String,
+    pub calls: u64,
+    pub errors: u64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+    pub buckets: Vec<LatencyBucket>,
+}
+
+/// 统计快照
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsSnapshot {
+    /// 开始统计的时间（首次记录时），尚无记录时为 None
+    pub since: Option<String>,
+    /// 按调用次数降序排列
+    pub commands: Vec<CommandMetrics>,
+}
+
+/// 记录一次命令调用
+pub fn record_command(command: &'static str, duration: Duration, success: bool) {
+    let ms = duration.as_secs_f64() * 1000.0;
+    let bucket = BUCKET_BOUNDS_MS
+        .iter()
+        .position(|bound| ms <= *bound as f64)
+        .unwrap_or(BUCKET_BOUNDS_MS.len());
+
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    registry
+        .since
+        .get_or_insert_with(|| chrono::Local::now().to_rfc3339());
+
+    let stats = registry.commands.entry(command).or_default();
+    stats.calls += 1;
+    if !success {
+        stats.errors += 1;
+    }
+    stats.total_ms += ms;
+    stats.max_ms = stats.max_ms.max(ms);
+    stats.buckets[bucket] += 1;
+}
+
+/// 当前的统计快照
+pub fn snapshot() -> MetricsSnapshot {
+    let registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut commands: Vec<CommandMetrics> = registry
+        .commands
+        .iter()
+        .map(|(name, stats)| CommandMetrics {
+            command: name.to_string(),
+            calls: stats.calls,
+            errors: stats.errors,
+            avg_ms: stats.total_ms / stats.calls.max(1) as f64,
+            max_ms: stats.max_ms,
+            buckets: stats
+                .buckets
+                .iter()
+                .enumerate()
+                .map(|(i, count)| LatencyBucket {
+                    le_ms: BUCKET_BOUNDS_MS.get(i).copied(),
+                    count: *count,
+                })
+                .collect(),
+        })
+        .collect();
+    commands.sort_by(|a, b| {
+        b.calls
+            .cmp(&a.calls)
+            .then_with(|| a.command.cmp(&b.command))
+    });
+
+    MetricsSnapshot {
+        since: registry.since.clone(),
+        commands,
+    }
+}
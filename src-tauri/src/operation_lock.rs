@@ -0,0 +1,135 @@
+//! 操作锁
+//! 按资源（Antigravity 状态数据库、账户备份目录）互斥，防止切换、恢复、清除、备份等破坏性操作并发执行。
+//! 锁是进程级的，GUI 命令、HTTP 控制接口与后台监听器共用同一份状态；
+//! 账户备份目录另外通过锁文件与命令行模式等其他进程互斥（见 [`crate::dir_lock`]）
+
+use serde::Serialize;
+use std::sync::Mutex;
+
+use crate::dir_lock::DirLock;
+use crate::error::{AgentError, AgentResult};
+use crate::state_bus::StateUpdate;
+
+/// 受保护的资源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockResource {
+    /// Antigravity 的 state.vscdb
+    StateDb,
+    /// 账户备份目录
+    BackupDir,
+}
+
+impl LockResource {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::StateDb => "Antigravity 数据库",
+            Self::BackupDir => "账户备份目录",
+        }
+    }
+}
+
+/// 正在进行的操作
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveOperation {
+    pub id: u64,
+    /// 操作名，如 `switch_account`
+    pub operation: String,
+    pub resources: Vec<LockResource>,
+    pub started_at: String,
+}
+
+struct Registry {
+    next_id: u64,
+    active: Vec<ActiveOperation>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    next_id: 1,
+    active: Vec::new(),
+});
+
+/// 操作锁守卫，离开作用域时释放
+#[must_use = "守卫被丢弃时锁会立即释放"]
+pub struct OperationGuard {
+    id: u64,
+    /// 备份目录的跨进程锁（操作涉及 `BackupDir` 时持有）
+    dir_lock: Option<DirLock>,
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        // 先删除锁文件再释放进程内的锁，避免下一个操作把尚未删除的锁文件当作遗留锁清理
+        drop(self.dir_lock.take());
+        let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+        registry.active.retain(|op| op.id != self.id);
+        let names = operation_names(&registry);
+        drop(registry);
+        crate::state_bus::publish(StateUpdate::ActiveOperations(names));
+    }
+}
+
+/// 获取指定资源的锁，任一资源被占用时返回 `OperationInProgress`，只读模式下返回 `ReadOnly`
+pub fn acquire(operation: &str, resources: &[LockResource]) -> AgentResult<OperationGuard> {
+    crate::app_settings::ensure_writable()?;
+
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+
+    if let Some((busy, resource)) = registry.active.iter().find_map(|op| {
+        resources
+            .iter()
+            .find(|r| op.resources.contains(r))
+            .map(|r| (op, r))
+    }) {
+        tracing::warn!(
+            target: "operation_lock",
+            requested = operation,
+            running = %busy.operation,
+            resource = ?resource,
+            "资源被占用，拒绝操作"
+        );
+        return Err(AgentError::OperationInProgress(format!(
+            "{}正在被「{}」使用，请稍后再试",
+            resource.label(),
+            busy.operation
+        )));
+    }
+
+    let dir_lock = if resources.contains(&LockResource::BackupDir) {
+        Some(crate::dir_lock::acquire(operation)?)
+    } else {
+        None
+    };
+
+    let id = registry.next_id;
+    registry.next_id += 1;
+    registry.active.push(ActiveOperation {
+        id,
+        operation: operation.to_string(),
+        resources: resources.to_vec(),
+        started_at: chrono::Local::now().to_rfc3339(),
+    });
+    let names = operation_names(&registry);
+    drop(registry);
+    crate::state_bus::publish(StateUpdate::ActiveOperations(names));
+
+    Ok(OperationGuard { id, dir_lock })
+}
+
+fn operation_names(registry: &Registry) -> Vec<String> {
+    registry
+        .active
+        .iter()
+        .map(|op| op.operation.clone())
+        .collect()
+}
+
+/// 当前正在进行的操作
+pub fn active_operations() -> Vec<ActiveOperation> {
+    REGISTRY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .active
+        .clone()
+}
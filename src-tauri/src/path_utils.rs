@@ -3,6 +3,10 @@ use dirs::*;
 ///
 /// 提供跨平台兼容的路径处理方法，避免硬编码路径
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 检测到的 Antigravity 数据目录（外层 None 表示尚未检测或已失效）
+static DATA_DIR: Mutex<Option<Option<PathBuf>>> = Mutex::new(None);
 
 /// 应用程序相关路径管理器
 pub struct AppPaths;
@@ -13,19 +17,38 @@ impl AppPaths {
     /// 跨平台路径:
     /// - Windows: %APPDATA%\Antigravity\User\globalStorage\
     /// - macOS: ~/Library/Application Support/Antigravity/User/globalStorage/
-    /// - Linux: ~/.config/Antigravity/User/globalStorage/
+    /// - Linux: ~/.config/Antigravity/User/globalStorage/（另外检查 Flatpak、Snap 沙箱中的位置）
+    ///
+    /// 取第一个已存在 state.vscdb 的候选目录，都不存在时取默认位置；
+    /// 检测结果缓存到 [`AppPaths::reset_antigravity_data_dir`] 使其失效为止
     pub fn antigravity_data_dir() -> Option<PathBuf> {
-        let result = antigravity_data_dir_impl();
-
-        match &result {
-            Some(path) => {
-                let sanitized_path = sanitize_user_path(path);
-                tracing::info!("🔍 检测 Antigravity 数据目录: {}", sanitized_path);
-            }
-            None => tracing::info!("🔍 检测 Antigravity 数据目录: 未找到"),
-        }
+        DATA_DIR
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(|| {
+                let result = antigravity_data_dir_impl();
+
+                match &result {
+                    Some(path) => {
+                        let sanitized_path = sanitize_user_path(path);
+                        tracing::info!("🔍 检测 Antigravity 数据目录: {}", sanitized_path);
+                    }
+                    None => tracing::info!("🔍 检测 Antigravity 数据目录: 未找到"),
+                }
+
+                result
+            })
+            .clone()
+    }
 
-        result
+    /// 丢弃缓存的数据目录，下次调用时重新检测（安装或卸载 Antigravity 后数据目录可能变化）
+    pub fn reset_antigravity_data_dir() {
+        *DATA_DIR.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Antigravity 数据目录的所有候选位置（默认位置在前）
+    pub fn antigravity_data_dir_candidates() -> Vec<PathBuf> {
+        antigravity_data_dir_candidates_impl()
     }
 
     /// 获取 Antigravity 可执行文件路径
@@ -39,6 +62,16 @@ impl AppPaths {
     }
 }
 
+/// 取第一个已存在 state.vscdb 的候选目录，都不存在时取默认位置
+fn antigravity_data_dir_impl() -> Option<PathBuf> {
+    let candidates = antigravity_data_dir_candidates_impl();
+    candidates
+        .iter()
+        .find(|dir| dir.join("state.vscdb").exists())
+        .or_else(|| candidates.first())
+        .cloned()
+}
+
 /// 跨平台路径脱敏函数
 /// 将用户名替换为 ****，支持 Windows、macOS、Linux
 fn sanitize_user_path(path: &Path) -> String {
@@ -50,8 +83,11 @@ fn sanitize_user_path(path: &Path) -> String {
 // ----------------------------
 
 #[cfg(target_os = "windows")]
-fn antigravity_data_dir_impl() -> Option<PathBuf> {
-    config_dir().map(|path| path.join("Antigravity").join("User").join("globalStorage"))
+fn antigravity_data_dir_candidates_impl() -> Vec<PathBuf> {
+    config_dir()
+        .map(|path| path.join("Antigravity").join("User").join("globalStorage"))
+        .into_iter()
+        .collect()
 }
 
 #[cfg(target_os = "windows")]
@@ -141,8 +177,11 @@ fn get_program_files_x86_dir() -> Option<PathBuf> {
 // ----------------------------
 
 #[cfg(target_os = "macos")]
-fn antigravity_data_dir_impl() -> Option<PathBuf> {
-    data_dir().map(|path| path.join("Antigravity").join("User").join("globalStorage"))
+fn antigravity_data_dir_candidates_impl() -> Vec<PathBuf> {
+    data_dir()
+        .map(|path| path.join("Antigravity").join("User").join("globalStorage"))
+        .into_iter()
+        .collect()
 }
 
 #[cfg(target_os = "macos")]
@@ -200,13 +239,47 @@ fn get_applications_dir() -> Option<PathBuf> {
 // ----------------------------
 
 #[cfg(target_os = "linux")]
-fn antigravity_data_dir_impl() -> Option<PathBuf> {
-    // 优先使用 ~/.config，其次 ~/.local/share
-    config_dir()
-        .map(|path| path.join("Antigravity").join("User").join("globalStorage"))
-        .or_else(|| {
-            data_dir().map(|path| path.join("Antigravity").join("User").join("globalStorage"))
-        })
+fn antigravity_data_dir_candidates_impl() -> Vec<PathBuf> {
+    let global_storage = |config: PathBuf| {
+        config
+            .join("Antigravity")
+            .join("User")
+            .join("globalStorage")
+    };
+    let mut candidates = Vec::new();
+
+    // 原生安装：优先使用 ~/.config，其次 ~/.local/share
+    candidates.extend(config_dir().map(global_storage));
+    candidates.extend(data_dir().map(global_storage));
+
+    if let Some(home) = home_dir() {
+        // Flatpak：~/.var/app/<应用 ID>/config（应用 ID 不固定，按名称匹配）
+        if let Ok(entries) = std::fs::read_dir(home.join(".var").join("app")) {
+            let mut flatpak_dirs: Vec<PathBuf> = entries
+                .flatten()
+                .filter(|entry| {
+                    entry
+                        .file_name()
+                        .to_string_lossy()
+                        .to_lowercase()
+                        .contains("antigravity")
+                })
+                .map(|entry| global_storage(entry.path().join("config")))
+                .collect();
+            flatpak_dirs.sort();
+            candidates.extend(flatpak_dirs);
+        }
+
+        // Snap：~/snap/<包名>/current/.config
+        candidates.push(global_storage(
+            home.join("snap")
+                .join("antigravity")
+                .join("current")
+                .join(".config"),
+        ));
+    }
+
+    candidates
 }
 
 #[cfg(target_os = "linux")]
@@ -268,8 +341,11 @@ fn sanitize_user_path_impl(path: &Path) -> String {
 // ----------------------------
 
 #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
-fn antigravity_data_dir_impl() -> Option<PathBuf> {
-    data_dir().map(|path| path.join("Antigravity").join("User").join("globalStorage"))
+fn antigravity_data_dir_candidates_impl() -> Vec<PathBuf> {
+    data_dir()
+        .map(|path| path.join("Antigravity").join("User").join("globalStorage"))
+        .into_iter()
+        .collect()
 }
 
 #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
@@ -1,22 +1,64 @@
+use crate::error::{AgentError, AgentResult};
 use crate::path_utils::AppPaths;
 use std::path::PathBuf;
 
+/// 覆盖 Antigravity 数据目录的环境变量
+pub const DATA_DIR_ENV: &str = "ANTIGRAVITY_AGENT_DATA_DIR";
+
+/// 用户指定的数据目录：环境变量优先，其次是设置中保存的自定义目录
+fn data_dir_override() -> Option<PathBuf> {
+    let env_dir = std::env::var_os(DATA_DIR_ENV).filter(|value| !value.is_empty());
+    let dir = env_dir.map(PathBuf::from).or_else(|| {
+        crate::antigravity::path_config::get_custom_data_dir()
+            .ok()
+            .flatten()
+            .map(PathBuf::from)
+    })?;
+    Some(crate::antigravity::path_config::resolve_data_dir(&dir))
+}
+
 /// 获取Antigravity应用数据目录（跨平台）
+/// 优先使用 `ANTIGRAVITY_AGENT_DATA_DIR` 环境变量或设置中的自定义目录，其次是用户选择的安装，
+/// 再次是 WSL 模式下 Windows 上的数据目录，否则自动检测
 pub fn get_antigravity_data_dir() -> Option<PathBuf> {
-    AppPaths::antigravity_data_dir()
+    data_dir_override()
+        .or_else(super::installs::selected_data_dir)
+        .or_else(|| {
+            super::wsl::windows_mode_enabled()
+                .then(super::wsl::windows_data_dir)
+                .flatten()
+        })
+        .or_else(AppPaths::antigravity_data_dir)
 }
 
 /// 获取Antigravity状态数据库文件路径
-/// 使用自动检测的路径
 pub fn get_antigravity_db_path() -> Option<PathBuf> {
     get_antigravity_data_dir().map(|dir| dir.join("state.vscdb"))
 }
 
-/// 检查Antigravity是否安装并运行
+/// 获取当前使用的Antigravity状态数据库路径
+/// 优先使用自动检测的主路径，否则回退到其他可能的位置；
+/// 运行在 WSL 中且无法访问数据目录时返回 `DataDirUnreachable` 并说明原因
+pub fn resolve_antigravity_db_path() -> AgentResult<PathBuf> {
+    let main_path = get_antigravity_db_path();
+    if let Some(reason) =
+        super::wsl::unreachable_reason(main_path.as_deref(), data_dir_override().is_some())
+    {
+        return Err(AgentError::DataDirUnreachable(reason));
+    }
+    if let Some(path) = main_path {
+        return Ok(path);
+    }
+
+    get_all_antigravity_db_paths()
+        .into_iter()
+        .next()
+        .ok_or(AgentError::AntigravityNotFound)
+}
+
+/// 检查Antigravity是否已安装（使用缓存的检测结果，后台安装监听会定期更新）
 pub fn is_antigravity_available() -> bool {
-    get_antigravity_db_path()
-        .map(|path| path.exists())
-        .unwrap_or(false)
+    super::detection::platform_detection().available
 }
 
 /// 搜索可能的Antigravity安装位置
@@ -36,8 +78,13 @@ pub fn find_antigravity_installations() -> Vec<PathBuf> {
     possible_paths
 }
 
-/// 获取所有可能的Antigravity数据库路径
+/// 获取所有可能的Antigravity数据库路径（使用缓存的检测结果）
 pub fn get_all_antigravity_db_paths() -> Vec<PathBuf> {
+    super::detection::platform_detection().db_paths.clone()
+}
+
+/// 遍历文件系统搜索所有可能的Antigravity数据库路径
+pub(super) fn scan_antigravity_db_paths() -> Vec<PathBuf> {
     let mut db_paths = Vec::new();
 
     // 主要路径
@@ -45,6 +92,14 @@ pub fn get_all_antigravity_db_paths() -> Vec<PathBuf> {
         db_paths.push(main_path);
     }
 
+    // 其他候选数据目录（Flatpak、Snap 沙箱等）
+    for dir in AppPaths::antigravity_data_dir_candidates() {
+        let path = dir.join("state.vscdb");
+        if path.is_file() && !db_paths.contains(&path) {
+            db_paths.push(path);
+        }
+    }
+
     // 搜索其他可能的位置
     for install_dir in find_antigravity_installations() {
         if install_dir.exists() {
@@ -0,0 +1,201 @@
+//! Antigravity 安装检测缓存
+//! 数据库路径与可执行文件的检测需要遍历文件系统，结果缓存在进程内共享；
+//! 账户监听发现数据库出现/消失或用户在界面中手动刷新时调用 `refresh_platform_detection` 重新检测。
+//! 后台安装监听定期重新检测，Agent 运行期间安装或卸载 Antigravity 时更新缓存并通知前端与托盘
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::state_bus::StateUpdate;
+use crate::system_tray::TooltipUpdate;
+
+/// Antigravity 安装状态变化事件名
+pub const AVAILABILITY_CHANGED_EVENT: &str = "antigravity:availability-changed";
+
+/// 后台重新检测的间隔
+const REDETECT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Antigravity 的安装方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallFlavor {
+    /// 原生安装（安装包、系统包管理器或解压版）
+    Native,
+    Snap,
+    Flatpak,
+    AppImage,
+}
+
+impl InstallFlavor {
+    /// 根据可执行文件与数据目录的位置判断安装方式
+    pub(super) fn detect(executable: Option<&Path>, data_dir: Option<&Path>) -> Self {
+        let contains = |path: Option<&Path>, part: &str| {
+            path.is_some_and(|path| path.to_string_lossy().contains(part))
+        };
+
+        if executable.is_some_and(|path| path.extension().is_some_and(|ext| ext == "AppImage")) {
+            Self::AppImage
+        } else if contains(executable, "/snap/") || contains(data_dir, "/snap/") {
+            Self::Snap
+        } else if contains(executable, "/flatpak/") || contains(data_dir, "/.var/app/") {
+            Self::Flatpak
+        } else {
+            Self::Native
+        }
+    }
+}
+
+/// 检测结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformDetection {
+    /// 找到的 Antigravity 状态数据库路径（主路径在前）
+    pub db_paths: Vec<PathBuf>,
+    /// 自动检测到的可执行文件（不含用户自定义路径）
+    pub executable: Option<PathBuf>,
+    /// 安装方式（用户自定义的可执行文件优先）
+    pub flavor: InstallFlavor,
+    /// Antigravity 是否可用（找到状态数据库或可执行文件）
+    pub available: bool,
+    /// 检测时间
+    pub detected_at: String,
+}
+
+/// 当前缓存的检测结果（None 表示尚未检测或已失效）
+static CACHE: Mutex<Option<Arc<PlatformDetection>>> = Mutex::new(None);
+
+fn detect() -> PlatformDetection {
+    let db_paths = super::antigravity::scan_antigravity_db_paths();
+    // 用户选择了某个安装时使用与其数据目录对应的可执行文件
+    let executable = super::installs::selected_executable()
+        .or_else(crate::antigravity::starter::scan_antigravity_executable);
+    let custom_executable = crate::antigravity::path_config::get_custom_executable_path()
+        .ok()
+        .flatten()
+        .map(PathBuf::from);
+    let flavor = InstallFlavor::detect(
+        custom_executable.as_deref().or(executable.as_deref()),
+        db_paths.first().and_then(|path| path.parent()),
+    );
+
+    let available = db_paths.iter().any(|path| path.is_file())
+        || executable.is_some()
+        || custom_executable.is_some_and(|path| path.exists());
+
+    PlatformDetection {
+        db_paths,
+        executable,
+        flavor,
+        available,
+        detected_at: chrono::Local::now().to_rfc3339(),
+    }
+}
+
+/// 获取检测结果（首次调用或失效后重新检测）
+pub fn platform_detection() -> Arc<PlatformDetection> {
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.get_or_insert_with(|| Arc::new(detect())).clone()
+}
+
+/// 丢弃缓存并立即重新检测
+pub fn refresh_platform_detection() -> Arc<PlatformDetection> {
+    crate::path_utils::AppPaths::reset_antigravity_data_dir();
+    let detection = Arc::new(detect());
+    *CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some(detection.clone());
+    tracing::info!(
+        target: "platform::detect",
+        db_paths = detection.db_paths.len(),
+        executable_found = detection.executable.is_some(),
+        flavor = ?detection.flavor,
+        available = detection.available,
+        "已重新检测 Antigravity 安装"
+    );
+    detection
+}
+
+/// 重新检测，结果与缓存不同时替换缓存（不记录未变化的检测）
+fn redetect() -> Arc<PlatformDetection> {
+    let detected = detect();
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(cached) = cache.as_ref().filter(|cached| {
+        cached.db_paths == detected.db_paths
+            && cached.executable == detected.executable
+            && cached.flavor == detected.flavor
+            && cached.available == detected.available
+    }) {
+        return cached.clone();
+    }
+
+    // 安装位置变化后数据目录也可能变化
+    crate::path_utils::AppPaths::reset_antigravity_data_dir();
+    let detection = Arc::new(detected);
+    *cache = Some(detection.clone());
+    tracing::info!(
+        target: "platform::detect",
+        db_paths = detection.db_paths.len(),
+        executable_found = detection.executable.is_some(),
+        available = detection.available,
+        "Antigravity 安装位置发生变化，已更新检测结果"
+    );
+    detection
+}
+
+/// 安装状态变化事件数据
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailabilityChangedEvent {
+    pub available: bool,
+    /// 主数据库路径
+    pub db_path: Option<PathBuf>,
+    /// 自动检测到的可执行文件
+    pub executable: Option<PathBuf>,
+}
+
+/// 启动后台安装监听：定期重新检测，可用状态变化时推送事件并更新托盘与状态总线
+pub fn spawn_install_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(REDETECT_INTERVAL);
+        let mut last_available: Option<bool> = None;
+        loop {
+            ticker.tick().await;
+
+            let detection = match tokio::task::spawn_blocking(redetect).await {
+                Ok(detection) => detection,
+                Err(e) => {
+                    tracing::error!(target: "platform::detect", error = %e, "重新检测任务异常");
+                    continue;
+                }
+            };
+            let available = detection.available;
+            crate::state_bus::publish(StateUpdate::AntigravityAvailable(available));
+            crate::system_tray::update_tray_tooltip(
+                &app,
+                TooltipUpdate::AntigravityAvailable(available),
+            );
+
+            // 首次检测只记录基线
+            if last_available
+                .replace(available)
+                .is_none_or(|previous| previous == available)
+            {
+                continue;
+            }
+            tracing::info!(
+                target: "platform::detect",
+                available,
+                "Antigravity 可用状态发生变化"
+            );
+            let event = AvailabilityChangedEvent {
+                available,
+                db_path: detection.db_paths.first().cloned(),
+                executable: detection.executable.clone(),
+            };
+            if let Err(e) = app.emit(AVAILABILITY_CHANGED_EVENT, &event) {
+                tracing::error!(target: "platform::detect", "推送安装状态变化事件失败: {}", e);
+            }
+        }
+    });
+}
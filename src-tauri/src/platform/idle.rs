@@ -0,0 +1,92 @@
+//! 系统空闲时间
+//! 读取用户最后一次键盘、鼠标输入至今的时间，定时任务据此在用户使用电脑时推迟备份等耗时操作。
+//! macOS 通过 `ioreg` 读取 HIDIdleTime，Windows 调用 GetLastInputInfo，
+//! Linux 依次尝试 `xprintidle` 与 GNOME Mutter 的 IdleMonitor；无法获取时返回 None
+
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// 运行命令并返回标准输出（失败时返回 None）
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    Command::new(program)
+        .args(args)
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// 解析 `ioreg -c IOHIDSystem` 输出中的 `"HIDIdleTime" = <纳秒>`
+fn parse_hid_idle_time(output: &str) -> Option<Duration> {
+    output.lines().find_map(|line| {
+        let (_, value) = line.split_once("\"HIDIdleTime\" =")?;
+        value.trim().parse().ok().map(Duration::from_nanos)
+    })
+}
+
+/// 解析 Mutter IdleMonitor 的返回值 `(uint64 <毫秒>,)`
+fn parse_mutter_idle_time(output: &str) -> Option<Duration> {
+    let (_, value) = output.split_once("uint64")?;
+    let millis: String = value
+        .trim_start()
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    millis.parse().ok().map(Duration::from_millis)
+}
+
+#[cfg(windows)]
+fn windows_idle_time() -> Option<Duration> {
+    use windows::Win32::System::SystemInformation::GetTickCount;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    // SAFETY: info 为有效的 LASTINPUTINFO 且 cbSize 已正确设置
+    if !unsafe { GetLastInputInfo(&mut info) }.as_bool() {
+        return None;
+    }
+    // GetTickCount 约 49.7 天回绕一次，按无符号减法计算差值
+    let elapsed = unsafe { GetTickCount() }.wrapping_sub(info.dwTime);
+    Some(Duration::from_millis(u64::from(elapsed)))
+}
+
+#[cfg(not(windows))]
+fn windows_idle_time() -> Option<Duration> {
+    None
+}
+
+/// 用户已空闲的时间，无法获取时返回 None
+pub fn idle_duration() -> Option<Duration> {
+    if cfg!(target_os = "macos") {
+        command_output("ioreg", &["-c", "IOHIDSystem", "-d", "4"])
+            .as_deref()
+            .and_then(parse_hid_idle_time)
+    } else if cfg!(windows) {
+        windows_idle_time()
+    } else {
+        command_output("xprintidle", &[])
+            .and_then(|output| output.trim().parse().ok())
+            .map(Duration::from_millis)
+            .or_else(|| {
+                command_output(
+                    "gdbus",
+                    &[
+                        "call",
+                        "--session",
+                        "--dest",
+                        "org.gnome.Mutter.IdleMonitor",
+                        "--object-path",
+                        "/org/gnome/Mutter/IdleMonitor/Core",
+                        "--method",
+                        "org.gnome.Mutter.IdleMonitor.GetIdletime",
+                    ],
+                )
+                .as_deref()
+                .and_then(parse_mutter_idle_time)
+            })
+    }
+}
@@ -0,0 +1,92 @@
+//! 多个 Antigravity 安装的选择
+//! 同一台机器上可能同时存在原生安装、Flatpak、Snap 等多份 Antigravity，各自使用独立的数据目录。
+//! 这里列出检测到的安装（数据目录、对应的可执行文件与版本），用户选中的安装记录在路径配置中，
+//! 之后备份、恢复与启动都使用该安装
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::detection::InstallFlavor;
+use crate::path_utils::AppPaths;
+
+/// 检测到的一份 Antigravity 安装
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AntigravityInstall {
+    /// 数据目录（选择安装时作为标识）
+    pub data_dir: PathBuf,
+    pub db_path: PathBuf,
+    /// 与数据目录对应的可执行文件
+    pub executable: Option<PathBuf>,
+    pub version: Option<String>,
+    pub flavor: InstallFlavor,
+    /// 是否为当前使用的安装
+    pub active: bool,
+    /// 是否为用户选择的安装
+    pub selected: bool,
+}
+
+/// 与数据目录安装方式相同的可执行文件（原生数据目录也接受 AppImage）
+pub(super) fn executable_for_data_dir(data_dir: &Path) -> Option<PathBuf> {
+    let flavor = InstallFlavor::detect(None, Some(data_dir));
+    let candidates: Vec<PathBuf> = AppPaths::antigravity_executable_paths()
+        .into_iter()
+        .filter(|path| path.exists())
+        .collect();
+    let flavor_of = |path: &PathBuf| InstallFlavor::detect(Some(path), None);
+
+    candidates
+        .iter()
+        .find(|path| flavor_of(path) == flavor)
+        .or_else(|| {
+            candidates.iter().find(|path| {
+                flavor == InstallFlavor::Native && flavor_of(path) == InstallFlavor::AppImage
+            })
+        })
+        .cloned()
+}
+
+/// 用户选择的安装的数据目录（数据库已不存在时忽略）
+pub(super) fn selected_data_dir() -> Option<PathBuf> {
+    crate::antigravity::path_config::get_selected_install()
+        .ok()
+        .flatten()
+        .map(PathBuf::from)
+        .filter(|dir| dir.join("state.vscdb").is_file())
+}
+
+/// 用户选择的安装对应的可执行文件
+pub fn selected_executable() -> Option<PathBuf> {
+    selected_data_dir().and_then(|dir| executable_for_data_dir(&dir))
+}
+
+/// 列出检测到的所有安装（只包含已有状态数据库的数据目录）
+pub fn list_installs() -> Vec<AntigravityInstall> {
+    let active_dir = super::get_antigravity_data_dir();
+    let selected_dir = selected_data_dir();
+
+    let mut installs: Vec<AntigravityInstall> = Vec::new();
+    for db_path in super::get_all_antigravity_db_paths() {
+        let Some(data_dir) = db_path.parent().map(Path::to_path_buf) else {
+            continue;
+        };
+        if !db_path.is_file() || installs.iter().any(|i| i.data_dir == data_dir) {
+            continue;
+        }
+
+        let executable = executable_for_data_dir(&data_dir);
+        let version = executable
+            .as_deref()
+            .and_then(crate::antigravity::version::read_version);
+        installs.push(AntigravityInstall {
+            flavor: InstallFlavor::detect(executable.as_deref(), Some(&data_dir)),
+            active: active_dir.as_ref() == Some(&data_dir),
+            selected: selected_dir.as_ref() == Some(&data_dir),
+            data_dir,
+            db_path,
+            executable,
+            version,
+        });
+    }
+    installs
+}
@@ -0,0 +1,150 @@
+//! Windows 任务栏跳转列表
+//! 提供"备份当前账户"、"重启 Antigravity"任务及最近使用的账户，
+//! 点击后以命令行模式调用 Agent 自身（与托盘、HTTP 接口共用同一套后端逻辑）
+
+/// 跳转列表中最多显示的最近账户数
+const MAX_RECENT_ACCOUNTS: usize = 5;
+
+/// 跳转列表项
+#[cfg_attr(not(windows), allow(dead_code))]
+struct JumpItem {
+    title: String,
+    args: String,
+}
+
+impl JumpItem {
+    fn new(title: impl Into<String>, args: impl Into<String>) -> Self {
+        let mut args = args.into();
+        if crate::directories::is_portable_mode() {
+            args.push_str(" --portable");
+        }
+        Self {
+            title: title.into(),
+            args,
+        }
+    }
+}
+
+/// 最近使用的账户（置顶在前，其余按最近使用排序）
+fn recent_accounts() -> Vec<String> {
+    crate::account_metadata::recent_accounts(&crate::antigravity::backup::list_backup_emails())
+        .into_iter()
+        .map(|account| account.email)
+        .take(MAX_RECENT_ACCOUNTS)
+        .collect()
+}
+
+/// 在后台线程中重建跳转列表（非 Windows 平台无操作）
+pub fn refresh_jump_list() {
+    if !cfg!(windows) {
+        return;
+    }
+
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            tracing::warn!(target: "platform::jump_list", error = %e, "获取可执行文件路径失败，跳过跳转列表更新");
+            return;
+        }
+    };
+
+    std::thread::spawn(move || {
+        let tasks = vec![
+            JumpItem::new("备份当前账户", "backup"),
+            JumpItem::new("重启 Antigravity", "restart"),
+        ];
+        let recent: Vec<JumpItem> = recent_accounts()
+            .into_iter()
+            .map(|email| {
+                JumpItem::new(
+                    crate::utils::email_mask::mask_email(&email),
+                    format!("switch --email \"{email}\""),
+                )
+            })
+            .collect();
+
+        match imp::apply(&exe, &tasks, &recent) {
+            Ok(()) => {
+                tracing::debug!(target: "platform::jump_list", recent = recent.len(), "跳转列表已更新")
+            }
+            Err(e) => tracing::warn!(target: "platform::jump_list", error = %e, "更新跳转列表失败"),
+        }
+    });
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::JumpItem;
+    use std::path::Path;
+    use windows::core::{Interface, Result, HSTRING};
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::StructuredStorage::PROPVARIANT;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER,
+        COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW, ShellLink,
+    };
+
+    fn shell_link(exe: &HSTRING, item: &JumpItem) -> Result<IShellLinkW> {
+        unsafe {
+            let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+            link.SetPath(exe)?;
+            link.SetArguments(&HSTRING::from(item.args.as_str()))?;
+            link.SetIconLocation(exe, 0)?;
+
+            let store: IPropertyStore = link.cast()?;
+            store.SetValue(&PKEY_Title, &PROPVARIANT::from(item.title.as_str()))?;
+            store.Commit()?;
+            Ok(link)
+        }
+    }
+
+    fn collection(exe: &HSTRING, items: &[JumpItem]) -> Result<IObjectArray> {
+        unsafe {
+            let collection: IObjectCollection =
+                CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+            for item in items {
+                collection.AddObject(&shell_link(exe, item)?)?;
+            }
+            collection.cast()
+        }
+    }
+
+    pub(super) fn apply(exe: &Path, tasks: &[JumpItem], recent: &[JumpItem]) -> Result<()> {
+        let exe = HSTRING::from(exe);
+
+        unsafe {
+            CoInitializeEx(None, COINIT_APARTMENTTHREADED).ok()?;
+            let result = (|| {
+                let list: ICustomDestinationList =
+                    CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+                let mut min_slots = 0u32;
+                let _removed: IObjectArray = list.BeginList(&mut min_slots)?;
+                if !recent.is_empty() {
+                    list.AppendCategory(&HSTRING::from("最近账户"), &collection(&exe, recent)?)?;
+                }
+                list.AddUserTasks(&collection(&exe, tasks)?)?;
+                list.CommitList()
+            })();
+            CoUninitialize();
+            result
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod imp {
+    use super::JumpItem;
+
+    pub(super) fn apply(
+        _exe: &std::path::Path,
+        _tasks: &[JumpItem],
+        _recent: &[JumpItem],
+    ) -> Result<(), String> {
+        Ok(())
+    }
+}
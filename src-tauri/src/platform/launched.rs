@@ -0,0 +1,84 @@
+//! Agent 启动的 Antigravity 进程登记
+//! 启动后不再持有的子进程句柄在 Linux 上退出后会成为僵尸进程，且丢失 PID。
+//! 这里接管句柄：每个子进程由后台线程等待并回收退出状态，进程运行期间 PID 登记在表中，
+//! 供状态快照与进程命令查询。
+//! 注意：macOS 上通过 `open` 启动时登记的是 `open` 进程本身，它会在拉起应用后很快退出
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::process::Child;
+use std::sync::Mutex;
+
+use crate::state_bus::StateUpdate;
+
+/// 运行中的已启动进程
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchedProcess {
+    pub pid: u32,
+    /// 启动的程序
+    pub program: String,
+    pub launched_at: String,
+}
+
+static LAUNCHED: Mutex<BTreeMap<u32, LaunchedProcess>> = Mutex::new(BTreeMap::new());
+
+/// 接管刚启动的子进程：登记 PID，并在后台线程中等待其退出后回收
+pub fn track(mut child: Child, program: impl Into<String>) -> u32 {
+    let pid = child.id();
+    let program = program.into();
+    tracing::info!(target: "platform::launched", pid, program = %program, "已启动 Antigravity 进程");
+
+    let pids = {
+        let mut launched = LAUNCHED.lock().unwrap_or_else(|e| e.into_inner());
+        launched.insert(
+            pid,
+            LaunchedProcess {
+                pid,
+                program,
+                launched_at: chrono::Local::now().to_rfc3339(),
+            },
+        );
+        launched.keys().copied().collect()
+    };
+    crate::state_bus::publish(StateUpdate::LaunchedPids(pids));
+
+    let spawned = std::thread::Builder::new()
+        .name(format!("reap-{pid}"))
+        .spawn(move || {
+            match child.wait() {
+                Ok(status) => {
+                    tracing::info!(target: "platform::launched", pid, status = %status, "Antigravity 进程已退出")
+                }
+                Err(e) => {
+                    tracing::warn!(target: "platform::launched", pid, error = %e, "等待 Antigravity 进程退出失败")
+                }
+            }
+            untrack(pid);
+        });
+    if let Err(e) = spawned {
+        // 句柄已随线程闭包释放，无法再跟踪该进程
+        tracing::warn!(target: "platform::launched", pid, error = %e, "创建进程回收线程失败");
+        untrack(pid);
+    }
+    pid
+}
+
+fn untrack(pid: u32) {
+    let pids = {
+        let mut launched = LAUNCHED.lock().unwrap_or_else(|e| e.into_inner());
+        launched.remove(&pid);
+        launched.keys().copied().collect()
+    };
+    crate::state_bus::publish(StateUpdate::LaunchedPids(pids));
+}
+
+/// 当前仍在运行的已启动进程（按 PID 排序）
+pub fn launched_processes() -> Vec<LaunchedProcess> {
+    LAUNCHED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect()
+}
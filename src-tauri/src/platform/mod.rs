@@ -2,8 +2,19 @@
 //! Provides cross-platform functionality for interacting with Antigravity
 
 pub mod antigravity;
+pub mod detection;
+pub mod idle;
+pub mod installs;
+pub mod jump_list;
+pub mod launched;
+pub mod power;
 pub mod process;
+pub mod wait;
+pub mod wsl;
 
 // Re-export commonly used types and functions
 pub use antigravity::*;
+pub use detection::{
+    platform_detection, refresh_platform_detection, spawn_install_watcher, PlatformDetection,
+};
 pub use process::*;
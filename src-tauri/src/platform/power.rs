@@ -0,0 +1,109 @@
+//! 电源状态
+//! 判断笔记本是否在使用电池供电及剩余电量，定时任务据此在低电量时跳过或推迟备份等耗时操作。
+//! Linux 读取 `/sys/class/power_supply`，macOS 解析 `pmset -g batt`，Windows 调用 GetSystemPowerStatus；
+//! 台式机或无法获取时返回 None
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// 电源状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerStatus {
+    /// 正在使用电池供电（未接通电源）
+    pub on_battery: bool,
+    /// 剩余电量百分比
+    pub battery_percent: Option<u8>,
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+/// 读取 Linux 的 power_supply 信息（多块电池时取电量最低的一块）
+fn linux_power_status() -> Option<PowerStatus> {
+    let mut has_battery = false;
+    let mut discharging = false;
+    let mut ac_online = false;
+    let mut percent: Option<u8> = None;
+
+    for entry in fs::read_dir("/sys/class/power_supply").ok()?.flatten() {
+        let dir = entry.path();
+        match read_trimmed(&dir.join("type")).as_deref() {
+            Some("Battery") => {
+                // 外设（鼠标、键盘）的电池不计入
+                if read_trimmed(&dir.join("scope")).as_deref() == Some("Device") {
+                    continue;
+                }
+                has_battery = true;
+                discharging |= read_trimmed(&dir.join("status")).as_deref() == Some("Discharging");
+                if let Some(capacity) =
+                    read_trimmed(&dir.join("capacity")).and_then(|c| c.parse().ok())
+                {
+                    percent = Some(percent.map_or(capacity, |p: u8| p.min(capacity)));
+                }
+            }
+            Some("Mains") => {
+                ac_online |= read_trimmed(&dir.join("online")).as_deref() == Some("1");
+            }
+            _ => {}
+        }
+    }
+
+    has_battery.then_some(PowerStatus {
+        on_battery: discharging && !ac_online,
+        battery_percent: percent,
+    })
+}
+
+/// 解析 `pmset -g batt` 的输出（`Now drawing from 'Battery Power'` 与 `-InternalBattery-0 ... 85%; discharging; ...`）
+fn parse_pmset(output: &str) -> Option<PowerStatus> {
+    let battery_line = output
+        .lines()
+        .find(|line| line.contains("InternalBattery"))?;
+    let battery_percent = battery_line
+        .split_whitespace()
+        .find_map(|part| part.strip_suffix("%;")?.parse().ok());
+    Some(PowerStatus {
+        on_battery: output.contains("'Battery Power'"),
+        battery_percent,
+    })
+}
+
+#[cfg(windows)]
+fn windows_power_status() -> Option<PowerStatus> {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status = SYSTEM_POWER_STATUS::default();
+    // SAFETY: status 为有效的 SYSTEM_POWER_STATUS
+    unsafe { GetSystemPowerStatus(&mut status) }.ok()?;
+    // BatteryFlag 128 表示没有电池
+    if status.BatteryFlag == 128 {
+        return None;
+    }
+    Some(PowerStatus {
+        on_battery: status.ACLineStatus == 0,
+        battery_percent: (status.BatteryLifePercent <= 100).then_some(status.BatteryLifePercent),
+    })
+}
+
+#[cfg(not(windows))]
+fn windows_power_status() -> Option<PowerStatus> {
+    None
+}
+
+/// 当前电源状态，没有电池或无法获取时返回 None
+pub fn power_status() -> Option<PowerStatus> {
+    if cfg!(target_os = "macos") {
+        Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| parse_pmset(&String::from_utf8_lossy(&output.stdout)))
+    } else if cfg!(windows) {
+        windows_power_status()
+    } else {
+        linux_power_status()
+    }
+}
@@ -1,79 +1,344 @@
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use sysinfo::Pid;
+
+use crate::error::{AgentError, AgentResult};
+
+/// 进程信息
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessEntry {
+    pub pid: u32,
+    pub name: String,
+}
+
+impl fmt::Display for ProcessEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (PID: {})", self.name, self.pid)
+    }
+}
+
+/// 关闭 Antigravity 进程的结果（至少关闭了一个进程）
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KillReport {
+    /// 已关闭的进程
+    pub killed: Vec<ProcessEntry>,
+    /// 关闭失败的进程
+    pub failed: Vec<ProcessEntry>,
+}
+
+impl fmt::Display for KillReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "已成功关闭Antigravity进程: {}",
+            join_entries(&self.killed)
+        )?;
+        if !self.failed.is_empty() {
+            write!(f, "（关闭失败: {}）", join_entries(&self.failed))?;
+        }
+        Ok(())
+    }
+}
+
+fn join_entries(entries: &[ProcessEntry]) -> String {
+    entries
+        .iter()
+        .map(ProcessEntry::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// 关闭Antigravity进程 - 使用sysinfo库实现跨平台统一处理
-pub fn kill_antigravity_processes() -> Result<String, String> {
+///
+/// 未找到进程时返回 `ProcessNotFound`，找到的进程全部关闭失败时返回 `ProcessKillFailed`
+pub fn kill_antigravity_processes() -> AgentResult<KillReport> {
     tracing::info!("🔍 开始搜索并关闭 Antigravity 进程");
 
+    // WSL 模式下关闭 Windows 上的进程
+    if super::wsl::windows_mode_enabled() {
+        return super::wsl::kill_windows_processes();
+    }
+
     // 使用sysinfo库获取所有进程
     let mut system = sysinfo::System::new_all();
     system.refresh_all();
 
-    let mut killed_processes = Vec::new();
+    // 先找出所有目标进程，再统一关闭（关闭前执行 pre-kill 钩子）
+    let targets = find_antigravity_processes(&system);
+    kill_matched(&system, targets)
+}
 
-    // 定义需要关闭的进程模式（按优先级排序）
-    let process_patterns = get_antigravity_process_patterns();
+/// 关闭找到的进程，没有进程时返回 `ProcessNotFound`
+fn kill_matched(system: &sysinfo::System, targets: Vec<MatchedProcess>) -> AgentResult<KillReport> {
+    if targets.is_empty() {
+        tracing::info!("ℹ️ 未找到匹配的 Antigravity 进程");
+        return Err(AgentError::ProcessNotFound);
+    }
 
-    for (pid, process) in system.processes() {
-        let process_name = process.name();
-        let process_cmd = process.cmd().join(" ");
-
-        // 检查进程名或命令行是否匹配任何模式
-        if matches_antigravity_process(process_name, &process_cmd, &process_patterns) {
-            tracing::info!("🎯 找到目标进程: {} (PID: {})", process_name, pid);
-            tracing::info!("📝 命令行: {}", process_cmd);
-
-            // 尝试终止进程
-            if process.kill() {
-                killed_processes.push(format!("{} (PID: {})", process_name, pid));
-                tracing::info!("✅ 成功终止进程: {} (PID: {})", process_name, pid);
-            } else {
-                tracing::warn!("⚠️ 终止进程失败: {} (PID: {})", process_name, pid);
-
-                // 尝试多次终止（如果第一次失败）
-                if process.kill() {
-                    killed_processes.push(format!("{} (PID: {} - 强制)", process_name, pid));
-                    tracing::info!("✅ 强制终止进程: {} (PID: {})", process_name, pid);
-                } else {
-                    tracing::error!("❌ 强制终止也失败: {} (PID: {})", process_name, pid);
-                }
-            }
+    let pids: Vec<u32> = targets.iter().map(|target| target.pid).collect();
+    crate::hooks::run_hooks(
+        crate::hooks::HookEvent::PreKill,
+        serde_json::json!({ "pids": pids }),
+    )?;
+
+    let mut report = KillReport::default();
+    for target in targets {
+        let entry = ProcessEntry {
+            pid: target.pid,
+            name: target.name,
+        };
+        let Some(process) = system.process(Pid::from_u32(target.pid)) else {
+            continue;
+        };
+
+        tracing::info!("🎯 找到目标进程: {}", entry);
+        tracing::info!("📝 命令行: {}", target.command);
+
+        // 第一次失败时再尝试一次
+        if process.kill() || process.kill() {
+            tracing::info!("✅ 成功终止进程: {}", entry);
+            report.killed.push(entry);
+        } else {
+            tracing::error!("❌ 终止进程失败: {}", entry);
+            report.failed.push(entry);
         }
     }
 
-    if killed_processes.is_empty() {
-        tracing::info!("ℹ️ 未找到匹配的 Antigravity 进程");
-        tracing::info!("🔍 搜索的进程模式: {:?}", process_patterns);
-        Err("未找到Antigravity进程".to_string())
-    } else {
-        let success_msg = format!("已成功关闭Antigravity进程: {}", killed_processes.join(", "));
-        tracing::info!("🎉 {}", success_msg);
-        Ok(success_msg)
+    if report.killed.is_empty() {
+        return Err(AgentError::ProcessKillFailed(join_entries(&report.failed)));
     }
+
+    tracing::info!("🎉 {}", report);
+    Ok(report)
 }
 
 /// 检查 Antigravity 进程是否正在运行（使用 sysinfo）
 pub fn is_antigravity_running() -> bool {
     tracing::debug!("🔍 检查 Antigravity 进程是否运行");
 
+    if super::wsl::windows_mode_enabled() {
+        return !super::wsl::windows_processes().is_empty();
+    }
+
     let mut system = sysinfo::System::new_all();
     system.refresh_all();
 
-    let process_patterns = get_antigravity_process_patterns();
-
-    for (pid, process) in system.processes() {
-        let process_name = process.name();
-        let process_cmd = process.cmd().join(" ");
-
-        if matches_antigravity_process(process_name, &process_cmd, &process_patterns) {
+    match find_antigravity_processes(&system).first() {
+        Some(process) => {
             tracing::debug!(
                 "✅ 发现运行中的 Antigravity 进程: {} (PID: {})",
-                process_name,
-                pid
+                process.name,
+                process.pid
             );
-            return true;
+            true
+        }
+        None => {
+            tracing::debug!("ℹ️ 未发现运行中的 Antigravity 进程");
+            false
+        }
+    }
+}
+
+/// 列出会被关闭的 Antigravity 进程（不关闭，用于预览）
+pub fn list_matching_processes() -> Vec<MatchedProcess> {
+    let mut system = sysinfo::System::new_all();
+    system.refresh_all();
+    find_antigravity_processes(&system)
+}
+
+/// 匹配到的 Antigravity 进程
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchedProcess {
+    pub pid: u32,
+    pub name: String,
+    /// 可执行文件路径（无权限读取时为 None）
+    pub exe: Option<PathBuf>,
+    pub command: String,
+    /// 可执行文件位于检测到的安装目录内；为 false 时无法确定安装目录或进程路径，仅按名称匹配
+    pub in_install_dir: bool,
+}
+
+/// Antigravity 安装目录（用户自定义的可执行文件优先，其次自动检测）
+///
+/// macOS 取整个 .app 包，其他平台取可执行文件所在目录。
+/// AppImage 运行时挂载到临时目录、snap/flatpak 通过包装程序启动，这些情况无法确定安装目录，返回 None
+fn install_root() -> Option<PathBuf> {
+    let executable = crate::antigravity::path_config::get_custom_executable_path()
+        .ok()
+        .flatten()
+        .map(PathBuf::from)
+        .filter(|path| path.exists())
+        .or_else(crate::antigravity::starter::detect_antigravity_executable)?;
+    install_root_of(executable)
+}
+
+/// 可执行文件所在的安装目录（规则同 [`install_root`]）
+fn install_root_of(executable: PathBuf) -> Option<PathBuf> {
+    let executable = executable.canonicalize().unwrap_or(executable);
+
+    if let Some(bundle) = executable
+        .ancestors()
+        .find(|path| path.extension().is_some_and(|ext| ext == "app"))
+    {
+        return Some(bundle.to_path_buf());
+    }
+
+    let stem = executable.file_stem()?.to_string_lossy().to_lowercase();
+    if executable.extension().is_some_and(|ext| ext == "AppImage")
+        || !(stem.contains("antigravity") || stem.contains("electron"))
+    {
+        return None;
+    }
+    executable.parent().map(Path::to_path_buf)
+}
+
+/// 查找属于当前 Antigravity 安装的进程
+///
+/// 进程名/命令行需要匹配 Antigravity 模式；能确定安装目录时，还要求可执行文件位于安装目录内，
+/// 避免误关命令行中恰好包含 "Antigravity" 的其他程序。Agent 自身始终排除
+fn find_antigravity_processes(system: &sysinfo::System) -> Vec<MatchedProcess> {
+    find_processes_in(system, install_root().as_deref())
+}
+
+/// 查找属于指定安装目录的 Antigravity 进程（无法确定安装目录时只按名称匹配）
+fn find_processes_in(system: &sysinfo::System, root: Option<&Path>) -> Vec<MatchedProcess> {
+    let own_pid = std::process::id();
+    let process_patterns = get_antigravity_process_patterns();
+
+    let mut matched: Vec<MatchedProcess> = system
+        .processes()
+        .iter()
+        .filter(|(pid, _)| pid.as_u32() != own_pid)
+        .filter_map(|(pid, process)| {
+            let command = process.cmd().join(" ");
+            if !matches_antigravity_process(process.name(), &command, &process_patterns) {
+                return None;
+            }
+
+            let exe = process
+                .exe()
+                .map(|path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+            let in_install_dir = match (root, &exe) {
+                (Some(root), Some(exe)) if !exe.starts_with(root) => {
+                    tracing::debug!(
+                        target: "platform::process",
+                        pid = pid.as_u32(),
+                        exe = %exe.display(),
+                        "跳过安装目录之外的同名进程"
+                    );
+                    return None;
+                }
+                (Some(_), Some(_)) => true,
+                _ => false,
+            };
+
+            Some(MatchedProcess {
+                pid: pid.as_u32(),
+                name: process.name().to_string(),
+                exe,
+                command,
+                in_install_dir,
+            })
+        })
+        .collect();
+    matched.sort_by_key(|process| process.pid);
+    matched
+}
+
+/// 确保 Antigravity 未在运行（恢复、清除数据库前调用）
+///
+/// 正在运行时：`close_if_running` 为 true 则关闭并等待退出，否则返回 `AntigravityRunning`
+pub fn ensure_antigravity_stopped(close_if_running: bool) -> AgentResult<()> {
+    if !is_antigravity_running() {
+        return Ok(());
+    }
+    if !close_if_running {
+        tracing::warn!(target: "platform::process", "Antigravity 正在运行，拒绝写入数据库");
+        return Err(AgentError::AntigravityRunning);
+    }
+
+    match kill_antigravity_processes() {
+        Ok(_) | Err(AgentError::ProcessNotFound) => {}
+        Err(e) => return Err(e),
+    }
+    wait_until_stopped(is_antigravity_running)
+}
+
+/// 等待进程退出，超过设置的等待时间返回 `AntigravityRunning`
+fn wait_until_stopped(is_running: impl Fn() -> bool) -> AgentResult<()> {
+    let deadline = Instant::now() + super::wait::load_settings().process_exit_timeout();
+    while is_running() {
+        if Instant::now() >= deadline {
+            tracing::error!(target: "platform::process", "等待 Antigravity 退出超时");
+            return Err(AgentError::AntigravityRunning);
         }
+        std::thread::sleep(Duration::from_millis(250));
     }
+    tracing::info!(target: "platform::process", "Antigravity 已关闭");
+    Ok(())
+}
 
-    tracing::debug!("ℹ️ 未发现运行中的 Antigravity 进程");
-    false
+/// 使用这些数据目录的 Antigravity 进程（按各数据目录对应的安装目录匹配，无法确定时按名称匹配所有实例）
+fn processes_for_data_dirs(system: &sysinfo::System, data_dirs: &[PathBuf]) -> Vec<MatchedProcess> {
+    let mut roots: Vec<Option<PathBuf>> = Vec::new();
+    for data_dir in data_dirs {
+        let root = super::installs::executable_for_data_dir(data_dir).and_then(install_root_of);
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+
+    let mut matched: Vec<MatchedProcess> = Vec::new();
+    for root in &roots {
+        for process in find_processes_in(system, root.as_deref()) {
+            if !matched.iter().any(|m| m.pid == process.pid) {
+                matched.push(process);
+            }
+        }
+    }
+    matched.sort_by_key(|process| process.pid);
+    matched
+}
+
+/// 确保使用这些数据目录的 Antigravity 实例都未在运行（写入当前使用的安装以外的数据目录前调用）
+///
+/// 正在运行时：`close_if_running` 为 true 则关闭并等待退出，否则返回 `AntigravityRunning`
+pub fn ensure_installs_stopped(data_dirs: &[PathBuf], close_if_running: bool) -> AgentResult<()> {
+    // WSL 模式下无法区分 Windows 上的安装，按所有进程处理
+    if super::wsl::windows_mode_enabled() {
+        return ensure_antigravity_stopped(close_if_running);
+    }
+
+    let running = || {
+        let mut system = sysinfo::System::new_all();
+        system.refresh_all();
+        let processes = processes_for_data_dirs(&system, data_dirs);
+        (system, processes)
+    };
+    let (system, processes) = running();
+    if processes.is_empty() {
+        return Ok(());
+    }
+    if !close_if_running {
+        tracing::warn!(
+            target: "platform::process",
+            pids = ?processes.iter().map(|p| p.pid).collect::<Vec<_>>(),
+            "目标数据目录的 Antigravity 实例正在运行，拒绝写入数据库"
+        );
+        return Err(AgentError::AntigravityRunning);
+    }
+
+    match kill_matched(&system, processes) {
+        Ok(_) | Err(AgentError::ProcessNotFound) => {}
+        Err(e) => return Err(e),
+    }
+    wait_until_stopped(|| !running().1.is_empty())
 }
 
 /// 获取 Antigravity 进程匹配模式
@@ -0,0 +1,168 @@
+//! 条件等待
+//! 关闭 Antigravity 后等待进程真正退出、写入数据库前等待数据库写锁释放，
+//! 代替固定时长的休眠；超时时间可在设置中调整，实际等待时长会写入操作结果
+
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::error::{AgentError, AgentResult};
+
+/// 两次检查之间的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 超时设置的上限（2 分钟）
+const MAX_TIMEOUT_MS: u64 = 120_000;
+
+/// 等待超时设置（毫秒）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WaitSettings {
+    /// 关闭 Antigravity 后等待进程退出的最长时间
+    pub process_exit_timeout_ms: u64,
+    /// 等待 Antigravity 数据库写锁释放的最长时间
+    pub db_unlock_timeout_ms: u64,
+}
+
+impl Default for WaitSettings {
+    fn default() -> Self {
+        Self {
+            process_exit_timeout_ms: 10_000,
+            db_unlock_timeout_ms: 5_000,
+        }
+    }
+}
+
+impl WaitSettings {
+    /// 校验超时设置
+    pub fn validate(&self) -> AgentResult<()> {
+        if self.process_exit_timeout_ms > MAX_TIMEOUT_MS
+            || self.db_unlock_timeout_ms > MAX_TIMEOUT_MS
+        {
+            return Err(AgentError::InvalidInput(format!(
+                "等待超时不能超过 {} 秒",
+                MAX_TIMEOUT_MS / 1000
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn process_exit_timeout(&self) -> Duration {
+        Duration::from_millis(self.process_exit_timeout_ms)
+    }
+
+    pub fn db_unlock_timeout(&self) -> Duration {
+        Duration::from_millis(self.db_unlock_timeout_ms)
+    }
+}
+
+/// 从设置文件读取当前的等待超时设置
+pub fn load_settings() -> WaitSettings {
+    crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file())
+        .wait_settings
+}
+
+/// 一次等待的结果
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitOutcome {
+    /// 实际等待的时长
+    pub elapsed_ms: u64,
+    /// 是否等待超时（条件仍未满足）
+    pub timed_out: bool,
+}
+
+impl fmt::Display for WaitOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.timed_out {
+            write!(f, "等待超时 {}ms", self.elapsed_ms)
+        } else {
+            write!(f, "等待 {}ms", self.elapsed_ms)
+        }
+    }
+}
+
+/// 在阻塞线程池中反复检查条件，直到满足或超时
+async fn wait_until<F>(timeout: Duration, condition: F) -> WaitOutcome
+where
+    F: Fn() -> bool + Send + Sync + 'static,
+{
+    let condition = std::sync::Arc::new(condition);
+    let start = Instant::now();
+    loop {
+        let check = condition.clone();
+        let satisfied = tokio::task::spawn_blocking(move || check())
+            .await
+            .unwrap_or(false);
+        let elapsed = start.elapsed();
+        if satisfied || elapsed >= timeout {
+            return WaitOutcome {
+                elapsed_ms: elapsed.as_millis() as u64,
+                timed_out: !satisfied,
+            };
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// 等待 Antigravity 进程全部退出
+pub async fn wait_for_process_exit(timeout: Duration) -> WaitOutcome {
+    let outcome = wait_until(timeout, || !super::is_antigravity_running()).await;
+    if outcome.timed_out {
+        tracing::warn!(target: "platform::wait", elapsed_ms = outcome.elapsed_ms, "等待 Antigravity 退出超时");
+    } else {
+        tracing::debug!(target: "platform::wait", elapsed_ms = outcome.elapsed_ms, "Antigravity 已退出");
+    }
+    outcome
+}
+
+/// 数据库当前能否获得写锁（文件不存在视为未锁定）
+fn db_unlocked(path: &Path) -> bool {
+    if !path.exists() {
+        return true;
+    }
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        .and_then(|conn| conn.execute_batch("BEGIN IMMEDIATE; ROLLBACK;"))
+        .is_ok()
+}
+
+/// 等待 Antigravity 状态数据库的写锁释放
+pub async fn wait_for_db_unlocked(path: PathBuf, timeout: Duration) -> WaitOutcome {
+    let outcome = wait_until(timeout, move || db_unlocked(&path)).await;
+    if outcome.timed_out {
+        tracing::warn!(target: "platform::wait", elapsed_ms = outcome.elapsed_ms, "等待数据库解锁超时");
+    } else {
+        tracing::debug!(target: "platform::wait", elapsed_ms = outcome.elapsed_ms, "数据库未被锁定");
+    }
+    outcome
+}
+
+/// 等待当前 Antigravity 状态数据库的写锁释放（找不到数据库时不等待）
+pub async fn wait_for_antigravity_db(settings: &WaitSettings) -> WaitOutcome {
+    match crate::platform::resolve_antigravity_db_path() {
+        Ok(db_path) => wait_for_db_unlocked(db_path, settings.db_unlock_timeout()).await,
+        Err(_) => WaitOutcome {
+            elapsed_ms: 0,
+            timed_out: false,
+        },
+    }
+}
+
+/// 关闭 Antigravity 后等待进程退出、数据库写锁释放
+///
+/// 进程未在超时时间内退出时返回 `AntigravityRunning`（继续写入会在其退出时被覆盖）；
+/// 数据库仍被锁定时只记录警告，由之后的写入操作报告具体错误
+pub async fn wait_after_kill(settings: &WaitSettings) -> AgentResult<WaitOutcome> {
+    let exit = wait_for_process_exit(settings.process_exit_timeout()).await;
+    if exit.timed_out {
+        return Err(AgentError::AntigravityRunning);
+    }
+
+    let unlock = wait_for_antigravity_db(settings).await;
+    Ok(WaitOutcome {
+        elapsed_ms: exit.elapsed_ms + unlock.elapsed_ms,
+        timed_out: unlock.timed_out,
+    })
+}
@@ -0,0 +1,205 @@
+//! WSL 支持
+//! Agent 运行在 WSL 中时，Linux 侧通常没有 Antigravity 数据。开启「WSL 模式」后改为操作 Windows 上的
+//! Antigravity：数据目录与可执行文件从 Windows 用户目录（`/mnt/c/Users/<用户>`）中查找，
+//! 进程检测与关闭通过 `tasklist.exe` / `taskkill.exe` 完成（WSL 内看不到 Windows 进程）
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use super::process::{KillReport, ProcessEntry};
+use crate::error::{AgentError, AgentResult};
+
+/// Windows 上 Antigravity 的进程名
+const WINDOWS_IMAGE_NAME: &str = "Antigravity.exe";
+
+/// Agent 是否运行在 WSL 中
+pub fn is_wsl() -> bool {
+    static IS_WSL: OnceLock<bool> = OnceLock::new();
+    *IS_WSL.get_or_init(|| {
+        if !cfg!(target_os = "linux") {
+            return false;
+        }
+        std::env::var_os("WSL_DISTRO_NAME").is_some()
+            || std::fs::read_to_string("/proc/sys/kernel/osrelease")
+                .is_ok_and(|release| release.to_lowercase().contains("microsoft"))
+    })
+}
+
+/// 是否在 WSL 中操作 Windows 上的 Antigravity（运行在 WSL 中且设置中开启了 WSL 模式）
+pub fn windows_mode_enabled() -> bool {
+    is_wsl()
+        && crate::app_settings::load_settings_from_disk(
+            &crate::directories::get_app_settings_file(),
+        )
+        .wsl_windows_mode
+}
+
+/// 把 Windows 路径（如 `C:\Users\me`）转换为 WSL 中的挂载路径（`/mnt/c/Users/me`）
+pub fn windows_to_wsl_path(path: &str) -> Option<PathBuf> {
+    let path = path.trim();
+    let mut chars = path.chars();
+    let drive = chars.next().filter(char::is_ascii_alphabetic)?;
+    if chars.next() != Some(':') {
+        return None;
+    }
+
+    let mut result = PathBuf::from("/mnt").join(drive.to_ascii_lowercase().to_string());
+    for part in chars
+        .as_str()
+        .split(['\\', '/'])
+        .filter(|part| !part.is_empty())
+    {
+        result.push(part);
+    }
+    Some(result)
+}
+
+/// 通过 WSL 互操作读取 Windows 用户目录，互操作不可用时在 `/mnt/c/Users` 中查找装有 Antigravity 的用户
+fn windows_user_profile() -> Option<PathBuf> {
+    static PROFILE: OnceLock<Option<PathBuf>> = OnceLock::new();
+    PROFILE
+        .get_or_init(|| {
+            // 在 Windows 盘符下执行，避免 cmd.exe 对 UNC 工作目录的警告
+            let from_cmd = Command::new("cmd.exe")
+                .args(["/C", "echo %USERPROFILE%"])
+                .current_dir("/mnt/c")
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .and_then(|output| windows_to_wsl_path(&String::from_utf8_lossy(&output.stdout)))
+                .filter(|path| path.is_dir());
+
+            let profile = from_cmd.or_else(|| {
+                std::fs::read_dir("/mnt/c/Users").ok()?.flatten().find_map(|entry| {
+                    let path = entry.path();
+                    path.join("AppData")
+                        .join("Roaming")
+                        .join("Antigravity")
+                        .is_dir()
+                        .then_some(path)
+                })
+            });
+
+            match &profile {
+                Some(path) => {
+                    tracing::info!(target: "platform::wsl", path = %path.display(), "已定位 Windows 用户目录")
+                }
+                None => tracing::warn!(target: "platform::wsl", "无法定位 Windows 用户目录"),
+            }
+            profile
+        })
+        .clone()
+}
+
+/// Windows 上 Antigravity 的数据目录
+pub fn windows_data_dir() -> Option<PathBuf> {
+    windows_user_profile().map(|profile| {
+        profile
+            .join("AppData")
+            .join("Roaming")
+            .join("Antigravity")
+            .join("User")
+            .join("globalStorage")
+    })
+}
+
+/// Windows 上 Antigravity 的可执行文件（可通过 WSL 互操作直接启动）
+pub fn windows_executable() -> Option<PathBuf> {
+    windows_user_profile()
+        .map(|profile| {
+            profile
+                .join("AppData")
+                .join("Local")
+                .join("Programs")
+                .join("Antigravity")
+                .join(WINDOWS_IMAGE_NAME)
+        })
+        .filter(|path| path.exists())
+}
+
+/// 当前环境无法访问 Antigravity 数据目录的原因（不在 WSL 中或可以访问时返回 None）
+///
+/// `db_path` 为按常规方式解析出的数据库路径，`overridden` 表示用户指定了数据目录
+pub fn unreachable_reason(db_path: Option<&Path>, overridden: bool) -> Option<String> {
+    if !is_wsl() || overridden {
+        return None;
+    }
+    if windows_mode_enabled() {
+        return windows_data_dir().is_none().then(|| {
+            "已开启 WSL 模式，但无法访问 Windows 用户目录，请确认 /mnt/c 已挂载且 WSL 互操作可用"
+                .to_string()
+        });
+    }
+    if db_path.is_some_and(Path::exists) {
+        return None;
+    }
+    Some(
+        "Agent 运行在 WSL 中，未在 Linux 侧找到 Antigravity 数据；Antigravity 安装在 Windows 上时请在设置中开启 WSL 模式"
+            .to_string(),
+    )
+}
+
+/// 解析 `tasklist.exe /FO CSV /NH` 的输出
+fn parse_tasklist(output: &str) -> Vec<ProcessEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split("\",\"").map(|field| field.trim_matches('"'));
+            let name = fields.next()?.trim().to_string();
+            let pid = fields.next()?.trim().parse().ok()?;
+            name.eq_ignore_ascii_case(WINDOWS_IMAGE_NAME)
+                .then_some(ProcessEntry { pid, name })
+        })
+        .collect()
+}
+
+/// 正在运行的 Windows Antigravity 进程
+pub fn windows_processes() -> Vec<ProcessEntry> {
+    let filter = format!("IMAGENAME eq {WINDOWS_IMAGE_NAME}");
+    match Command::new("tasklist.exe")
+        .args(["/FI", filter.as_str(), "/FO", "CSV", "/NH"])
+        .current_dir("/mnt/c")
+        .output()
+    {
+        Ok(output) => parse_tasklist(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => {
+            tracing::warn!(target: "platform::wsl", error = %e, "调用 tasklist.exe 失败");
+            Vec::new()
+        }
+    }
+}
+
+/// 关闭 Windows 上的 Antigravity 进程
+pub fn kill_windows_processes() -> AgentResult<KillReport> {
+    let targets = windows_processes();
+    if targets.is_empty() {
+        return Err(AgentError::ProcessNotFound);
+    }
+
+    let pids: Vec<u32> = targets.iter().map(|target| target.pid).collect();
+    crate::hooks::run_hooks(
+        crate::hooks::HookEvent::PreKill,
+        serde_json::json!({ "pids": pids }),
+    )?;
+
+    Command::new("taskkill.exe")
+        .args(["/IM", WINDOWS_IMAGE_NAME, "/T", "/F"])
+        .current_dir("/mnt/c")
+        .output()
+        .map_err(|e| AgentError::ProcessKillFailed(format!("调用 taskkill.exe 失败: {e}")))?;
+
+    // taskkill 只返回整体结果，按关闭后仍在运行的进程区分成功与失败
+    let remaining: Vec<u32> = windows_processes().iter().map(|p| p.pid).collect();
+    let (failed, killed) = targets
+        .into_iter()
+        .partition::<Vec<_>, _>(|target| remaining.contains(&target.pid));
+    let report = KillReport { killed, failed };
+
+    if report.killed.is_empty() {
+        let failed: Vec<String> = report.failed.iter().map(ToString::to_string).collect();
+        return Err(AgentError::ProcessKillFailed(failed.join(", ")));
+    }
+    tracing::info!(target: "platform::wsl", "🎉 {}", report);
+    Ok(report)
+}
@@ -0,0 +1,200 @@
+//! 账户配额历史
+//! 配额由前端通过 Cloud Code 接口获取，每次获取后调用 `record_account_quota` 记录到 agent.db，
+//! 保留最近 30 天。切换账户前可以比较各账户最近一次记录的剩余配额，
+//! 推荐剩余最多的账户；重置时间已过的模型按配额已恢复计算
+
+use chrono::{DateTime, Local, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::agent_db;
+use crate::error::{AgentError, AgentResult};
+
+/// 配额记录保留天数
+const RETENTION_DAYS: i64 = 30;
+
+/// 单个模型的配额
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaSample {
+    pub model: String,
+    /// 剩余比例（0-1）
+    pub remaining_fraction: f64,
+    /// 配额重置时间（RFC 3339）
+    pub reset_time: Option<String>,
+}
+
+/// 最近一次记录的模型配额
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelQuota {
+    pub model: String,
+    /// 记录时的剩余比例
+    pub remaining_fraction: f64,
+    pub reset_time: Option<String>,
+    /// 重置时间已过（比较时按剩余 1.0 计算）
+    pub reset_passed: bool,
+    pub recorded_at: String,
+}
+
+impl ModelQuota {
+    /// 比较时使用的剩余比例
+    fn effective_fraction(&self) -> f64 {
+        if self.reset_passed {
+            1.0
+        } else {
+            self.remaining_fraction
+        }
+    }
+}
+
+/// 单个账户的配额
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountQuota {
+    pub email: String,
+    pub models: Vec<ModelQuota>,
+    /// 用于比较的剩余比例（指定模型时为该模型，否则为所有模型的平均值；没有记录时为 None）
+    pub score: Option<f64>,
+    /// 最近一次记录的时间
+    pub last_recorded_at: Option<String>,
+}
+
+/// 配额比较结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaComparison {
+    /// 按剩余配额从多到少排序，没有记录的账户排在最后
+    pub accounts: Vec<AccountQuota>,
+    /// 剩余配额最多的账户
+    pub suggested: Option<String>,
+}
+
+/// 记录一次获取到的配额，同时清理超过保留期的记录
+pub fn record(email: &str, samples: &[QuotaSample]) -> AgentResult<()> {
+    if email.trim().is_empty() {
+        return Err(AgentError::InvalidInput("邮箱不能为空".to_string()));
+    }
+    if let Some(sample) = samples
+        .iter()
+        .find(|s| !(0.0..=1.0).contains(&s.remaining_fraction))
+    {
+        return Err(AgentError::InvalidInput(format!(
+            "模型 {} 的剩余配额比例应在 0-1 之间",
+            sample.model
+        )));
+    }
+
+    let now = Local::now();
+    let recorded_at = now.to_rfc3339();
+    let recorded_ms = now.timestamp_millis();
+    let cutoff_ms = recorded_ms - RETENTION_DAYS * 24 * 60 * 60 * 1000;
+    agent_db::transaction(|tx| {
+        for sample in samples {
+            tx.execute(
+                "INSERT OR REPLACE INTO quota_samples
+                     (email, model, remaining_fraction, reset_time, recorded_at, recorded_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    email,
+                    sample.model,
+                    sample.remaining_fraction,
+                    sample.reset_time,
+                    recorded_at,
+                    recorded_ms
+                ],
+            )?;
+        }
+        tx.execute(
+            "DELETE FROM quota_samples WHERE recorded_ms < ?1",
+            [cutoff_ms],
+        )?;
+        Ok(())
+    })?;
+    tracing::debug!(target: "quota_history", models = samples.len(), "已记录账户配额");
+    Ok(())
+}
+
+/// 账户每个模型最近一次记录的配额
+fn latest(conn: &Connection, email: &str, now: DateTime<Utc>) -> AgentResult<Vec<ModelQuota>> {
+    let mut stmt = conn.prepare(
+        "SELECT model, remaining_fraction, reset_time, recorded_at FROM quota_samples AS s
+         WHERE email = ?1
+           AND recorded_ms = (SELECT MAX(recorded_ms) FROM quota_samples
+                              WHERE email = s.email AND model = s.model)
+         ORDER BY model",
+    )?;
+    let rows = stmt.query_map([email], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, f64>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, String>(3)?,
+        ))
+    })?;
+
+    let mut models = Vec::new();
+    for row in rows {
+        let (model, remaining_fraction, reset_time, recorded_at) = row?;
+        let reset_passed = reset_time
+            .as_deref()
+            .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+            .is_some_and(|time| time <= now);
+        models.push(ModelQuota {
+            model,
+            remaining_fraction,
+            reset_time,
+            reset_passed,
+            recorded_at,
+        });
+    }
+    Ok(models)
+}
+
+/// 比较账户最近一次记录的配额（指定模型时只比较该模型）
+pub fn compare(emails: &[String], model: Option<&str>) -> AgentResult<QuotaComparison> {
+    let now = Utc::now();
+    let mut accounts = agent_db::with_connection(|conn| {
+        let mut accounts = Vec::with_capacity(emails.len());
+        // 去重并保持传入顺序
+        let mut seen = BTreeSet::new();
+        for email in emails.iter().filter(|email| !email.trim().is_empty()) {
+            if !seen.insert(email.as_str()) {
+                continue;
+            }
+            let models = latest(conn, email, now)?;
+            let considered: Vec<f64> = models
+                .iter()
+                .filter(|quota| model.is_none_or(|model| quota.model == model))
+                .map(ModelQuota::effective_fraction)
+                .collect();
+            let score = (!considered.is_empty())
+                .then(|| considered.iter().sum::<f64>() / considered.len() as f64);
+            let last_recorded_at = models.iter().map(|quota| &quota.recorded_at).max().cloned();
+            accounts.push(AccountQuota {
+                email: email.clone(),
+                models,
+                score,
+                last_recorded_at,
+            });
+        }
+        Ok(accounts)
+    })?;
+
+    // 稳定排序：剩余相同的账户保持传入顺序
+    accounts.sort_by(|a, b| match (a.score, b.score) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    let suggested = accounts
+        .first()
+        .filter(|account| account.score.is_some())
+        .map(|account| account.email.clone());
+    Ok(QuotaComparison {
+        accounts,
+        suggested,
+    })
+}
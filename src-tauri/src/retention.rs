@@ -0,0 +1,205 @@
+//! 快照保留策略
+//! 数据库文件快照、Antigravity 更新快照与设备标识快照按类型分别清理：每类始终保留最新的
+//! `keep_last` 份，其余超过 `max_age_days` 天的删除（未设置天数时超出数量即删除）。
+//! 修改策略前可以先模拟，查看将被删除的快照与可回收的空间。
+//! 默认策略只清理数据库文件快照并保留 10 份，与之前的行为一致
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{AgentError, AgentResult};
+
+/// 快照类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotKind {
+    /// 数据库文件快照（db-snapshots）
+    Db,
+    /// Antigravity 更新前的快照
+    Update,
+    /// 设备标识快照
+    MachineId,
+}
+
+impl SnapshotKind {
+    const ALL: [SnapshotKind; 3] = [Self::Db, Self::Update, Self::MachineId];
+
+    fn directory(self) -> PathBuf {
+        match self {
+            Self::Db => crate::directories::get_db_snapshots_directory(),
+            Self::Update => crate::directories::get_update_snapshots_directory(),
+            Self::MachineId => crate::directories::get_machine_id_snapshots_directory(),
+        }
+    }
+}
+
+/// 快照保留策略
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RetentionPolicy {
+    /// 自动清理的快照类型（未列出的类型不清理）
+    pub kinds: Vec<SnapshotKind>,
+    /// 每类快照始终保留最新的份数（至少 1 份）
+    pub keep_last: u32,
+    /// 超出保留份数的快照再保留的天数（None 表示超出份数即删除）
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            kinds: vec![SnapshotKind::Db],
+            keep_last: 10,
+            max_age_days: None,
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// 校验保留策略
+    pub fn validate(&self) -> AgentResult<()> {
+        if self.keep_last == 0 {
+            return Err(AgentError::InvalidInput(
+                "至少需要保留 1 份快照".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 单个快照
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotEntry {
+    pub kind: SnapshotKind,
+    /// 快照目录名
+    pub id: String,
+    pub created_at: String,
+    pub size_bytes: u64,
+}
+
+/// 按策略清理的结果（模拟时不删除任何文件）
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPlan {
+    /// 将被删除（或已删除）的快照，最新的在前
+    pub deleted: Vec<SnapshotEntry>,
+    /// 保留的快照数量
+    pub kept_count: usize,
+    /// 可回收（或已回收）的空间
+    pub reclaimed_bytes: u64,
+    /// 删除失败的快照 ID（仅实际清理时）
+    pub failed: Vec<String>,
+}
+
+/// 快照时间：目录名以 `%Y%m%d-%H%M%S` 开头，无法解析时使用目录修改时间
+fn snapshot_time(path: &Path, id: &str) -> DateTime<Local> {
+    id.get(..15)
+        .and_then(|prefix| NaiveDateTime::parse_from_str(prefix, "%Y%m%d-%H%M%S").ok())
+        .and_then(|time| Local.from_local_datetime(&time).earliest())
+        .or_else(|| {
+            fs::metadata(path)
+                .and_then(|meta| meta.modified())
+                .ok()
+                .map(DateTime::<Local>::from)
+        })
+        .unwrap_or_else(Local::now)
+}
+
+/// 某类快照（最新的在前）
+fn list(kind: SnapshotKind) -> Vec<(SnapshotEntry, DateTime<Local>)> {
+    let Ok(entries) = fs::read_dir(kind.directory()) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<_> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| {
+            let path = entry.path();
+            let id = entry.file_name().to_string_lossy().to_string();
+            let time = snapshot_time(&path, &id);
+            let snapshot = SnapshotEntry {
+                kind,
+                created_at: time.to_rfc3339(),
+                size_bytes: crate::storage_stats::dir_size(&path),
+                id,
+            };
+            (snapshot, time)
+        })
+        .collect();
+    snapshots.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| b.0.id.cmp(&a.0.id)));
+    snapshots
+}
+
+/// 计算策略下将被删除的快照（只处理 `kinds` 中同时属于策略的类型）
+fn plan(policy: &RetentionPolicy, kinds: &[SnapshotKind]) -> RetentionPlan {
+    let now = Local::now();
+    let mut plan = RetentionPlan::default();
+    for kind in kinds.iter().filter(|kind| policy.kinds.contains(kind)) {
+        for (index, (snapshot, time)) in list(*kind).into_iter().enumerate() {
+            let within_count = index < policy.keep_last as usize;
+            let within_age = policy
+                .max_age_days
+                .is_some_and(|days| now.signed_duration_since(time).num_days() < days as i64);
+            if within_count || within_age {
+                plan.kept_count += 1;
+            } else {
+                plan.reclaimed_bytes += snapshot.size_bytes;
+                plan.deleted.push(snapshot);
+            }
+        }
+    }
+    plan
+}
+
+/// 模拟按策略清理所有类型的快照，不删除任何文件
+pub fn simulate(policy: &RetentionPolicy) -> AgentResult<RetentionPlan> {
+    policy.validate()?;
+    Ok(plan(policy, &SnapshotKind::ALL))
+}
+
+/// 按策略删除快照
+fn execute(policy: &RetentionPolicy, kinds: &[SnapshotKind]) -> RetentionPlan {
+    let mut result = plan(policy, kinds);
+    let mut deleted = Vec::with_capacity(result.deleted.len());
+    for snapshot in std::mem::take(&mut result.deleted) {
+        match fs::remove_dir_all(snapshot.kind.directory().join(&snapshot.id)) {
+            Ok(()) => deleted.push(snapshot),
+            Err(e) => {
+                tracing::warn!(target: "retention", kind = ?snapshot.kind, id = %snapshot.id, error = %e, "删除旧快照失败");
+                result.reclaimed_bytes -= snapshot.size_bytes;
+                result.failed.push(snapshot.id);
+            }
+        }
+    }
+    result.deleted = deleted;
+    if !result.deleted.is_empty() {
+        tracing::info!(
+            target: "retention",
+            deleted = result.deleted.len(),
+            reclaimed_bytes = result.reclaimed_bytes,
+            "已按保留策略清理快照"
+        );
+    }
+    result
+}
+
+/// 按策略清理所有类型的快照
+pub fn apply(policy: &RetentionPolicy) -> AgentResult<RetentionPlan> {
+    policy.validate()?;
+    Ok(execute(policy, &SnapshotKind::ALL))
+}
+
+/// 创建快照后按已保存的策略清理该类快照（失败只记录日志）
+pub fn enforce(kind: SnapshotKind) {
+    let policy =
+        crate::app_settings::load_settings_from_disk(&crate::directories::get_app_settings_file())
+            .retention_policy;
+    if policy.validate().is_err() {
+        tracing::warn!(target: "retention", "保存的保留策略无效，跳过清理");
+        return;
+    }
+    execute(&policy, &[kind]);
+}
@@ -0,0 +1,192 @@
+//! 定时任务的运行条件
+//! 用户正在使用电脑时推迟备份等耗时任务，避免占用 state.vscdb 与磁盘 I/O；
+//! 系统空闲达到设定时间后再运行，推迟超过上限时不再等待。
+//! 笔记本使用电池且电量低于阈值时按设置跳过（记入运行历史）或推迟到接通电源后运行
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::ScheduledJob;
+use crate::error::{AgentError, AgentResult};
+use crate::platform::power::PowerStatus;
+
+/// 空闲等待时间的上限（1 天）
+const MAX_IDLE_MINUTES: u32 = 24 * 60;
+
+/// 因用户活跃而推迟的最长时间，超过后直接运行
+const MAX_IDLE_DEFERRAL_MINUTES: i64 = 120;
+
+/// 使用电池且电量低于阈值时耗时任务的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatteryPolicy {
+    /// 照常运行
+    Ignore,
+    /// 推迟到接通电源或电量回升后运行
+    #[default]
+    Postpone,
+    /// 跳过本次运行（记入运行历史）
+    Skip,
+}
+
+/// 定时任务设置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SchedulerSettings {
+    /// 系统空闲多少分钟后才运行耗时任务（0 表示不等待）
+    pub idle_minutes: u32,
+    /// 使用电池且电量低于阈值时的处理方式
+    pub battery_policy: BatteryPolicy,
+    /// 电量阈值（百分比，100 表示使用电池时总是按策略处理）
+    pub battery_threshold_percent: u8,
+}
+
+impl Default for SchedulerSettings {
+    fn default() -> Self {
+        Self {
+            idle_minutes: 5,
+            battery_policy: BatteryPolicy::default(),
+            battery_threshold_percent: 30,
+        }
+    }
+}
+
+impl SchedulerSettings {
+    /// 校验定时任务设置
+    pub fn validate(&self) -> AgentResult<()> {
+        if self.idle_minutes > MAX_IDLE_MINUTES {
+            return Err(AgentError::InvalidInput(format!(
+                "空闲等待时间不能超过 {} 小时",
+                MAX_IDLE_MINUTES / 60
+            )));
+        }
+        if self.battery_threshold_percent > 100 {
+            return Err(AgentError::InvalidInput(
+                "电量阈值应在 0-100 之间".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 任务本轮的处理方式
+pub enum Decision {
+    Run,
+    /// 本轮不运行，之后重新检查
+    Defer,
+    /// 跳过本次运行（原因记入运行历史）
+    Skip(String),
+}
+
+/// 各任务开始因用户活跃被推迟的时间
+static DEFERRED_SINCE: Mutex<BTreeMap<String, DateTime<Local>>> = Mutex::new(BTreeMap::new());
+
+/// 本轮检查时的运行条件
+pub struct RunConditions {
+    /// 用户正在使用电脑（空闲时间不足设定值）
+    user_active: bool,
+    /// 使用电池且电量低于阈值时的电源状态
+    low_battery: Option<PowerStatus>,
+    battery_policy: BatteryPolicy,
+}
+
+/// 使用电池且电量低于阈值（电量未知时按低电量处理）
+fn is_low_battery(status: &PowerStatus, threshold: u8) -> bool {
+    status.on_battery
+        && (threshold >= 100
+            || status
+                .battery_percent
+                .is_none_or(|percent| percent < threshold))
+}
+
+impl RunConditions {
+    /// 检查运行条件（只在有耗时任务到期时读取空闲时间与电源状态）
+    pub async fn check(settings: &SchedulerSettings, due: &[ScheduledJob]) -> Self {
+        let has_heavy = due.iter().any(|job| job.action.is_heavy());
+
+        let user_active = has_heavy
+            && settings.idle_minutes > 0
+            && tokio::task::spawn_blocking(crate::platform::idle::idle_duration)
+                .await
+                .ok()
+                .flatten()
+                // 无法获取空闲时间时不推迟
+                .is_some_and(|idle| {
+                    idle < Duration::from_secs(u64::from(settings.idle_minutes) * 60)
+                });
+
+        let low_battery = if has_heavy && settings.battery_policy != BatteryPolicy::Ignore {
+            tokio::task::spawn_blocking(crate::platform::power::power_status)
+                .await
+                .ok()
+                .flatten()
+                .filter(|status| is_low_battery(status, settings.battery_threshold_percent))
+        } else {
+            None
+        };
+
+        Self {
+            user_active,
+            low_battery,
+            battery_policy: settings.battery_policy,
+        }
+    }
+
+    /// 任务本轮的处理方式（电量条件优先于空闲条件）
+    pub fn decide(&self, job: &ScheduledJob, now: DateTime<Local>) -> Decision {
+        if !job.action.is_heavy() {
+            return Decision::Run;
+        }
+
+        if let Some(status) = &self.low_battery {
+            let battery = status.battery_percent.map_or_else(
+                || "电量未知".to_string(),
+                |percent| format!("电量 {percent}%"),
+            );
+            match self.battery_policy {
+                BatteryPolicy::Skip => {
+                    return Decision::Skip(format!("使用电池供电（{battery}），跳过本次运行"));
+                }
+                BatteryPolicy::Postpone => {
+                    tracing::debug!(target: "scheduler", job = %job.name, battery = %battery, "使用电池供电，推迟定时任务");
+                    return Decision::Defer;
+                }
+                BatteryPolicy::Ignore => {}
+            }
+        }
+
+        if self.should_defer_for_idle(job, now) {
+            Decision::Defer
+        } else {
+            Decision::Run
+        }
+    }
+
+    /// 用户正在使用电脑时推迟（推迟超过上限时不再等待）
+    fn should_defer_for_idle(&self, job: &ScheduledJob, now: DateTime<Local>) -> bool {
+        let mut deferred = DEFERRED_SINCE.lock().unwrap_or_else(|e| e.into_inner());
+        if !self.user_active {
+            deferred.remove(&job.name);
+            return false;
+        }
+
+        let since = *deferred.entry(job.name.clone()).or_insert_with(|| {
+            tracing::info!(target: "scheduler", job = %job.name, "用户正在使用电脑，推迟定时任务到系统空闲后运行");
+            now
+        });
+        if now - since >= chrono::Duration::minutes(MAX_IDLE_DEFERRAL_MINUTES) {
+            tracing::warn!(
+                target: "scheduler",
+                job = %job.name,
+                deferred_minutes = MAX_IDLE_DEFERRAL_MINUTES,
+                "定时任务推迟已达上限，不再等待系统空闲"
+            );
+            deferred.remove(&job.name);
+            return false;
+        }
+        true
+    }
+}
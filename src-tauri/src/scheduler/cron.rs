@@ -0,0 +1,466 @@
+//! Cron 表达式
+//! 支持标准的 5 段格式（分 时 日 月 周），每段可使用 `*`、列表 `1,2`、范围 `1-5`、步长 `*/15` / `1-30/5`，
+//! 月份与星期可使用英文缩写（`JAN`、`MON`），星期中 0 与 7 都表示周日。
+//! 日期段与星期段按 vixie cron 的规则组合：两段都有限制时满足其一即可，任一段以 `*` 开头时两段都需满足。
+//! 另支持 `@hourly`、`@daily` 等常用简写。时间按本地时区计算
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Timelike};
+
+use crate::error::{AgentError, AgentResult};
+
+/// 查找下次运行时间的范围（超过后视为永远不会触发，如 2 月 30 日）
+const SEARCH_YEARS: i32 = 5;
+
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// 解析后的 Cron 表达式（各段为允许取值的位集合）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// 日期段以 `*` 开头（含 `*/2` 等步长）
+    any_day_of_month: bool,
+    /// 星期段以 `*` 开头（含 `*/2` 等步长）
+    any_day_of_week: bool,
+}
+
+/// 一段的取值范围与可用的名称
+struct FieldSpec {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    names: &'static [&'static str],
+    /// 名称对应的第一个数值
+    names_start: u32,
+}
+
+const MINUTE: FieldSpec = FieldSpec {
+    name: "分钟",
+    min: 0,
+    max: 59,
+    names: &[],
+    names_start: 0,
+};
+const HOUR: FieldSpec = FieldSpec {
+    name: "小时",
+    min: 0,
+    max: 23,
+    names: &[],
+    names_start: 0,
+};
+const DAY_OF_MONTH: FieldSpec = FieldSpec {
+    name: "日期",
+    min: 1,
+    max: 31,
+    names: &[],
+    names_start: 0,
+};
+const MONTH: FieldSpec = FieldSpec {
+    name: "月份",
+    min: 1,
+    max: 12,
+    names: &MONTH_NAMES,
+    names_start: 1,
+};
+const DAY_OF_WEEK: FieldSpec = FieldSpec {
+    name: "星期",
+    min: 0,
+    max: 7,
+    names: &WEEKDAY_NAMES,
+    names_start: 0,
+};
+
+impl FieldSpec {
+    fn value(&self, token: &str) -> Result<u32, String> {
+        let upper = token.to_ascii_uppercase();
+        if let Some(index) = self.names.iter().position(|name| *name == upper) {
+            return Ok(self.names_start + index as u32);
+        }
+        let value: u32 = token
+            .parse()
+            .map_err(|_| format!("{}段中的 `{token}` 不是有效的值", self.name))?;
+        if value < self.min || value > self.max {
+            return Err(format!(
+                "{}段的值 {value} 超出范围 {}-{}",
+                self.name, self.min, self.max
+            ));
+        }
+        Ok(value)
+    }
+
+    /// 解析一段，返回允许取值的位集合
+    fn parse(&self, field: &str) -> Result<u64, String> {
+        let mut bits = 0u64;
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => {
+                    let step: u32 = step
+                        .parse()
+                        .ok()
+                        .filter(|step| *step > 0)
+                        .ok_or_else(|| format!("{}段中的步长 `{step}` 无效", self.name))?;
+                    (range, step)
+                }
+                None => (part, 1),
+            };
+            let (start, end) = match range {
+                "*" => (self.min, self.max),
+                _ => match range.split_once('-') {
+                    Some((start, end)) => (self.value(start)?, self.value(end)?),
+                    // `5/10` 表示从 5 开始每 10 个取一次
+                    None if part.contains('/') => (self.value(range)?, self.max),
+                    None => {
+                        let value = self.value(range)?;
+                        (value, value)
+                    }
+                },
+            };
+            if start > end {
+                return Err(format!(
+                    "{}段中的范围 `{range}` 起始值大于结束值",
+                    self.name
+                ));
+            }
+            for value in (start..=end).step_by(step as usize) {
+                bits |= 1 << value;
+            }
+        }
+        Ok(bits)
+    }
+}
+
+/// 常用简写对应的表达式
+fn expand_macro(expr: &str) -> Option<&'static str> {
+    match expr.to_ascii_lowercase().as_str() {
+        "@yearly" | "@annually" => Some("0 0 1 1 *"),
+        "@monthly" => Some("0 0 1 * *"),
+        "@weekly" => Some("0 0 * * 0"),
+        "@daily" | "@midnight" => Some("0 0 * * *"),
+        "@hourly" => Some("0 * * * *"),
+        _ => None,
+    }
+}
+
+fn has(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+impl CronExpr {
+    /// 解析 Cron 表达式
+    pub fn parse(expr: &str) -> AgentResult<Self> {
+        let expr = expr.trim();
+        let expanded = expand_macro(expr).unwrap_or(expr);
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(AgentError::InvalidInput(format!(
+                "Cron 表达式应包含 5 段（分 时 日 月 周），实际为 {} 段",
+                fields.len()
+            )));
+        };
+
+        let parse =
+            |spec: &FieldSpec, field: &str| spec.parse(field).map_err(AgentError::InvalidInput);
+        let mut days_of_week = parse(&DAY_OF_WEEK, day_of_week)?;
+        // 7 与 0 都表示周日
+        if has(days_of_week, 7) {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+        let cron = Self {
+            minutes: parse(&MINUTE, minute)?,
+            hours: parse(&HOUR, hour)?,
+            days_of_month: parse(&DAY_OF_MONTH, day_of_month)?,
+            months: parse(&MONTH, month)?,
+            days_of_week,
+            any_day_of_month: day_of_month.starts_with('*'),
+            any_day_of_week: day_of_week.starts_with('*'),
+        };
+
+        if cron.next_after(Local::now()).is_none() {
+            return Err(AgentError::InvalidInput(format!(
+                "Cron 表达式 `{expr}` 在 {SEARCH_YEARS} 年内不会触发"
+            )));
+        }
+        Ok(cron)
+    }
+
+    /// 日期是否匹配（与 vixie cron 一致）：任一段以 `*` 开头时两段都需满足
+    /// （`*/2` 的步长照常生效），两段都有限制时满足其一即可
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let by_month = has(self.days_of_month, date.day());
+        let by_week = has(self.days_of_week, date.weekday().num_days_from_sunday());
+        if self.any_day_of_month || self.any_day_of_week {
+            by_month && by_week
+        } else {
+            by_month || by_week
+        }
+    }
+
+    /// `after` 之后（不含）的下一次运行时间
+    pub fn next_after<Tz: TimeZone>(&self, after: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let end_year = start.year() + SEARCH_YEARS;
+        let mut t = start;
+
+        while t.year() <= end_year {
+            if !has(self.months, t.month()) {
+                // 跳到下个月 1 日 0 点
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.matches_day(t.date()) {
+                t = (t.date() + Duration::days(1)).and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !has(self.hours, t.hour()) {
+                t = t.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !has(self.minutes, t.minute()) {
+                t += Duration::minutes(1);
+                continue;
+            }
+            // 夏令时跳过的时间不存在，顺延到下一个匹配的时间
+            if let Some(local) = after.timezone().from_local_datetime(&t).earliest() {
+                return Some(local);
+            }
+            t += Duration::minutes(1);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, MappedLocalTime, NaiveDateTime, Utc};
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M")
+            .unwrap()
+            .and_utc()
+    }
+
+    fn next(expr: &str, after: &str) -> String {
+        CronExpr::parse(expr)
+            .unwrap()
+            .next_after(utc(after))
+            .unwrap()
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    }
+
+    fn days(expr: &str, after: &str, count: usize) -> Vec<String> {
+        let cron = CronExpr::parse(expr).unwrap();
+        std::iter::successors(cron.next_after(utc(after)), |last| cron.next_after(*last))
+            .take(count)
+            .map(|t| t.format("%m-%d").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn parses_steps_ranges_and_lists() {
+        let cron = CronExpr::parse("*/15 9-17 1,15 * *").unwrap();
+        assert_eq!(cron.minutes, 1 | 1 << 15 | 1 << 30 | 1 << 45);
+        assert_eq!(cron.hours, (9..=17).fold(0, |bits, h| bits | 1 << h));
+        assert_eq!(cron.days_of_month, 1 << 1 | 1 << 15);
+
+        let cron = CronExpr::parse("5/20 1-10/3 * * *").unwrap();
+        assert_eq!(cron.minutes, 1 << 5 | 1 << 25 | 1 << 45);
+        assert_eq!(cron.hours, 1 << 1 | 1 << 4 | 1 << 7 | 1 << 10);
+    }
+
+    #[test]
+    fn parses_names_and_sunday_as_seven() {
+        let cron = CronExpr::parse("0 0 * jan-mar MON,fri").unwrap();
+        assert_eq!(cron.months, 1 << 1 | 1 << 2 | 1 << 3);
+        assert_eq!(cron.days_of_week, 1 << 1 | 1 << 5);
+
+        assert_eq!(
+            CronExpr::parse("0 0 * * 7").unwrap().days_of_week,
+            CronExpr::parse("0 0 * * SUN").unwrap().days_of_week
+        );
+        assert_eq!(
+            CronExpr::parse("0 0 * * 5-7").unwrap().days_of_week,
+            1 | 1 << 5 | 1 << 6
+        );
+    }
+
+    #[test]
+    fn expands_macros() {
+        assert_eq!(
+            CronExpr::parse("@daily").unwrap(),
+            CronExpr::parse("0 0 * * *").unwrap()
+        );
+        assert_eq!(
+            CronExpr::parse("@weekly").unwrap(),
+            CronExpr::parse("0 0 * * 0").unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expr in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "10-5 * * * *",
+            "* * * FOO *",
+            "0 0 30 2 *",
+        ] {
+            assert!(CronExpr::parse(expr).is_err(), "{expr}");
+        }
+    }
+
+    #[test]
+    fn finds_next_time() {
+        assert_eq!(next("*/15 * * * *", "2026-01-01 10:07"), "2026-01-01 10:15");
+        assert_eq!(next("*/15 * * * *", "2026-01-01 10:15"), "2026-01-01 10:30");
+        assert_eq!(next("30 9 * * *", "2026-01-01 10:00"), "2026-01-02 09:30");
+        assert_eq!(next("0 0 1 * *", "2026-01-31 23:59"), "2026-02-01 00:00");
+        assert_eq!(next("0 0 1 1 *", "2026-06-01 00:00"), "2027-01-01 00:00");
+        assert_eq!(next("0 0 29 2 *", "2026-01-01 00:00"), "2028-02-29 00:00");
+        // 2026-01-01 是周四
+        assert_eq!(next("0 8 * * MON", "2026-01-01 00:00"), "2026-01-05 08:00");
+        assert_eq!(next("0 8 * * 7", "2026-01-01 00:00"), "2026-01-04 08:00");
+    }
+
+    #[test]
+    fn day_of_month_step_is_honoured() {
+        assert_eq!(
+            days("0 0 */2 * *", "2026-01-01 00:00", 4),
+            ["01-03", "01-05", "01-07", "01-09"]
+        );
+        // 星期段为 `*` 时日期段的步长照常生效
+        assert_eq!(
+            days("0 0 */10 * *", "2026-01-01 00:00", 3),
+            ["01-11", "01-21", "01-31"]
+        );
+    }
+
+    #[test]
+    fn combines_day_of_month_and_day_of_week() {
+        // 两段都有限制：满足其一即可（1 日或周一）
+        assert_eq!(
+            days("0 0 1 * MON", "2026-01-01 00:00", 3),
+            ["01-05", "01-12", "01-19"]
+        );
+        assert_eq!(
+            days("0 0 1 * MON", "2026-01-26 00:00", 2),
+            ["02-01", "02-02"]
+        );
+        // 日期段以 `*` 开头：两段都需满足（单数日且为周一）
+        assert_eq!(
+            days("0 0 */2 * MON", "2026-01-01 00:00", 3),
+            ["01-05", "01-19", "02-09"]
+        );
+        // 星期段以 `*` 开头：只按日期匹配
+        assert_eq!(
+            days("0 0 13 * *", "2026-01-01 00:00", 2),
+            ["01-13", "02-13"]
+        );
+    }
+
+    /// 2026-03-08 02:00 从 UTC-8 跳到 UTC-7，当天 02:00-02:59 不存在
+    #[derive(Debug, Clone, Copy)]
+    struct SpringForward;
+
+    impl SpringForward {
+        fn standard() -> FixedOffset {
+            FixedOffset::west_opt(8 * 3600).unwrap()
+        }
+
+        fn daylight() -> FixedOffset {
+            FixedOffset::west_opt(7 * 3600).unwrap()
+        }
+
+        fn local_gap() -> (NaiveDateTime, NaiveDateTime) {
+            let day = NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
+            (
+                day.and_hms_opt(2, 0, 0).unwrap(),
+                day.and_hms_opt(3, 0, 0).unwrap(),
+            )
+        }
+    }
+
+    impl TimeZone for SpringForward {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            Self
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(
+            &self,
+            local: &NaiveDateTime,
+        ) -> MappedLocalTime<FixedOffset> {
+            let (gap_start, gap_end) = Self::local_gap();
+            if *local < gap_start {
+                MappedLocalTime::Single(Self::standard())
+            } else if *local < gap_end {
+                MappedLocalTime::None
+            } else {
+                MappedLocalTime::Single(Self::daylight())
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            if *utc < Self::local_gap().0 + Duration::hours(8) {
+                Self::standard()
+            } else {
+                Self::daylight()
+            }
+        }
+    }
+
+    #[test]
+    fn skips_times_in_dst_gap() {
+        let after = SpringForward
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2026, 3, 8)
+                    .unwrap()
+                    .and_hms_opt(1, 0, 0)
+                    .unwrap(),
+            )
+            .unwrap();
+        let format = |t: DateTime<SpringForward>| t.naive_local().format("%m-%d %H:%M").to_string();
+
+        // 02:30 当天不存在，顺延到次日
+        let cron = CronExpr::parse("30 2 * * *").unwrap();
+        assert_eq!(format(cron.next_after(after).unwrap()), "03-09 02:30");
+
+        // 每分钟的任务跳过缺失的一小时
+        let cron = CronExpr::parse("* * * * *").unwrap();
+        let gap_edge = SpringForward
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2026, 3, 8)
+                    .unwrap()
+                    .and_hms_opt(1, 59, 0)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(format(cron.next_after(gap_edge).unwrap()), "03-08 03:00");
+    }
+}
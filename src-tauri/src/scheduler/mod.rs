@@ -0,0 +1,477 @@
+//! 定时任务
+//! 按固定间隔或 Cron 表达式在后台运行任务（自动备份、刷新账户配额），每个任务有独立的名称与计划。
+//! 任务配置与最近的运行历史保存在 scheduled_jobs.json 中；Agent 未运行期间错过的任务在启动后补跑一次。
+//! 备份等耗时任务在用户使用电脑或低电量时推迟、跳过（见 [`conditions`]）
+
+pub mod conditions;
+pub mod cron;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::{interval, Duration};
+
+use crate::antigravity::backup::BackupTrigger;
+use crate::error::{AgentError, AgentResult};
+use conditions::{Decision, RunConditions};
+use cron::CronExpr;
+
+pub use conditions::SchedulerSettings;
+
+/// 通知前端刷新账户配额的事件名
+pub const QUOTA_POLL_EVENT: &str = "scheduler:quota-poll";
+
+/// 检查到期任务的间隔
+pub const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 保留的运行历史条数
+const HISTORY_LIMIT: usize = 200;
+
+/// 任务名称的最大长度
+const MAX_NAME_LEN: usize = 64;
+
+/// 任务的运行计划
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum Schedule {
+    /// 每隔固定分钟数运行
+    Interval { minutes: u32 },
+    /// 按 Cron 表达式运行（见 [`cron`]）
+    Cron { expression: String },
+}
+
+impl Schedule {
+    /// 检查计划是否有效
+    pub fn validate(&self) -> AgentResult<()> {
+        match self {
+            Self::Interval { minutes: 0 } => Err(AgentError::InvalidInput(
+                "运行间隔至少为 1 分钟".to_string(),
+            )),
+            Self::Interval { .. } => Ok(()),
+            Self::Cron { expression } => CronExpr::parse(expression).map(|_| ()),
+        }
+    }
+
+    /// `after` 之后的下一次运行时间
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            Self::Interval { minutes } => {
+                Some(after + chrono::Duration::minutes(i64::from(*minutes)))
+            }
+            Self::Cron { expression } => CronExpr::parse(expression).ok()?.next_after(after),
+        }
+    }
+
+    /// 从 `after` 开始的接下来 `count` 次运行时间
+    pub fn upcoming(&self, after: DateTime<Local>, count: usize) -> Vec<DateTime<Local>> {
+        std::iter::successors(self.next_after(after), |last| self.next_after(*last))
+            .take(count)
+            .collect()
+    }
+}
+
+/// 定时任务执行的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobAction {
+    /// 备份当前登录的账户（内容未变化时跳过写入）
+    AutoBackup,
+    /// 通知前端刷新各账户的配额数据
+    QuotaPoll,
+}
+
+impl JobAction {
+    /// 是否为需要读写数据库或大量 I/O 的耗时操作（用户活跃时推迟）
+    pub fn is_heavy(&self) -> bool {
+        matches!(self, Self::AutoBackup)
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 定时任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledJob {
+    /// 任务名称（唯一）
+    pub name: String,
+    pub action: JobAction,
+    pub schedule: Schedule,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub created_at: String,
+    /// 上次运行时间（RFC 3339）
+    pub last_run_at: Option<String>,
+}
+
+impl ScheduledJob {
+    /// 下次运行时间（从上次运行或创建时间起计算）
+    pub fn next_run(&self) -> Option<DateTime<Local>> {
+        let base = self.last_run_at.as_deref().unwrap_or(&self.created_at);
+        let base = DateTime::parse_from_rfc3339(base)
+            .ok()?
+            .with_timezone(&Local);
+        self.schedule.next_after(base)
+    }
+}
+
+/// 定时任务及其下次运行时间
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStatus {
+    #[serde(flatten)]
+    pub job: ScheduledJob,
+    /// 下次运行时间，任务已停用时为 None
+    pub next_run_at: Option<String>,
+}
+
+impl From<ScheduledJob> for JobStatus {
+    fn from(job: ScheduledJob) -> Self {
+        let next_run_at = job
+            .enabled
+            .then(|| job.next_run())
+            .flatten()
+            .map(|time| time.to_rfc3339());
+        Self { job, next_run_at }
+    }
+}
+
+/// 新建或修改定时任务时提交的内容
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobInput {
+    pub name: String,
+    pub action: JobAction,
+    pub schedule: Schedule,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// 一次运行的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    Succeeded,
+    Failed,
+    /// 条件不满足（如未登录、低电量）而跳过
+    Skipped,
+}
+
+/// 运行历史中的一项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunRecord {
+    pub job: String,
+    pub action: JobAction,
+    pub started_at: String,
+    pub finished_at: String,
+    pub outcome: RunOutcome,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SchedulerFile {
+    jobs: Vec<ScheduledJob>,
+    /// 运行历史（最新的在后）
+    history: Vec<RunRecord>,
+}
+
+/// 内存中的定时任务（None 表示尚未从磁盘加载）
+static SCHEDULER: Mutex<Option<SchedulerFile>> = Mutex::new(None);
+
+/// 调度循环最近一次检查到期任务的时间（Unix 时间戳，0 表示尚未运行）
+static LAST_TICK: AtomicI64 = AtomicI64::new(0);
+
+fn read_file() -> SchedulerFile {
+    fs::read_to_string(crate::directories::get_scheduler_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_file(file: &SchedulerFile) -> AgentResult<()> {
+    let path = crate::directories::get_scheduler_file();
+    fs::write(&path, serde_json::to_string_pretty(file)?)?;
+    Ok(())
+}
+
+/// 修改定时任务并写回磁盘（写入失败时丢弃修改）
+fn update<T>(f: impl FnOnce(&mut SchedulerFile) -> AgentResult<T>) -> AgentResult<T> {
+    let mut guard = SCHEDULER.lock().unwrap_or_else(|e| e.into_inner());
+    let file = guard.get_or_insert_with(read_file);
+    let result = f(file)?;
+    if let Err(e) = write_file(file) {
+        *guard = None;
+        return Err(e);
+    }
+    Ok(result)
+}
+
+/// 读取定时任务
+fn read<T>(f: impl FnOnce(&SchedulerFile) -> T) -> T {
+    let mut guard = SCHEDULER.lock().unwrap_or_else(|e| e.into_inner());
+    f(guard.get_or_insert_with(read_file))
+}
+
+/// 所有定时任务
+pub fn list_jobs() -> Vec<JobStatus> {
+    read(|file| file.jobs.clone())
+        .into_iter()
+        .map(JobStatus::from)
+        .collect()
+}
+
+/// 新建或修改定时任务（按名称匹配，修改时保留创建时间与上次运行时间；只读模式下拒绝）
+pub fn save_job(input: JobInput) -> AgentResult<JobStatus> {
+    crate::app_settings::ensure_writable()?;
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AgentError::InvalidInput("任务名称不能为空".to_string()));
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(AgentError::InvalidInput(format!(
+            "任务名称不能超过 {MAX_NAME_LEN} 个字符"
+        )));
+    }
+    input.schedule.validate()?;
+
+    let job = update(|file| {
+        let job = match file.jobs.iter_mut().find(|job| job.name == name) {
+            Some(job) => {
+                job.action = input.action;
+                job.schedule = input.schedule;
+                job.enabled = input.enabled;
+                job.clone()
+            }
+            None => {
+                let job = ScheduledJob {
+                    name: name.clone(),
+                    action: input.action,
+                    schedule: input.schedule,
+                    enabled: input.enabled,
+                    created_at: Local::now().to_rfc3339(),
+                    last_run_at: None,
+                };
+                file.jobs.push(job.clone());
+                job
+            }
+        };
+        Ok(job)
+    })?;
+
+    tracing::info!(
+        target: "scheduler",
+        job = %job.name,
+        action = ?job.action,
+        schedule = ?job.schedule,
+        enabled = job.enabled,
+        "已保存定时任务"
+    );
+    Ok(job.into())
+}
+
+/// 删除定时任务（只读模式下拒绝）
+pub fn delete_job(name: &str) -> AgentResult<()> {
+    crate::app_settings::ensure_writable()?;
+    update(|file| {
+        let before = file.jobs.len();
+        file.jobs.retain(|job| job.name != name);
+        if file.jobs.len() == before {
+            return Err(AgentError::InvalidInput(format!("定时任务 {name} 不存在")));
+        }
+        Ok(())
+    })?;
+    tracing::info!(target: "scheduler", job = %name, "已删除定时任务");
+    Ok(())
+}
+
+/// 最近的运行历史（最新的在前）
+pub fn history(limit: usize) -> Vec<RunRecord> {
+    read(|file| file.history.iter().rev().take(limit).cloned().collect())
+}
+
+/// 调度循环最近一次检查到期任务的时间（尚未运行时为 None），用于健康检查
+pub fn last_tick() -> Option<DateTime<Local>> {
+    match LAST_TICK.load(Ordering::SeqCst) {
+        0 => None,
+        secs => DateTime::from_timestamp(secs, 0).map(|time| time.with_timezone(&Local)),
+    }
+}
+
+/// 记录一次运行结果
+fn record_run(record: RunRecord) {
+    let result = update(|file| {
+        file.history.push(record);
+        let overflow = file.history.len().saturating_sub(HISTORY_LIMIT);
+        file.history.drain(..overflow);
+        Ok(())
+    });
+    if let Err(e) = result {
+        tracing::warn!(target: "scheduler", error = %e, "写入定时任务运行历史失败");
+    }
+}
+
+/// 到期的任务
+fn due_jobs(now: DateTime<Local>) -> Vec<ScheduledJob> {
+    read(|file| {
+        file.jobs
+            .iter()
+            .filter(|job| job.enabled && job.next_run().is_some_and(|next| next <= now))
+            .cloned()
+            .collect()
+    })
+}
+
+/// 把任务的上次运行时间记为 `now`
+fn mark_started(jobs: &[ScheduledJob], now: DateTime<Local>) -> AgentResult<()> {
+    update(|file| {
+        for job in file
+            .jobs
+            .iter_mut()
+            .filter(|job| jobs.iter().any(|started| started.name == job.name))
+        {
+            job.last_run_at = Some(now.to_rfc3339());
+        }
+        Ok(())
+    })
+}
+
+/// 执行任务的操作
+async fn execute(app: &AppHandle, action: JobAction) -> (RunOutcome, Option<String>) {
+    match action {
+        JobAction::AutoBackup => {
+            let result = crate::tasks::run_task(app, None, "scheduled_backup", |_task| {
+                crate::utils::blocking::run_blocking(|| {
+                    crate::antigravity::backup::smart_backup_antigravity_account(
+                        BackupTrigger::Scheduled,
+                        None,
+                    )
+                })
+            })
+            .await;
+            match result {
+                Ok(outcome) if outcome.changed => (
+                    RunOutcome::Succeeded,
+                    Some(format!("已备份 {}", outcome.email)),
+                ),
+                Ok(outcome) => (
+                    RunOutcome::Succeeded,
+                    Some(format!("{} 的备份内容未变化", outcome.email)),
+                ),
+                Err(
+                    e @ (AgentError::NotLoggedIn(_)
+                    | AgentError::DatabaseNotFound(_)
+                    | AgentError::OperationInProgress(_)
+                    | AgentError::ReadOnly),
+                ) => (RunOutcome::Skipped, Some(e.to_string())),
+                Err(e) => (RunOutcome::Failed, Some(e.to_string())),
+            }
+        }
+        JobAction::QuotaPoll => match app.emit(QUOTA_POLL_EVENT, ()) {
+            Ok(()) => (RunOutcome::Succeeded, None),
+            Err(e) => (RunOutcome::Failed, Some(e.to_string())),
+        },
+    }
+}
+
+/// 运行一个到期的任务并记录结果
+async fn run_job(app: AppHandle, job: ScheduledJob) {
+    let started_at = Local::now().to_rfc3339();
+    let (outcome, message) = execute(&app, job.action).await;
+
+    match outcome {
+        RunOutcome::Failed => tracing::warn!(
+            target: "scheduler",
+            job = %job.name,
+            message = message.as_deref().unwrap_or_default(),
+            "定时任务运行失败"
+        ),
+        _ => tracing::info!(
+            target: "scheduler",
+            job = %job.name,
+            outcome = ?outcome,
+            message = message.as_deref().unwrap_or_default(),
+            "定时任务运行完成"
+        ),
+    }
+
+    record_run(RunRecord {
+        job: job.name,
+        action: job.action,
+        started_at,
+        finished_at: Local::now().to_rfc3339(),
+        outcome,
+        message,
+    });
+}
+
+/// 记录因运行条件不满足而跳过的任务
+fn record_skipped(job: ScheduledJob, reason: String, now: DateTime<Local>) {
+    tracing::info!(target: "scheduler", job = %job.name, reason = %reason, "跳过定时任务");
+    record_run(RunRecord {
+        job: job.name,
+        action: job.action,
+        started_at: now.to_rfc3339(),
+        finished_at: now.to_rfc3339(),
+        outcome: RunOutcome::Skipped,
+        message: Some(reason),
+    });
+}
+
+/// 启动定时任务调度（定期检查到期的任务）
+pub fn spawn_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = interval(TICK_INTERVAL);
+        loop {
+            ticker.tick().await;
+
+            let now = Local::now();
+            LAST_TICK.store(now.timestamp(), Ordering::SeqCst);
+            let due = due_jobs(now);
+            if due.is_empty() {
+                continue;
+            }
+
+            let settings = app
+                .state::<crate::app_settings::AppSettingsManager>()
+                .get_settings()
+                .scheduler;
+            let conditions = RunConditions::check(&settings, &due).await;
+            let mut runnable = Vec::new();
+            let mut skipped = Vec::new();
+            for job in due {
+                match conditions.decide(&job, now) {
+                    Decision::Run => runnable.push(job),
+                    Decision::Defer => {}
+                    Decision::Skip(reason) => skipped.push((job, reason)),
+                }
+            }
+
+            let started: Vec<ScheduledJob> = runnable
+                .iter()
+                .chain(skipped.iter().map(|(job, _)| job))
+                .cloned()
+                .collect();
+            if let Err(e) = mark_started(&started, now) {
+                tracing::warn!(target: "scheduler", error = %e, "记录定时任务运行时间失败");
+                continue;
+            }
+            for (job, reason) in skipped {
+                record_skipped(job, reason, now);
+            }
+            for job in runnable {
+                tauri::async_runtime::spawn(run_job(app.clone(), job));
+            }
+        }
+    });
+}
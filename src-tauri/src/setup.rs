@@ -1,17 +1,55 @@
-use crate::{app_settings, db_monitor, system_tray, window};
+use crate::startup_metrics::{background_phase, phase};
+use crate::{account_watcher, app_settings, db_monitor, http_api, system_tray, window};
 use std::sync::Arc;
 use tauri::{App, Manager};
 
 pub fn init(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>> {
     tracing::info!(target: "app::setup", "开始应用程序设置");
+    let _setup_phase = phase("setup");
 
     // 初始化应用设置管理器
     let app_handle = app.handle();
-    app.manage(app_settings::AppSettingsManager::new(app_handle));
+    {
+        let _phase = phase("setup::settings");
+        app.manage(app_settings::AppSettingsManager::new(app_handle));
+    }
+
+    // 绑定状态事件总线与磁盘空间不足通知
+    crate::state_bus::init(app_handle);
+    crate::disk_space::init(app_handle);
+
+    // 应用保存的界面主题，并在跟随系统时监听系统主题变化
+    crate::appearance::init(app_handle);
+
+    // 收到 SIGTERM 等退出信号时先保存窗口状态、任务日志与设置
+    crate::shutdown::spawn_signal_listener(app_handle.clone());
+
+    // 初始化长耗时任务管理器，并把上次运行时未结束的任务标记为中断
+    crate::task_journal::load_at_startup();
+    app.manage(crate::tasks::TaskManager::new());
 
     // 初始化系统托盘管理器
     app.manage(system_tray::SystemTrayManager::new());
 
+    // 初始化本地 HTTP 控制接口（按设置决定是否启动）
+    app.manage(http_api::HttpApiServer::new());
+    {
+        let _phase = phase("setup::http_api");
+        if let Err(e) = crate::commands::apply_http_api_settings(app.handle()) {
+            tracing::error!(target: "app::setup::http_api", error = %e, "启动 HTTP 控制接口失败");
+        }
+    }
+
+    // 更新 Windows 任务栏跳转列表（后台线程执行）
+    crate::platform::jump_list::refresh_jump_list();
+
+    // 后台预热 Antigravity 路径检测，避免首次命令调用时阻塞
+    tauri::async_runtime::spawn_blocking(|| {
+        let _phase = background_phase("detect_antigravity_paths");
+        crate::platform::get_antigravity_data_dir();
+        crate::platform::platform_detection();
+    });
+
     // Tracing 日志记录器已在 main 函数中初始化，这里跳过
 
     // 在 release 模式下禁用右键菜单
@@ -32,32 +70,75 @@ pub fn init(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>
 
     tracing::info!(target: "app::setup::db_monitor", "数据库监控器初始化完成");
 
-    // 初始化窗口事件处理器
-    if let Err(e) = window::init_window_event_handler(app) {
-        tracing::error!(target: "app::setup::window", error = %e, "窗口事件处理器初始化失败");
-    } else {
-        tracing::info!(target: "app::setup::window", "窗口事件处理器初始化完成");
-    }
+    // 初始化并启动账户变化监听器
+    let account_watcher = Arc::new(account_watcher::AccountWatcher::new(app.handle().clone()));
+    app.manage(account_watcher.clone());
+    tauri::async_runtime::spawn(async move {
+        account_watcher.start().await;
+    });
+
+    // 启动 Antigravity 安装监听（运行期间安装或卸载时更新检测结果）
+    crate::platform::spawn_install_watcher(app.handle().clone());
+
+    // 启动 Antigravity 版本检查（版本变化时自动快照）
+    crate::antigravity::update_snapshot::spawn_version_monitor(app.handle().clone());
+
+    // 启动定时任务调度
+    crate::scheduler::spawn_scheduler(app.handle().clone());
+
+    // 按设置开启使用统计，并定期把本地汇总写入磁盘
+    crate::telemetry::apply_settings(
+        &app.state::<app_settings::AppSettingsManager>()
+            .get_settings()
+            .telemetry,
+    );
+    crate::telemetry::spawn_flush_loop();
+
+    // 启动 Agent 更新检查（可在设置中关闭）
+    crate::updater::spawn_update_checker(app.handle().clone());
 
     // 检查静默启动设置
     let settings_manager = app.state::<app_settings::AppSettingsManager>();
     let settings = settings_manager.get_settings();
 
-    // 根据设置决定是否创建系统托盘
-    if settings.system_tray_enabled {
-        tracing::info!(target: "app::setup::tray", "系统托盘已启用，正在创建托盘");
-        let system_tray = app.state::<system_tray::SystemTrayManager>();
-        if let Err(e) = system_tray.enable(app.handle()) {
-            tracing::error!(target: "app::setup::tray", error = %e, "启动时创建系统托盘失败");
-        } else {
-            tracing::info!(target: "app::setup::tray", "系统托盘已创建");
+    // 当前桌面环境不支持托盘时按未启用处理（保留用户设置，环境就绪后自动恢复）
+    let tray_capability = system_tray::tray_capability();
+    let tray_enabled = settings.system_tray_enabled && tray_capability.supported;
+    if settings.system_tray_enabled && !tray_capability.supported {
+        tracing::warn!(
+            target: "app::setup::tray",
+            reason = tray_capability.reason.as_deref().unwrap_or_default(),
+            "系统托盘已启用但当前系统不支持，跳过创建"
+        );
+    }
+
+    crate::state_bus::publish(crate::state_bus::StateUpdate::TrayEnabled(tray_enabled));
+
+    // 根据设置决定是否创建系统托盘（延后到事件循环启动后在主线程创建，不阻塞窗口显示）
+    if tray_enabled {
+        tracing::info!(target: "app::setup::tray", "系统托盘已启用，将在窗口显示后创建");
+        let tray_handle = app.handle().clone();
+        let scheduled = app.handle().run_on_main_thread(move || {
+            let _phase = background_phase("system_tray");
+            let system_tray = tray_handle.state::<system_tray::SystemTrayManager>();
+            if let Err(e) = system_tray.enable(&tray_handle) {
+                tracing::error!(target: "app::setup::tray", error = %e, "启动时创建系统托盘失败");
+            } else {
+                tracing::info!(target: "app::setup::tray", "系统托盘已创建");
+            }
+        });
+        if let Err(e) = scheduled {
+            tracing::error!(target: "app::setup::tray", error = %e, "调度系统托盘创建失败");
         }
-    } else {
+    } else if !settings.system_tray_enabled {
         tracing::info!(target: "app::setup::tray", "系统托盘已禁用，跳过创建");
     }
 
+    // 上次退出时窗口隐藏在托盘中，本次启动同样隐藏（与静默启动一样需要托盘可用）
+    let was_hidden_in_tray = window::state_manager::was_hidden_in_tray();
+
     // 双重检查：如果静默启动但未启用系统托盘，这是不允许的
-    if settings.silent_start_enabled && !settings.system_tray_enabled {
+    let start_hidden = if settings.silent_start_enabled && !settings.system_tray_enabled {
         tracing::warn!(
             target: "app::setup::silent_start",
             "检测到危险配置：静默启动已启用但系统托盘未启用。自动禁用静默启动以确保安全。"
@@ -75,8 +156,17 @@ pub fn init(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>
         }
 
         tracing::info!(target: "app::setup::silent_start", "已禁用静默启动，正常显示窗口");
-    } else if settings.silent_start_enabled && settings.system_tray_enabled {
-        tracing::info!(target: "app::setup::silent_start", "静默启动模式已启用（系统托盘已启用），准备隐藏主窗口");
+        false
+    } else if (settings.silent_start_enabled || was_hidden_in_tray) && !tray_enabled {
+        tracing::warn!(target: "app::setup::silent_start", "系统托盘不可用，本次忽略静默启动，正常显示窗口");
+        false
+    } else if settings.silent_start_enabled || was_hidden_in_tray {
+        tracing::info!(
+            target: "app::setup::silent_start",
+            silent_start = settings.silent_start_enabled,
+            was_hidden_in_tray,
+            "窗口将隐藏在托盘中启动，准备隐藏主窗口"
+        );
 
         // 延迟执行静默启动，确保在窗口状态恢复完成后隐藏窗口
         let app_handle_for_silent = app.handle().clone();
@@ -102,8 +192,20 @@ pub fn init(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>
                 tracing::error!(target: "app::setup::silent_start", "无法获取主窗口进行静默启动");
             }
         });
+        true
     } else {
         tracing::debug!(target: "app::setup::silent_start", "静默启动未启用，正常显示窗口");
+        false
+    };
+
+    // 初始化窗口事件处理器
+    let window_phase = phase("setup::window_handler");
+    let window_result = window::init_window_event_handler(app, start_hidden);
+    drop(window_phase);
+    if let Err(e) = window_result {
+        tracing::error!(target: "app::setup::window", error = %e, "窗口事件处理器初始化失败");
+    } else {
+        tracing::info!(target: "app::setup::window", "窗口事件处理器初始化完成");
     }
 
     tracing::info!(target: "app::setup", "应用程序设置完成");
@@ -0,0 +1,114 @@
+//! 退出前保存状态
+//! 窗口状态只在移动、缩放与关闭窗口时保存，通过托盘菜单“退出应用”、系统关机或收到 SIGTERM 退出时
+//! 最新的窗口位置可能丢失。退出流程（`RunEvent::ExitRequested` / `RunEvent::Exit`）与 Unix 信号
+//! 都会调用 [`flush_state`]，写入窗口状态（含是否隐藏在托盘中）、任务日志、设置与使用统计，整个进程只执行一次
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+
+use crate::window::state_manager::{self, MAIN_WINDOW};
+
+/// 已执行过退出前保存
+static FLUSHED: AtomicBool = AtomicBool::new(false);
+
+/// 退出前保存所有尚未写入磁盘的状态（重复调用时直接返回）
+pub fn flush_state(app: &AppHandle, reason: &str) {
+    if FLUSHED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let started = Instant::now();
+
+    // 1. 窗口状态（主窗口隐藏在托盘中时只记录隐藏状态，位置与大小保留上次保存的值）
+    for (label, window) in app.webview_windows() {
+        let hidden_in_tray = label == MAIN_WINDOW
+            && app
+                .try_state::<crate::system_tray::SystemTrayManager>()
+                .is_some_and(|tray| tray.is_enabled_setting(app))
+            && !window.is_visible().unwrap_or(true);
+        if hidden_in_tray {
+            tracing::debug!(target: "app::shutdown", "窗口隐藏在托盘中，下次启动时保持隐藏");
+        } else {
+            save_window(&label, &window);
+        }
+        if label == MAIN_WINDOW {
+            if let Err(e) = state_manager::save_hidden_in_tray(hidden_in_tray) {
+                tracing::warn!(target: "app::shutdown", error = %e, "退出前保存托盘隐藏状态失败");
+            }
+        }
+    }
+
+    // 2. 任务日志（进行中的任务保留在日志中，下次启动时提示为中断任务）
+    crate::task_journal::flush();
+
+    // 3. 设置
+    if let Some(settings) = app.try_state::<crate::app_settings::AppSettingsManager>() {
+        if let Err(e) = settings.flush() {
+            tracing::warn!(target: "app::shutdown", error = %e, "退出前保存设置失败");
+        }
+    }
+
+    // 4. 使用统计
+    if let Err(e) = crate::telemetry::flush() {
+        tracing::warn!(target: "app::shutdown", error = %e, "退出前写入使用统计失败");
+    }
+
+    // 5. Agent 数据库
+    crate::agent_db::checkpoint();
+
+    tracing::info!(
+        target: "app::shutdown",
+        reason,
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "退出前状态已保存"
+    );
+}
+
+/// 保存单个窗口的位置与大小（窗口最小化或状态无效时保留上次保存的值）
+fn save_window(label: &str, window: &tauri::WebviewWindow) {
+    let Some(state) = crate::window::event_handler::capture_window_state(window) else {
+        tracing::debug!(target: "app::shutdown", label, "无法读取窗口状态，跳过保存");
+        return;
+    };
+    match state_manager::save_window_state_blocking(label, &state) {
+        Ok(true) => {}
+        Ok(false) => tracing::debug!(target: "app::shutdown", label, "窗口状态无效，跳过保存"),
+        Err(e) => {
+            tracing::warn!(target: "app::shutdown", label, error = %e, "退出前保存窗口状态失败")
+        }
+    }
+}
+
+/// 监听 SIGTERM、SIGINT 与 SIGHUP：保存状态后正常退出
+#[cfg(unix)]
+pub fn spawn_signal_listener(app: AppHandle) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    tauri::async_runtime::spawn(async move {
+        let (Ok(mut term), Ok(mut int), Ok(mut hup)) = (
+            signal(SignalKind::terminate()),
+            signal(SignalKind::interrupt()),
+            signal(SignalKind::hangup()),
+        ) else {
+            tracing::warn!(target: "app::shutdown", "注册退出信号监听失败");
+            return;
+        };
+
+        let name = tokio::select! {
+            _ = term.recv() => "SIGTERM",
+            _ = int.recv() => "SIGINT",
+            _ = hup.recv() => "SIGHUP",
+        };
+        tracing::info!(target: "app::shutdown", signal = name, "收到退出信号，保存状态后退出");
+
+        let handle = app.clone();
+        if let Err(e) = tokio::task::spawn_blocking(move || flush_state(&handle, name)).await {
+            tracing::error!(target: "app::shutdown", error = %e, "退出前保存状态失败");
+        }
+        app.exit(0);
+    });
+}
+
+/// Windows 下系统关机与注销经由窗口消息进入正常退出流程，无需监听信号
+#[cfg(not(unix))]
+pub fn spawn_signal_listener(_app: AppHandle) {}
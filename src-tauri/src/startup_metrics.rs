@@ -0,0 +1,120 @@
+//! 启动性能统计
+//! 记录启动各阶段耗时（相对进程启动时间），通过 `get_startup_metrics` 命令查看
+
+use serde::Serialize;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+static PROCESS_START: OnceLock<Instant> = OnceLock::new();
+
+static PHASES: Mutex<Vec<StartupPhase>> = Mutex::new(Vec::new());
+
+/// 单个启动阶段
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupPhase {
+    pub name: String,
+    /// 相对进程启动的开始时间
+    pub start_ms: f64,
+    pub duration_ms: f64,
+    /// 是否在后台执行（不阻塞窗口显示）
+    pub background: bool,
+}
+
+/// 启动性能统计
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupMetrics {
+    pub phases: Vec<StartupPhase>,
+    /// 进程启动到页面首次加载完成的耗时
+    pub time_to_window_ms: Option<f64>,
+}
+
+/// 页面首次加载完成的阶段名
+const WINDOW_READY: &str = "window_ready";
+
+/// 记录进程启动时间（在 main 开头调用）
+pub fn mark_process_start() {
+    PROCESS_START.get_or_init(Instant::now);
+}
+
+fn since_start(instant: Instant) -> f64 {
+    let start = *PROCESS_START.get_or_init(Instant::now);
+    instant.saturating_duration_since(start).as_secs_f64() * 1000.0
+}
+
+fn push(phase: StartupPhase) {
+    tracing::debug!(
+        target: "app::startup::metrics",
+        phase = %phase.name,
+        duration_ms = phase.duration_ms,
+        background = phase.background,
+        "启动阶段完成"
+    );
+    PHASES.lock().unwrap_or_else(|e| e.into_inner()).push(phase);
+}
+
+/// 阶段计时器，离开作用域时记录耗时
+pub struct PhaseTimer {
+    name: &'static str,
+    start: Instant,
+    background: bool,
+}
+
+impl Drop for PhaseTimer {
+    fn drop(&mut self) {
+        push(StartupPhase {
+            name: self.name.to_string(),
+            start_ms: since_start(self.start),
+            duration_ms: self.start.elapsed().as_secs_f64() * 1000.0,
+            background: self.background,
+        });
+    }
+}
+
+/// 开始一个阻塞启动流程的阶段
+pub fn phase(name: &'static str) -> PhaseTimer {
+    PhaseTimer {
+        name,
+        start: Instant::now(),
+        background: false,
+    }
+}
+
+/// 开始一个后台阶段
+pub fn background_phase(name: &'static str) -> PhaseTimer {
+    PhaseTimer {
+        name,
+        start: Instant::now(),
+        background: true,
+    }
+}
+
+/// 标记页面首次加载完成（只记录一次）
+pub fn mark_window_ready() {
+    let mut phases = PHASES.lock().unwrap_or_else(|e| e.into_inner());
+    if phases.iter().any(|p| p.name == WINDOW_READY) {
+        return;
+    }
+    let now = since_start(Instant::now());
+    phases.push(StartupPhase {
+        name: WINDOW_READY.to_string(),
+        start_ms: now,
+        duration_ms: 0.0,
+        background: false,
+    });
+    tracing::info!(target: "app::startup::metrics", time_to_window_ms = now, "窗口首次加载完成");
+}
+
+/// 当前的启动统计
+pub fn snapshot() -> StartupMetrics {
+    let phases = PHASES.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let time_to_window_ms = phases
+        .iter()
+        .find(|p| p.name == WINDOW_READY)
+        .map(|p| p.start_ms);
+    StartupMetrics {
+        phases,
+        time_to_window_ms,
+    }
+}
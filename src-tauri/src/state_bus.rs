@@ -0,0 +1,153 @@
+//! 状态事件总线
+//! 托盘启用状态、Antigravity 安装与运行状态、当前账户、可用更新、进行中的操作与 Agent 启动的进程由各模块上报到这里，
+//! 合并为一份快照；短时间内的多次变化只推送一次 `agent:state` 事件，前端无需轮询。
+//! 前端启动时调用 `subscribe_state` / `get_full_state` 获取完整快照，之后只需监听事件
+
+use serde::Serialize;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// 状态快照事件名
+pub const AGENT_STATE_EVENT: &str = "agent:state";
+
+/// 合并推送的等待时间
+const COALESCE_DELAY: Duration = Duration::from_millis(150);
+
+/// Agent 状态快照（None 表示尚未检测）
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentStateSnapshot {
+    /// 每次变化递增，前端可据此丢弃过期快照
+    pub revision: u64,
+    pub tray_enabled: Option<bool>,
+    pub antigravity_running: Option<bool>,
+    /// Antigravity 是否已安装
+    pub antigravity_available: Option<bool>,
+    /// 当前登录账户（未检测或未登录时为 None，见 `account_checked`）
+    pub active_account: Option<String>,
+    /// 是否已检测过当前登录账户
+    pub account_checked: bool,
+    /// 可用的 Agent 新版本
+    pub update_available: Option<String>,
+    /// 进行中的操作名（来自操作锁）
+    pub active_operations: Vec<String>,
+    /// Agent 启动且仍在运行的 Antigravity 进程 PID
+    pub launched_pids: Vec<u32>,
+}
+
+/// 状态的一项更新
+#[derive(Debug, Clone)]
+pub enum StateUpdate {
+    TrayEnabled(bool),
+    AntigravityRunning(bool),
+    AntigravityAvailable(bool),
+    /// 当前登录账户（None 表示未登录）
+    ActiveAccount(Option<String>),
+    UpdateAvailable(Option<String>),
+    ActiveOperations(Vec<String>),
+    LaunchedPids(Vec<u32>),
+}
+
+struct Bus {
+    snapshot: AgentStateSnapshot,
+    /// 已安排推送、尚未执行
+    flush_scheduled: bool,
+}
+
+static BUS: Mutex<Bus> = Mutex::new(Bus {
+    snapshot: AgentStateSnapshot {
+        revision: 0,
+        tray_enabled: None,
+        antigravity_running: None,
+        antigravity_available: None,
+        active_account: None,
+        account_checked: false,
+        update_available: None,
+        active_operations: Vec::new(),
+        launched_pids: Vec::new(),
+    },
+    flush_scheduled: false,
+});
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// 绑定应用句柄（在 setup 中调用一次），之前上报的状态在绑定后推送
+pub fn init(app: &AppHandle) {
+    if APP_HANDLE.set(app.clone()).is_ok() {
+        BUS.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .flush_scheduled = true;
+        schedule_flush();
+    }
+}
+
+/// 上报状态变化（内容未变化时不推送）
+pub fn publish(update: StateUpdate) {
+    let mut bus = BUS.lock().unwrap_or_else(|e| e.into_inner());
+    let snapshot = &mut bus.snapshot;
+    let changed = match update {
+        StateUpdate::TrayEnabled(enabled) => replace(&mut snapshot.tray_enabled, Some(enabled)),
+        StateUpdate::AntigravityRunning(running) => {
+            replace(&mut snapshot.antigravity_running, Some(running))
+        }
+        StateUpdate::AntigravityAvailable(available) => {
+            replace(&mut snapshot.antigravity_available, Some(available))
+        }
+        StateUpdate::ActiveAccount(email) => {
+            let checked = !std::mem::replace(&mut snapshot.account_checked, true);
+            replace(&mut snapshot.active_account, email) || checked
+        }
+        StateUpdate::UpdateAvailable(version) => replace(&mut snapshot.update_available, version),
+        StateUpdate::ActiveOperations(operations) => {
+            replace(&mut snapshot.active_operations, operations)
+        }
+        StateUpdate::LaunchedPids(pids) => replace(&mut snapshot.launched_pids, pids),
+    };
+    if !changed {
+        return;
+    }
+
+    snapshot.revision += 1;
+    if APP_HANDLE.get().is_some() && !std::mem::replace(&mut bus.flush_scheduled, true) {
+        drop(bus);
+        schedule_flush();
+    }
+}
+
+fn replace<T: PartialEq>(slot: &mut T, value: T) -> bool {
+    if *slot == value {
+        false
+    } else {
+        *slot = value;
+        true
+    }
+}
+
+/// 当前完整快照
+pub fn snapshot() -> AgentStateSnapshot {
+    BUS.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .snapshot
+        .clone()
+}
+
+/// 等待合并窗口结束后推送最新快照（调用方已设置 `flush_scheduled`）
+fn schedule_flush() {
+    let Some(app) = APP_HANDLE.get().cloned() else {
+        return;
+    };
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(COALESCE_DELAY).await;
+        let snapshot = {
+            let mut bus = BUS.lock().unwrap_or_else(|e| e.into_inner());
+            bus.flush_scheduled = false;
+            bus.snapshot.clone()
+        };
+        tracing::trace!(target: "state_bus", revision = snapshot.revision, "推送状态快照");
+        if let Err(e) = app.emit(AGENT_STATE_EVENT, &snapshot) {
+            tracing::warn!(target: "state_bus", error = %e, "推送状态快照失败");
+        }
+    });
+}
@@ -0,0 +1,123 @@
+//! 备份存储占用统计
+//! 汇总账户备份与各类快照占用的磁盘空间，供界面展示并在占用过大时提示清理。
+//! 删除备份是永久删除（没有回收站），因此不统计回收站占用
+
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// 列出的最大账户备份数量
+const MAX_LARGEST_ACCOUNTS: usize = 5;
+
+/// 总占用超过此值时建议清理
+const PRUNE_THRESHOLD_BYTES: u64 = 200 * 1024 * 1024;
+
+/// 单个账户备份的占用
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUsage {
+    pub email: String,
+    pub file: String,
+    pub size_bytes: u64,
+}
+
+/// 一类快照的数量与占用
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotUsage {
+    pub count: usize,
+    pub size_bytes: u64,
+}
+
+/// 存储占用统计
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageStats {
+    pub account_count: usize,
+    pub account_bytes: u64,
+    /// 占用最大的账户备份（从大到小）
+    pub largest_accounts: Vec<AccountUsage>,
+    /// 数据库文件快照（db-snapshots）
+    pub db_snapshots: SnapshotUsage,
+    /// Antigravity 更新前的快照
+    pub update_snapshots: SnapshotUsage,
+    /// 设备标识快照
+    pub machine_id_snapshots: SnapshotUsage,
+    pub total_bytes: u64,
+    /// 总占用超过阈值，建议清理旧快照或无用备份
+    pub prune_recommended: bool,
+}
+
+/// 目录（递归）占用的字节数
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// 快照目录下每个子目录算一份快照
+fn snapshot_usage(dir: &Path) -> SnapshotUsage {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return SnapshotUsage::default();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .fold(SnapshotUsage::default(), |mut usage, entry| {
+            usage.count += 1;
+            usage.size_bytes += dir_size(&entry.path());
+            usage
+        })
+}
+
+/// 统计账户备份与快照的存储占用
+pub fn collect() -> StorageStats {
+    let mut accounts: Vec<AccountUsage> = crate::antigravity::backup::list_backups()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| {
+            let file = item["file"].as_str()?.to_string();
+            let size_bytes = fs::metadata(&file).map(|m| m.len()).unwrap_or(0);
+            Some(AccountUsage {
+                email: item["email"].as_str().unwrap_or_default().to_string(),
+                file,
+                size_bytes,
+            })
+        })
+        .collect();
+    accounts.sort_by_key(|a| std::cmp::Reverse(a.size_bytes));
+
+    let account_count = accounts.len();
+    let account_bytes = accounts.iter().map(|a| a.size_bytes).sum();
+    accounts.truncate(MAX_LARGEST_ACCOUNTS);
+
+    let db_snapshots = snapshot_usage(&crate::directories::get_db_snapshots_directory());
+    let update_snapshots = snapshot_usage(&crate::directories::get_update_snapshots_directory());
+    let machine_id_snapshots =
+        snapshot_usage(&crate::directories::get_machine_id_snapshots_directory());
+
+    let total_bytes = account_bytes
+        + db_snapshots.size_bytes
+        + update_snapshots.size_bytes
+        + machine_id_snapshots.size_bytes;
+
+    tracing::debug!(target: "storage_stats", account_count, total_bytes, "已统计存储占用");
+    StorageStats {
+        account_count,
+        account_bytes,
+        largest_accounts: accounts,
+        db_snapshots,
+        update_snapshots,
+        machine_id_snapshots,
+        total_bytes,
+        prune_recommended: total_bytes > PRUNE_THRESHOLD_BYTES,
+    }
+}
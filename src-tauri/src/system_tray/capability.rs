@@ -0,0 +1,172 @@
+//! 托盘能力检测
+//!
+//! Linux 下托盘依赖 libayatana-appindicator（或旧版 libappindicator）以及桌面环境提供的
+//! StatusNotifierWatcher 服务，部分桌面（如未安装 AppIndicator 扩展的 GNOME）不会显示托盘图标。
+//! 检测结果在进程内缓存，其他平台始终视为支持
+
+use serde::Serialize;
+use std::sync::OnceLock;
+
+static CAPABILITY: OnceLock<TrayCapability> = OnceLock::new();
+
+/// 托盘能力
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrayCapability {
+    pub supported: bool,
+    /// 不支持的原因
+    pub reason: Option<String>,
+}
+
+/// 当前系统的托盘能力（首次调用时检测）
+pub fn tray_capability() -> &'static TrayCapability {
+    CAPABILITY.get_or_init(|| {
+        let capability = detect();
+        if capability.supported {
+            tracing::debug!(target: "system_tray::capability", "系统托盘可用");
+        } else {
+            tracing::warn!(
+                target: "system_tray::capability",
+                reason = capability.reason.as_deref().unwrap_or_default(),
+                "系统托盘不可用"
+            );
+        }
+        capability
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn detect() -> TrayCapability {
+    if !linux::has_appindicator_library() {
+        return TrayCapability {
+            supported: false,
+            reason: Some(
+                "未找到 libayatana-appindicator3 或 libappindicator3，请安装后重启程序".to_string(),
+            ),
+        };
+    }
+
+    // 无法查询 D-Bus 时不做判断，按支持处理
+    if linux::has_status_notifier_watcher() == Some(false) {
+        return TrayCapability {
+            supported: false,
+            reason: Some(
+                "桌面环境未提供 StatusNotifier 托盘服务（GNOME 需安装 AppIndicator 扩展）"
+                    .to_string(),
+            ),
+        };
+    }
+
+    TrayCapability {
+        supported: true,
+        reason: None,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn detect() -> TrayCapability {
+    TrayCapability {
+        supported: true,
+        reason: None,
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    /// Tauri 按顺序尝试加载的 appindicator 库
+    const LIBRARIES: [&str; 2] = ["libayatana-appindicator3.so.1", "libappindicator3.so.1"];
+
+    const LIB_DIRS: [&str; 8] = [
+        "/usr/lib",
+        "/usr/lib64",
+        "/usr/local/lib",
+        "/lib",
+        "/lib64",
+        "/usr/lib/x86_64-linux-gnu",
+        "/usr/lib/aarch64-linux-gnu",
+        "/app/lib",
+    ];
+
+    const WATCHER_NAMES: [&str; 2] = [
+        "org.kde.StatusNotifierWatcher",
+        "org.freedesktop.StatusNotifierWatcher",
+    ];
+
+    fn search_dirs() -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = std::env::var("LD_LIBRARY_PATH")
+            .map(|paths| std::env::split_paths(&paths).collect())
+            .unwrap_or_default();
+        // AppImage 会把依赖打包到 $APPDIR/usr/lib
+        if let Some(appdir) = std::env::var_os("APPDIR") {
+            dirs.push(PathBuf::from(appdir).join("usr/lib"));
+        }
+        dirs.extend(LIB_DIRS.iter().map(PathBuf::from));
+        dirs
+    }
+
+    pub fn has_appindicator_library() -> bool {
+        let dirs = search_dirs();
+        LIBRARIES
+            .iter()
+            .any(|lib| dirs.iter().any(|dir| dir.join(lib).exists()))
+    }
+
+    /// 查询会话总线上是否有人持有该名称，命令不可用时返回 None
+    fn name_has_owner(name: &str) -> Option<bool> {
+        let output = Command::new("dbus-send")
+            .args([
+                "--session",
+                "--dest=org.freedesktop.DBus",
+                "--type=method_call",
+                "--print-reply",
+                "/org/freedesktop/DBus",
+                "org.freedesktop.DBus.NameHasOwner",
+                &format!("string:{name}"),
+            ])
+            .output()
+            .or_else(|_| {
+                Command::new("gdbus")
+                    .args([
+                        "call",
+                        "--session",
+                        "--dest",
+                        "org.freedesktop.DBus",
+                        "--object-path",
+                        "/org/freedesktop/DBus",
+                        "--method",
+                        "org.freedesktop.DBus.NameHasOwner",
+                        name,
+                    ])
+                    .output()
+            })
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if stdout.contains("true") {
+            Some(true)
+        } else if stdout.contains("false") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// 桌面环境是否提供 StatusNotifierWatcher，无法判断时返回 None
+    pub fn has_status_notifier_watcher() -> Option<bool> {
+        let mut answered = false;
+        for name in WATCHER_NAMES {
+            match name_has_owner(name) {
+                Some(true) => return Some(true),
+                Some(false) => answered = true,
+                None => {}
+            }
+        }
+        answered.then_some(false)
+    }
+}
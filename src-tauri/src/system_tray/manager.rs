@@ -1,82 +1,165 @@
-use tauri::{AppHandle, Manager};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
 
 use crate::app_settings::AppSettingsManager;
+use crate::error::{AgentError, AgentResult};
+
+/// 托盘启用状态变化事件名
+pub const TRAY_STATE_CHANGED_EVENT: &str = "tray:state-changed";
+
+/// 托盘状态（`tray:state-changed` 事件负载）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrayState {
+    pub enabled: bool,
+}
 
 /// 系统托盘管理器
-pub struct SystemTrayManager;
+pub struct SystemTrayManager {
+    /// 串行化启用/禁用，避免设置与托盘图标状态交错
+    transition: Mutex<()>,
+}
 
 impl SystemTrayManager {
     /// 创建新的管理器
     pub fn new() -> Self {
-        Self
+        Self {
+            transition: Mutex::new(()),
+        }
+    }
+
+    /// 启用或禁用系统托盘，返回变更后的启用状态
+    pub fn set_enabled(&self, app_handle: &AppHandle, enabled: bool) -> AgentResult<bool> {
+        if enabled {
+            self.enable(app_handle)?;
+        } else {
+            self.disable(app_handle)?;
+        }
+        Ok(app_handle
+            .state::<AppSettingsManager>()
+            .get_settings()
+            .system_tray_enabled)
     }
 
     /// 启用系统托盘
-    pub fn enable(&self, app_handle: &AppHandle) -> Result<(), String> {
-        // 1. 更新设置
+    pub fn enable(&self, app_handle: &AppHandle) -> AgentResult<()> {
+        let _transition = self.transition.lock().unwrap_or_else(|e| e.into_inner());
+
+        // 0. 当前桌面环境不支持托盘时拒绝启用，避免窗口隐藏后无法找回
+        let capability = crate::system_tray::tray_capability();
+        if !capability.supported {
+            return Err(AgentError::Tray(
+                capability
+                    .reason
+                    .clone()
+                    .unwrap_or_else(|| "当前系统不支持系统托盘".to_string()),
+            ));
+        }
+
+        // 1. 更新设置（状态未变化时不写入，启动时恢复托盘不受只读模式影响）
         let settings_manager = app_handle.state::<AppSettingsManager>();
-        settings_manager
-            .update_settings(|s| s.system_tray_enabled = true)
-            .map_err(|e| e.to_string())?;
+        if !settings_manager.get_settings().system_tray_enabled {
+            settings_manager.update_settings(|s| s.system_tray_enabled = true)?;
+        }
 
         // 2. 检查是否已存在托盘
         if let Some(app_tray) = app_handle.tray_by_id("main") {
-            tracing::info!("显示现有托盘");
+            tracing::info!(target: "system_tray::manager", "显示现有托盘");
             app_tray.set_visible(true).map_err(|e| {
-                tracing::error!("显示托盘图标失败: {e}");
-                e.to_string()
+                tracing::error!(target: "system_tray::manager", error = %e, "显示托盘图标失败");
+                AgentError::Tray(e.to_string())
             })?;
         } else {
             // 创建新的托盘
             crate::system_tray::create_tray_with_return(app_handle)?;
-            tracing::info!("系统托盘已创建");
+            tracing::info!(target: "system_tray::manager", "系统托盘已创建");
         }
 
+        self.apply_dock_visibility(app_handle);
+        emit_state_changed(app_handle, true);
         Ok(())
     }
 
     /// 禁用系统托盘
-    pub fn disable(&self, app_handle: &AppHandle) -> Result<(), String> {
+    pub fn disable(&self, app_handle: &AppHandle) -> AgentResult<()> {
+        let _transition = self.transition.lock().unwrap_or_else(|e| e.into_inner());
+
         // 1. 更新设置
         let settings_manager = app_handle.state::<AppSettingsManager>();
-        settings_manager
-            .update_settings(|s| s.system_tray_enabled = false)
-            .map_err(|e| e.to_string())?;
+        if settings_manager.get_settings().system_tray_enabled {
+            settings_manager.update_settings(|s| s.system_tray_enabled = false)?;
+        }
 
         // 2. 隐藏托盘
         if let Some(app_tray) = app_handle.tray_by_id("main") {
             app_tray.set_visible(false).map_err(|e| {
-                tracing::error!("隐藏托盘图标失败: {e}");
-                e.to_string()
+                tracing::error!(target: "system_tray::manager", error = %e, "隐藏托盘图标失败");
+                AgentError::Tray(e.to_string())
             })?;
-            tracing::info!("托盘图标已隐藏");
+            tracing::info!(target: "system_tray::manager", "托盘图标已隐藏");
         }
 
+        // 托盘关闭后必须恢复 Dock 图标，否则窗口隐藏后无法再打开
+        self.apply_dock_visibility(app_handle);
+        emit_state_changed(app_handle, false);
         Ok(())
     }
 
-    /// 检查系统托盘是否应启用（基于设置）
+    /// 根据设置显示或隐藏 macOS Dock 图标（仅在托盘启用时隐藏），其他平台无操作
+    pub fn apply_dock_visibility(&self, app_handle: &AppHandle) {
+        #[cfg(target_os = "macos")]
+        {
+            let settings = app_handle.state::<AppSettingsManager>().get_settings();
+            let hide = settings.hide_dock_icon && settings.system_tray_enabled;
+            let policy = if hide {
+                tauri::ActivationPolicy::Accessory
+            } else {
+                tauri::ActivationPolicy::Regular
+            };
+            match app_handle.set_activation_policy(policy) {
+                Ok(()) => {
+                    tracing::info!(target: "system_tray::manager", hide, "已更新 Dock 图标显示状态")
+                }
+                Err(e) => {
+                    tracing::warn!(target: "system_tray::manager", error = %e, "设置 Dock 图标显示状态失败")
+                }
+            }
+        }
+        #[cfg(not(target_os = "macos"))]
+        let _ = app_handle;
+    }
+
+    /// 检查系统托盘是否应启用（基于设置，当前系统不支持托盘时始终为 false）
     pub fn is_enabled_setting(&self, app_handle: &AppHandle) -> bool {
-        app_handle
-            .state::<AppSettingsManager>()
-            .get_settings()
-            .system_tray_enabled
+        crate::system_tray::tray_capability().supported
+            && app_handle
+                .state::<AppSettingsManager>()
+                .get_settings()
+                .system_tray_enabled
     }
 
     /// 最小化窗口到托盘
-    pub fn minimize_to_tray(&self, app_handle: &AppHandle) -> Result<(), String> {
+    pub fn minimize_to_tray(&self, app_handle: &AppHandle) -> AgentResult<()> {
         if let Some(window) = app_handle.get_webview_window("main") {
-            window.hide().map_err(|e| e.to_string())?;
+            window.hide().map_err(|e| AgentError::Tray(e.to_string()))?;
         }
         Ok(())
     }
 
     /// 从托盘恢复窗口
-    pub fn restore_from_tray(&self, app_handle: &AppHandle) -> Result<(), String> {
+    pub fn restore_from_tray(&self, app_handle: &AppHandle) -> AgentResult<()> {
         if let Some(window) = app_handle.get_webview_window("main") {
-            window.show().map_err(|e| e.to_string())?;
-            window.set_focus().map_err(|e| e.to_string())?;
+            crate::window::event_handler::show_window(&window)
+                .map_err(|e| AgentError::Tray(e.to_string()))?;
         }
         Ok(())
     }
 }
+
+fn emit_state_changed(app_handle: &AppHandle, enabled: bool) {
+    crate::state_bus::publish(crate::state_bus::StateUpdate::TrayEnabled(enabled));
+    if let Err(e) = app_handle.emit(TRAY_STATE_CHANGED_EVENT, TrayState { enabled }) {
+        tracing::warn!(target: "system_tray::manager", error = %e, "推送托盘状态变化事件失败");
+    }
+}
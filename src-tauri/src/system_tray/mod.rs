@@ -2,9 +2,14 @@
 //!
 //! 使用 Tauri 2.9 内置 API 实现后端控制托盘，前端通过命令更新菜单
 
+pub mod capability;
 pub mod manager;
 pub mod tray;
 
 // Re-export the main structs for convenience
+pub use capability::tray_capability;
 pub use manager::SystemTrayManager;
-pub use tray::{create_tray_with_return, update_tray_menu};
+pub use tray::{
+    create_tray_with_return, refresh_tray_icon, refresh_tray_language, set_tray_busy,
+    update_tray_menu, update_tray_tooltip, TooltipUpdate,
+};
@@ -2,160 +2,553 @@
 //!
 //! 使用 Tauri 2.9 内置的 tray API 实现后端控制托盘
 
+use crate::account_metadata::AccountIdentity;
 use crate::app_settings::AppSettingsManager;
+use crate::error::{AgentError, AgentResult};
+use crate::utils::email_mask::mask_email;
+use crate::utils::i18n::{t, t_with};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::image::Image;
 use tauri::menu::{Menu, MenuBuilder, MenuItem};
 use tauri::tray::{TrayIcon, TrayIconBuilder};
 use tauri::{AppHandle, Emitter, Manager};
 
+/// 托盘请求切换账户事件名
+pub const TRAY_SWITCH_ACCOUNT_EVENT: &str = "tray-switch-account";
+
+/// 托盘立即备份完成事件名
+pub const TRAY_BACKUP_FINISHED_EVENT: &str = "tray:backup-finished";
+
+/// 托盘清除全部数据完成事件名
+pub const TRAY_CLEAR_DATA_FINISHED_EVENT: &str = "tray:clear-data-finished";
+
+/// 托盘复制当前账户邮箱完成事件名
+pub const TRAY_COPY_FINISHED_EVENT: &str = "tray:copy-finished";
+
+/// 账户菜单项 ID 前缀（后接不透明的账户 ID，不含邮箱）
+const ACCOUNT_MENU_PREFIX: &str = "account_";
+
+/// 托盘操作结果（`tray:backup-finished`、`tray:clear-data-finished` 事件负载）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrayActionEvent {
+    pub success: bool,
+    pub message: String,
+}
+
+/// 最近一次前端提供的账户列表
+static MENU_ACCOUNTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
 /// 创建系统托盘（返回托盘实例）
-pub fn create_tray_with_return(app: &AppHandle) -> Result<TrayIcon, String> {
+pub fn create_tray_with_return(app: &AppHandle) -> AgentResult<TrayIcon> {
     // 创建基础菜单（账户列表将由前端动态更新）
     let menu = create_basic_menu(app)?;
 
     // 构建托盘图标
-    let tray = TrayIconBuilder::with_id("main")
+    let mut builder = TrayIconBuilder::with_id("main")
         .menu(&menu)
         .on_menu_event(handle_tray_menu_event)
-        .show_menu_on_left_click(true)
-        .build(app)
-        .map_err(|e| format!("创建系统托盘失败: {e}"))?;
+        .show_menu_on_left_click(true);
 
-    // 设置托盘图标
-    if let Some(icon) = app.default_window_icon() {
-        tray.set_icon(Some(icon.clone()))
-            .map_err(|e| format!("设置托盘图标失败: {e}"))?;
+    // 设置托盘图标（macOS 使用模板图标，由系统按浅色/深色菜单栏自动着色）
+    if let Some(icon) = status_icon(app) {
+        builder = builder.icon(icon);
+        #[cfg(target_os = "macos")]
+        {
+            builder = builder.icon_as_template(true);
+        }
     }
 
+    let tray = builder
+        .build(app)
+        .map_err(|e| AgentError::Tray(format!("创建系统托盘失败: {e}")))?;
+
     Ok(tray)
 }
 
+/// 托盘的状态图标（由应用图标生成）
+///
+/// macOS 使用模板图标由系统着色，其他平台按当前主题使用浅色或深色的单色图标
+fn status_icon(app: &AppHandle) -> Option<Image<'static>> {
+    let icon = app.default_window_icon()?;
+    #[cfg(target_os = "macos")]
+    {
+        Some(template_icon(icon))
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Some(themed_icon(icon, crate::appearance::is_dark()))
+    }
+}
+
+/// 由应用图标生成单色图标：深色主题下为白色，浅色主题下为深灰色
+#[cfg(not(target_os = "macos"))]
+fn themed_icon(icon: &Image<'_>, dark: bool) -> Image<'static> {
+    let shade = if dark { 0xff } else { 0x30 };
+    let rgba = icon
+        .rgba()
+        .chunks_exact(4)
+        .flat_map(|px| {
+            let luminance = (px[0] as u32 * 299 + px[1] as u32 * 587 + px[2] as u32 * 114) / 1000;
+            let alpha = (px[3] as u32 * luminance / 255) as u8;
+            [shade, shade, shade, alpha]
+        })
+        .collect();
+    Image::new_owned(rgba, icon.width(), icon.height())
+}
+
+/// 主题变化后更新托盘图标（忙碌动画进行中时由动画结束后恢复）
+pub fn refresh_tray_icon(app: &AppHandle) {
+    if BUSY.load(Ordering::SeqCst) {
+        return;
+    }
+    if let (Some(tray), Some(icon)) = (app.tray_by_id("main"), status_icon(app)) {
+        set_tray_icon(&tray, &icon);
+        tracing::debug!(target: "system_tray::tray", dark = crate::appearance::is_dark(), "托盘图标已按主题更新");
+    }
+}
+
+/// 忙碌动画中与状态图标交替显示的半透明图标
+fn dimmed_icon(icon: &Image<'_>) -> Image<'static> {
+    let rgba = icon
+        .rgba()
+        .chunks_exact(4)
+        .flat_map(|px| [px[0], px[1], px[2], px[3] / 3])
+        .collect();
+    Image::new_owned(rgba, icon.width(), icon.height())
+}
+
+/// 设置托盘图标（macOS 需要重新标记为模板图标）
+fn set_tray_icon(tray: &TrayIcon, icon: &Image<'static>) {
+    if let Err(e) = tray.set_icon(Some(icon.clone())) {
+        tracing::debug!(target: "system_tray::tray", error = %e, "设置托盘图标失败");
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let _ = tray.set_icon_as_template(true);
+    }
+}
+
+/// 是否有长耗时操作在进行
+static BUSY: AtomicBool = AtomicBool::new(false);
+
+/// 忙碌动画的代数，每次开始或结束时递增，过期的动画任务据此退出
+static BUSY_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 忙碌动画每帧的时长
+const BUSY_FRAME_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 开始或结束托盘忙碌动画（由任务管理器在第一个任务开始、最后一个任务结束时调用）
+///
+/// 动画期间状态图标与半透明图标交替显示，结束后恢复状态图标
+pub fn set_tray_busy(app: &AppHandle, busy: bool) {
+    if BUSY.swap(busy, Ordering::SeqCst) == busy {
+        return;
+    }
+    let generation = BUSY_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if !busy {
+        // 动画任务检测到代数变化后恢复状态图标
+        return;
+    }
+
+    let (Some(tray), Some(icon)) = (app.tray_by_id("main"), status_icon(app)) else {
+        return;
+    };
+    let dimmed = dimmed_icon(&icon);
+    tracing::debug!(target: "system_tray::tray", "开始托盘忙碌动画");
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut dim = false;
+        while BUSY_GENERATION.load(Ordering::SeqCst) == generation {
+            dim = !dim;
+            set_tray_icon(&tray, if dim { &dimmed } else { &icon });
+            tokio::time::sleep(BUSY_FRAME_INTERVAL).await;
+        }
+        if !BUSY.load(Ordering::SeqCst) {
+            // 动画期间主题可能已变化，重新生成状态图标
+            set_tray_icon(&tray, &status_icon(&app).unwrap_or(icon));
+            tracing::debug!(target: "system_tray::tray", "托盘忙碌动画结束");
+        }
+    });
+}
+
+/// 由应用图标生成 macOS 菜单栏模板图标
+///
+/// 模板图标只使用 alpha 通道：图标中浅色的图形保留为不透明，深色背景变为透明
+#[cfg(target_os = "macos")]
+fn template_icon(icon: &Image<'_>) -> Image<'static> {
+    let rgba = icon
+        .rgba()
+        .chunks_exact(4)
+        .flat_map(|px| {
+            let luminance = (px[0] as u32 * 299 + px[1] as u32 * 587 + px[2] as u32 * 114) / 1000;
+            let alpha = (px[3] as u32 * luminance / 255) as u8;
+            [0, 0, 0, alpha]
+        })
+        .collect();
+    Image::new_owned(rgba, icon.width(), icon.height())
+}
+
 /// 创建基础菜单（不含账户列表）
-fn create_basic_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, String> {
-    MenuBuilder::new(app)
+fn create_basic_menu(app: &AppHandle) -> AgentResult<Menu<tauri::Wry>> {
+    build_menu(app, &[])
+}
+
+/// 按当前语言构建托盘菜单（账户列表菜单项 ID 使用不透明的账户 ID）
+fn build_menu(app: &AppHandle, identities: &[AccountIdentity]) -> AgentResult<Menu<tauri::Wry>> {
+    let mut menu_builder = MenuBuilder::new(app)
         .item(
-            &MenuItem::with_id(app, "show_main", "显示主窗口", true, None::<&str>)
-                .map_err(|e| format!("创建显示主窗口菜单失败: {e}"))?,
+            &MenuItem::with_id(app, "show_main", t("tray.show_main"), true, None::<&str>)
+                .map_err(|e| AgentError::Tray(format!("创建显示主窗口菜单失败: {e}")))?,
         )
+        .item(
+            &MenuItem::with_id(app, "backup_now", t("tray.backup_now"), true, None::<&str>)
+                .map_err(|e| AgentError::Tray(format!("创建立即备份菜单失败: {e}")))?,
+        )
+        .item(
+            &MenuItem::with_id(
+                app,
+                "copy_active_email",
+                t("tray.copy_active_email"),
+                true,
+                None::<&str>,
+            )
+            .map_err(|e| AgentError::Tray(format!("创建复制邮箱菜单失败: {e}")))?,
+        );
+
+    // 添加账户列表
+    if !identities.is_empty() {
+        menu_builder = menu_builder.separator();
+
+        for identity in identities {
+            menu_builder = menu_builder.item(
+                &MenuItem::with_id(
+                    app,
+                    format!("{ACCOUNT_MENU_PREFIX}{}", identity.id),
+                    display_name(identity),
+                    true,
+                    None::<&str>,
+                )
+                .map_err(|e| AgentError::Tray(format!("创建账户菜单失败: {e}")))?,
+            );
+        }
+    }
+
+    // 清除数据与退出应用
+    menu_builder
         .separator()
         .item(
-            &MenuItem::with_id(app, "quit", "退出应用", true, None::<&str>)
-                .map_err(|e| format!("创建退出菜单失败: {e}"))?,
+            &MenuItem::with_id(
+                app,
+                "clear_all_data",
+                t("tray.clear_all_data"),
+                true,
+                None::<&str>,
+            )
+            .map_err(|e| AgentError::Tray(format!("创建清除数据菜单失败: {e}")))?,
+        )
+        .item(
+            &MenuItem::with_id(app, "quit", t("tray.quit"), true, None::<&str>)
+                .map_err(|e| AgentError::Tray(format!("创建退出菜单失败: {e}")))?,
         )
         .build()
-        .map_err(|e| format!("构建基础菜单失败: {e}"))
+        .map_err(|e| AgentError::Tray(format!("构建托盘菜单失败: {e}")))
+}
+
+/// 托盘菜单中立即备份当前账户，结果通过 `tray:backup-finished` 事件通知前端
+fn backup_now(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = crate::tasks::run_task(&app, None, "backup_account", |_task| {
+            crate::commands::save_antigravity_current_account(Some("托盘：立即备份".to_string()))
+        })
+        .await;
+        let event = TrayActionEvent {
+            success: result.is_ok(),
+            message: match &result {
+                Ok(message) => message.clone(),
+                Err(e) => e.to_string(),
+            },
+        };
+        match result {
+            Ok(_) => {
+                tracing::info!(target: "system_tray::tray", "托盘立即备份完成");
+                update_tray_tooltip(&app, TooltipUpdate::LastBackup(Some(chrono::Local::now())));
+            }
+            Err(e) => tracing::warn!(target: "system_tray::tray", error = %e, "托盘立即备份失败"),
+        }
+        if let Err(e) = app.emit(TRAY_BACKUP_FINISHED_EVENT, &event) {
+            tracing::error!(target: "system_tray::tray", error = %e, "发射托盘备份结果事件失败");
+        }
+    });
+}
+
+/// 托盘菜单中复制当前账户邮箱，结果通过 `tray:copy-finished` 事件通知前端
+fn copy_active_email(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let content = crate::commands::ClipboardContent::ActiveAccountEmail;
+        let result = match crate::commands::clipboard_text(&app, content).await {
+            Ok(email) => crate::utils::clipboard::copy(email, None).await,
+            Err(e) => Err(e),
+        };
+        let event = TrayActionEvent {
+            success: result.is_ok(),
+            message: match &result {
+                Ok(()) => "已复制当前账户邮箱".to_string(),
+                Err(e) => e.to_string(),
+            },
+        };
+        if let Err(e) = &result {
+            tracing::warn!(target: "system_tray::tray", error = %e, "托盘复制账户邮箱失败");
+        }
+        if let Err(e) = app.emit(TRAY_COPY_FINISHED_EVENT, &event) {
+            tracing::error!(target: "system_tray::tray", error = %e, "发射托盘复制结果事件失败");
+        }
+    });
+}
+
+/// 托盘菜单中清除 Antigravity 全部登录数据（原生对话框确认后执行，会先关闭 Antigravity），
+/// 结果通过 `tray:clear-data-finished` 事件通知前端
+fn clear_all_data(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if !crate::dialog::confirm(&app, crate::dialog::ConfirmPrompt::ClearAllData).await {
+            return;
+        }
+
+        // 已通过原生对话框确认，直接签发令牌
+        let token =
+            crate::confirmation::request(crate::confirmation::DangerousOperation::LogoutAll).token;
+        let result = crate::commands::clear_all_antigravity_data(Some(token), Some(true)).await;
+        let event = TrayActionEvent {
+            success: result.is_ok(),
+            message: match &result {
+                Ok(message) => message.clone(),
+                Err(e) => e.to_string(),
+            },
+        };
+        match result {
+            Ok(_) => tracing::info!(target: "system_tray::tray", "托盘清除数据完成"),
+            Err(e) => tracing::warn!(target: "system_tray::tray", error = %e, "托盘清除数据失败"),
+        }
+        if let Err(e) = app.emit(TRAY_CLEAR_DATA_FINISHED_EVENT, &event) {
+            tracing::error!(target: "system_tray::tray", error = %e, "发射托盘清除数据结果事件失败");
+        }
+    });
+}
+
+/// 托盘菜单中退出应用：有任务进行中时先通过原生对话框确认
+fn quit(app: &AppHandle) {
+    if app.state::<crate::tasks::TaskManager>().list().is_empty() {
+        tracing::info!(target: "system_tray::tray", "退出应用");
+        app.exit(0);
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if crate::dialog::confirm(&app, crate::dialog::ConfirmPrompt::QuitWhileBusy).await {
+            tracing::warn!(target: "system_tray::tray", "任务进行中退出应用");
+            app.exit(0);
+        }
+    });
 }
 
 /// 处理托盘菜单事件
 fn handle_tray_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
-    tracing::info!("处理托盘菜单事件: {}", event.id.0);
+    tracing::info!(target: "system_tray::tray", menu_id = %event.id.0, "处理托盘菜单事件");
 
     match event.id.0.as_str() {
         "show_main" => {
             if let Some(window) = app.get_webview_window("main") {
-                let _ = window.unminimize();
-                let _ = window.show();
-                let _ = window.set_focus();
+                let _ = crate::window::event_handler::show_window(&window);
             }
         }
-        "quit" => {
-            tracing::info!("退出应用");
-            app.exit(0);
-        }
+        "backup_now" => backup_now(app),
+        "copy_active_email" => copy_active_email(app),
+        "clear_all_data" => clear_all_data(app),
+        "quit" => quit(app),
         // 账户切换事件
-        account_id if account_id.starts_with("account_") => {
-            let account_email = account_id.strip_prefix("account_").unwrap_or("");
-            tracing::info!("请求切换到账户: {account_email}");
+        menu_id if menu_id.starts_with(ACCOUNT_MENU_PREFIX) => {
+            let account_id = &menu_id[ACCOUNT_MENU_PREFIX.len()..];
+            let Some(identity) = crate::account_metadata::resolve_id(account_id) else {
+                tracing::warn!(target: "system_tray::tray", account_id, "未找到托盘菜单对应的账户");
+                return;
+            };
+            tracing::info!(target: "system_tray::tray", account_id, "请求切换到账户");
 
-            // 发射事件到前端
-            if let Err(e) = app.emit("tray-switch-account", account_email) {
-                tracing::error!("发射账户切换事件失败: {e}");
+            // 发射事件到前端（包含 ID 与别名，前端据此切换并提示）
+            if let Err(e) = app.emit(TRAY_SWITCH_ACCOUNT_EVENT, &identity) {
+                tracing::error!(target: "system_tray::tray", error = %e, "发射账户切换事件失败");
             }
         }
         _ => {
-            tracing::warn!("未处理的菜单事件: {}", event.id.0);
+            tracing::warn!(target: "system_tray::tray", menu_id = %event.id.0, "未处理的菜单事件");
         }
     }
 }
 
 /// 更新托盘菜单（添加账户列表）
-pub fn update_tray_menu(app: &AppHandle, accounts: Vec<String>) -> Result<(), String> {
+pub fn update_tray_menu(app: &AppHandle, accounts: Vec<String>) -> AgentResult<()> {
     // 检查托盘是否应该启用
     let settings_manager = app.state::<AppSettingsManager>();
     let settings = settings_manager.get_settings();
 
     if !settings.system_tray_enabled {
-        tracing::info!("托盘已禁用，跳过菜单更新");
+        tracing::info!(target: "system_tray::tray", "托盘已禁用，跳过菜单更新");
         return Ok(());
     }
 
     let Some(tray) = app.tray_by_id("main") else {
-        return Err("未找到系统托盘".to_string());
+        return Err(AgentError::Tray("未找到系统托盘".to_string()));
     };
 
+    // 记录账户列表，切换语言时据此重建菜单
+    *MENU_ACCOUNTS.lock().unwrap_or_else(|e| e.into_inner()) = accounts.clone();
+
     // 创建包含账户列表的完整菜单
-    let mut menu_builder = MenuBuilder::new(app);
+    let identities = crate::account_metadata::ensure_identities(&accounts)?;
+    let new_menu = build_menu(app, &identities)?;
 
-    // 显示主窗口
-    menu_builder = menu_builder.item(
-        &MenuItem::with_id(app, "show_main", "显示主窗口", true, None::<&str>)
-            .map_err(|e| format!("创建显示主窗口菜单失败: {e}"))?,
-    );
+    tray.set_menu(Some(new_menu))
+        .map_err(|e| AgentError::Tray(format!("设置托盘菜单失败: {e}")))?;
 
-    // 添加账户列表
-    if !accounts.is_empty() {
-        menu_builder = menu_builder.separator();
+    tracing::info!(target: "system_tray::tray", account_count = accounts.len(), "托盘菜单已更新");
+    Ok(())
+}
 
-        for account in &accounts {
-            let masked_email = mask_email(account);
-            menu_builder = menu_builder.item(
-                &MenuItem::with_id(
-                    app,
-                    format!("account_{}", account),
-                    &masked_email,
-                    true,
-                    None::<&str>,
-                )
-                .map_err(|e| format!("创建账户菜单失败: {e}"))?,
-            );
+/// 托盘提示状态的一项更新
+#[derive(Debug, Clone)]
+pub enum TooltipUpdate {
+    /// 当前登录账户（None 表示未登录）
+    Account(Option<String>),
+    /// Antigravity 是否正在运行
+    AntigravityRunning(bool),
+    /// Antigravity 是否已安装
+    AntigravityAvailable(bool),
+    /// 当前账户最近一次备份的时间
+    LastBackup(Option<chrono::DateTime<chrono::Local>>),
+    /// 可用的 Agent 新版本（None 清除标记）
+    UpdateAvailable(Option<String>),
+}
+
+/// 托盘提示中展示的状态（None 表示尚未检测）
+struct TooltipState {
+    account: Option<Option<String>>,
+    antigravity_running: Option<bool>,
+    antigravity_available: Option<bool>,
+    last_backup: Option<Option<chrono::DateTime<chrono::Local>>>,
+    update_version: Option<String>,
+    /// 最近一次设置的提示文本，内容不变时不重复设置
+    rendered: Option<String>,
+}
+
+static TOOLTIP_STATE: Mutex<TooltipState> = Mutex::new(TooltipState {
+    account: None,
+    antigravity_running: None,
+    antigravity_available: None,
+    last_backup: None,
+    update_version: None,
+    rendered: None,
+});
+
+impl TooltipState {
+    fn apply(&mut self, update: TooltipUpdate) {
+        match update {
+            TooltipUpdate::Account(email) => self.account = Some(email),
+            TooltipUpdate::AntigravityRunning(running) => self.antigravity_running = Some(running),
+            TooltipUpdate::AntigravityAvailable(available) => {
+                self.antigravity_available = Some(available)
+            }
+            TooltipUpdate::LastBackup(time) => self.last_backup = Some(time),
+            TooltipUpdate::UpdateAvailable(version) => self.update_version = version,
         }
     }
 
-    // 退出应用
-    menu_builder = menu_builder.separator().item(
-        &MenuItem::with_id(app, "quit", "退出应用", true, None::<&str>)
-            .map_err(|e| format!("创建退出菜单失败: {e}"))?,
-    );
+    fn render(&self) -> String {
+        let mut lines = vec!["Antigravity Agent".to_string()];
 
-    // 构建并设置新菜单
-    let new_menu = menu_builder
-        .build()
-        .map_err(|e| format!("构建新菜单失败: {e}"))?;
+        match &self.account {
+            Some(Some(email)) => lines.push(t_with("tray.account", &mask_email(email))),
+            Some(None) => lines.push(t("tray.not_logged_in").to_string()),
+            None => {}
+        }
+        if self.antigravity_available == Some(false) {
+            lines.push(t("tray.not_installed").to_string());
+        } else if let Some(running) = self.antigravity_running {
+            lines.push(
+                t(if running {
+                    "tray.running"
+                } else {
+                    "tray.not_running"
+                })
+                .to_string(),
+            );
+        }
+        match &self.last_backup {
+            Some(Some(time)) => lines.push(t_with(
+                "tray.last_backup",
+                &time.format("%m-%d %H:%M").to_string(),
+            )),
+            Some(None) => lines.push(t("tray.no_backup").to_string()),
+            None => {}
+        }
+        if let Some(version) = &self.update_version {
+            lines.push(t_with("tray.update_available", version));
+        }
 
-    tray.set_menu(Some(new_menu))
-        .map_err(|e| format!("设置托盘菜单失败: {e}"))?;
+        lines.join("\n")
+    }
+}
 
-    tracing::info!("✅ 托盘菜单已更新，包含 {} 个账户", accounts.len());
-    Ok(())
+/// 更新托盘提示（各监听器、更新检查等统一通过此接口上报状态）
+pub fn update_tray_tooltip(app: &AppHandle, update: TooltipUpdate) {
+    let mut state = TOOLTIP_STATE.lock().unwrap_or_else(|e| e.into_inner());
+    state.apply(update);
+    render_tooltip(app, &mut state);
 }
 
-/// 邮箱打码函数
-fn mask_email(email: &str) -> String {
-    let parts: Vec<&str> = email.split('@').collect();
-    if parts.len() != 2 {
-        return email.to_string();
+fn render_tooltip(app: &AppHandle, state: &mut TooltipState) {
+    let tooltip = state.render();
+    if state.rendered.as_deref() == Some(tooltip.as_str()) {
+        return;
     }
 
-    let (local_part, domain) = (parts[0], parts[1]);
+    let Some(tray) = app.tray_by_id("main") else {
+        return;
+    };
+    match tray.set_tooltip(Some(&tooltip)) {
+        Ok(()) => state.rendered = Some(tooltip),
+        Err(e) => tracing::warn!(target: "system_tray::tray", error = %e, "更新托盘提示失败"),
+    }
+}
+
+/// 界面语言变化后按新语言重建托盘菜单与提示
+pub fn refresh_tray_language(app: &AppHandle) -> AgentResult<()> {
+    render_tooltip(
+        app,
+        &mut TOOLTIP_STATE.lock().unwrap_or_else(|e| e.into_inner()),
+    );
 
-    match local_part.len() {
-        0 => email.to_string(),
-        1 => format!("{}*@{}", &local_part[..1], domain),
-        2 => format!("{}*@{}", &local_part[..1], domain),
-        _ => format!(
-            "{}***{}@{}",
-            &local_part[..1],
-            &local_part[local_part.len() - 1..],
-            domain
-        ),
+    if app.tray_by_id("main").is_none() {
+        return Ok(());
     }
+    let accounts = MENU_ACCOUNTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    update_tray_menu(app, accounts)
+}
+
+/// 账户菜单显示名称：有别名时使用别名，否则使用打码后的邮箱
+fn display_name(identity: &AccountIdentity) -> String {
+    identity
+        .alias
+        .clone()
+        .unwrap_or_else(|| mask_email(&identity.email))
 }
@@ -0,0 +1,196 @@
+//! 任务日志
+//! 把正在运行的长耗时任务写入磁盘。Agent 在恢复、切换等操作中途被关闭时，下次启动据此发现
+//! 中断的操作，由用户选择继续执行（重新执行同一操作）、回滚到操作前的数据库快照或忽略
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::sync::Mutex;
+
+use crate::error::{AgentError, AgentResult};
+
+/// 中断后可以重新执行的任务类型
+const RESUMABLE_KINDS: [&str; 2] = ["restore_account", "switch_account"];
+
+/// 任务日志中的一项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    pub id: String,
+    /// 任务类型，如 `switch_account`、`restore_account`
+    pub kind: String,
+    /// 操作对象（如账户备份名），重新执行时使用
+    pub target: Option<String>,
+    /// 影响操作结果的选项（如是否替换当前会话），重新执行时原样传回
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub options: Map<String, Value>,
+    pub started_at: String,
+    /// 中断前最后一次上报的进度
+    pub message: Option<String>,
+}
+
+/// 中断的任务
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterruptedTask {
+    #[serde(flatten)]
+    pub entry: JournalEntry,
+    /// 能否重新执行
+    pub resumable: bool,
+    /// 操作开始后创建的数据库快照，可回滚到操作前的状态
+    pub rollback_snapshot: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Journal {
+    /// 正在运行的任务
+    running: Vec<JournalEntry>,
+    /// 上次运行时未结束、尚未处理的任务
+    interrupted: Vec<JournalEntry>,
+}
+
+/// 内存中的任务日志（None 表示尚未从磁盘加载）
+static JOURNAL: Mutex<Option<Journal>> = Mutex::new(None);
+
+fn read_journal() -> Journal {
+    fs::read_to_string(crate::directories::get_task_journal_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_journal(journal: &Journal) {
+    let path = crate::directories::get_task_journal_file();
+    let result = serde_json::to_string_pretty(journal)
+        .map_err(AgentError::from)
+        .and_then(|content| Ok(fs::write(&path, content)?));
+    if let Err(e) = result {
+        tracing::warn!(target: "task_journal", path = %path.display(), error = %e, "写入任务日志失败");
+    }
+}
+
+/// 修改任务日志并写回磁盘
+fn update<T>(f: impl FnOnce(&mut Journal) -> T) -> T {
+    let mut guard = JOURNAL.lock().unwrap_or_else(|e| e.into_inner());
+    let journal = guard.get_or_insert_with(read_journal);
+    let result = f(journal);
+    write_journal(journal);
+    result
+}
+
+/// 把内存中的任务日志写回磁盘（退出前调用；尚未加载时没有需要写入的内容）
+pub fn flush() {
+    let guard = JOURNAL.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(journal) = guard.as_ref() {
+        write_journal(journal);
+    }
+}
+
+/// 读取任务日志
+fn read<T>(f: impl FnOnce(&Journal) -> T) -> T {
+    let mut guard = JOURNAL.lock().unwrap_or_else(|e| e.into_inner());
+    f(guard.get_or_insert_with(read_journal))
+}
+
+/// 启动时加载任务日志：上次运行时未结束的任务转为中断任务
+pub fn load_at_startup() {
+    update(|journal| {
+        let unfinished = std::mem::take(&mut journal.running);
+        for entry in &unfinished {
+            tracing::warn!(
+                target: "task_journal",
+                task_id = %entry.id,
+                kind = %entry.kind,
+                started_at = %entry.started_at,
+                "发现上次运行时中断的任务"
+            );
+        }
+        journal.interrupted.extend(unfinished);
+    });
+}
+
+/// 记录任务开始
+pub fn record_start(id: &str, kind: &str, started_at: &str) {
+    update(|journal| {
+        journal.running.push(JournalEntry {
+            id: id.to_string(),
+            kind: kind.to_string(),
+            target: None,
+            options: Map::new(),
+            started_at: started_at.to_string(),
+            message: None,
+        })
+    });
+}
+
+fn update_running(id: &str, f: impl FnOnce(&mut JournalEntry)) {
+    update(|journal| {
+        if let Some(entry) = journal.running.iter_mut().find(|e| e.id == id) {
+            f(entry);
+        }
+    });
+}
+
+/// 记录任务的操作对象
+pub fn record_target(id: &str, target: &str) {
+    update_running(id, |entry| entry.target = Some(target.to_string()));
+}
+
+/// 记录任务的选项
+pub fn record_option(id: &str, key: &str, value: Value) {
+    update_running(id, |entry| {
+        entry.options.insert(key.to_string(), value);
+    });
+}
+
+/// 记录任务进度
+pub fn record_progress(id: &str, message: &str) {
+    update_running(id, |entry| entry.message = Some(message.to_string()));
+}
+
+/// 记录任务结束（无论成功与否）
+pub fn record_finish(id: &str) {
+    update(|journal| journal.running.retain(|e| e.id != id));
+}
+
+/// 任务开始后为回滚创建的数据库快照（快照原因与任务类型相同）
+fn rollback_snapshot(entry: &JournalEntry) -> Option<String> {
+    let started_at = chrono::DateTime::parse_from_rfc3339(&entry.started_at).ok()?;
+    crate::antigravity::db_snapshot::list_snapshots()
+        .into_iter()
+        // 列表中最新的在前，取操作开始后的第一个快照
+        .rfind(|snapshot| {
+            snapshot.reason == entry.kind
+                && chrono::DateTime::parse_from_rfc3339(&snapshot.created_at)
+                    .is_ok_and(|created_at| created_at >= started_at)
+        })
+        .map(|snapshot| snapshot.id)
+}
+
+/// 尚未处理的中断任务
+pub fn interrupted() -> Vec<InterruptedTask> {
+    let entries = read(|journal| journal.interrupted.clone());
+    entries
+        .into_iter()
+        .map(|entry| InterruptedTask {
+            resumable: entry.target.is_some() && RESUMABLE_KINDS.contains(&entry.kind.as_str()),
+            rollback_snapshot: rollback_snapshot(&entry),
+            entry,
+        })
+        .collect()
+}
+
+/// 查找中断任务
+pub fn find_interrupted(id: &str) -> AgentResult<InterruptedTask> {
+    interrupted()
+        .into_iter()
+        .find(|task| task.entry.id == id)
+        .ok_or_else(|| AgentError::InvalidInput(format!("中断任务 {id} 不存在或已处理")))
+}
+
+/// 移除已处理的中断任务
+pub fn dismiss(id: &str) {
+    update(|journal| journal.interrupted.retain(|e| e.id != id));
+    tracing::info!(target: "task_journal", task_id = %id, "中断任务已处理");
+}
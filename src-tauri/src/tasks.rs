@@ -0,0 +1,300 @@
+//! 长耗时任务管理
+//! 为恢复、导入导出等操作分配任务 ID，推送进度事件，并支持在安全检查点取消；
+//! 有任务在进行时托盘图标显示忙碌动画。运行中的任务同时记录在任务日志中，见 [`crate::task_journal`]。
+//! 任务 ID 同时作为操作 ID：任务在 `operation` span 中执行，后端日志、审计日志与前端提交的日志
+//! 都带有同一个 `operation_id`，可通过 `get_operation_trace` 汇总查看一次操作的全部记录
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::Instrument;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::error::{AgentError, AgentResult};
+
+/// 任务进度事件名
+pub const TASK_PROGRESS_EVENT: &str = "task:progress";
+
+/// 任务结束事件名
+pub const TASK_FINISHED_EVENT: &str = "task:finished";
+
+/// 任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// 任务信息
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskInfo {
+    pub id: String,
+    /// 任务类型，如 `switch_account`、`export_accounts`
+    pub kind: String,
+    pub status: TaskStatus,
+    pub current: u64,
+    /// 总步数（未知时为 None）
+    pub total: Option<u64>,
+    pub message: Option<String>,
+    pub started_at: String,
+    /// 失败原因
+    pub error: Option<AgentError>,
+}
+
+struct TaskEntry {
+    info: TaskInfo,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// 任务管理器（注册为 Tauri 状态）
+#[derive(Default)]
+pub struct TaskManager {
+    tasks: Mutex<HashMap<String, TaskEntry>>,
+}
+
+impl TaskManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册新任务（未指定 ID 时自动生成）
+    pub fn start(
+        &self,
+        app: &AppHandle,
+        id: Option<String>,
+        kind: &str,
+    ) -> AgentResult<TaskHandle> {
+        let id = id
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let started_at = chrono::Local::now().to_rfc3339();
+
+        let mut tasks = self.tasks.lock().unwrap();
+        if tasks.contains_key(&id) {
+            return Err(AgentError::InvalidInput(format!("任务 {id} 已在运行")));
+        }
+        tasks.insert(
+            id.clone(),
+            TaskEntry {
+                info: TaskInfo {
+                    id: id.clone(),
+                    kind: kind.to_string(),
+                    status: TaskStatus::Running,
+                    current: 0,
+                    total: None,
+                    message: None,
+                    started_at: started_at.clone(),
+                    error: None,
+                },
+                cancelled: cancelled.clone(),
+            },
+        );
+
+        let first_task = tasks.len() == 1;
+        drop(tasks);
+        crate::task_journal::record_start(&id, kind, &started_at);
+        if first_task {
+            crate::system_tray::set_tray_busy(app, true);
+        }
+
+        tracing::info!(target: "tasks", task_id = %id, kind, "任务开始");
+        Ok(TaskHandle {
+            id,
+            app: Some(app.clone()),
+            cancelled,
+        })
+    }
+
+    /// 请求取消任务，任务会在下一个检查点停止
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.tasks.lock().unwrap().get(id) {
+            Some(entry) => {
+                entry.cancelled.store(true, Ordering::SeqCst);
+                tracing::info!(target: "tasks", task_id = %id, "已请求取消任务");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 正在运行的任务
+    pub fn list(&self) -> Vec<TaskInfo> {
+        self.tasks
+            .lock()
+            .unwrap()
+            .values()
+            .map(|entry| entry.info.clone())
+            .collect()
+    }
+
+    fn update(&self, id: &str, f: impl FnOnce(&mut TaskInfo)) -> Option<TaskInfo> {
+        self.tasks.lock().unwrap().get_mut(id).map(|entry| {
+            f(&mut entry.info);
+            entry.info.clone()
+        })
+    }
+
+    /// 移除已结束的任务，最后一个任务结束时停止托盘忙碌动画
+    fn remove(&self, app: &AppHandle, id: &str) -> Option<TaskInfo> {
+        let (info, idle) = {
+            let mut tasks = self.tasks.lock().unwrap();
+            let info = tasks.remove(id).map(|entry| entry.info);
+            (info, tasks.is_empty())
+        };
+        crate::task_journal::record_finish(id);
+        if idle {
+            crate::system_tray::set_tray_busy(app, false);
+        }
+        info
+    }
+}
+
+/// 任务句柄，传入长耗时操作用于上报进度和检查取消
+#[derive(Clone)]
+pub struct TaskHandle {
+    id: String,
+    /// 无界面调用（命令行、HTTP 接口）时为 None，只记录日志
+    app: Option<AppHandle>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TaskHandle {
+    /// 任务 ID（同时作为操作 ID）
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 不注册到任务管理器、不推送事件的句柄
+    pub fn detached() -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            app: None,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// 上报进度
+    pub fn progress(&self, current: u64, total: Option<u64>, message: impl Into<String>) {
+        let message = message.into();
+        tracing::debug!(target: "tasks", task_id = %self.id, current, total = ?total, message = %message, "任务进度");
+
+        let Some(app) = &self.app else {
+            return;
+        };
+        crate::task_journal::record_progress(&self.id, &message);
+        let info = app.state::<TaskManager>().update(&self.id, |info| {
+            info.current = current;
+            info.total = total;
+            info.message = Some(message);
+        });
+        if let Some(info) = info {
+            if let Err(e) = app.emit(TASK_PROGRESS_EVENT, &info) {
+                tracing::warn!(target: "tasks", error = %e, "推送任务进度失败");
+            }
+        }
+    }
+
+    /// 记录任务的操作对象（如账户备份名），任务中断后据此重新执行
+    pub fn record_target(&self, target: &str) {
+        if self.app.is_some() {
+            crate::task_journal::record_target(&self.id, target);
+        }
+    }
+
+    /// 记录影响操作结果的选项，任务中断后重新执行时使用相同的选项
+    pub fn record_option(&self, key: &str, value: serde_json::Value) {
+        if self.app.is_some() {
+            crate::task_journal::record_option(&self.id, key, value);
+        }
+    }
+
+    /// 安全检查点：任务已被取消时返回 `AgentError::Cancelled`
+    pub fn checkpoint(&self) -> AgentResult<()> {
+        if self.cancelled.load(Ordering::SeqCst) {
+            tracing::info!(target: "tasks", task_id = %self.id, "任务在检查点被取消");
+            Err(AgentError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn finish<T>(&self, result: &AgentResult<T>) {
+        let Some(app) = &self.app else {
+            return;
+        };
+        let Some(mut info) = app.state::<TaskManager>().remove(app, &self.id) else {
+            return;
+        };
+
+        info.status = match result {
+            Ok(_) => TaskStatus::Completed,
+            Err(AgentError::Cancelled) => TaskStatus::Cancelled,
+            Err(e) => {
+                info.error = Some(e.clone());
+                TaskStatus::Failed
+            }
+        };
+        tracing::info!(target: "tasks", task_id = %self.id, status = ?info.status, "任务结束");
+
+        if let Err(e) = app.emit(TASK_FINISHED_EVENT, &info) {
+            tracing::warn!(target: "tasks", error = %e, "推送任务结束事件失败");
+        }
+    }
+}
+
+/// 记录在 `operation` span 上的操作 ID
+struct OperationId(String);
+
+/// 创建携带操作 ID 的 span，并把 ID 存入 span 扩展供 [`current_operation_id`] 读取
+fn operation_span(id: &str, kind: &str) -> tracing::Span {
+    let span = tracing::info_span!("operation", operation_id = %id, kind);
+    span.with_subscriber(|(span_id, dispatch)| {
+        if let Some(span) = dispatch
+            .downcast_ref::<tracing_subscriber::Registry>()
+            .and_then(|registry| registry.span(span_id))
+        {
+            span.extensions_mut().insert(OperationId(id.to_string()));
+        }
+    });
+    span
+}
+
+/// 当前所在操作的 ID（在任务或其阻塞子任务中调用时）
+pub fn current_operation_id() -> Option<String> {
+    tracing::Span::current()
+        .with_subscriber(|(span_id, dispatch)| {
+            let registry = dispatch.downcast_ref::<tracing_subscriber::Registry>()?;
+            registry.span(span_id)?.scope().find_map(|span| {
+                span.extensions()
+                    .get::<OperationId>()
+                    .map(|operation| operation.0.clone())
+            })
+        })
+        .flatten()
+}
+
+/// 以任务形式执行操作：注册任务、在 `operation` span 中执行并推送结束事件
+pub async fn run_task<T, F, Fut>(
+    app: &AppHandle,
+    task_id: Option<String>,
+    kind: &str,
+    f: F,
+) -> AgentResult<T>
+where
+    F: FnOnce(TaskHandle) -> Fut,
+    Fut: Future<Output = AgentResult<T>>,
+{
+    let handle = app.state::<TaskManager>().start(app, task_id, kind)?;
+    let span = operation_span(handle.id(), kind);
+    let result = f(handle.clone()).instrument(span.clone()).await;
+    span.in_scope(|| handle.finish(&result));
+    result
+}
@@ -0,0 +1,412 @@
+//! 使用统计（需用户主动开启）
+//! 开启后在本地按天汇总匿名计数：各功能（命令）的调用次数与失败次数，不记录参数、邮箱、路径等任何内容。
+//! 「仅本地」模式的数据只供统计面板查看，不会离开本机；「共享」模式下需先预览将要发送的完整内容，
+//! 用户确认后才把预览中的内容原样发送到设置的地址。关闭后清除已收集的数据
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::Url;
+use tokio::time::{interval, Duration};
+
+use crate::error::{AgentError, AgentResult};
+
+/// 本地保留的天数
+const RETENTION_DAYS: i64 = 30;
+
+/// 把内存中的计数写入磁盘的间隔
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// 发送内容的格式版本
+const PAYLOAD_SCHEMA_VERSION: u32 = 1;
+
+/// 发送超时
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 使用统计模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TelemetryMode {
+    /// 不收集
+    #[default]
+    Off,
+    /// 只在本地汇总，供统计面板查看
+    LocalOnly,
+    /// 本地汇总，用户预览并确认后可发送
+    Share,
+}
+
+/// 使用统计设置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TelemetrySettings {
+    pub mode: TelemetryMode,
+    /// 共享模式下的发送地址（仅支持 HTTPS）
+    pub endpoint: Option<String>,
+}
+
+impl TelemetrySettings {
+    /// 校验使用统计设置
+    pub fn validate(&self) -> AgentResult<()> {
+        if self.mode == TelemetryMode::Share {
+            self.endpoint_url()?;
+        }
+        Ok(())
+    }
+
+    fn endpoint_url(&self) -> AgentResult<Url> {
+        let endpoint = self
+            .endpoint
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| AgentError::InvalidInput("共享模式需要设置发送地址".to_string()))?;
+        let url = Url::parse(endpoint)
+            .map_err(|e| AgentError::InvalidInput(format!("发送地址无效: {e}")))?;
+        if url.scheme() != "https" {
+            return Err(AgentError::InvalidInput(
+                "发送地址必须使用 HTTPS".to_string(),
+            ));
+        }
+        Ok(url)
+    }
+}
+
+/// 一天内某个功能的计数
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureCounter {
+    pub calls: u64,
+    pub errors: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct TelemetryFile {
+    /// 日期（YYYY-MM-DD）-> 功能名 -> 计数
+    days: BTreeMap<String, BTreeMap<String, FeatureCounter>>,
+    /// 已发送过的最后一天，之后的完整天数才会出现在下次发送内容中
+    last_sent_day: Option<String>,
+}
+
+struct Store {
+    /// None 表示尚未从磁盘加载
+    file: Option<TelemetryFile>,
+    /// 有未写入磁盘的计数
+    dirty: bool,
+    /// 等待用户确认发送的内容（报告 ID，发送地址，内容，包含的最后一天）
+    pending: Option<(String, Url, String, String)>,
+}
+
+static STORE: Mutex<Store> = Mutex::new(Store {
+    file: None,
+    dirty: false,
+    pending: None,
+});
+
+/// 是否收集（随设置更新，命令调用时无需读取设置）
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+fn read_file() -> TelemetryFile {
+    fs::read_to_string(crate::directories::get_telemetry_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_file(file: &TelemetryFile) -> AgentResult<()> {
+    fs::write(
+        crate::directories::get_telemetry_file(),
+        serde_json::to_string_pretty(file)?,
+    )?;
+    Ok(())
+}
+
+fn with_store<T>(f: impl FnOnce(&mut Store) -> T) -> T {
+    let mut store = STORE.lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut store)
+}
+
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+/// 按设置开启或关闭收集；关闭时清除已收集的数据
+pub fn apply_settings(settings: &TelemetrySettings) {
+    let enabled = settings.mode != TelemetryMode::Off;
+    let was_enabled = ENABLED.swap(enabled, Ordering::SeqCst);
+    if !enabled && was_enabled {
+        if let Err(e) = clear() {
+            tracing::warn!(target: "telemetry", error = %e, "清除使用统计数据失败");
+        }
+    }
+    if enabled != was_enabled {
+        tracing::info!(target: "telemetry", mode = ?settings.mode, "使用统计模式已更新");
+    }
+}
+
+/// 记录一次功能调用（未开启时忽略）
+pub fn record(feature: &'static str, success: bool) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    with_store(|store| {
+        let file = store.file.get_or_insert_with(read_file);
+        let counter = file
+            .days
+            .entry(today())
+            .or_default()
+            .entry(feature.to_string())
+            .or_default();
+        counter.calls += 1;
+        if !success {
+            counter.errors += 1;
+        }
+        store.dirty = true;
+    });
+}
+
+/// 去掉超过保留期的数据并写入磁盘
+pub fn flush() -> AgentResult<()> {
+    with_store(|store| {
+        if !store.dirty {
+            return Ok(());
+        }
+        let Some(file) = store.file.as_mut() else {
+            return Ok(());
+        };
+        let oldest = (chrono::Local::now() - chrono::Duration::days(RETENTION_DAYS))
+            .format("%Y-%m-%d")
+            .to_string();
+        file.days.retain(|day, _| *day >= oldest);
+        write_file(file)?;
+        store.dirty = false;
+        Ok(())
+    })
+}
+
+/// 清除已收集的数据
+pub fn clear() -> AgentResult<()> {
+    with_store(|store| {
+        store.file = Some(TelemetryFile::default());
+        store.dirty = false;
+        store.pending = None;
+    });
+    let path = crate::directories::get_telemetry_file();
+    if path.exists() {
+        fs::remove_file(&path)?;
+    }
+    tracing::info!(target: "telemetry", "已清除使用统计数据");
+    Ok(())
+}
+
+/// 某个功能的汇总
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureUsage {
+    pub feature: String,
+    pub calls: u64,
+    pub errors: u64,
+    /// 失败率（0-1）
+    pub error_rate: f64,
+}
+
+/// 一天的汇总
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyUsage {
+    pub day: String,
+    pub calls: u64,
+    pub errors: u64,
+}
+
+/// 本地使用统计（供统计面板展示）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetrySummary {
+    pub mode: TelemetryMode,
+    /// 保留期内按功能汇总，按调用次数降序排列
+    pub features: Vec<FeatureUsage>,
+    /// 按天汇总，最早的在前
+    pub days: Vec<DailyUsage>,
+    pub last_sent_day: Option<String>,
+}
+
+fn error_rate(calls: u64, errors: u64) -> f64 {
+    if calls == 0 {
+        0.0
+    } else {
+        errors as f64 / calls as f64
+    }
+}
+
+/// 汇总本地使用统计
+pub fn summary(mode: TelemetryMode) -> TelemetrySummary {
+    with_store(|store| {
+        let file = store.file.get_or_insert_with(read_file);
+
+        let mut totals: BTreeMap<&str, FeatureCounter> = BTreeMap::new();
+        let days = file
+            .days
+            .iter()
+            .map(|(day, features)| {
+                let mut daily = DailyUsage {
+                    day: day.clone(),
+                    calls: 0,
+                    errors: 0,
+                };
+                for (feature, counter) in features {
+                    let total = totals.entry(feature).or_default();
+                    total.calls += counter.calls;
+                    total.errors += counter.errors;
+                    daily.calls += counter.calls;
+                    daily.errors += counter.errors;
+                }
+                daily
+            })
+            .collect();
+
+        let mut features: Vec<FeatureUsage> = totals
+            .into_iter()
+            .map(|(feature, counter)| FeatureUsage {
+                feature: feature.to_string(),
+                calls: counter.calls,
+                errors: counter.errors,
+                error_rate: error_rate(counter.calls, counter.errors),
+            })
+            .collect();
+        features.sort_by_key(|usage| std::cmp::Reverse(usage.calls));
+
+        TelemetrySummary {
+            mode,
+            features,
+            days,
+            last_sent_day: file.last_sent_day.clone(),
+        }
+    })
+}
+
+/// 待发送的使用统计报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryReport {
+    /// 确认发送时传回
+    pub id: String,
+    pub endpoint: String,
+    /// 将要发送的完整内容（原样发送）
+    pub payload: String,
+}
+
+/// 生成待发送的报告：只包含上次发送后、今天之前的完整天数
+pub fn prepare_report(settings: &TelemetrySettings) -> AgentResult<TelemetryReport> {
+    if settings.mode != TelemetryMode::Share {
+        return Err(AgentError::InvalidInput(
+            "只有共享模式才能发送使用统计".to_string(),
+        ));
+    }
+    let endpoint = settings.endpoint_url()?;
+    let today = today();
+
+    with_store(|store| {
+        let file = store.file.get_or_insert_with(read_file);
+        let days: BTreeMap<&String, &BTreeMap<String, FeatureCounter>> = file
+            .days
+            .iter()
+            .filter(|(day, _)| {
+                **day < today && file.last_sent_day.as_ref().is_none_or(|sent| *day > sent)
+            })
+            .collect();
+        let Some(last_day) = days.keys().next_back().map(|day| day.to_string()) else {
+            return Err(AgentError::InvalidInput(
+                "没有尚未发送的完整统计数据".to_string(),
+            ));
+        };
+
+        let payload = serde_json::to_string_pretty(&serde_json::json!({
+            "schemaVersion": PAYLOAD_SCHEMA_VERSION,
+            "appVersion": env!("CARGO_PKG_VERSION"),
+            "os": std::env::consts::OS,
+            "days": days,
+        }))?;
+        let id = uuid::Uuid::new_v4().to_string();
+        store.pending = Some((id.clone(), endpoint.clone(), payload.clone(), last_day));
+
+        Ok(TelemetryReport {
+            id,
+            endpoint: endpoint.to_string(),
+            payload,
+        })
+    })
+}
+
+/// 发送用户确认过的报告
+pub async fn send_report(id: &str, proxy: &crate::proxy::ProxySettings) -> AgentResult<()> {
+    let (endpoint, payload, last_day) = with_store(|store| match store.pending.take() {
+        Some((pending_id, endpoint, payload, last_day)) if pending_id == id => {
+            Ok((endpoint, payload, last_day))
+        }
+        other => {
+            store.pending = other;
+            Err(AgentError::InvalidInput(
+                "报告不存在或已过期，请重新预览".to_string(),
+            ))
+        }
+    })?;
+
+    use tauri_plugin_http::reqwest;
+    let mut builder = reqwest::Client::builder().timeout(SEND_TIMEOUT);
+    // HTTP 客户端未启用 SOCKS 支持，与更新检查一样只使用 HTTP 代理
+    if proxy.kind == crate::proxy::ProxyKind::Http {
+        if let Some(proxy_url) = proxy.url_for_host(endpoint.host_str().unwrap_or_default()) {
+            let proxy = reqwest::Proxy::all(&proxy_url)
+                .map_err(|e| AgentError::InvalidInput(format!("代理地址无效: {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+    }
+    let response = builder
+        .build()
+        .map_err(|e| AgentError::Network(e.to_string()))?
+        .post(endpoint.clone())
+        .header("Content-Type", "application/json")
+        .body(payload)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+
+    let success = response.is_ok();
+    crate::audit::record(
+        "telemetry",
+        "send_report",
+        success,
+        serde_json::json!({ "endpoint": endpoint.as_str(), "lastDay": last_day }),
+    );
+    response.map_err(|e| AgentError::Network(e.to_string()))?;
+
+    with_store(|store| {
+        let file = store.file.get_or_insert_with(read_file);
+        file.last_sent_day = Some(last_day.clone());
+        store.dirty = true;
+    });
+    flush()?;
+    tracing::info!(target: "telemetry", last_day = %last_day, "已发送使用统计");
+    Ok(())
+}
+
+/// 启动定期写入（未开启时没有计数需要写入）
+pub fn spawn_flush_loop() {
+    tauri::async_runtime::spawn(async {
+        let mut ticker = interval(FLUSH_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = tokio::task::spawn_blocking(flush)
+                .await
+                .map_err(|e| AgentError::Internal(e.to_string()))
+                .and_then(|result| result)
+            {
+                tracing::warn!(target: "telemetry", error = %e, "写入使用统计失败");
+            }
+        }
+    });
+}
@@ -0,0 +1,164 @@
+//! Agent 自更新模块
+//! 基于 tauri-plugin-updater：从 GitHub Releases（或设置中的自定义地址）获取更新清单，
+//! 按 semver 比较版本，下载后使用 tauri.conf.json 中的公钥校验 minisign 签名再安装
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_updater::{Update, UpdaterExt};
+use tokio::time::{interval, Duration};
+
+use crate::app_settings::AppSettingsManager;
+use crate::error::{AgentError, AgentResult};
+use crate::proxy::ProxyKind;
+use crate::system_tray::TooltipUpdate;
+
+/// 发现新版本事件名
+pub const UPDATE_AVAILABLE_EVENT: &str = "update:available";
+
+/// 下载进度事件名
+pub const UPDATE_PROGRESS_EVENT: &str = "update:download-progress";
+
+/// 后台检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 可用更新信息
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    /// 发布时间
+    pub date: Option<String>,
+    /// 更新说明
+    pub body: Option<String>,
+}
+
+impl From<&Update> for UpdateInfo {
+    fn from(update: &Update) -> Self {
+        Self {
+            version: update.version.clone(),
+            current_version: update.current_version.clone(),
+            date: update.date.map(|d| d.to_string()),
+            body: update.body.clone(),
+        }
+    }
+}
+
+/// 下载进度
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadProgress {
+    downloaded: u64,
+    total: Option<u64>,
+}
+
+fn to_update_error(e: tauri_plugin_updater::Error) -> AgentError {
+    AgentError::Update(e.to_string())
+}
+
+/// 查询更新源，有新版本时返回更新句柄
+async fn fetch_update(app: &AppHandle) -> AgentResult<Option<Update>> {
+    let settings = app.state::<AppSettingsManager>().get_settings();
+
+    let mut builder = app.updater_builder();
+    let mut endpoint_host = String::from("github.com");
+    if let Some(endpoint) = settings.update_endpoint.filter(|s| !s.trim().is_empty()) {
+        let url = Url::parse(endpoint.trim())
+            .map_err(|e| AgentError::InvalidInput(format!("更新源地址无效: {}", e)))?;
+        endpoint_host = url.host_str().unwrap_or_default().to_string();
+        builder = builder.endpoints(vec![url]).map_err(to_update_error)?;
+    }
+
+    // 更新器的 HTTP 客户端只支持 HTTP 代理
+    if settings.proxy.kind == ProxyKind::Http {
+        if let Some(proxy) = settings.proxy.url_for_host(&endpoint_host) {
+            let proxy = Url::parse(&proxy)
+                .map_err(|e| AgentError::InvalidInput(format!("代理地址无效: {}", e)))?;
+            builder = builder.proxy(proxy);
+        }
+    }
+
+    builder
+        .build()
+        .map_err(to_update_error)?
+        .check()
+        .await
+        .map_err(to_update_error)
+}
+
+/// 检查是否有可用更新
+pub async fn check_for_updates(app: &AppHandle) -> AgentResult<Option<UpdateInfo>> {
+    let update = fetch_update(app).await?;
+    match &update {
+        Some(update) => {
+            tracing::info!(target: "updater", current = %update.current_version, latest = %update.version, "发现新版本")
+        }
+        None => tracing::info!(target: "updater", "当前已是最新版本"),
+    }
+    Ok(update.as_ref().map(UpdateInfo::from))
+}
+
+/// 下载、校验并安装更新，完成后重启应用
+pub async fn install_update(app: &AppHandle) -> AgentResult<()> {
+    let update = fetch_update(app)
+        .await?
+        .ok_or_else(|| AgentError::Update("没有可用的更新".to_string()))?;
+
+    tracing::info!(target: "updater", version = %update.version, "开始下载更新");
+
+    let mut downloaded: u64 = 0;
+    // download 内部会在下载完成后校验签名，签名不匹配时返回错误
+    let bytes = update
+        .download(
+            |chunk, total| {
+                downloaded += chunk as u64;
+                let _ = app.emit(
+                    UPDATE_PROGRESS_EVENT,
+                    DownloadProgress { downloaded, total },
+                );
+            },
+            || tracing::info!(target: "updater", "更新包下载完成，签名校验中"),
+        )
+        .await
+        .map_err(to_update_error)?;
+
+    update.install(bytes).map_err(to_update_error)?;
+    tracing::info!(target: "updater", version = %update.version, "✅ 更新安装完成，重启应用");
+
+    app.restart()
+}
+
+/// 启动后台更新检查（启动时立即检查一次，之后定期检查）
+pub fn spawn_update_checker(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = interval(CHECK_INTERVAL);
+        loop {
+            ticker.tick().await;
+
+            let enabled = app
+                .state::<AppSettingsManager>()
+                .get_settings()
+                .auto_check_updates;
+            if !enabled {
+                continue;
+            }
+
+            match check_for_updates(&app).await {
+                Ok(Some(info)) => {
+                    crate::system_tray::update_tray_tooltip(
+                        &app,
+                        TooltipUpdate::UpdateAvailable(Some(info.version.clone())),
+                    );
+                    crate::state_bus::publish(crate::state_bus::StateUpdate::UpdateAvailable(
+                        Some(info.version.clone()),
+                    ));
+                    if let Err(e) = app.emit(UPDATE_AVAILABLE_EVENT, &info) {
+                        tracing::error!(target: "updater", "推送更新事件失败: {}", e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!(target: "updater", error = %e, "后台检查更新失败"),
+            }
+        }
+    });
+}
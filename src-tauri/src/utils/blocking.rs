@@ -0,0 +1,118 @@
+//! 阻塞操作的异步封装
+//! 异步命令中的文件扫描、大量读写与数据库访问放到阻塞线程池执行，避免占用异步运行时的工作线程导致界面卡顿。
+//! 阻塞线程继承调用方的 tracing span，任务中的日志与审计记录仍能关联到所属操作
+
+use crate::error::{AgentError, AgentResult};
+
+/// 在阻塞线程池中执行操作并等待结果
+pub async fn run_blocking<T, F>(f: F) -> AgentResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> AgentResult<T> + Send + 'static,
+{
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || span.in_scope(f))
+        .await
+        .map_err(|e| AgentError::Internal(format!("后台任务执行失败: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    const FILE_COUNT: usize = 1_000;
+
+    /// 生成 1,000 个与账户备份大小相近的 JSON 文件
+    fn create_backup_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("agent-bench-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let state = "A".repeat(4096);
+        for i in 0..FILE_COUNT {
+            let content = serde_json::json!({
+                "antigravityUnifiedStateSync.oauthToken": state,
+                "antigravityAgent.meta": { "created_at": "2026-01-01T00:00:00Z", "trigger": "manual" },
+            });
+            fs::write(
+                dir.join(format!("user{i}@example.com.json")),
+                content.to_string(),
+            )
+            .unwrap();
+        }
+        dir
+    }
+
+    /// 与导出账户时相同的扫描：列出目录并逐个读取、解析备份文件
+    fn scan(dir: &Path) -> usize {
+        fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .filter(|content| serde_json::from_str::<serde_json::Value>(content).is_ok())
+            .count()
+    }
+
+    /// 扫描期间运行时上的其他任务（界面事件）最长被阻塞的时间；扫描与计时任务运行在同一个工作线程上
+    async fn max_stall_during(
+        scan_task: impl std::future::Future<Output = usize> + Send + 'static,
+    ) -> Duration {
+        let done = Arc::new(AtomicBool::new(false));
+        let ticker = tokio::spawn({
+            let done = done.clone();
+            async move {
+                let mut max_stall = Duration::ZERO;
+                while !done.load(Ordering::SeqCst) {
+                    let start = Instant::now();
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                    max_stall = max_stall.max(start.elapsed());
+                }
+                max_stall
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(tokio::spawn(scan_task).await.unwrap(), FILE_COUNT);
+        done.store(true, Ordering::SeqCst);
+        ticker.await.unwrap()
+    }
+
+    /// 基准：在单个工作线程的运行时上扫描 1,000 个备份文件，对比直接执行与放到阻塞线程池执行
+    ///
+    /// 运行：`cargo test --release scan_backup_dir_benchmark -- --ignored --nocapture`
+    #[test]
+    #[ignore = "基准测试，需手动运行"]
+    fn scan_backup_dir_benchmark() {
+        let dir = create_backup_dir();
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let (inline, offloaded) = runtime.block_on(async {
+            let inline_dir = dir.clone();
+            let start = Instant::now();
+            let inline_stall = max_stall_during(async move { scan(&inline_dir) }).await;
+            let inline = (start.elapsed(), inline_stall);
+
+            let blocking_dir = dir.clone();
+            let start = Instant::now();
+            let offloaded_stall = max_stall_during(async move {
+                run_blocking(move || Ok(scan(&blocking_dir))).await.unwrap()
+            })
+            .await;
+            (inline, (start.elapsed(), offloaded_stall))
+        });
+        fs::remove_dir_all(&dir).unwrap();
+
+        println!(
+            "{FILE_COUNT} 个备份文件：直接执行耗时 {:?}，其他任务最长阻塞 {:?}；阻塞线程池耗时 {:?}，其他任务最长阻塞 {:?}",
+            inline.0, inline.1, offloaded.0, offloaded.1
+        );
+        assert!(offloaded.1 < inline.1);
+    }
+}
@@ -0,0 +1,161 @@
+//! 系统剪贴板
+//! 通过各平台自带的剪贴板工具读写文本（macOS: pbcopy/pbpaste，Windows 与 WSL: clip.exe/PowerShell，
+//! Linux: wl-copy/xclip/xsel），托盘等没有前端参与的场景也能复制内容。
+//! 复制的内容可在指定时间后自动清除，清除前确认剪贴板中仍是复制的内容，不影响用户之后复制的其他内容
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::error::{AgentError, AgentResult};
+
+/// 每次复制递增，新的复制会取消之前安排的自动清除
+static COPY_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 写入剪贴板的命令（按顺序尝试）
+fn copy_commands() -> Vec<(&'static str, Vec<&'static str>)> {
+    if cfg!(target_os = "macos") {
+        vec![("pbcopy", vec![])]
+    } else if cfg!(target_os = "windows") || crate::platform::wsl::is_wsl() {
+        vec![("clip.exe", vec![])]
+    } else {
+        let mut commands = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            commands.push(("wl-copy", vec![]));
+        }
+        commands.push(("xclip", vec!["-selection", "clipboard"]));
+        commands.push(("xsel", vec!["--clipboard", "--input"]));
+        commands
+    }
+}
+
+/// 读取剪贴板的命令（按顺序尝试）
+fn paste_commands() -> Vec<(&'static str, Vec<&'static str>)> {
+    if cfg!(target_os = "macos") {
+        vec![("pbpaste", vec![])]
+    } else if cfg!(target_os = "windows") || crate::platform::wsl::is_wsl() {
+        vec![(
+            "powershell.exe",
+            vec!["-NoProfile", "-Command", "Get-Clipboard -Raw"],
+        )]
+    } else {
+        let mut commands = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            commands.push(("wl-paste", vec!["--no-newline"]));
+        }
+        commands.push(("xclip", vec!["-selection", "clipboard", "-o"]));
+        commands.push(("xsel", vec!["--clipboard", "--output"]));
+        commands
+    }
+}
+
+/// clip.exe 按系统代码页解析输入，写入带 BOM 的 UTF-16LE 才能正确处理中文路径
+fn encode_for(program: &str, text: &str) -> Vec<u8> {
+    if program == "clip.exe" {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        bytes
+    } else {
+        text.as_bytes().to_vec()
+    }
+}
+
+/// 创建剪贴板工具的命令（Windows 上不弹出控制台窗口）
+fn command(program: &str, args: &[&str]) -> Command {
+    let mut command = Command::new(program);
+    command.args(args);
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        // CREATE_NO_WINDOW：不弹出控制台窗口
+        command.creation_flags(0x0800_0000);
+    }
+
+    command
+}
+
+fn run_copy(program: &str, args: &[&str], text: &str) -> std::io::Result<()> {
+    let mut child = command(program, args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(&encode_for(program, text))?;
+    }
+    let status = child.wait()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!("退出码 {status}")))
+    }
+}
+
+/// 把文本写入剪贴板（阻塞当前线程）
+fn write_text(text: &str) -> AgentResult<()> {
+    let mut errors = Vec::new();
+    for (program, args) in copy_commands() {
+        match run_copy(program, &args, text) {
+            Ok(()) => return Ok(()),
+            Err(e) => errors.push(format!("{program}: {e}")),
+        }
+    }
+    Err(AgentError::Clipboard(format!(
+        "没有可用的剪贴板工具（{}）",
+        errors.join("; ")
+    )))
+}
+
+/// 读取剪贴板中的文本，无法读取时返回 None
+fn read_text() -> Option<String> {
+    paste_commands().into_iter().find_map(|(program, args)| {
+        command(program, &args)
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+    })
+}
+
+/// 复制文本到剪贴板；`clear_after` 不为 None 时到时自动清除（剪贴板内容已被替换时保留）
+pub async fn copy(text: String, clear_after: Option<Duration>) -> AgentResult<()> {
+    let generation = COPY_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let content = text.clone();
+    crate::utils::blocking::run_blocking(move || write_text(&content)).await?;
+    tracing::info!(
+        target: "clipboard",
+        chars = text.chars().count(),
+        clear_after_secs = clear_after.map(|d| d.as_secs()),
+        "已复制到剪贴板"
+    );
+
+    let Some(delay) = clear_after else {
+        return Ok(());
+    };
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(delay).await;
+        if COPY_GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        let result = tokio::task::spawn_blocking(move || {
+            // 无法读取剪贴板时也清除，避免敏感内容残留
+            match read_text() {
+                Some(current) if current.trim_end() != text.trim_end() => Ok(false),
+                _ => write_text("").map(|()| true),
+            }
+        })
+        .await;
+        match result {
+            Ok(Ok(true)) => tracing::info!(target: "clipboard", "已自动清除剪贴板"),
+            Ok(Ok(false)) => {
+                tracing::debug!(target: "clipboard", "剪贴板内容已变化，跳过自动清除")
+            }
+            Ok(Err(e)) => tracing::warn!(target: "clipboard", error = %e, "自动清除剪贴板失败"),
+            Err(e) => tracing::error!(target: "clipboard", error = %e, "自动清除剪贴板任务异常"),
+        }
+    });
+    Ok(())
+}
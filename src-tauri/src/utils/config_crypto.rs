@@ -0,0 +1,324 @@
+//! 导出配置的加密
+//! 账户导出文件使用密码加密：密码与随机盐经 Argon2id 派生出密钥，再以 ChaCha20-Poly1305 加密并认证，
+//! 解密时可以可靠地判断密码是否正确。盐与随机数来自系统随机源；文件头记录格式版本与派生参数（同时作为附加认证数据），
+//! 以后调整算法或参数时旧文件仍可解密。
+//! 加密前检查密码强度；本次运行中连续解密失败时逐步延长等待时间，拖慢对导出文件的暴力破解。
+//! 旧版本导出的文件（直接与密码异或）仍可解密。派生的密钥与解密出的明文在使用后清零
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::error::{AgentError, AgentResult};
+use crate::utils::secret::SecretString;
+
+/// 加密文件的前缀（其后为 Base64 编码的文件头与密文）
+const FORMAT_PREFIX: &str = "AGENC:";
+
+/// 当前的格式版本：Argon2id + ChaCha20-Poly1305
+const FORMAT_VERSION: u8 = 1;
+
+/// Argon2id 的内存开销（KiB）、迭代次数与并行度
+const KDF_MEMORY_KIB: u32 = 64 * 1024;
+const KDF_ITERATIONS: u32 = 3;
+const KDF_PARALLELISM: u32 = 1;
+
+/// 解密时接受的最大内存开销，避免被篡改的文件头耗尽内存
+const MAX_KDF_MEMORY_KIB: u32 = 1024 * 1024;
+
+/// 解密时接受的最大迭代次数
+const MAX_KDF_ITERATIONS: u32 = 64;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// 文件头：版本（1 字节）、内存开销、迭代次数、并行度（各 4 字节，小端）、盐、随机数
+const HEADER_LEN: usize = 1 + 4 * 3 + SALT_LEN + NONCE_LEN;
+
+/// 认证标签长度
+const TAG_LEN: usize = 16;
+
+/// 密码的最小长度
+const MIN_PASSWORD_LEN: usize = 8;
+
+/// 常见的弱密码（不区分大小写）
+const COMMON_PASSWORDS: &[&str] = &[
+    "password",
+    "password1",
+    "passw0rd",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "87654321",
+    "11111111",
+    "00000000",
+    "qwertyui",
+    "qwerty123",
+    "iloveyou",
+    "abcdefgh",
+    "abc12345",
+    "antigravity",
+    "letmein1",
+    "welcome1",
+    "admin123",
+];
+
+/// 不限制的连续解密失败次数
+const FREE_ATTEMPTS: u32 = 3;
+
+/// 连续失败后的最长等待时间
+const MAX_LOCKOUT: Duration = Duration::from_secs(5 * 60);
+
+/// 本次运行中的解密失败记录
+struct FailureState {
+    failures: u32,
+    locked_until: Option<Instant>,
+}
+
+static FAILURES: Mutex<FailureState> = Mutex::new(FailureState {
+    failures: 0,
+    locked_until: None,
+});
+
+/// 检查导出密码强度：至少 8 位、包含两类以上字符（小写、大写、数字、符号），且不是常见弱密码
+pub fn check_password_strength(password: &str) -> AgentResult<()> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(AgentError::InvalidInput(format!(
+            "密码至少需要 {MIN_PASSWORD_LEN} 个字符"
+        )));
+    }
+
+    let mut chars = password.chars();
+    let first = chars.next();
+    if chars.all(|c| Some(c) == first) {
+        return Err(AgentError::InvalidInput(
+            "密码不能由同一个字符重复组成".to_string(),
+        ));
+    }
+    if COMMON_PASSWORDS.contains(&password.to_lowercase().as_str()) {
+        return Err(AgentError::InvalidInput(
+            "密码过于常见，请换一个".to_string(),
+        ));
+    }
+
+    let classes = [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_ascii_digit()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ];
+    if classes.iter().filter(|has| **has).count() < 2 {
+        return Err(AgentError::InvalidInput(
+            "密码需要包含至少两类字符（小写字母、大写字母、数字、符号）".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 文件头中记录的密钥派生参数与随机值
+struct Header {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    salt: [u8; SALT_LEN],
+    nonce: [u8; NONCE_LEN],
+}
+
+impl Header {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN);
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(&self.memory_kib.to_le_bytes());
+        bytes.extend_from_slice(&self.iterations.to_le_bytes());
+        bytes.extend_from_slice(&self.parallelism.to_le_bytes());
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&self.nonce);
+        bytes
+    }
+
+    fn parse(bytes: &[u8]) -> AgentResult<Self> {
+        if bytes.len() < HEADER_LEN {
+            return Err(AgentError::InvalidInput("加密数据不完整".to_string()));
+        }
+        if bytes[0] != FORMAT_VERSION {
+            return Err(AgentError::InvalidInput(format!(
+                "不支持的加密格式版本 {}，请更新到最新版本后再导入",
+                bytes[0]
+            )));
+        }
+        let u32_at = |offset: usize| {
+            u32::from_le_bytes(bytes[offset..offset + 4].try_into().expect("长度已校验"))
+        };
+        let header = Self {
+            memory_kib: u32_at(1),
+            iterations: u32_at(5),
+            parallelism: u32_at(9),
+            salt: bytes[13..13 + SALT_LEN].try_into().expect("长度已校验"),
+            nonce: bytes[13 + SALT_LEN..HEADER_LEN]
+                .try_into()
+                .expect("长度已校验"),
+        };
+        if header.memory_kib > MAX_KDF_MEMORY_KIB || header.iterations > MAX_KDF_ITERATIONS {
+            return Err(AgentError::InvalidInput(
+                "加密数据的密钥派生参数超出范围".to_string(),
+            ));
+        }
+        Ok(header)
+    }
+
+    /// 由密码派生密钥
+    fn derive_key(&self, password: &str) -> AgentResult<Zeroizing<[u8; 32]>> {
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .map_err(|e| AgentError::InvalidInput(format!("密钥派生参数无效: {e}")))?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password.as_bytes(), &self.salt, key.as_mut())
+            .map_err(|e| AgentError::Internal(format!("密钥派生失败: {e}")))?;
+        Ok(key)
+    }
+}
+
+fn random_bytes<const N: usize>() -> AgentResult<[u8; N]> {
+    let mut bytes = [0u8; N];
+    getrandom::fill(&mut bytes)
+        .map_err(|e| AgentError::Internal(format!("无法获取系统随机数: {e}")))?;
+    Ok(bytes)
+}
+
+fn cipher(key: &[u8; 32]) -> ChaCha20Poly1305 {
+    ChaCha20Poly1305::new(key.into())
+}
+
+/// 加密导出配置（会先检查密码强度）
+pub fn encrypt(json_data: &str, password: &str) -> AgentResult<String> {
+    check_password_strength(password)?;
+
+    let header = Header {
+        memory_kib: KDF_MEMORY_KIB,
+        iterations: KDF_ITERATIONS,
+        parallelism: KDF_PARALLELISM,
+        salt: random_bytes()?,
+        nonce: random_bytes()?,
+    };
+    let key = header.derive_key(password)?;
+    let mut body = header.to_bytes();
+    let ciphertext = cipher(&key)
+        .encrypt(
+            Nonce::from_slice(&header.nonce),
+            Payload {
+                msg: json_data.as_bytes(),
+                aad: &body,
+            },
+        )
+        .map_err(|_| AgentError::Internal("加密失败".to_string()))?;
+    body.extend_from_slice(&ciphertext);
+    Ok(format!("{FORMAT_PREFIX}{}", BASE64.encode(body)))
+}
+
+/// 解密旧版本导出的文件（与密码异或），结果不是 JSON 时视为密码错误
+fn decrypt_legacy(encrypted: &str, password: &str) -> AgentResult<Option<SecretString>> {
+    let decoded = BASE64
+        .decode(encrypted)
+        .map_err(|_| AgentError::InvalidInput("Base64 解码失败".to_string()))?;
+    let password_bytes = password.as_bytes();
+    let result: Vec<u8> = decoded
+        .iter()
+        .enumerate()
+        .map(|(i, byte)| byte ^ password_bytes[i % password_bytes.len()])
+        .collect();
+    let text = match String::from_utf8(result) {
+        Ok(text) => SecretString::new(text),
+        Err(e) => {
+            e.into_bytes().zeroize();
+            return Ok(None);
+        }
+    };
+    Ok(serde_json::from_str::<serde::de::IgnoredAny>(text.expose())
+        .is_ok()
+        .then_some(text))
+}
+
+/// 按格式解密，密码错误时返回 None
+fn decrypt_inner(encrypted: &str, password: &str) -> AgentResult<Option<SecretString>> {
+    let Some(encoded) = encrypted.trim().strip_prefix(FORMAT_PREFIX) else {
+        return decrypt_legacy(encrypted.trim(), password);
+    };
+    let body = BASE64
+        .decode(encoded)
+        .map_err(|_| AgentError::InvalidInput("Base64 解码失败".to_string()))?;
+    let header = Header::parse(&body)?;
+    if body.len() < HEADER_LEN + TAG_LEN {
+        return Err(AgentError::InvalidInput("加密数据不完整".to_string()));
+    }
+
+    let (aad, ciphertext) = body.split_at(HEADER_LEN);
+    let key = header.derive_key(password)?;
+    // 认证失败说明密码错误（或数据被篡改）
+    let Ok(plaintext) = cipher(&key).decrypt(
+        Nonce::from_slice(&header.nonce),
+        Payload {
+            msg: ciphertext,
+            aad,
+        },
+    ) else {
+        return Ok(None);
+    };
+    match String::from_utf8(plaintext) {
+        Ok(text) => Ok(Some(SecretString::new(text))),
+        Err(e) => {
+            e.into_bytes().zeroize();
+            Err(AgentError::InvalidInput(
+                "解密失败，数据可能已损坏".to_string(),
+            ))
+        }
+    }
+}
+
+/// 解密导出配置；连续输错密码后需等待一段时间才能再次尝试
+pub fn decrypt(encrypted: &str, password: &str) -> AgentResult<SecretString> {
+    if password.is_empty() {
+        return Err(AgentError::InvalidInput("密码不能为空".to_string()));
+    }
+    {
+        let state = FAILURES.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(remaining) = state
+            .locked_until
+            .and_then(|until| until.checked_duration_since(Instant::now()))
+        {
+            return Err(AgentError::InvalidInput(format!(
+                "密码错误次数过多，请在 {} 秒后重试",
+                remaining.as_secs().max(1)
+            )));
+        }
+    }
+
+    let result = decrypt_inner(encrypted, password)?;
+    let mut state = FAILURES.lock().unwrap_or_else(|e| e.into_inner());
+    match result {
+        Some(plaintext) => {
+            state.failures = 0;
+            state.locked_until = None;
+            Ok(plaintext)
+        }
+        None => {
+            state.failures += 1;
+            if state.failures > FREE_ATTEMPTS {
+                // 超出后每次失败等待时间翻倍：2 秒、4 秒、8 秒……
+                let exponent = (state.failures - FREE_ATTEMPTS).min(16);
+                let lockout = Duration::from_secs(1 << exponent).min(MAX_LOCKOUT);
+                state.locked_until = Some(Instant::now() + lockout);
+                tracing::warn!(
+                    target: "config_crypto",
+                    failures = state.failures,
+                    lockout_secs = lockout.as_secs(),
+                    "导出配置解密连续失败"
+                );
+            }
+            Err(AgentError::InvalidInput("密码错误".to_string()))
+        }
+    }
+}
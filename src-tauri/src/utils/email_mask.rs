@@ -0,0 +1,89 @@
+//! 邮箱打码
+//! 日志、托盘、跳转列表与导出进度共用同一套打码规则，按字符（而非字节）处理，
+//! 本地部分含中文等多字节字符或格式异常时也不会 panic。打码风格可在设置中调整
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 邮箱打码风格
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailMaskStyle {
+    /// 完全隐藏本地部分与子域名：`***@***.com`
+    Full,
+    /// 保留首尾字符与域名：`u***r@gmail.com`
+    #[default]
+    Partial,
+    /// 不打码（仅建议在可信的个人电脑上使用）
+    None,
+}
+
+impl EmailMaskStyle {
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::Full => 0,
+            Self::Partial => 1,
+            Self::None => 2,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Full,
+            2 => Self::None,
+            _ => Self::Partial,
+        }
+    }
+}
+
+/// 当前生效的打码风格（日志层在每条日志上调用，避免反复读取设置文件）
+static CURRENT_STYLE: AtomicU8 = AtomicU8::new(1);
+
+/// 设置当前生效的打码风格（加载或修改设置时调用）
+pub fn set_style(style: EmailMaskStyle) {
+    CURRENT_STYLE.store(style.to_u8(), Ordering::Relaxed);
+}
+
+/// 当前生效的打码风格
+pub fn style() -> EmailMaskStyle {
+    EmailMaskStyle::from_u8(CURRENT_STYLE.load(Ordering::Relaxed))
+}
+
+/// 按当前设置的风格打码邮箱
+pub fn mask_email(email: &str) -> String {
+    mask_email_with(email, style())
+}
+
+/// 按指定风格打码邮箱；不含 `@` 的字符串视为非邮箱原样返回
+pub fn mask_email_with(email: &str, style: EmailMaskStyle) -> String {
+    if style == EmailMaskStyle::None {
+        return email.to_string();
+    }
+    // 本地部分允许出现引号包裹的 `@`，以最后一个 `@` 分隔域名
+    let Some((local, domain)) = email.rsplit_once('@') else {
+        return email.to_string();
+    };
+
+    match style {
+        EmailMaskStyle::Full => format!("***@{}", mask_domain(domain)),
+        _ => format!("{}@{}", mask_local(local), domain),
+    }
+}
+
+/// 保留首尾字符，中间固定为 `***`（不暴露长度）
+fn mask_local(local: &str) -> String {
+    let chars: Vec<char> = local.chars().collect();
+    match chars.as_slice() {
+        [] => String::new(),
+        [first] | [first, _] => format!("{first}*"),
+        [first, .., last] => format!("{first}***{last}"),
+    }
+}
+
+/// 只保留顶级域名
+fn mask_domain(domain: &str) -> String {
+    match domain.rsplit_once('.') {
+        Some((_, tld)) if !tld.is_empty() => format!("***.{tld}"),
+        _ => "***".to_string(),
+    }
+}
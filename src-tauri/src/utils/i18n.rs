@@ -0,0 +1,113 @@
+//! 后端文案翻译
+//! 托盘菜单、托盘提示、原生对话框等由后端直接展示给用户的文案按界面语言设置从这里取。
+//! 前端界面的翻译不在此处
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    #[serde(rename = "en-US")]
+    EnUs,
+}
+
+/// 文案目录：(键, 中文, 英文)，`{}` 为参数占位符
+const CATALOG: &[(&str, &str, &str)] = &[
+    ("tray.show_main", "显示主窗口", "Show Main Window"),
+    (
+        "tray.backup_now",
+        "立即备份当前账户",
+        "Back Up Current Account",
+    ),
+    (
+        "tray.copy_active_email",
+        "复制当前账户邮箱",
+        "Copy Current Account Email",
+    ),
+    (
+        "tray.clear_all_data",
+        "清除所有 Antigravity 数据…",
+        "Clear All Antigravity Data…",
+    ),
+    ("tray.quit", "退出应用", "Quit"),
+    ("tray.account", "账户: {}", "Account: {}"),
+    (
+        "tray.not_logged_in",
+        "账户: 未登录",
+        "Account: Not signed in",
+    ),
+    (
+        "tray.running",
+        "Antigravity: 运行中",
+        "Antigravity: Running",
+    ),
+    (
+        "tray.not_running",
+        "Antigravity: 未运行",
+        "Antigravity: Not running",
+    ),
+    (
+        "tray.not_installed",
+        "Antigravity: 未安装",
+        "Antigravity: Not installed",
+    ),
+    ("tray.last_backup", "上次备份: {}", "Last backup: {}"),
+    ("tray.no_backup", "上次备份: 无", "Last backup: Never"),
+    (
+        "tray.update_available",
+        "发现新版本 v{}",
+        "Update available: v{}",
+    ),
+    ("dialog.proceed", "继续", "Continue"),
+    (
+        "dialog.proceed_remember",
+        "继续且不再询问",
+        "Continue, Don't Ask Again",
+    ),
+    ("dialog.cancel", "取消", "Cancel"),
+    (
+        "dialog.quit_while_busy",
+        "有操作正在进行，现在退出可能导致账户数据不完整。确定要退出吗？",
+        "An operation is still in progress. Quitting now may leave account data incomplete. Quit anyway?",
+    ),
+    (
+        "dialog.clear_all_data",
+        "将关闭 Antigravity 并清除其全部登录数据。确定要继续吗？",
+        "This will close Antigravity and clear all of its sign-in data. Continue?",
+    ),
+];
+
+/// 当前生效的语言（加载或修改设置时更新）
+static CURRENT: Mutex<Language> = Mutex::new(Language::ZhCn);
+
+/// 设置当前生效的语言
+pub fn set_language(language: Language) {
+    *CURRENT.lock().unwrap_or_else(|e| e.into_inner()) = language;
+}
+
+/// 当前生效的语言
+pub fn language() -> Language {
+    *CURRENT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 按当前语言取文案（未知的键原样返回）
+pub fn t(key: &'static str) -> &'static str {
+    let language = language();
+    CATALOG
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, zh, en)| match language {
+            Language::ZhCn => *zh,
+            Language::EnUs => *en,
+        })
+        .unwrap_or(key)
+}
+
+/// 按当前语言取文案并替换第一个 `{}` 占位符
+pub fn t_with(key: &'static str, arg: &str) -> String {
+    t(key).replacen("{}", arg, 1)
+}
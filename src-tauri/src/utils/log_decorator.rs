@@ -31,6 +31,9 @@ macro_rules! log_async_command {
             }
         };
 
+        $crate::metrics::record_command($command_name, duration, result.is_ok());
+        $crate::telemetry::record($command_name, result.is_ok());
+
         if result.is_ok() {
             tracing::info!(
                 target: "command::success",
@@ -18,7 +18,7 @@ pub struct LogSanitizer {
 impl Default for LogSanitizer {
     fn default() -> Self {
         Self {
-            email_regex: Regex::new(r"(?i)[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}").unwrap(),
+            email_regex: Regex::new(r"[\w.%+-]+@[\w-]+(?:\.[\w-]+)*\.\w{2,}").unwrap(),
             api_key_regex: Regex::new(r"(?i)(?P<prefix>key|token|secret|api[-_]?key|access[-_]?token)[\s=:]+(?P<key>[a-zA-Z0-9+/=_-]{20,})").unwrap(),
             user_home_regex: Regex::new(r"(?P<prefix>/home/[^/]+)").unwrap(),
             windows_user_regex: Regex::new(r"C:\\\\Users\\\\[^\\\\]+").unwrap(),
@@ -48,42 +48,18 @@ impl LogSanitizer {
         result
     }
 
-    /// 智能邮箱脱敏 - 保留首尾字符，中间用*替代
+    /// 邮箱脱敏，按设置中的打码风格处理（见 [`crate::utils::email_mask`]）
     ///
-    /// 策略：
-    /// - 1个字符：保留原样
-    /// - 2个字符：显示首字符 + *
-    /// - 3个及以上：显示首字符 + 中间* + 尾字符
-    ///
-    /// # 示例
+    /// # 示例（默认风格）
     /// ```
-    /// "a@domain.com" → "a@domain.com"
-    /// "ab@domain.com" → "a*@domain.com"
+    /// "a@domain.com" → "a*@domain.com"
     /// "user@domain.com" → "u***r@domain.com"
-    /// "very.long.email@domain.com" → "v***l@domain.com"
+    /// "张三丰@例子.中国" → "张***丰@例子.中国"
     /// ```
     pub fn sanitize_email(&self, input: &str) -> String {
         self.email_regex
             .replace_all(input, |caps: &regex::Captures| {
-                let email = &caps[0];
-
-                let at_pos = email.find('@').unwrap_or(0);
-                let (local_part, domain) = email.split_at(at_pos);
-
-                match local_part.len() {
-                    0 | 1 => email.to_string(),
-                    2 => {
-                        let first_char = local_part.chars().next().unwrap_or('_');
-                        format!("{}*{}", first_char, domain)
-                    }
-                    _ => {
-                        let first_char = local_part.chars().next().unwrap_or('_');
-                        let last_char = local_part.chars().last().unwrap_or('_');
-                        let middle_stars =
-                            "*".repeat(local_part.len().saturating_sub(2).saturating_sub(2));
-                        format!("{}{}{}@{}", first_char, middle_stars, last_char, domain)
-                    }
-                }
+                crate::utils::email_mask::mask_email(&caps[0])
             })
             .to_string()
     }
@@ -1,6 +1,14 @@
 //! 工具模块
 
+pub mod blocking;
+pub mod clipboard;
+pub mod config_crypto;
+pub mod email_mask;
+pub mod i18n;
 pub mod log_decorator;
 pub mod log_sanitizer;
+pub mod permissions;
 pub mod sanitizing_layer;
+pub mod secret;
+pub mod time_format;
 pub mod tracing_config;
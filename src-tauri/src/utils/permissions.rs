@@ -0,0 +1,219 @@
+//! 配置目录的文件权限
+//! 账户备份与快照中包含登录令牌，Unix 下按默认 umask 创建的文件其他用户可读。
+//! 配置目录与备份、快照目录以 0700 创建，其中的文件以 0600 写入；
+//! 健康检查列出权限过宽的路径，`fix_permissions` 命令将其收紧。
+//! Windows 下用户目录本身有访问控制，不做处理
+
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[cfg(unix)]
+const PRIVATE_DIR_MODE: u32 = 0o700;
+#[cfg(unix)]
+const PRIVATE_FILE_MODE: u32 = 0o600;
+
+/// 权限过宽的路径
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionIssue {
+    pub path: String,
+    pub is_dir: bool,
+    /// 当前权限（八进制，如 "644"）
+    pub mode: String,
+    /// 期望的权限
+    pub expected: String,
+}
+
+/// 无法修复的路径
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionFixFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// 权限修复结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionFixReport {
+    /// 已收紧权限的路径
+    pub fixed: Vec<String>,
+    pub failed: Vec<PermissionFixFailure>,
+}
+
+/// 创建仅当前用户可访问的目录（含缺失的上级目录；已存在的目录权限不变）
+pub fn create_private_dir(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(PRIVATE_DIR_MODE)
+            .create(path)
+    }
+    #[cfg(not(unix))]
+    {
+        fs::create_dir_all(path)
+    }
+}
+
+/// 以 0600 创建文件的打开选项（写入并截断）
+pub fn private_file_options() -> fs::OpenOptions {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(PRIVATE_FILE_MODE);
+    }
+    options
+}
+
+/// 写入仅当前用户可读写的文件；覆盖已有文件时同时收紧其权限
+pub fn write_private(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let mut file = private_file_options().open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(PRIVATE_FILE_MODE))?;
+    }
+    file.write_all(contents.as_ref())
+}
+
+/// 将已有文件收紧为仅当前用户可读写
+pub fn restrict_file(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(PRIVATE_FILE_MODE))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Ok(())
+    }
+}
+
+/// 复制文件并收紧权限（`fs::copy` 会沿用源文件的权限）
+pub fn copy_private(from: &Path, to: &Path) -> io::Result<u64> {
+    let size = fs::copy(from, to)?;
+    restrict_file(to)?;
+    Ok(size)
+}
+
+/// 需要检查的路径：配置目录本身、包含令牌的文件，以及备份与快照目录（递归检查）
+#[cfg(unix)]
+fn sensitive_paths() -> Vec<std::path::PathBuf> {
+    use crate::directories;
+    use std::path::PathBuf;
+
+    let agent_db = directories::get_agent_db_file();
+    let journal_files = ["-wal", "-shm"].map(|suffix| {
+        let mut name = agent_db.clone().into_os_string();
+        name.push(suffix);
+        PathBuf::from(name)
+    });
+
+    let mut paths = vec![
+        directories::get_config_directory(),
+        directories::get_app_settings_file(),
+        agent_db,
+    ];
+    paths.extend(journal_files);
+    paths.extend([
+        directories::get_accounts_directory(),
+        directories::get_update_snapshots_directory(),
+        directories::get_machine_id_snapshots_directory(),
+        directories::get_db_snapshots_directory(),
+    ]);
+    paths
+}
+
+/// 检查单个路径，目录（配置目录除外）递归检查其内容
+#[cfg(unix)]
+fn check_path(path: &Path, config_dir: &Path, issues: &mut Vec<PermissionIssue>) {
+    use std::os::unix::fs::PermissionsExt;
+
+    // 不跟随符号链接
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return;
+    };
+    if meta.file_type().is_symlink() {
+        return;
+    }
+    let mode = meta.permissions().mode() & 0o777;
+    if mode & 0o077 != 0 {
+        let expected = if meta.is_dir() {
+            PRIVATE_DIR_MODE
+        } else {
+            PRIVATE_FILE_MODE
+        };
+        issues.push(PermissionIssue {
+            path: path.display().to_string(),
+            is_dir: meta.is_dir(),
+            mode: format!("{mode:o}"),
+            expected: format!("{expected:o}"),
+        });
+    }
+    if meta.is_dir() && path != config_dir {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                check_path(&entry.path(), config_dir, issues);
+            }
+        }
+    }
+}
+
+/// 列出组用户或其他用户可访问的敏感路径（Windows 下始终为空）
+pub fn audit() -> Vec<PermissionIssue> {
+    #[cfg(unix)]
+    {
+        let config_dir = crate::directories::get_config_directory();
+        let mut issues = Vec::new();
+        for path in sensitive_paths() {
+            check_path(&path, &config_dir, &mut issues);
+        }
+        issues
+    }
+    #[cfg(not(unix))]
+    {
+        Vec::new()
+    }
+}
+
+/// 将权限过宽的敏感路径收紧为 0700（目录）或 0600（文件）
+#[cfg(unix)]
+pub fn fix() -> PermissionFixReport {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut report = PermissionFixReport::default();
+    for issue in audit() {
+        let mode = if issue.is_dir {
+            PRIVATE_DIR_MODE
+        } else {
+            PRIVATE_FILE_MODE
+        };
+        match fs::set_permissions(&issue.path, fs::Permissions::from_mode(mode)) {
+            Ok(()) => {
+                tracing::info!(target: "permissions", path = %issue.path, from = %issue.mode, to = %issue.expected, "已收紧文件权限");
+                report.fixed.push(issue.path);
+            }
+            Err(e) => {
+                tracing::warn!(target: "permissions", path = %issue.path, error = %e, "收紧文件权限失败");
+                report.failed.push(PermissionFixFailure {
+                    path: issue.path,
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+    report
+}
+
+/// 将权限过宽的敏感路径收紧（Windows 下无需处理）
+#[cfg(not(unix))]
+pub fn fix() -> PermissionFixReport {
+    PermissionFixReport::default()
+}
@@ -1,12 +1,24 @@
 /// 自定义日志写入器，仅对文件输出进行脱敏
-/// 控制台输出保持原始内容
+/// 控制台输出保持原始内容；日志目录所在磁盘空间不足时暂停写入，避免写出被截断的日志文件
 use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use tracing_appender::rolling::RollingFileAppender;
 use tracing_subscriber::fmt::writer::MakeWriter;
 
+/// 日志文件名前缀，滚动后的文件名为 `antigravity-agent.2024-01-15`
+pub const LOG_FILE_PREFIX: &str = "antigravity-agent";
+
+/// 检查日志目录剩余空间的间隔
+const SPACE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 /// 脱敏文件写入器
 pub struct SanitizingFileWriter {
     appender: RollingFileAppender,
+    log_dir: PathBuf,
+    space_checked_at: Option<Instant>,
+    /// 磁盘空间不足，暂停写入
+    suspended: bool,
 }
 
 impl SanitizingFileWriter {
@@ -14,15 +26,48 @@ impl SanitizingFileWriter {
         let log_dir = crate::directories::get_log_directory();
 
         // 创建按日期滚动的日志文件
-        // 文件名格式: antigravity-agent.2024-01-15.log
-        let appender = tracing_appender::rolling::daily(&log_dir, "antigravity-agent");
+        // 文件名格式: antigravity-agent.2024-01-15
+        let appender = tracing_appender::rolling::daily(&log_dir, LOG_FILE_PREFIX);
+
+        Ok(Self {
+            appender,
+            log_dir,
+            space_checked_at: None,
+            suspended: false,
+        })
+    }
+
+    /// 定期检查日志目录的剩余空间（在日志写入线程中调用，不能再输出 tracing 日志）
+    fn check_space(&mut self) {
+        if self
+            .space_checked_at
+            .is_some_and(|at| at.elapsed() < SPACE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.space_checked_at = Some(Instant::now());
 
-        Ok(Self { appender })
+        let low = crate::disk_space::is_low(&self.log_dir);
+        if low != self.suspended {
+            self.suspended = low;
+            if low {
+                eprintln!("警告：日志目录所在磁盘空间不足，暂停写入日志文件");
+            } else {
+                let _ = self
+                    .appender
+                    .write_all("磁盘空间已恢复，继续写入日志文件\n".as_bytes());
+            }
+        }
     }
 }
 
 impl Write for SanitizingFileWriter {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.check_space();
+        if self.suspended {
+            return Ok(buf.len());
+        }
+
         // 将字节转换为字符串进行脱敏处理
         let msg = String::from_utf8_lossy(buf);
         let sanitized = crate::utils::log_sanitizer::sanitize_log_message(&msg);
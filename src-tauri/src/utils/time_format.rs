@@ -0,0 +1,97 @@
+//! 时间显示格式
+//! 日志信息、备份列表与审计记录中的时间统一在这里格式化：返回毫秒时间戳（供前端排序、
+//! 自行格式化）以及按设置的语言与时区生成的显示文本
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// 时间显示语言（决定日期格式）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeLocale {
+    /// `2024-01-02 15:04:05`
+    #[default]
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    /// `01/02/2024 03:04:05 PM`
+    #[serde(rename = "en-US")]
+    EnUs,
+    /// `2024-01-02T15:04:05+08:00`
+    #[serde(rename = "iso")]
+    Iso,
+}
+
+impl TimeLocale {
+    fn pattern(self) -> &'static str {
+        match self {
+            Self::ZhCn => "%Y-%m-%d %H:%M:%S",
+            Self::EnUs => "%m/%d/%Y %I:%M:%S %p",
+            Self::Iso => "%Y-%m-%dT%H:%M:%S%:z",
+        }
+    }
+}
+
+/// 显示时间使用的时区
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeZoneMode {
+    /// 系统本地时区
+    #[default]
+    Local,
+    Utc,
+}
+
+/// 时间显示设置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TimeDisplaySettings {
+    pub locale: TimeLocale,
+    pub time_zone: TimeZoneMode,
+}
+
+/// 格式化后的时间
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormattedTime {
+    /// Unix 毫秒时间戳
+    pub epoch_ms: i64,
+    /// 按设置格式化的显示文本
+    pub display: String,
+}
+
+/// 当前生效的显示设置（加载或修改设置时更新，避免每次格式化都读取设置文件）
+static CURRENT: Mutex<TimeDisplaySettings> = Mutex::new(TimeDisplaySettings {
+    locale: TimeLocale::ZhCn,
+    time_zone: TimeZoneMode::Local,
+});
+
+/// 设置当前生效的显示设置
+pub fn set_settings(settings: TimeDisplaySettings) {
+    *CURRENT.lock().unwrap_or_else(|e| e.into_inner()) = settings;
+}
+
+/// 当前生效的显示设置
+pub fn settings() -> TimeDisplaySettings {
+    *CURRENT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 按当前设置格式化时间
+pub fn format_time<Tz: TimeZone>(time: &DateTime<Tz>) -> FormattedTime {
+    let settings = settings();
+    let pattern = settings.locale.pattern();
+    let display = match settings.time_zone {
+        TimeZoneMode::Local => time.with_timezone(&Local).format(pattern).to_string(),
+        TimeZoneMode::Utc => time.with_timezone(&Utc).format(pattern).to_string(),
+    };
+    FormattedTime {
+        epoch_ms: time.timestamp_millis(),
+        display,
+    }
+}
+
+/// 格式化 RFC 3339 时间字符串（无法解析时返回 None）
+pub fn format_rfc3339(value: &str) -> Option<FormattedTime> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| format_time(&time))
+}
@@ -1,69 +1,88 @@
 // 窗口事件处理模块
-// 负责在应用启动时恢复窗口状态
+// 负责在窗口创建时恢复窗口状态，并在移动、缩放、关闭时按窗口 label 保存
 
-use super::state_manager::{load_window_state, save_window_state, WindowState};
+use super::state_manager::{load_window_state, save_window_state, WindowState, MAIN_WINDOW};
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tauri::Manager;
+use tauri::{Manager, WebviewWindow};
 
-/// 初始化窗口事件处理器
-pub fn init_window_event_handler(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+/// 启动时隐藏的窗口尚未应用的保存状态（按窗口 label）
+static PENDING_STATE: Mutex<BTreeMap<String, WindowState>> = Mutex::new(BTreeMap::new());
+
+/// 初始化主窗口事件处理器
+///
+/// `start_hidden` 为 true 时窗口将隐藏在托盘中启动，保存的位置与大小推迟到 [`show_window`] 时应用
+pub fn init_window_event_handler(
+    app: &tauri::App,
+    start_hidden: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     // 获取主窗口
-    let main_window = app.get_webview_window("main").ok_or("无法获取主窗口")?;
+    let main_window = app
+        .get_webview_window(MAIN_WINDOW)
+        .ok_or("无法获取主窗口")?;
+    track_window_state(&main_window, start_hidden);
+
+    // 主窗口关闭时处理系统托盘逻辑
+    let window_for_events = main_window.clone();
+    main_window.on_window_event(move |event| {
+        let tauri::WindowEvent::CloseRequested { api, .. } = event else {
+            return;
+        };
+
+        // 检查系统托盘是否启用
+        let app_handle = window_for_events.app_handle();
+        let system_tray = app_handle.state::<crate::system_tray::SystemTrayManager>();
+        if !system_tray.is_enabled_setting(app_handle) {
+            tracing::info!(target: "window::event", "系统托盘未启用，允许关闭");
+            return;
+        }
+
+        tracing::info!(target: "window::event", "系统托盘已启用，阻止关闭并最小化到托盘");
+        // 阻止窗口关闭
+        api.prevent_close();
+
+        // 在异步运行时中执行最小化操作
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let system_tray = app_handle.state::<crate::system_tray::SystemTrayManager>();
+            if let Err(e) = system_tray.minimize_to_tray(&app_handle) {
+                tracing::error!(target: "window::event", error = %e, "最小化到托盘失败");
+            }
+        });
+    });
+
+    Ok(())
+}
+
+/// 恢复窗口保存的位置与大小，并在移动、缩放（防抖）与关闭时保存
+///
+/// 状态按窗口 label 分别记录，有效性按 [`super::state_manager::profile`] 中该窗口的配置校验；
+/// 新增窗口创建后调用一次即可。`start_hidden` 为 true 时保存的状态推迟到 [`show_window`] 时应用
+pub fn track_window_state(window: &WebviewWindow, start_hidden: bool) {
+    let label = window.label().to_string();
 
     // 创建保存状态的共享状态，用于防抖和恢复标志
     let is_restoring = Arc::new(Mutex::new(true)); // 恢复标志，防止保存状态
     let debounce_timer = Arc::new(Mutex::new(None::<tauri::async_runtime::JoinHandle<()>>)); // 防抖定时器句柄
     const DEBOUNCE_DURATION: Duration = Duration::from_secs(2); // 防抖延迟时间
 
-    // 应用启动时，尝试恢复上次保存的窗口状态
-    let window_clone = main_window.clone();
+    // 尝试恢复上次保存的窗口状态（启动时隐藏的窗口推迟到显示时再应用）
+    let window_clone = window.clone();
     let is_restoring_clone = is_restoring.clone();
     tauri::async_runtime::spawn(async move {
-        match load_window_state().await {
-            Ok(saved_state) => {
-                tracing::debug!(
-                    target: "window::restore",
-                    x = %saved_state.x,
-                    y = %saved_state.y,
-                    width = %saved_state.width,
-                    height = %saved_state.height,
-                    maximized = %saved_state.maximized,
-                    "恢复窗口状态"
-                );
-
-                // 设置窗口位置
-                if let Err(e) =
-                    window_clone.set_position(tauri::Position::Physical(tauri::PhysicalPosition {
-                        x: saved_state.x as i32,
-                        y: saved_state.y as i32,
-                    }))
-                {
-                    tracing::warn!(target: "window::restore", error = %e, "恢复窗口位置失败，使用默认位置");
-                }
-
-                // 设置窗口大小
-                if let Err(e) = window_clone.set_size(tauri::Size::Physical(tauri::PhysicalSize {
-                    width: saved_state.width as u32,
-                    height: saved_state.height as u32,
-                })) {
-                    tracing::warn!(target: "window::restore", error = %e, "恢复窗口大小失败，使用默认大小");
-                }
-
-                // 如果之前是最大化状态，则恢复最大化
-                if saved_state.maximized {
-                    if let Err(e) = window_clone.maximize() {
-                        eprintln!("⚠️ 恢复窗口最大化状态失败: {}", e);
-                    } else {
-                        println!("✅ 窗口状态恢复完成（包含最大化）");
-                    }
-                } else {
-                    println!("✅ 窗口状态恢复完成");
-                }
+        let label = window_clone.label().to_string();
+        match load_window_state(&label).await {
+            Ok(saved_state) if start_hidden => {
+                tracing::debug!(target: "window::restore", label = %label, "窗口启动时隐藏，显示时再恢复位置与大小");
+                PENDING_STATE
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(label, saved_state);
             }
+            Ok(saved_state) => apply_window_state(&window_clone, &saved_state),
             Err(e) => {
-                eprintln!("⚠️ 加载窗口状态失败: {}，将使用默认状态", e);
-                println!("✅ 使用默认窗口状态");
+                tracing::warn!(target: "window::restore", error = %e, "加载窗口状态失败，使用默认状态");
             }
         }
 
@@ -73,16 +92,16 @@ pub fn init_window_event_handler(app: &tauri::App) -> Result<(), Box<dyn std::er
         match is_restoring_clone.lock() {
             Ok(mut flag) => {
                 *flag = false;
-                println!("✅ 窗口状态恢复标志已清除，开始响应窗口变化事件");
+                tracing::debug!(target: "window::restore", "窗口状态恢复标志已清除，开始响应窗口变化事件");
             }
             Err(_) => {
-                eprintln!("⚠️ 恢复标志锁中毒，无法清除标志");
+                tracing::error!(target: "window::restore", "恢复标志锁中毒，无法清除标志");
             }
         }
     });
 
     // 防抖保存函数 - 更简单的实现，避免复杂的借用
-    let window_for_save = main_window.clone();
+    let window_for_save = window.clone();
     let is_restoring_for_save = is_restoring.clone();
     let timer_for_save = debounce_timer.clone();
 
@@ -133,47 +152,19 @@ pub fn init_window_event_handler(app: &tauri::App) -> Result<(), Box<dyn std::er
     };
 
     // 监听窗口事件，包括大小变化、移动和关闭
-    let window_for_events = main_window.clone();
-    let schedule_save_clone = schedule_save.clone();
-
-    window_for_events.clone().on_window_event(move |event| {
+    let window_for_events = window.clone();
+    window.on_window_event(move |event| {
         match event {
             // 窗口大小变化或移动时，使用防抖机制延迟保存
             tauri::WindowEvent::Resized { .. } | tauri::WindowEvent::Moved { .. } => {
-                tracing::debug!(target: "window::event", "检测到窗口变化，启动防抖保存");
-                schedule_save_clone();
+                tracing::debug!(target: "window::event", label = %label, "检测到窗口变化，启动防抖保存");
+                schedule_save();
             }
             // 注意：Tauri 2.x 中没有 Maximized/Unmaximized 事件
             // 最大化/还原状态会在 Resized 事件中捕获和处理
-            // 窗口关闭时处理系统托盘逻辑
-            tauri::WindowEvent::CloseRequested { api, .. } => {
-                tracing::info!(target: "window::event", "收到窗口关闭请求事件");
-
-                // 检查系统托盘是否启用
-                let app_handle = window_for_events.app_handle();
-                let system_tray = app_handle.state::<crate::system_tray::SystemTrayManager>();
-                let tray_enabled = system_tray.is_enabled_setting(app_handle);
-
-                if tray_enabled {
-                    tracing::info!(target: "window::event", "系统托盘已启用，阻止关闭并最小化到托盘");
-                    // 阻止窗口关闭
-                    api.prevent_close();
-
-                    // 在异步运行时中执行最小化操作
-                    let app_handle = window_for_events.app_handle().clone();
-                    tauri::async_runtime::spawn(async move {
-                        let system_tray =
-                            app_handle.state::<crate::system_tray::SystemTrayManager>();
-                        if let Err(e) = system_tray.minimize_to_tray(&app_handle) {
-                            tracing::error!(target: "window::event", error = %e, "最小化到托盘失败");
-                        }
-                    });
-                    return;
-                }
-
-                tracing::info!(target: "window::event", "系统托盘未启用，立即保存状态并允许关闭");
-
-                // 如果系统托盘未启用，立即保存状态并允许关闭（不需要防抖）
+            // 窗口关闭（或主窗口最小化到托盘）前立即保存状态（不需要防抖）
+            tauri::WindowEvent::CloseRequested { .. } => {
+                tracing::info!(target: "window::event", label = %label, "收到窗口关闭请求事件，立即保存状态");
                 let window = window_for_events.clone();
                 tauri::async_runtime::spawn(async move {
                     save_current_window_state(&window).await;
@@ -183,27 +174,103 @@ pub fn init_window_event_handler(app: &tauri::App) -> Result<(), Box<dyn std::er
             _ => {}
         }
     });
+}
 
-    Ok(())
+/// 把保存的位置、大小与最大化状态应用到窗口
+fn apply_window_state(window: &WebviewWindow, saved_state: &WindowState) {
+    tracing::debug!(
+        target: "window::restore",
+        x = %saved_state.x,
+        y = %saved_state.y,
+        width = %saved_state.width,
+        height = %saved_state.height,
+        maximized = %saved_state.maximized,
+        "恢复窗口状态"
+    );
+
+    // 设置窗口位置
+    if let Err(e) = window.set_position(tauri::Position::Physical(tauri::PhysicalPosition {
+        x: saved_state.x as i32,
+        y: saved_state.y as i32,
+    })) {
+        tracing::warn!(target: "window::restore", error = %e, "恢复窗口位置失败，使用默认位置");
+    }
+
+    // 设置窗口大小
+    if let Err(e) = window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
+        width: saved_state.width as u32,
+        height: saved_state.height as u32,
+    })) {
+        tracing::warn!(target: "window::restore", error = %e, "恢复窗口大小失败，使用默认大小");
+    }
+
+    // 如果之前是最大化状态，则恢复最大化
+    if saved_state.maximized {
+        if let Err(e) = window.maximize() {
+            tracing::warn!(target: "window::restore", error = %e, "恢复窗口最大化状态失败");
+        } else {
+            tracing::info!(target: "window::restore", maximized = true, "窗口状态恢复完成");
+        }
+    } else {
+        tracing::info!(target: "window::restore", maximized = false, "窗口状态恢复完成");
+    }
 }
 
-/// 保存当前窗口状态的辅助函数
-async fn save_current_window_state(window: &tauri::WebviewWindow) {
-    if let (Ok(outer_position), Ok(outer_size), Ok(is_maximized)) = (
+/// 丢弃启动时隐藏的窗口尚未应用的保存状态（窗口位置已由其他方式确定）
+pub fn clear_pending_state(label: &str) {
+    PENDING_STATE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(label);
+}
+
+/// 显示并聚焦窗口；启动时隐藏的窗口首次显示前先恢复保存的位置与大小
+pub fn show_window(window: &WebviewWindow) -> tauri::Result<()> {
+    let pending = PENDING_STATE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(window.label());
+    if let Some(saved_state) = pending {
+        apply_window_state(window, &saved_state);
+    }
+    window.unminimize()?;
+    window.show()?;
+    window.set_focus()
+}
+
+/// 读取当前窗口的位置、大小与最大化状态
+///
+/// 最小化时位置与大小不可靠，启动时隐藏且尚未显示过的窗口仍是默认位置，均返回 None
+pub fn capture_window_state(window: &WebviewWindow) -> Option<WindowState> {
+    if window.is_minimized().unwrap_or(false)
+        || PENDING_STATE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(window.label())
+    {
+        return None;
+    }
+    let (Ok(outer_position), Ok(outer_size), Ok(is_maximized)) = (
         window.outer_position(),
         window.outer_size(),
         window.is_maximized(),
-    ) {
-        let current_state = WindowState {
-            x: outer_position.x as f64,
-            y: outer_position.y as f64,
-            width: outer_size.width as f64,
-            height: outer_size.height as f64,
-            maximized: is_maximized,
-        };
+    ) else {
+        return None;
+    };
+    Some(WindowState {
+        x: outer_position.x as f64,
+        y: outer_position.y as f64,
+        width: outer_size.width as f64,
+        height: outer_size.height as f64,
+        maximized: is_maximized,
+    })
+}
 
-        if let Err(e) = save_window_state(current_state).await {
-            eprintln!("保存窗口状态失败: {}", e);
+/// 保存当前窗口状态的辅助函数
+async fn save_current_window_state(window: &WebviewWindow) {
+    if let Some(current_state) = capture_window_state(window) {
+        if let Err(e) = save_window_state(window.label(), current_state).await {
+            tracing::warn!(target: "window::save", error = %e, "保存窗口状态失败");
         }
     }
 }
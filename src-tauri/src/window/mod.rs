@@ -2,6 +2,7 @@
 //! Handles window state persistence and event handling
 
 pub mod event_handler;
+pub mod presets;
 pub mod state_manager;
 
 // Re-export commonly used functions
@@ -0,0 +1,129 @@
+//! 窗口布局预设与重置
+//! 保存的位置在屏幕外但仍通过有效性校验时（如拔掉外接显示器），用户可以一键把窗口放到
+//! 当前显示器的左半屏、右半屏或居中，或清除保存的状态并恢复默认大小居中显示
+
+use serde::Deserialize;
+use tauri::{PhysicalPosition, PhysicalSize, WebviewWindow};
+
+use super::event_handler::{capture_window_state, clear_pending_state};
+use super::state_manager::{self, WindowState};
+use crate::error::{AgentError, AgentResult};
+
+/// 居中小窗口的逻辑尺寸
+const SMALL_SIZE: (f64, f64) = (900.0, 640.0);
+
+/// 居中大窗口占工作区的比例
+const LARGE_RATIO: f64 = 0.85;
+
+/// 窗口布局预设
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WindowPreset {
+    LeftHalf,
+    RightHalf,
+    CenteredSmall,
+    CenteredLarge,
+}
+
+fn window_error(e: tauri::Error) -> AgentError {
+    AgentError::Internal(format!("调整窗口失败: {e}"))
+}
+
+/// 窗口所在显示器（窗口在屏幕外时取主显示器）的工作区（不含任务栏、菜单栏）与缩放比例
+fn work_area(
+    window: &WebviewWindow,
+) -> AgentResult<(PhysicalPosition<i32>, PhysicalSize<u32>, f64)> {
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .or_else(|| window.primary_monitor().ok().flatten())
+        .ok_or_else(|| AgentError::Internal("无法获取显示器信息".to_string()))?;
+    let area = monitor.work_area();
+    Ok((area.position, area.size, monitor.scale_factor()))
+}
+
+/// 预设对应的窗口位置与大小（物理像素）
+fn preset_rect(
+    preset: WindowPreset,
+    origin: PhysicalPosition<i32>,
+    area: PhysicalSize<u32>,
+    scale_factor: f64,
+) -> (PhysicalPosition<i32>, PhysicalSize<u32>) {
+    let half_width = area.width / 2;
+    let size = match preset {
+        WindowPreset::LeftHalf | WindowPreset::RightHalf => {
+            PhysicalSize::new(half_width, area.height)
+        }
+        WindowPreset::CenteredSmall => PhysicalSize::new(
+            ((SMALL_SIZE.0 * scale_factor) as u32).min(area.width),
+            ((SMALL_SIZE.1 * scale_factor) as u32).min(area.height),
+        ),
+        WindowPreset::CenteredLarge => PhysicalSize::new(
+            (area.width as f64 * LARGE_RATIO) as u32,
+            (area.height as f64 * LARGE_RATIO) as u32,
+        ),
+    };
+    let position = match preset {
+        WindowPreset::LeftHalf => origin,
+        WindowPreset::RightHalf => PhysicalPosition::new(origin.x + half_width as i32, origin.y),
+        WindowPreset::CenteredSmall | WindowPreset::CenteredLarge => PhysicalPosition::new(
+            origin.x + (area.width - size.width) as i32 / 2,
+            origin.y + (area.height - size.height) as i32 / 2,
+        ),
+    };
+    (position, size)
+}
+
+/// 取消最大化、最小化与隐藏，之后才能设置位置与大小
+fn prepare(window: &WebviewWindow) -> AgentResult<()> {
+    clear_pending_state(window.label());
+    window.unmaximize().map_err(window_error)?;
+    window.unminimize().map_err(window_error)?;
+    window.show().map_err(window_error)?;
+    window.set_focus().map_err(window_error)
+}
+
+/// 按预设调整窗口并立即保存，返回调整后的窗口状态
+pub async fn apply_preset(
+    window: &WebviewWindow,
+    preset: WindowPreset,
+) -> AgentResult<WindowState> {
+    prepare(window)?;
+    let (origin, area, scale_factor) = work_area(window)?;
+    let (position, size) = preset_rect(preset, origin, area, scale_factor);
+    window.set_size(size).map_err(window_error)?;
+    window.set_position(position).map_err(window_error)?;
+
+    let state = capture_window_state(window).unwrap_or(WindowState {
+        x: position.x as f64,
+        y: position.y as f64,
+        width: size.width as f64,
+        height: size.height as f64,
+        maximized: false,
+    });
+    state_manager::save_window_state(window.label(), state.clone())
+        .await
+        .map_err(AgentError::Internal)?;
+    tracing::info!(target: "window::preset", label = window.label(), preset = ?preset, x = state.x, y = state.y, width = state.width, height = state.height, "已应用窗口布局预设");
+    Ok(state)
+}
+
+/// 清除保存的窗口状态，恢复默认大小并居中显示
+pub async fn reset(window: &WebviewWindow) -> AgentResult<WindowState> {
+    state_manager::clear_window_state(window.label()).await?;
+    prepare(window)?;
+
+    let default = state_manager::profile(window.label()).default_state();
+    window
+        .set_size(PhysicalSize::new(
+            default.width as u32,
+            default.height as u32,
+        ))
+        .map_err(window_error)?;
+    window.center().map_err(window_error)?;
+
+    let state = capture_window_state(window).unwrap_or(default);
+    tracing::info!(target: "window::preset", label = window.label(), x = state.x, y = state.y, "窗口状态已重置");
+    Ok(state)
+}
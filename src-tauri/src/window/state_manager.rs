@@ -1,10 +1,15 @@
 // 窗口状态管理模块
-// 负责保存和恢复应用程序窗口状态
+// 负责保存和恢复应用程序窗口状态（保存在 agent.db 中，每个窗口按 label 一条记录）
 
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
 use serde::{Deserialize, Serialize};
 use std::fs;
 
-use crate::config_manager::ConfigManager;
+use crate::error::AgentResult;
+use crate::utils::blocking::run_blocking;
+
+/// 主窗口的 label
+pub const MAIN_WINDOW: &str = "main";
 
 // 窗口状态结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,90 +23,284 @@ pub struct WindowState {
 
 impl Default for WindowState {
     fn default() -> Self {
-        Self {
+        profile(MAIN_WINDOW).default_state()
+    }
+}
+
+/// 各窗口的默认大小与最小尺寸
+#[derive(Debug, Clone, Copy)]
+pub struct WindowProfile {
+    pub label: &'static str,
+    pub default_width: f64,
+    pub default_height: f64,
+    /// 小于该尺寸的状态视为无效
+    pub min_width: f64,
+    pub min_height: f64,
+}
+
+impl WindowProfile {
+    /// 默认窗口状态
+    pub fn default_state(&self) -> WindowState {
+        WindowState {
             x: 100.0,
             y: 100.0,
-            width: 800.0,
-            height: 600.0,
+            width: self.default_width,
+            height: self.default_height,
             maximized: false,
         }
     }
 }
 
+/// 已知窗口的配置；新增窗口时在这里追加一项，未登记的窗口使用 [`GENERIC_PROFILE`]
+const WINDOW_PROFILES: &[WindowProfile] = &[
+    WindowProfile {
+        label: MAIN_WINDOW,
+        default_width: 800.0,
+        default_height: 600.0,
+        min_width: 400.0,
+        min_height: 400.0,
+    },
+    WindowProfile {
+        label: "log-viewer",
+        default_width: 900.0,
+        default_height: 600.0,
+        min_width: 400.0,
+        min_height: 300.0,
+    },
+    WindowProfile {
+        label: "dashboard",
+        default_width: 480.0,
+        default_height: 360.0,
+        min_width: 300.0,
+        min_height: 200.0,
+    },
+];
+
+/// 未登记窗口的配置
+const GENERIC_PROFILE: WindowProfile = WindowProfile {
+    label: "",
+    default_width: 800.0,
+    default_height: 600.0,
+    min_width: 300.0,
+    min_height: 200.0,
+};
+
+/// 窗口的配置
+pub fn profile(label: &str) -> WindowProfile {
+    WINDOW_PROFILES
+        .iter()
+        .find(|profile| profile.label == label)
+        .copied()
+        .unwrap_or(GENERIC_PROFILE)
+}
+
 impl WindowState {
-    /// 验证窗口状态是否有效
+    /// 按窗口配置验证状态是否有效
     ///
     /// 过滤以下无效状态：
     /// - 窗口位置超出合理范围（如 -32000，表示窗口被隐藏）
-    /// - 窗口大小过小（宽度或高度 < 400）
+    /// - 窗口大小小于该窗口的最小尺寸
     /// - 窗口大小过大（宽度 > 4000 或高度 > 3000）
-    pub fn is_valid(&self) -> bool {
+    pub fn is_valid(&self, profile: &WindowProfile) -> bool {
         // 检查位置是否在合理范围内（-1000 到 10000）
         let position_valid =
             self.x > -1000.0 && self.x < 10000.0 && self.y > -1000.0 && self.y < 10000.0;
 
-        // 检查窗口大小是否合理（400x400 到 4000x3000）
-        let size_valid = self.width >= 400.0
+        // 检查窗口大小是否合理（最小尺寸到 4000x3000）
+        let size_valid = self.width >= profile.min_width
             && self.width <= 4000.0
-            && self.height >= 400.0
+            && self.height >= profile.min_height
             && self.height <= 3000.0;
 
         position_valid && size_valid
     }
 }
 
+/// 导入旧版本的 window_state.json（由 agent.db 迁移调用），导入后文件重命名为 `.migrated`
+///
+/// 写入迁移 1 创建的单行表，之后的迁移会把它转为按 label 记录
+pub fn import_legacy_file(tx: &Transaction) -> AgentResult<()> {
+    let path = crate::directories::get_window_state_file();
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(());
+    };
+    match serde_json::from_str::<WindowState>(&content) {
+        Ok(state) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO window_state (id, x, y, width, height, maximized)
+                 VALUES (1, ?1, ?2, ?3, ?4, ?5)",
+                params![state.x, state.y, state.width, state.height, state.maximized],
+            )?;
+        }
+        Err(e) => {
+            tracing::warn!(target: "window::state", error = %e, "旧版窗口状态解析失败，跳过导入")
+        }
+    }
+    fs::rename(&path, path.with_extension("json.migrated"))?;
+    Ok(())
+}
+
+fn write_state(conn: &Connection, label: &str, state: &WindowState) -> AgentResult<()> {
+    conn.execute(
+        "INSERT INTO window_states (label, x, y, width, height, maximized)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT (label) DO UPDATE SET
+             x = excluded.x, y = excluded.y, width = excluded.width,
+             height = excluded.height, maximized = excluded.maximized",
+        params![
+            label,
+            state.x,
+            state.y,
+            state.width,
+            state.height,
+            state.maximized
+        ],
+    )?;
+    Ok(())
+}
+
+/// 记录退出时主窗口是否隐藏在托盘中（还没有保存过窗口位置时使用默认位置）
+pub fn save_hidden_in_tray(hidden: bool) -> AgentResult<()> {
+    let default = WindowState::default();
+    crate::agent_db::with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO window_states (label, x, y, width, height, maximized, hidden_in_tray)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT (label) DO UPDATE SET hidden_in_tray = excluded.hidden_in_tray",
+            params![
+                MAIN_WINDOW,
+                default.x,
+                default.y,
+                default.width,
+                default.height,
+                default.maximized,
+                hidden
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+/// 上次退出时主窗口是否隐藏在托盘中
+pub fn was_hidden_in_tray() -> bool {
+    crate::agent_db::with_connection(|conn| {
+        Ok(conn
+            .query_row(
+                "SELECT hidden_in_tray FROM window_states WHERE label = ?1",
+                [MAIN_WINDOW],
+                |row| row.get(0),
+            )
+            .optional()?)
+    })
+    .unwrap_or_else(|e| {
+        tracing::warn!(target: "window::restore", error = %e, "读取托盘隐藏状态失败");
+        None
+    })
+    .unwrap_or(false)
+}
+
 /// 保存窗口状态
-pub async fn save_window_state(state: WindowState) -> Result<(), String> {
+pub async fn save_window_state(label: &str, state: WindowState) -> Result<(), String> {
     // 验证窗口状态是否有效，拒绝保存异常值
-    if !state.is_valid() {
-        println!(
-            "⚠️ 检测到无效的窗口状态，跳过保存: 位置({:.1}, {:.1}), 大小({:.1}x{:.1})",
-            state.x, state.y, state.width, state.height
+    if !state.is_valid(&profile(label)) {
+        tracing::warn!(
+            target: "window::save",
+            label,
+            x = state.x,
+            y = state.y,
+            width = state.width,
+            height = state.height,
+            "检测到无效的窗口状态，跳过保存"
         );
         return Ok(()); // 不返回错误，静默忽略
     }
 
-    // 使用 ConfigManager 统一管理配置目录
-    let config_manager = ConfigManager::new()?;
-    let state_file = config_manager.window_state_file();
-
-    let json_content =
-        serde_json::to_string(&state).map_err(|e| format!("序列化窗口状态失败: {}", e))?;
+    let saved = state.clone();
+    let saved_label = label.to_string();
+    run_blocking(move || {
+        crate::agent_db::with_connection(|conn| write_state(conn, &saved_label, &saved))
+    })
+    .await
+    .map_err(|e| format!("保存窗口状态失败: {}", e))?;
 
-    fs::write(state_file, json_content).map_err(|e| format!("保存窗口状态失败: {}", e))?;
-
-    println!(
-        "💾 窗口状态已保存: 位置({:.1}, {:.1}), 大小({:.1}x{:.1}), 最大化:{}",
-        state.x, state.y, state.width, state.height, state.maximized
+    tracing::debug!(
+        target: "window::save",
+        label,
+        x = state.x,
+        y = state.y,
+        width = state.width,
+        height = state.height,
+        maximized = state.maximized,
+        "窗口状态已保存"
     );
 
     Ok(())
 }
 
-/// 加载窗口状态
-pub async fn load_window_state() -> Result<WindowState, String> {
-    // 使用 ConfigManager 统一管理配置目录
-    let config_manager = ConfigManager::new()?;
-    let state_file = config_manager.window_state_file();
-
-    if state_file.exists() {
-        let content =
-            fs::read_to_string(&state_file).map_err(|e| format!("读取窗口状态文件失败: {}", e))?;
-
-        let state: WindowState =
-            serde_json::from_str(&content).map_err(|e| format!("解析窗口状态失败: {}", e))?;
-
-        // 验证加载的状态是否有效
-        if !state.is_valid() {
-            println!(
-                "⚠️ 加载的窗口状态无效（位置({:.1}, {:.1}), 大小({:.1}x{:.1})），使用默认状态",
-                state.x, state.y, state.width, state.height
-            );
-            return Ok(WindowState::default());
-        }
+/// 同步保存窗口状态（退出流程中使用）；无效状态不保存并返回 false
+pub fn save_window_state_blocking(label: &str, state: &WindowState) -> AgentResult<bool> {
+    if !state.is_valid(&profile(label)) {
+        return Ok(false);
+    }
+    crate::agent_db::with_connection(|conn| write_state(conn, label, state))?;
+    Ok(true)
+}
+
+/// 清除保存的窗口状态（下次打开使用默认位置与大小）
+pub async fn clear_window_state(label: &str) -> AgentResult<()> {
+    let label = label.to_string();
+    run_blocking(move || {
+        crate::agent_db::with_connection(|conn| {
+            conn.execute("DELETE FROM window_states WHERE label = ?1", [&label])?;
+            Ok(())
+        })
+    })
+    .await
+}
 
-        Ok(state)
-    } else {
-        Ok(WindowState::default())
+/// 加载窗口状态，没有保存过或保存的状态无效时返回该窗口的默认状态
+pub async fn load_window_state(label: &str) -> Result<WindowState, String> {
+    let query_label = label.to_string();
+    let state = run_blocking(move || {
+        crate::agent_db::with_connection(|conn| {
+            Ok(conn
+                .query_row(
+                    "SELECT x, y, width, height, maximized FROM window_states WHERE label = ?1",
+                    [&query_label],
+                    |row| {
+                        Ok(WindowState {
+                            x: row.get(0)?,
+                            y: row.get(1)?,
+                            width: row.get(2)?,
+                            height: row.get(3)?,
+                            maximized: row.get(4)?,
+                        })
+                    },
+                )
+                .optional()?)
+        })
+    })
+    .await
+    .map_err(|e| format!("读取窗口状态失败: {}", e))?;
+
+    let profile = profile(label);
+    let Some(state) = state else {
+        return Ok(profile.default_state());
+    };
+
+    // 验证加载的状态是否有效
+    if !state.is_valid(&profile) {
+        tracing::warn!(
+            target: "window::restore",
+            label,
+            x = state.x,
+            y = state.y,
+            width = state.width,
+            height = state.height,
+            "加载的窗口状态无效，使用默认状态"
+        );
+        return Ok(profile.default_state());
     }
+
+    Ok(state)
 }
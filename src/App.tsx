@@ -4,12 +4,23 @@ import {useDevToolsShortcut} from './hooks/use-devTools-shortcut.ts';
 import {useAntigravityAccount} from './modules/use-antigravity-account.ts';
 import {DATABASE_EVENTS, useDbMonitoringStore} from './modules/db-monitoring-store';
 import {useAntigravityIsRunning} from './hooks/use-antigravity-is-running.ts';
-import {Toaster} from 'react-hot-toast';
+import toast, {Toaster} from 'react-hot-toast';
 import AppDock from './components/app/AppDock.tsx';
 import {AppContent} from "@/components/app/AppContent.tsx";
 import {AppLoader} from "@/components/app/AppLoader.tsx";
 import {PlatformCommands} from "@/commands/PlatformCommands.ts";
 import {useAppSettings} from "@/modules/use-app-settings.ts";
+import {listen} from "@tauri-apps/api/event";
+import type {AccountChangedEvent, BackupConsistencyReport, BackupStaleEvent, LoginProbeEvent} from "@/commands/types/account.types.ts";
+import type {AvailabilityChangedEvent, UpdateSnapshot} from "@/commands/types/platform.types.ts";
+import type {TrayActionEvent, TrayState} from "@/commands/types/tray.types.ts";
+import type {LowDiskSpaceEvent} from "@/commands/types/storage.types.ts";
+import {logger} from "@/lib/logger.ts";
+import {Modal} from 'antd';
+import {TaskCommands} from "@/commands/TaskCommands.ts";
+import type {InterruptedTask, InterruptedTaskAction} from "@/commands/types/task.types.ts";
+import {SettingsCommands} from "@/commands/SettingsCommands.ts";
+import type {ThemeState, UiPreferences} from "@/commands/types/settings.types.ts";
 
 function App() {
   // ========== 应用状态 ==========
@@ -39,6 +50,259 @@ function App() {
     };
   }, []);
 
+  // 监听后端检测到的 Antigravity 内登录账户变化
+  useEffect(() => {
+    const unlisten = listen<AccountChangedEvent>('account:changed', (event) => {
+      logger.info('检测到 Antigravity 登录账户变化', {
+        module: 'App',
+        backedUp: event.payload.backedUp
+      });
+      antigravityAccount.getAccounts();
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 启动时当前账户未备份或备份过期的提醒
+  useEffect(() => {
+    const unlisten = listen<BackupConsistencyReport>('account:backup-consistency', (event) => {
+      const {status, staleDays} = event.payload;
+      logger.warn('启动时备份一致性检查未通过', {module: 'App', status, staleDays});
+      toast(status === 'stale'
+        ? `当前账户已超过 ${staleDays} 天未备份，建议立即备份`
+        : '当前登录的账户尚未备份，建议立即备份');
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 监听当前账户备份过期提醒
+  useEffect(() => {
+    const unlisten = listen<BackupStaleEvent>('account:backup-stale', (event) => {
+      const {lastBackupAt, staleDays} = event.payload;
+      logger.warn('当前账户备份已过期', {
+        module: 'App',
+        lastBackupAt,
+        staleDays
+      });
+      toast(lastBackupAt
+        ? `当前账户已超过 ${staleDays} 天未备份，建议立即备份`
+        : '当前账户尚未备份，建议立即备份');
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 监听磁盘空间不足（备份、快照或恢复被中止）
+  useEffect(() => {
+    const unlisten = listen<LowDiskSpaceEvent>('disk:low-space', (event) => {
+      const {path, operation, availableBytes, requiredBytes} = event.payload;
+      logger.warn('磁盘剩余空间不足', {
+        module: 'App',
+        path,
+        operation,
+        availableBytes,
+        requiredBytes
+      });
+      const toMb = (bytes: number) => Math.floor(bytes / 1024 / 1024);
+      toast.error(`磁盘剩余空间不足（剩余 ${toMb(availableBytes)} MB，至少需要 ${toMb(requiredBytes)} MB），请清理磁盘后重试`);
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 监听切换账户后的登录验证结果
+  useEffect(() => {
+    const unlisten = listen<LoginProbeEvent>('account:login-probe', (event) => {
+      const {status, currentEmail, elapsedMs} = event.payload;
+      if (status === 'verified') {
+        logger.info('切换后的账户登录有效', {module: 'App', elapsedMs});
+        return;
+      }
+      logger.warn('切换后的账户登录未通过验证', {module: 'App', status, elapsedMs});
+      if (status === 'logged_out') {
+        toast.error('切换后的账户登录已失效，请在 Antigravity 中重新登录');
+      } else if (status === 'mismatch') {
+        toast.error(`Antigravity 当前登录的是其他账户：${currentEmail}`);
+      } else {
+        toast('未能确认切换后的账户是否登录成功，请检查 Antigravity');
+      }
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 监听 Antigravity 版本更新（后端已自动快照当前账户和设置）
+  useEffect(() => {
+    const unlisten = listen<UpdateSnapshot>('antigravity:version-changed', (event) => {
+      const {previousVersion, currentVersion} = event.payload;
+      logger.info('检测到 Antigravity 版本更新', {
+        module: 'App',
+        previousVersion,
+        currentVersion
+      });
+      toast(`Antigravity 已从 ${previousVersion} 更新到 ${currentVersion}，已自动创建快照`);
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 监听 Agent 运行期间 Antigravity 的安装与卸载
+  useEffect(() => {
+    const unlisten = listen<AvailabilityChangedEvent>('antigravity:availability-changed', (event) => {
+      const {available, dbPath} = event.payload;
+      logger.info('Antigravity 安装状态变化', {
+        module: 'App',
+        available,
+        dbPath
+      });
+      if (available) {
+        toast.success('已检测到 Antigravity');
+      } else {
+        toast.error('未再检测到 Antigravity，可能已被卸载');
+      }
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 同步后端托盘启用状态（设置页、首次运行引导等任一入口修改后推送）
+  useEffect(() => {
+    const unlisten = listen<TrayState>('tray:state-changed', (event) => {
+      useAppSettings.setState({systemTrayEnabled: event.payload.enabled});
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 同步界面主题（启动时读取，之后跟随设置修改与系统深浅色变化）
+  useEffect(() => {
+    const applyTheme = ({preference, effective}: ThemeState) => {
+      document.documentElement.classList.toggle('dark', effective === 'dark');
+      logger.info('界面主题已更新', {module: 'App', preference, effective});
+    };
+    SettingsCommands.getThemeState()
+      .then(applyTheme)
+      .catch(error => logger.warn('读取界面主题失败', {module: 'App', error: String(error)}));
+    const unlisten = listen<ThemeState>('appearance:theme-changed', (event) => applyTheme(event.payload));
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 同步强调色、界面密度与字号缩放（任一窗口修改后由后端推送给所有窗口）
+  useEffect(() => {
+    const applyUiPreferences = ({accentColor, density, fontScale}: UiPreferences) => {
+      const root = document.documentElement;
+      root.style.setProperty('--primary', accentColor);
+      root.style.fontSize = `${fontScale * 100}%`;
+      root.dataset.density = density;
+      logger.info('界面偏好已更新', {module: 'App', accentColor, density, fontScale});
+    };
+    SettingsCommands.getUiPreferences()
+      .then(applyUiPreferences)
+      .catch(error => logger.warn('读取界面偏好失败', {module: 'App', error: String(error)}));
+    const unlisten = listen<UiPreferences>('appearance:ui-preferences-changed', (event) => applyUiPreferences(event.payload));
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 托盘菜单中立即备份的结果
+  useEffect(() => {
+    const unlisten = listen<TrayActionEvent>('tray:backup-finished', (event) => {
+      const {success, message} = event.payload;
+      if (success) {
+        toast.success('已备份当前账户');
+      } else {
+        toast.error(`备份失败: ${message}`);
+      }
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 上次运行时中断的操作：提示继续执行或回滚
+  useEffect(() => {
+    const resolve = async (task: InterruptedTask, action: InterruptedTaskAction) => {
+      try {
+        toast.success(await TaskCommands.resolveInterruptedTask(task.id, action));
+      } catch (error) {
+        toast.error(`处理中断的操作失败: ${error}`);
+      }
+    };
+
+    TaskCommands.listInterruptedTasks().then(tasks => {
+      for (const task of tasks) {
+        const action: InterruptedTaskAction = task.resumable ? 'resume' : task.rollbackSnapshot ? 'rollback' : 'dismiss';
+        Modal.confirm({
+          centered: true,
+          title: '上次的操作未完成',
+          content: <p className={"wrap-break-word whitespace-pre-line"}>
+            {`Agent 在执行 ${task.kind}${task.target ? `（${task.target}）` : ''} 时被关闭${task.message ? `，中断于「${task.message}」` : ''}。`}
+          </p>,
+          okText: action === 'resume' ? '继续执行' : action === 'rollback' ? '回滚到操作前' : '忽略',
+          cancelText: '稍后处理',
+          onOk() {
+            return resolve(task, action);
+          },
+        });
+      }
+    }).catch(error => logger.error('获取中断的操作失败', {module: 'App', error}));
+  }, []);
+
+  // 托盘菜单中清除全部数据的结果
+  useEffect(() => {
+    const unlisten = listen<TrayActionEvent>('tray:clear-data-finished', (event) => {
+      const {success, message} = event.payload;
+      if (success) {
+        toast.success('已清除所有 Antigravity 数据');
+      } else {
+        toast.error(`清除数据失败: ${message}`);
+      }
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  // 托盘菜单中复制当前账户邮箱的结果
+  useEffect(() => {
+    const unlisten = listen<TrayActionEvent>('tray:copy-finished', (event) => {
+      const {success, message} = event.payload;
+      if (success) {
+        toast.success(message);
+      } else {
+        toast.error(`复制失败: ${message}`);
+      }
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
   // 启动 Antigravity 进程状态自动检查
   const antigravityIsRunning = useAntigravityIsRunning();
 
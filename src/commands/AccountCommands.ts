@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import {AntigravityAccount} from "@/commands/types/account.types.ts";
+import {AccountBackupResult, AntigravityAccount, BackupSchema, BackupSearchQuery, BackupSummary, RestoreQueueItem, RestoreQueueItemResult, RestoreReport} from "@/commands/types/account.types.ts";
 
 /**
  * Antigravity 账户管理命令
@@ -23,26 +23,94 @@ export class AccountCommands {
 
   /**
    * 备份当前登录的账户
+   * @param note 可选备注（如「重装系统前」），保存在备份元数据中
    * @returns 备份结果消息
    */
-  static async saveAntigravityCurrentAccount(): Promise<string> {
-    return invoke('save_antigravity_current_account');
+  static async saveAntigravityCurrentAccount(note?: string): Promise<string> {
+    return invoke('save_antigravity_current_account', { note });
+  }
+
+  /**
+   * 列出所有账户备份摘要，包含令牌过期状态，便于在恢复前发现失效的备份
+   * @returns 备份摘要列表（按邮箱排序）
+   */
+  static async listBackupSummaries(): Promise<BackupSummary[]> {
+    return invoke('list_backup_summaries');
+  }
+
+  /**
+   * 按邮箱、别名、备注与备份来源筛选账户备份
+   * @param query 搜索条件
+   * @returns 符合条件的备份摘要（按邮箱排序）
+   */
+  static async searchBackups(query: BackupSearchQuery): Promise<BackupSummary[]> {
+    return invoke('search_backups', { query });
+  }
+
+  /**
+   * 获取账户备份文件的结构说明，可用于校验或生成兼容的备份文件
+   * @returns 包含的键、元数据字段、结构版本与 JSON Schema
+   */
+  static async describeBackupSchema(): Promise<BackupSchema> {
+    return invoke('describe_backup_schema');
+  }
+
+  /**
+   * 批量刷新所有已备份账户（仅当前登录的账户会重新备份）
+   * @returns 每个账户的处理结果
+   */
+  static async backupAllKnownAccounts(): Promise<AccountBackupResult[]> {
+    return invoke('backup_all_known_accounts');
+  }
+
+  /**
+   * 将备份恢复到 Antigravity 数据库（不重启）
+   * @param accountName 账户名（邮箱）
+   * @param options.taskId 任务 ID（可选，用于接收 task:progress 事件或调用 cancel_task）
+   * @param options.closeAntigravity Antigravity 正在运行时是否先关闭它（否则返回 ANTIGRAVITY_RUNNING 错误）
+   * @param options.strict 任一键写入失败即回滚（结果见 success 字段）
+   * @param options.replaceSession Antigravity 当前登录了其他账户时先清除当前会话（否则返回 SESSION_MISMATCH 错误）
+   * @returns 逐键的恢复报告
+   */
+  static async restoreAntigravityAccount(
+    accountName: string,
+    options: { taskId?: string; closeAntigravity?: boolean; strict?: boolean; replaceSession?: boolean } = {},
+  ): Promise<RestoreReport> {
+    return invoke('restore_antigravity_account', { accountName, ...options });
+  }
+
+  /**
+   * 按顺序把多个备份恢复到各自的 Antigravity 数据目录（多实例），单项失败不影响后续项目
+   * @param items 恢复队列（同一数据目录只能出现一次）
+   * @param options.taskId 任务 ID（可选，用于接收 task:progress 事件或调用 cancel_task，取消后剩余项目标记为跳过）
+   * @param options.closeAntigravity Antigravity 正在运行时是否先关闭它（否则返回 ANTIGRAVITY_RUNNING 错误）
+   * @param options.strict 单项中任一键写入失败即回滚该项
+   * @returns 每一项的处理结果
+   */
+  static async runRestoreQueue(
+    items: RestoreQueueItem[],
+    options: { taskId?: string; closeAntigravity?: boolean; strict?: boolean } = {},
+  ): Promise<RestoreQueueItemResult[]> {
+    return invoke('run_restore_queue', { items, ...options });
   }
 
   /**
    * 切换到指定账户（完整流程：关闭进程 → 恢复数据 → 重启）
    * @param accountName 账户名（邮箱）
+   * @param taskId 任务 ID（可选，用于接收 task:progress 事件或调用 cancel_task）
    * @returns 切换结果消息
    */
-  static async switchToAntigravityAccount(accountName: string): Promise<string> {
-    return invoke('switch_to_antigravity_account', { accountName: accountName });
+  static async switchToAntigravityAccount(accountName: string, taskId?: string): Promise<string> {
+    return invoke('switch_to_antigravity_account', { accountName: accountName, taskId });
   }
 
   /**
    * 清除所有 Antigravity 数据（注销）
+   * @param confirmationToken 通过 ConfirmationCommands.requestDangerousOperation('logout_all') 获取的令牌
+   * @param closeAntigravity Antigravity 正在运行时是否先关闭它（否则返回 ANTIGRAVITY_RUNNING 错误）
    * @returns 清除结果消息
    */
-  static async clearAllData(): Promise<string> {
-    return invoke('clear_all_antigravity_data');
+  static async clearAllData(confirmationToken?: string, closeAntigravity?: boolean): Promise<string> {
+    return invoke('clear_all_antigravity_data', { confirmationToken, closeAntigravity });
   }
 }
@@ -1,16 +1,16 @@
 import {invoke} from '@tauri-apps/api/core';
-import type {BackupData, RestoreResult} from './types/account-manage.types.ts';
+import type {BackupData, BackupRenameResult, RestoreResult, SanitizedExport} from './types/account-manage.types.ts';
 
 /**
  * 账户与备份综合命令
  */
 export class AccountManageCommands {
-  static collectAccountContents(): Promise<BackupData[]> {
-    return invoke('collect_account_contents');
+  static collectAccountContents(taskId?: string): Promise<BackupData[]> {
+    return invoke('collect_account_contents', { taskId });
   }
 
-  static restoreBackupFiles(backups: BackupData[]): Promise<RestoreResult> {
-    return invoke('restore_backup_files', { accountFileData: backups });
+  static restoreBackupFiles(backups: BackupData[], taskId?: string): Promise<RestoreResult> {
+    return invoke('restore_backup_files', { accountFileData: backups, taskId });
   }
 
   static deleteBackup(name: string): Promise<string> {
@@ -21,11 +21,29 @@ export class AccountManageCommands {
     return invoke('clear_all_backups');
   }
 
+  static migrateBackupFileNames(): Promise<BackupRenameResult[]> {
+    return invoke('migrate_backup_file_names');
+  }
+
+  /**
+   * 导出账户备份的脱敏副本（令牌等凭据替换为占位值），用于问题反馈或协助排查
+   * @param name 账户邮箱
+   */
+  static exportSanitizedBackup(name: string): Promise<SanitizedExport> {
+    return invoke('export_sanitized_backup', { name });
+  }
+
   // ==== 配置加解密 ====
+  /**
+   * 加密导出配置（密码至少 8 位、包含两类以上字符且不是常见弱密码）
+   */
   static encryptConfig(jsonData: string, password: string): Promise<string> {
     return invoke('encrypt_config_data', { jsonData: jsonData, password });
   }
 
+  /**
+   * 解密导出配置（连续输错密码后需等待一段时间才能重试）
+   */
   static decryptConfig(encryptedData: string, password: string): Promise<string> {
     return invoke('decrypt_config_data', { encryptedData: encryptedData, password });
   }
@@ -0,0 +1,74 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { AccountAvatar, LaunchProfile, RecentAccount } from './types/account-metadata.types';
+
+/**
+ * 账户元数据命令
+ */
+export class AccountMetadataCommands {
+  /**
+   * 获取账户的启动配置
+   * @param email 账户邮箱
+   * @returns 启动配置，未设置时为 null
+   */
+  static async getLaunchProfile(email: string): Promise<LaunchProfile | null> {
+    return invoke('get_account_launch_profile', { email });
+  }
+
+  /**
+   * 保存账户的启动配置
+   * @param email 账户邮箱
+   * @param profile 启动配置，传入 null 时清除并恢复使用全局默认值
+   * @returns 保存后的启动配置
+   */
+  static async saveLaunchProfile(email: string, profile: LaunchProfile | null): Promise<LaunchProfile | null> {
+    return invoke('save_account_launch_profile', { email, profile });
+  }
+
+  /**
+   * 设置账户别名（托盘菜单中代替打码邮箱显示）
+   * @param email 账户邮箱
+   * @param alias 别名，传入 null 或空字符串时清除
+   * @returns 保存后的别名
+   */
+  static async saveAlias(email: string, alias: string | null): Promise<string | null> {
+    return invoke('save_account_alias', { email, alias });
+  }
+
+  /**
+   * 获取最近使用的账户（置顶在前，其余按切换、恢复、备份的先后排序）
+   * @param limit 最多返回的数量，不传时返回全部
+   * @returns 最近使用的账户
+   */
+  static async getRecentAccounts(limit?: number): Promise<RecentAccount[]> {
+    return invoke('get_recent_accounts', { limit });
+  }
+
+  /**
+   * 置顶或取消置顶账户
+   * @param email 账户邮箱
+   * @param pinned 是否置顶
+   * @returns 保存后的状态
+   */
+  static async setPinned(email: string, pinned: boolean): Promise<boolean> {
+    return invoke('set_account_pinned', { email, pinned });
+  }
+
+  /**
+   * 获取缓存的账户头像（不访问网络）
+   * @param email 账户邮箱
+   * @returns 头像，没有缓存时 dataUrl 为 null
+   */
+  static async getAvatar(email: string): Promise<AccountAvatar> {
+    return invoke('get_account_avatar', { email });
+  }
+
+  /**
+   * 下载并缓存账户头像（仅支持 HTTPS 地址），下载失败时返回已有的缓存
+   * @param email 账户邮箱
+   * @param url 头像地址（userinfo 接口返回的 picture）
+   * @returns 缓存后的头像
+   */
+  static async cacheAvatar(email: string, url: string): Promise<AccountAvatar> {
+    return invoke('cache_account_avatar', { email, url });
+  }
+}
@@ -0,0 +1,17 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ClipboardContent } from './types/clipboard.types';
+
+/**
+ * 剪贴板命令
+ */
+export class ClipboardCommands {
+  /**
+   * 复制账户邮箱、备份文件路径或诊断摘要到剪贴板
+   * @param content 要复制的内容
+   * @param clearAfterSecs 到时自动清除剪贴板（剪贴板已被替换为其他内容时保留），不传则不清除
+   * @returns 复制的文本
+   */
+  static async copy(content: ClipboardContent, clearAfterSecs?: number): Promise<string> {
+    return invoke('copy_to_clipboard', { content, clearAfterSecs });
+  }
+}
@@ -0,0 +1,16 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ConfirmationToken, DangerousOperation } from './types/confirmation.types';
+
+/**
+ * 危险操作确认命令
+ */
+export class ConfirmationCommands {
+  /**
+   * 申请危险操作的确认令牌，用户确认后再传给对应命令
+   * @param operation 操作名，如 logout_all
+   * @returns 短时有效的一次性令牌
+   */
+  static async requestDangerousOperation(operation: DangerousOperation): Promise<ConfirmationToken> {
+    return invoke('request_dangerous_operation', { operation });
+  }
+}
@@ -0,0 +1,25 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { CrashReport } from './types/crash.types';
+
+/**
+ * 崩溃报告命令
+ */
+export class CrashCommands {
+  /**
+   * 获取最近一次崩溃报告
+   * @returns 崩溃报告，没有时为 null
+   */
+  static async getLastCrashReport(): Promise<CrashReport | null> {
+    return invoke('get_last_crash_report');
+  }
+
+  /**
+   * 记录前端未捕获的渲染异常
+   * @param message 错误信息
+   * @param stack 调用栈（包含组件栈）
+   * @returns 报告文件路径
+   */
+  static async reportFrontendCrash(message: string, stack?: string): Promise<string> {
+    return invoke('report_frontend_crash', { message, stack });
+  }
+}
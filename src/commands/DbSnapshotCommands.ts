@@ -0,0 +1,24 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { DbSnapshot } from './types/db-snapshot.types';
+
+/**
+ * 数据库文件快照命令
+ */
+export class DbSnapshotCommands {
+  /**
+   * 列出所有数据库文件快照
+   * @returns 快照列表（最新的在前）
+   */
+  static async listDbSnapshots(): Promise<DbSnapshot[]> {
+    return invoke('list_db_snapshots');
+  }
+
+  /**
+   * 用快照整体回滚 Antigravity 数据库（需先关闭 Antigravity，回滚前会快照当前文件）
+   * @param id 快照 ID
+   * @returns 使用的快照
+   */
+  static async restoreDbSnapshot(id: string): Promise<DbSnapshot> {
+    return invoke('restore_db_snapshot', { id });
+  }
+}
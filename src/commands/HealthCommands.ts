@@ -0,0 +1,24 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { HealthReport, PermissionFixReport } from './types/health.types';
+
+/**
+ * 健康检查命令
+ */
+export class HealthCommands {
+  /**
+   * 执行健康检查
+   * @returns 各检查项结果及修复建议
+   */
+  static async run(): Promise<HealthReport> {
+    return invoke('run_health_check');
+  }
+
+  /**
+   * 将配置目录、备份与快照中权限过宽的文件收紧为仅当前用户可访问
+   * Unix 下目录改为 700、文件改为 600；Windows 下不做处理
+   * @returns 已修复与修复失败的路径
+   */
+  static async fixPermissions(): Promise<PermissionFixReport> {
+    return invoke('fix_permissions');
+  }
+}
@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { AuditEntry, HooksConfig } from './types/hooks.types';
+
+/**
+ * 生命周期钩子与审计日志命令
+ */
+export class HooksCommands {
+  /**
+   * 获取钩子配置
+   * @returns 钩子配置
+   */
+  static async getConfig(): Promise<HooksConfig> {
+    return invoke('get_hooks_config');
+  }
+
+  /**
+   * 保存钩子配置
+   * @param config 钩子配置
+   * @returns 保存后的配置
+   */
+  static async saveConfig(config: HooksConfig): Promise<HooksConfig> {
+    return invoke('save_hooks_config', { config });
+  }
+
+  /**
+   * 获取最近的审计日志（最新的在前）
+   * @param limit 返回条数，默认 200
+   * @returns 审计日志条目
+   */
+  static async getAuditLog(limit?: number): Promise<AuditEntry[]> {
+    return invoke('get_audit_log', { limit });
+  }
+}
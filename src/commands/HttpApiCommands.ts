@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { HttpApiStatus } from './types/http-api.types';
+
+/**
+ * 本地 HTTP 控制接口命令
+ */
+export class HttpApiCommands {
+  /**
+   * 获取 HTTP 控制接口状态
+   * @returns 接口状态
+   */
+  static async getStatus(): Promise<HttpApiStatus> {
+    return invoke('get_http_api_status');
+  }
+
+  /**
+   * 开启/关闭 HTTP 控制接口
+   * @param enabled 是否启用
+   * @param port 监听端口（不传则沿用当前设置）
+   * @returns 保存后的状态
+   */
+  static async saveState(enabled: boolean, port?: number): Promise<HttpApiStatus> {
+    return invoke('save_http_api_state', { enabled, port });
+  }
+
+  /**
+   * 重新生成访问令牌
+   * @returns 更新后的状态
+   */
+  static async regenerateToken(): Promise<HttpApiStatus> {
+    return invoke('regenerate_http_api_token');
+  }
+}
@@ -0,0 +1,23 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { IntegrityReport, RepairResult } from './types/integrity.types';
+
+/**
+ * 数据库完整性命令
+ */
+export class IntegrityCommands {
+  /**
+   * 检查 Antigravity 数据库完整性
+   * @returns 检查结果
+   */
+  static async checkDatabaseIntegrity(): Promise<IntegrityReport> {
+    return invoke('check_database_integrity');
+  }
+
+  /**
+   * 修复损坏的 Antigravity 数据库（需先关闭 Antigravity，修复前自动快照原文件）
+   * @returns 修复结果
+   */
+  static async repairDatabase(): Promise<RepairResult> {
+    return invoke('repair_database');
+  }
+}
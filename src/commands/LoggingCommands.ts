@@ -1,5 +1,11 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { FrontendLogEntry } from './types/logging.types';
+import type {
+  FrontendLogBatchResult,
+  FrontendLogEntry,
+  FrontendLogSessionStats,
+  LogInfo,
+  OperationTrace,
+} from './types/logging.types';
 
 /**
  * 日志和加密命令
@@ -13,6 +19,29 @@ export class LoggingCommands {
     return invoke('write_frontend_log', { logEntry });
   }
 
+  /**
+   * 批量写入前端日志（后端按会话限流，超出部分丢弃）
+   * @param entries 日志条目
+   */
+  static async writeFrontendLogs(entries: FrontendLogEntry[]): Promise<FrontendLogBatchResult> {
+    return invoke('write_frontend_logs', { entries });
+  }
+
+  /**
+   * 获取各前端会话的日志统计
+   */
+  static async getFrontendLogStats(): Promise<FrontendLogSessionStats[]> {
+    return invoke('get_frontend_log_stats');
+  }
+
+  /**
+   * 按操作 ID（即任务 ID）汇总前后端日志与审计记录
+   * @param operationId 操作 ID
+   */
+  static async getOperationTrace(operationId: string): Promise<OperationTrace> {
+    return invoke('get_operation_trace', { operationId });
+  }
+
   /**
    * 打开日志目录（在系统文件管理器中打开）
    */
@@ -27,6 +56,13 @@ export class LoggingCommands {
     return invoke('get_log_directory_path');
   }
 
+  /**
+   * 获取日志目录信息（各文件的绝对路径、类型、大小与按设置格式化的修改时间）
+   */
+  static async getLogInfo(): Promise<LogInfo> {
+    return invoke('get_log_info');
+  }
+
   /**
    * 写入文本文件
    * @param path 文件路径
@@ -0,0 +1,24 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { MachineIdReport, MachineIdResetResult } from './types/machine-id.types';
+
+/**
+ * Antigravity 设备标识命令
+ */
+export class MachineIdCommands {
+  /**
+   * 查看当前设备标识
+   * @returns 设备标识及所在文件
+   */
+  static async getMachineIds(): Promise<MachineIdReport> {
+    return invoke('get_machine_ids');
+  }
+
+  /**
+   * 重新生成设备标识（需先关闭 Antigravity，重置前会自动快照）
+   * @param confirmed 用户已确认
+   * @returns 重置前后的标识与快照目录
+   */
+  static async resetMachineIds(confirmed: boolean): Promise<MachineIdResetResult> {
+    return invoke('reset_machine_ids', { confirmed });
+  }
+}
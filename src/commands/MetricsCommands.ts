@@ -0,0 +1,15 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { MetricsSnapshot } from './types/metrics.types';
+
+/**
+ * 命令性能统计命令
+ */
+export class MetricsCommands {
+  /**
+   * 获取各命令的调用次数、失败次数与耗时分布
+   * @returns 统计快照
+   */
+  static async getMetricsSnapshot(): Promise<MetricsSnapshot> {
+    return invoke('get_metrics_snapshot');
+  }
+}
@@ -0,0 +1,15 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ActiveOperation } from './types/operation.types';
+
+/**
+ * 操作锁查询命令
+ */
+export class OperationCommands {
+  /**
+   * 获取当前正在进行的破坏性操作
+   * @returns 操作列表
+   */
+  static async getActiveOperations(): Promise<ActiveOperation[]> {
+    return invoke('get_active_operations');
+  }
+}
@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { PlatformInfo, DetectionResult, PathConfig } from './types/platform.types';
+import type { PlatformInfo, DetectionResult, PathConfig, PlatformDetection, ExecutableValidation, AntigravityInstall } from './types/platform.types';
 
 /**
  * 平台工具命令
@@ -37,6 +37,14 @@ export class PlatformCommands {
     return invoke('detect_antigravity_executable');
   }
 
+  /**
+   * 重新检测 Antigravity 安装位置（丢弃缓存的检测结果）
+   * @returns 新的检测结果
+   */
+  static async refreshDetection(): Promise<PlatformDetection> {
+    return invoke('refresh_platform_detection');
+  }
+
   /**
    * 验证 Antigravity 可执行文件路径
    * @param path 文件路径
@@ -46,6 +54,32 @@ export class PlatformCommands {
     return invoke('validate_antigravity_executable', { path });
   }
 
+  /**
+   * 完整验证 Antigravity 可执行文件（存在性、可执行权限、.app 包结构、macOS 签名与版本），保存前调用
+   * @param path 文件或 .app 包路径
+   * @returns 验证结果
+   */
+  static async validateCustomExecutable(path: string): Promise<ExecutableValidation> {
+    return invoke('validate_custom_executable', { path });
+  }
+
+  /**
+   * 列出检测到的所有 Antigravity 安装（数据目录、可执行文件与版本）
+   * @returns 安装列表
+   */
+  static async listInstalls(): Promise<AntigravityInstall[]> {
+    return invoke('list_antigravity_installs');
+  }
+
+  /**
+   * 选择要使用的 Antigravity 安装，备份、恢复与启动都使用该安装
+   * @param dataDir 安装的数据目录，null 恢复自动选择
+   * @returns 保存结果消息
+   */
+  static async selectInstall(dataDir: string | null): Promise<string> {
+    return invoke('select_antigravity_install', { dataDir });
+  }
+
   /**
    * 保存用户自定义的 Antigravity 可执行文件路径
    * @param path 文件路径
@@ -55,6 +89,15 @@ export class PlatformCommands {
     return invoke('save_antigravity_executable', { path });
   }
 
+  /**
+   * 保存用户自定义的 Antigravity 数据目录，保存后重新检测安装位置
+   * @param path 数据目录（User/globalStorage 或 Antigravity 根数据目录），传入 null 恢复自动检测
+   * @returns 保存结果消息
+   */
+  static async saveAntigravityDataDir(path: string | null): Promise<string> {
+    return invoke('save_antigravity_data_dir', { path });
+  }
+
   /**
    * 获取当前配置的路径
    * @returns 路径配置
@@ -1,4 +1,5 @@
 import {invoke} from '@tauri-apps/api/core';
+import type {LaunchedProcess, MatchedProcess} from './types/process.types';
 
 /**
  * 进程管理命令
@@ -11,4 +12,20 @@ export class ProcessCommands {
   static async isRunning(): Promise<boolean> {
     return invoke('is_antigravity_running');
   }
+
+  /**
+   * 获取 Agent 启动且仍在运行的 Antigravity 进程
+   * @returns 进程列表（按 PID 排序）
+   */
+  static async getLaunchedProcesses(): Promise<LaunchedProcess[]> {
+    return invoke('get_launched_processes');
+  }
+
+  /**
+   * 列出关闭 Antigravity 时会被关闭的进程（只查询，不关闭）
+   * @returns 匹配的进程（按 PID 排序）
+   */
+  static async listMatchingProcesses(): Promise<MatchedProcess[]> {
+    return invoke('list_matching_processes');
+  }
 }
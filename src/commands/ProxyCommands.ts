@@ -0,0 +1,34 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ProxySettings, ProxyTestResult } from './types/proxy.types';
+
+/**
+ * 代理设置命令
+ */
+export class ProxyCommands {
+  /**
+   * 获取代理设置
+   * @returns 代理设置
+   */
+  static async getSettings(): Promise<ProxySettings> {
+    return invoke('get_proxy_settings');
+  }
+
+  /**
+   * 保存代理设置
+   * @param settings 代理设置
+   * @returns 保存后的设置
+   */
+  static async saveSettings(settings: ProxySettings): Promise<ProxySettings> {
+    return invoke('save_proxy_settings', { settings });
+  }
+
+  /**
+   * 测试代理连接
+   * @param settings 待测试的设置（不传则使用已保存的设置）
+   * @param target 测试目标 host:port，默认 www.google.com:443
+   * @returns 测试结果
+   */
+  static async testConnection(settings?: ProxySettings, target?: string): Promise<ProxyTestResult> {
+    return invoke('test_proxy_connection', { settings, target });
+  }
+}
@@ -0,0 +1,26 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { QuotaComparison, QuotaSample } from './types/quota.types';
+
+/**
+ * 账户配额命令
+ */
+export class QuotaCommands {
+  /**
+   * 记录获取到的账户配额（保留最近 30 天）
+   * @param email 账户邮箱
+   * @param samples 各模型的配额
+   */
+  static async record(email: string, samples: QuotaSample[]): Promise<void> {
+    return invoke('record_account_quota', { email, samples });
+  }
+
+  /**
+   * 比较账户最近一次记录的剩余配额
+   * @param emails 要比较的账户邮箱
+   * @param model 只比较指定模型，不传时比较所有模型的平均值
+   * @returns 按剩余配额排序的账户与推荐切换的账户
+   */
+  static async compare(emails: string[], model?: string): Promise<QuotaComparison> {
+    return invoke('compare_accounts_quota', { emails, model });
+  }
+}
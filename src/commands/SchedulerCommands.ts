@@ -0,0 +1,56 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { CronValidation, JobInput, JobStatus, RunRecord, Schedule } from './types/scheduler.types';
+
+/**
+ * 定时任务命令
+ */
+export class SchedulerCommands {
+  /**
+   * 校验 Cron 表达式
+   * @param expression Cron 表达式（分 时 日 月 周）
+   * @param count 有效时预览的运行次数，默认 5 次
+   */
+  static async validateCron(expression: string, count?: number): Promise<CronValidation> {
+    return invoke('validate_cron', { expression, count });
+  }
+
+  /**
+   * 预览计划接下来几次运行时间（间隔计划从当前时间起算）
+   * @param schedule 运行计划
+   * @param count 预览的运行次数，默认 5 次
+   */
+  static async previewSchedule(schedule: Schedule, count?: number): Promise<string[]> {
+    return invoke('preview_schedule', { schedule, count });
+  }
+
+  /**
+   * 获取所有定时任务
+   */
+  static async listJobs(): Promise<JobStatus[]> {
+    return invoke('list_scheduled_jobs');
+  }
+
+  /**
+   * 新建或修改定时任务（按名称匹配）
+   * @param job 任务内容
+   */
+  static async saveJob(job: JobInput): Promise<JobStatus> {
+    return invoke('save_scheduled_job', { job });
+  }
+
+  /**
+   * 删除定时任务
+   * @param name 任务名称
+   */
+  static async deleteJob(name: string): Promise<void> {
+    return invoke('delete_scheduled_job', { name });
+  }
+
+  /**
+   * 获取定时任务的运行历史（最新的在前）
+   * @param limit 返回的条数，默认 50 条
+   */
+  static async getHistory(limit?: number): Promise<RunRecord[]> {
+    return invoke('get_scheduler_history', { limit });
+  }
+}
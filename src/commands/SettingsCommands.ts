@@ -1,20 +1,12 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { AppSettings } from './types/settings.types';
+import type { AppSettings, ConfirmPrompt, EffectiveKeys, EmailMaskStyle, Language, LaunchPolicy, SchedulerSettings, SetupReport, ThemePreference, ThemeState, TimeDisplaySettings, UiPreferences, WaitSettings } from './types/settings.types';
+import type { TelemetrySettings } from './types/telemetry.types';
 
 /**
  * 设置管理命令
  */
 export class SettingsCommands {
 
-  /**
-   * 保存系统托盘状态
-   * @param enabled 是否启用
-   * @returns 保存后的状态
-   */
-  static async saveSystemTrayState(enabled: boolean): Promise<boolean> {
-    return invoke('save_system_tray_state', { enabled });
-  }
-
   /**
    * 保存静默启动状态
    * @param enabled 是否启用
@@ -42,6 +34,230 @@ export class SettingsCommands {
     return invoke('save_debug_mode_state', { enabled });
   }
 
+  /**
+   * 保存账户变化自动备份状态
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveAutoBackupOnAccountChangeState(enabled: boolean): Promise<boolean> {
+    return invoke('save_auto_backup_on_account_change_state', { enabled });
+  }
+
+  /**
+   * 保存隐藏 Dock 图标状态（仅 macOS，托盘启用时生效）
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveHideDockIconState(enabled: boolean): Promise<boolean> {
+    return invoke('save_hide_dock_icon_state', { enabled });
+  }
+
+  /**
+   * 保存危险操作二次确认状态（开启后清除登录数据等操作需要先申请确认令牌）
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveConfirmDangerousOperationsState(enabled: boolean): Promise<boolean> {
+    return invoke('save_confirm_dangerous_operations_state', { enabled });
+  }
+
+  /**
+   * 保存写入前数据库文件快照状态（开启后恢复、清除数据前会整体复制 state.vscdb）
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveDbSnapshotBeforeWriteState(enabled: boolean): Promise<boolean> {
+    return invoke('save_db_snapshot_before_write_state', { enabled });
+  }
+
+  /**
+   * 保存清除数据前自动备份状态（开启后注销、切换账户前会先备份当前登录的账户）
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveBackupBeforeClearState(enabled: boolean): Promise<boolean> {
+    return invoke('save_backup_before_clear_state', { enabled });
+  }
+
+  /**
+   * 保存邮箱打码风格（作用于日志、托盘菜单与导出进度）
+   * @param style 打码风格
+   * @returns 保存后的风格
+   */
+  static async saveEmailMaskStyle(style: EmailMaskStyle): Promise<EmailMaskStyle> {
+    return invoke('save_email_mask_style', { style });
+  }
+
+  /**
+   * 保存备份文件命名模板（只影响新账户，已有文件需调用 AccountManageCommands.migrateBackupFileNames）
+   * @param template 模板，支持 {email} {alias} {date} {hash} {id}
+   * @returns 保存后的模板
+   */
+  static async saveBackupFileNameTemplate(template: string): Promise<string> {
+    return invoke('save_backup_file_name_template', { template });
+  }
+
+  /**
+   * 保存备份过期提醒天数
+   * @param days 天数，0 表示不提醒
+   * @returns 保存后的天数
+   */
+  static async saveBackupStaleDays(days: number): Promise<number> {
+    return invoke('save_backup_stale_days', { days });
+  }
+
+  /**
+   * 保存时间显示设置（立即作用于之后返回的日志信息、备份列表与审计记录）
+   * @param timeDisplay 显示语言与时区
+   * @returns 保存后的设置
+   */
+  static async saveTimeDisplaySettings(timeDisplay: TimeDisplaySettings): Promise<TimeDisplaySettings> {
+    return invoke('save_time_display_settings', { timeDisplay });
+  }
+
+  /**
+   * 保存启动策略（启动 Antigravity 时发现已有实例在运行的处理方式）
+   * @param policy 启动策略
+   * @returns 保存后的策略
+   */
+  static async saveLaunchPolicy(policy: LaunchPolicy): Promise<LaunchPolicy> {
+    return invoke('save_launch_policy', { policy });
+  }
+
+  /**
+   * 保存重启后是否重新打开之前的工作区
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveReopenWorkspaceOnRestart(enabled: boolean): Promise<boolean> {
+    return invoke('save_reopen_workspace_on_restart', { enabled });
+  }
+
+  /**
+   * 保存等待超时设置（关闭 Antigravity 后等待进程退出、数据库解锁的最长时间）
+   * @param waitSettings 超时设置
+   * @returns 保存后的设置
+   */
+  static async saveWaitSettings(waitSettings: WaitSettings): Promise<WaitSettings> {
+    return invoke('save_wait_settings', { waitSettings });
+  }
+
+  /**
+   * 保存切换账户后是否验证登录状态
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveVerifyLoginAfterSwitch(enabled: boolean): Promise<boolean> {
+    return invoke('save_verify_login_after_switch', { enabled });
+  }
+
+  /**
+   * 保存界面语言（托盘菜单与提示立即按新语言重建）
+   * @param language 界面语言
+   * @returns 保存后的语言
+   */
+  static async saveLanguage(language: Language): Promise<Language> {
+    return invoke('save_language', { language });
+  }
+
+  /**
+   * 保存不再询问的原生确认对话框（传入空数组恢复全部确认）
+   * @param prompts 不再询问的确认
+   * @returns 保存后的列表
+   */
+  static async saveSkippedConfirmPrompts(prompts: ConfirmPrompt[]): Promise<ConfirmPrompt[]> {
+    return invoke('save_skipped_confirm_prompts', { prompts });
+  }
+
+  /**
+   * 保存 WSL 模式（运行在 WSL 中时操作 Windows 上的 Antigravity），保存后重新检测安装位置
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveWslWindowsMode(enabled: boolean): Promise<boolean> {
+    return invoke('save_wsl_windows_mode', { enabled });
+  }
+
+  /**
+   * 保存额外备份的键与排除的键（设备标识等危险键不能加入，账户状态键不能排除）
+   * @param extraKeys 额外备份的键
+   * @param excludedKeys 不处理的键
+   * @returns 合并后实际处理的键
+   */
+  static async saveBackupKeyOverrides(extraKeys: string[], excludedKeys: string[]): Promise<EffectiveKeys> {
+    return invoke('save_backup_key_overrides', { extraKeys, excludedKeys });
+  }
+
+  /**
+   * 保存定时任务设置（用户使用电脑时推迟备份等耗时任务，低电量时按策略推迟或跳过）
+   * @param schedulerSettings 定时任务设置
+   * @returns 保存后的设置
+   */
+  static async saveSchedulerSettings(schedulerSettings: SchedulerSettings): Promise<SchedulerSettings> {
+    return invoke('save_scheduler_settings', { schedulerSettings });
+  }
+
+  /**
+   * 保存使用统计设置（关闭时清除已收集的数据）
+   * @param telemetrySettings 使用统计设置
+   * @returns 保存后的设置
+   */
+  static async saveTelemetrySettings(telemetrySettings: TelemetrySettings): Promise<TelemetrySettings> {
+    return invoke('save_telemetry_settings', { telemetrySettings });
+  }
+
+  /**
+   * 保存最小磁盘剩余空间（低于该值时备份、快照与恢复返回 INSUFFICIENT_DISK_SPACE）
+   * @param mb 最小剩余空间（MB，0 表示不检查）
+   * @returns 保存后的值
+   */
+  static async saveMinFreeDiskMb(mb: number): Promise<number> {
+    return invoke('save_min_free_disk_mb', { mb });
+  }
+
+  /**
+   * 获取当前主题（偏好与实际生效的深浅色）
+   * @returns 当前主题
+   */
+  static async getThemeState(): Promise<ThemeState> {
+    return invoke('get_theme_state');
+  }
+
+  /**
+   * 保存界面主题，立即应用到窗口与托盘图标
+   * @param theme 主题偏好
+   * @returns 应用后的主题
+   */
+  static async saveThemePreference(theme: ThemePreference): Promise<ThemeState> {
+    return invoke('save_theme_preference', { theme });
+  }
+
+  /**
+   * 获取界面偏好（强调色、界面密度与字号缩放）
+   * @returns 界面偏好
+   */
+  static async getUiPreferences(): Promise<UiPreferences> {
+    return invoke('get_ui_preferences');
+  }
+
+  /**
+   * 保存界面偏好，后端通过 appearance:ui-preferences-changed 事件同步到所有窗口
+   * @param uiPreferences 界面偏好
+   * @returns 保存后的界面偏好（强调色统一为大写）
+   */
+  static async setUiPreferences(uiPreferences: UiPreferences): Promise<UiPreferences> {
+    return invoke('set_ui_preferences', { uiPreferences });
+  }
+
+  /**
+   * 保存只读模式状态（开启后修改账户、备份与设置的命令返回 READ_ONLY_MODE 错误）
+   * @param enabled 是否启用
+   * @returns 保存后的状态
+   */
+  static async saveReadOnlyModeState(enabled: boolean): Promise<boolean> {
+    return invoke('save_read_only_mode_state', { enabled });
+  }
+
   /**
    * 获取所有应用设置
    * @returns 应用设置对象
@@ -49,4 +265,13 @@ export class SettingsCommands {
   static async getAll(): Promise<AppSettings> {
     return invoke('get_all_settings');
   }
+
+  /**
+   * 执行首次运行引导：检测环境并写入默认设置
+   * 已完成引导时只返回检测结果，不会覆盖已有设置
+   * @returns 首次运行报告
+   */
+  static async runFirstTimeSetup(): Promise<SetupReport> {
+    return invoke('run_first_time_setup');
+  }
 }
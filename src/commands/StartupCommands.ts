@@ -0,0 +1,15 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { StartupMetrics } from './types/startup.types';
+
+/**
+ * 启动性能统计命令
+ */
+export class StartupCommands {
+  /**
+   * 获取启动各阶段耗时
+   * @returns 启动性能统计
+   */
+  static async getStartupMetrics(): Promise<StartupMetrics> {
+    return invoke('get_startup_metrics');
+  }
+}
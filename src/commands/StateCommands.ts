@@ -0,0 +1,31 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { AgentStateSnapshot, DashboardSummary, StateSubscription } from './types/state.types';
+
+/**
+ * 状态订阅与首页概览命令
+ */
+export class StateCommands {
+  /**
+   * 获取完整状态快照
+   * @returns 当前快照
+   */
+  static async getFullState(): Promise<AgentStateSnapshot> {
+    return invoke('get_full_state');
+  }
+
+  /**
+   * 订阅状态变化，之后的变化通过返回的事件名推送完整快照
+   * @returns 事件名与当前快照
+   */
+  static async subscribeState(): Promise<StateSubscription> {
+    return invoke('subscribe_state');
+  }
+
+  /**
+   * 获取首页概览（当前账户、进程状态、备份情况与待处理提醒）
+   * @returns 首页概览
+   */
+  static async getDashboardSummary(): Promise<DashboardSummary> {
+    return invoke('get_dashboard_summary');
+  }
+}
@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { RetentionPlan, RetentionPolicy, StorageStats } from './types/storage.types';
+
+/**
+ * 存储占用命令
+ */
+export class StorageCommands {
+  /**
+   * 统计账户备份与快照的存储占用
+   * @returns 存储占用统计
+   */
+  static async getBackupStorageStats(): Promise<StorageStats> {
+    return invoke('get_backup_storage_stats');
+  }
+
+  /**
+   * 模拟按保留策略清理快照（不删除任何文件）
+   * @param policy 要模拟的保留策略
+   * @returns 将被删除的快照与可回收的空间
+   */
+  static async simulateRetention(policy: RetentionPolicy): Promise<RetentionPlan> {
+    return invoke('simulate_retention', { policy });
+  }
+
+  /**
+   * 保存快照保留策略并立即按新策略清理
+   * @param policy 保留策略
+   * @returns 实际删除的快照与回收的空间
+   */
+  static async saveRetentionPolicy(policy: RetentionPolicy): Promise<RetentionPlan> {
+    return invoke('save_retention_policy', { policy });
+  }
+}
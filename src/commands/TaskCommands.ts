@@ -0,0 +1,42 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { InterruptedTask, InterruptedTaskAction, TaskInfo } from './types/task.types';
+
+/**
+ * 长耗时任务命令
+ * 进度通过 task:progress 事件推送，结束时推送 task:finished
+ */
+export class TaskCommands {
+  /**
+   * 获取正在运行的任务
+   * @returns 任务列表
+   */
+  static async listTasks(): Promise<TaskInfo[]> {
+    return invoke('list_tasks');
+  }
+
+  /**
+   * 取消任务（任务会在下一个安全检查点停止）
+   * @param id 任务 ID
+   */
+  static async cancelTask(id: string): Promise<void> {
+    return invoke('cancel_task', { id });
+  }
+
+  /**
+   * 获取上次运行时中断、尚未处理的任务
+   * @returns 中断任务列表
+   */
+  static async listInterruptedTasks(): Promise<InterruptedTask[]> {
+    return invoke('list_interrupted_tasks');
+  }
+
+  /**
+   * 处理中断的任务，成功后不再提示
+   * @param id 任务 ID
+   * @param action 处理方式
+   * @returns 处理结果消息
+   */
+  static async resolveInterruptedTask(id: string, action: InterruptedTaskAction): Promise<string> {
+    return invoke('resolve_interrupted_task', { id, action });
+  }
+}
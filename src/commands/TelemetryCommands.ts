@@ -0,0 +1,37 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { TelemetryReport, TelemetrySummary } from './types/telemetry.types';
+
+/**
+ * 使用统计命令
+ */
+export class TelemetryCommands {
+  /**
+   * 获取本地汇总的使用统计（按功能与按天）
+   */
+  static async getSummary(): Promise<TelemetrySummary> {
+    return invoke('get_telemetry_summary');
+  }
+
+  /**
+   * 预览将要发送的使用统计（仅共享模式）
+   * @returns 报告 ID 与将要发送的完整内容
+   */
+  static async previewReport(): Promise<TelemetryReport> {
+    return invoke('preview_telemetry_report');
+  }
+
+  /**
+   * 发送预览过的使用统计（内容与预览完全一致）
+   * @param id 预览返回的报告 ID
+   */
+  static async sendReport(id: string): Promise<void> {
+    return invoke('send_telemetry_report', { id });
+  }
+
+  /**
+   * 清除本地收集的使用统计
+   */
+  static async clearData(): Promise<void> {
+    return invoke('clear_telemetry_data');
+  }
+}
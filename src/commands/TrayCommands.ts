@@ -20,7 +20,15 @@ export class TrayCommands {
     return invoke('restore_from_tray');
   }
 
-  
+  /**
+   * 启用或禁用系统托盘，变化会通过 tray:state-changed 事件推送
+   * @param enabled 是否启用
+   * @returns 变更后的启用状态
+   */
+  static async setEnabled(enabled: boolean): Promise<boolean> {
+    return invoke('set_system_tray_enabled', { enabled });
+  }
+
   /**
    * 更新托盘菜单
    * @param accounts 账户邮箱列表
@@ -0,0 +1,32 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { AgentUpdateInfo } from './types/updater.types';
+
+/**
+ * Agent 自更新命令
+ */
+export class UpdaterCommands {
+  /**
+   * 检查是否有可用更新
+   * @returns 更新信息，已是最新版本时为 null
+   */
+  static async checkForUpdates(): Promise<AgentUpdateInfo | null> {
+    return invoke('check_for_updates');
+  }
+
+  /**
+   * 下载、校验签名并安装更新，完成后应用会自动重启
+   * 下载进度通过 update:download-progress 事件推送
+   */
+  static async installUpdate(): Promise<void> {
+    return invoke('install_update');
+  }
+
+  /**
+   * 保存更新设置
+   * @param autoCheck 是否在后台定期检查更新
+   * @param endpoint 自定义更新源地址（不传则使用 GitHub Releases）
+   */
+  static async saveSettings(autoCheck: boolean, endpoint?: string | null): Promise<void> {
+    return invoke('save_update_settings', { autoCheck, endpoint });
+  }
+}
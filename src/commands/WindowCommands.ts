@@ -0,0 +1,27 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { WindowLabel, WindowPreset, WindowState } from './types/window.types';
+
+/**
+ * 窗口状态命令
+ */
+export class WindowCommands {
+  /**
+   * 按布局预设调整窗口（当前显示器的左半屏、右半屏或居中），并立即保存
+   * @param preset 布局预设
+   * @param label 窗口 label，默认主窗口
+   * @returns 调整后的窗口状态
+   */
+  static async applyPreset(preset: WindowPreset, label?: WindowLabel): Promise<WindowState> {
+    return invoke('apply_window_preset', { preset, label });
+  }
+
+  /**
+   * 清除保存的窗口状态，窗口恢复默认大小并居中
+   * 用于保存的位置跑到屏幕外等情况
+   * @param label 窗口 label，默认主窗口
+   * @returns 重置后的窗口状态
+   */
+  static async resetState(label?: WindowLabel): Promise<WindowState> {
+    return invoke('reset_window_state', { label });
+  }
+}
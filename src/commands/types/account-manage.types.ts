@@ -47,4 +47,50 @@ export interface RestoreResult {
 
   /** 失败的备份列表 */
   failed: FailedBackup[];
+
+  /** 从旧版本或其他工具的格式转换后导入的备份 */
+  converted: ConvertedBackup[];
+}
+
+/**
+ * 转换格式后导入的备份
+ */
+export interface ConvertedBackup {
+  /** 文件名 */
+  filename: string;
+
+  /** 识别出的原格式 */
+  format: string;
+
+  /** 未能对应到当前结构、没有写入的字段 */
+  unmappedFields: string[];
+}
+
+/**
+ * 单个备份文件的重命名结果
+ */
+export interface BackupRenameResult {
+  /** 备份中的账户邮箱（无法识别时为 null） */
+  email: string | null;
+
+  /** 原文件名 */
+  from: string;
+
+  /** 新文件名 */
+  to: string | null;
+
+  status: 'renamed' | 'unchanged' | 'failed';
+
+  error?: string;
+}
+
+/**
+ * 脱敏备份导出结果
+ */
+export interface SanitizedExport {
+  /** 导出的副本路径 */
+  path: string;
+
+  /** 被替换的字段（账户状态内的字段以 agentState. 开头） */
+  redactedFields: string[];
 }
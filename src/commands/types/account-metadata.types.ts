@@ -0,0 +1,48 @@
+/**
+ * 账户元数据相关类型定义
+ */
+import type { ProxySettings } from './proxy.types';
+
+/**
+ * 账户启动配置，切换到该账户后启动 Antigravity 时替代全局默认值
+ */
+export interface LaunchProfile {
+  /** 附加的命令行参数 */
+  args: string[];
+
+  /** 启动时打开的工作区（文件夹或 .code-workspace 文件） */
+  workspace?: string | null;
+
+  /** 代理设置，为 null 时使用全局代理设置 */
+  proxy?: ProxySettings | null;
+}
+
+/**
+ * 最近使用列表中的账户
+ */
+export interface RecentAccount {
+  email: string;
+  alias: string | null;
+
+  /** 是否置顶 */
+  pinned: boolean;
+
+  /** 最近一次切换、恢复或备份的时间，null 表示没有记录 */
+  lastUsedAt: string | null;
+}
+
+/**
+ * 账户头像（缓存在配置目录，离线时仍可显示）
+ */
+export interface AccountAvatar {
+  email: string;
+
+  /** 没有头像时显示的首字母（别名优先，其次邮箱） */
+  initial: string;
+
+  /** 缓存的图片（data: URL），没有缓存时为 null */
+  dataUrl: string | null;
+
+  /** 缓存时间，null 表示没有缓存 */
+  cachedAt: string | null;
+}
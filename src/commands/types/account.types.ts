@@ -1,3 +1,5 @@
+import type { AgentError } from './error.types';
+import type { FormattedTime } from './settings.types';
 
 // Antigravity 当前用户信息类型
 export interface AntigravityAccount {
@@ -57,3 +59,275 @@ interface Plan {
   upgrade_msg: string
   upgrade_url: string
 }
+
+/**
+ * 后端检测到 Antigravity 内登录账户变化时推送的事件（account:changed）
+ */
+export interface AccountChangedEvent {
+  /** 变化前的账户邮箱，null 表示之前未登录 */
+  previousEmail: string | null;
+
+  /** 当前账户邮箱，null 表示已登出 */
+  email: string | null;
+
+  /** 是否已自动备份新账户 */
+  backedUp: boolean;
+}
+
+/**
+ * 当前登录账户与备份的一致性
+ * - not_logged_in：Antigravity 未登录
+ * - consistent：当前账户已有未过期的备份
+ * - never_backed_up：当前账户从未备份
+ * - stale：当前账户的备份超过设置的提醒天数
+ */
+export type BackupConsistency = 'not_logged_in' | 'consistent' | 'never_backed_up' | 'stale';
+
+/**
+ * 启动时备份一致性检查结果（account:backup-consistency）
+ */
+export interface BackupConsistencyReport {
+  status: BackupConsistency;
+
+  /** 当前登录的账户 */
+  email: string | null;
+
+  /** 当前账户最近一次成功备份的时间 */
+  lastBackupAt: string | null;
+
+  /** 设置的提醒天数（0 表示不检查是否过期） */
+  staleDays: number;
+}
+
+/**
+ * 当前账户超过设置的天数未备份时推送的事件（account:backup-stale）
+ */
+export interface BackupStaleEvent {
+  email: string;
+
+  /** 最近一次成功备份的时间，null 表示从未备份 */
+  lastBackupAt: string | null;
+
+  /** 设置的提醒天数 */
+  staleDays: number;
+}
+
+/**
+ * 切换账户后的登录验证结果（account:login-probe）
+ * - verified：Antigravity 已接受恢复的登录状态
+ * - logged_out：登录状态被清除（凭据失效）
+ * - mismatch：当前登录的是其他账户
+ * - timeout：超时仍无法确定（例如 Antigravity 未启动）
+ */
+export interface LoginProbeEvent {
+  /** 切换到的账户 */
+  email: string;
+
+  status: 'verified' | 'logged_out' | 'mismatch' | 'timeout';
+
+  /** 当前登录的账户，null 表示未登录 */
+  currentEmail: string | null;
+
+  elapsedMs: number;
+}
+
+/**
+ * 批量备份中单个账户的结果
+ */
+export interface AccountBackupResult {
+  email: string;
+
+  /** refreshed：已重新备份；unchanged：内容未变化；not_logged_in：当前未登录，保留原备份 */
+  status: 'refreshed' | 'unchanged' | 'not_logged_in' | 'failed';
+
+  file: string;
+  error: AgentError | null;
+}
+
+/**
+ * 恢复队列中的一项：把哪个备份恢复到哪个数据目录
+ */
+export interface RestoreQueueItem {
+  /** 账户名（邮箱） */
+  accountName: string;
+
+  /** 目标数据目录（存放 state.vscdb 的目录或 Antigravity 根数据目录），为空时使用当前使用的安装 */
+  dataDir?: string | null;
+
+  /** 目标数据目录登录了其他账户时先清除其会话（否则该项返回 SESSION_MISMATCH 错误） */
+  replaceSession?: boolean;
+}
+
+/**
+ * 恢复队列中单项的结果
+ */
+export interface RestoreQueueItemResult {
+  accountName: string;
+
+  /** 实际写入的状态数据库（无法确定目标时为 null） */
+  dbPath: string | null;
+
+  /** failed：出错，或严格模式下有键写入失败并已回滚；skipped：任务取消后未执行 */
+  status: 'restored' | 'failed' | 'skipped';
+
+  /** 恢复前是否清除了目标数据目录中其他账户的会话 */
+  replacedSession: boolean;
+
+  report: RestoreReport | null;
+  error: AgentError | null;
+}
+
+/**
+ * 备份中访问令牌的状态
+ * - expiring_soon：24 小时内过期
+ * - expired：已过期，恢复后需要在 Antigravity 中重新登录
+ * - unknown：备份中没有可解析的过期时间
+ */
+export type TokenStatus = 'valid' | 'expiring_soon' | 'expired' | 'unknown';
+
+/**
+ * 备份触发来源
+ * - manual：手动备份
+ * - account_change：监听数据库时检测到登录账户变化
+ * - version_change：检测到 Antigravity 版本更新
+ * - before_clear：清除登录数据前
+ * - scheduled：定时任务
+ * - before_switch：切换账户或登录新账户前
+ */
+export type BackupTrigger = 'manual' | 'account_change' | 'version_change' | 'before_clear' | 'scheduled' | 'before_switch';
+
+/**
+ * 备份搜索条件（各条件同时满足）
+ */
+export interface BackupSearchQuery {
+  /** 匹配邮箱、别名、备注或文件名（不区分大小写） */
+  text?: string | null;
+
+  /** 只返回这些来源的备份，为空时不限 */
+  triggers?: BackupTrigger[];
+
+  /** true 只返回自动备份，false 只返回手动备份（没有来源记录的旧备份按手动备份处理） */
+  automatic?: boolean | null;
+}
+
+/**
+ * 键在备份与恢复中的作用
+ */
+export type BackupKeyRole = 'agent_state' | 'meta' | 'extra' | 'removed_on_restore' | 'onboarding' | 'denied';
+
+/**
+ * 备份文件结构中单个键的说明
+ */
+export interface BackupSchemaKey {
+  /** 键名（以 `*` 结尾时按前缀匹配） */
+  key: string;
+  role: BackupKeyRole;
+  /** 备份文件中是否必需 */
+  required: boolean;
+  description: string;
+}
+
+/**
+ * 备份元数据字段的说明
+ */
+export interface BackupSchemaField {
+  name: string;
+  /** JSON 类型，可为空时为 ['string', 'null'] */
+  type: string | string[];
+  required: boolean;
+  description: string;
+}
+
+/**
+ * 备份文件结构
+ */
+export interface BackupSchema {
+  /** 备份文件结构版本 */
+  formatVersion: number;
+  /** 备份与恢复涉及的键（额外备份的键取决于当前设置） */
+  keys: BackupSchemaKey[];
+  /** 备份元数据（antigravityAgent.meta）的字段 */
+  metaFields: BackupSchemaField[];
+  /** 备份元数据中 trigger 的取值 */
+  triggers: BackupTrigger[];
+  /** 导入时能识别的格式（current 为当前结构，其余在导入时转换） */
+  importFormats: string[];
+  /** 当前结构的 JSON Schema（draft 2020-12） */
+  jsonSchema: Record<string, unknown>;
+}
+
+/**
+ * 账户备份摘要
+ */
+export interface BackupSummary {
+  email: string;
+  file: string;
+  modifiedAt: string | null;
+
+  /** 最近一次成功备份的时间（内容未变化的备份也会更新） */
+  lastBackupAt: string | null;
+
+  /** 按时间显示设置格式化的时间 */
+  displayTimes: {
+    modifiedAt: FormattedTime | null;
+    lastBackupAt: FormattedTime | null;
+  };
+
+  /** 备份元数据（旧版本备份没有） */
+  meta: {
+    created_at: string;
+    trigger: BackupTrigger;
+    antigravity_version: string | null;
+    platform: string;
+    /** 备份时填写的备注 */
+    note?: string | null;
+  } | null;
+
+  token: {
+    status: TokenStatus;
+    expiresAt: string | null;
+  };
+}
+
+/** 单个键的恢复记录 */
+export interface KeyRestoreResult {
+  key: string;
+  action: 'write' | 'delete';
+  status: 'ok' | 'skipped' | 'failed';
+  error?: string;
+}
+
+/** 单个数据库的恢复结果 */
+export interface DatabaseRestoreReport {
+  database: string;
+  keys: KeyRestoreResult[];
+  restored: number;
+  failed: number;
+  /** 严格模式下因失败回滚了事务 */
+  rolledBack: boolean;
+}
+
+/** 账户恢复报告 */
+export interface RestoreReport {
+  mode: 'lenient' | 'strict';
+  databases: DatabaseRestoreReport[];
+  /** 已处理的数据库中没有失败的键 */
+  success: boolean;
+  message: string;
+  /** 备份来自其他平台时的路径转换结果 */
+  platform?: {
+    sourcePlatform: string;
+    targetPlatform: string;
+    /** 已改写为当前用户目录的路径数量 */
+    rewritten: number;
+    /** 无法转换、保留原值的路径 */
+    untranslatable: string[];
+  };
+  /** 备份文件大小与实际读入内存的数据量（恢复只解析需要的字段） */
+  source: {
+    fileBytes: number;
+    retainedBytes: number;
+    /** 跳过的字段数量 */
+    skippedKeys: number;
+  };
+}
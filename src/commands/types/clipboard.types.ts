@@ -0,0 +1,16 @@
+/**
+ * 剪贴板相关类型定义
+ */
+
+/**
+ * 要复制的内容
+ */
+export type ClipboardContent =
+  /** 当前在 Antigravity 中登录的账户邮箱 */
+  | { kind: 'active_account_email' }
+  /** 指定账户的邮箱 */
+  | { kind: 'account_email'; email: string }
+  /** 指定账户的备份文件路径 */
+  | { kind: 'backup_path'; email: string }
+  /** 诊断摘要（已脱敏） */
+  | { kind: 'diagnostic_summary' };
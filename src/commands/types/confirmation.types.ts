@@ -0,0 +1,22 @@
+/**
+ * 危险操作确认相关类型定义
+ */
+
+/**
+ * 需要二次确认的操作
+ */
+export type DangerousOperation = 'logout_all';
+
+/**
+ * 确认令牌（一次性，过期后需重新申请）
+ */
+export interface ConfirmationToken {
+  /** 令牌，传给对应的危险命令 */
+  token: string;
+
+  /** 令牌对应的操作 */
+  operation: DangerousOperation;
+
+  /** 有效期（秒） */
+  expiresInSecs: number;
+}
@@ -0,0 +1,31 @@
+/**
+ * 崩溃报告相关类型定义
+ */
+
+/**
+ * 崩溃报告
+ */
+export interface CrashReport {
+  timestamp: string;
+
+  /** 崩溃来源 */
+  source: 'backend' | 'frontend';
+
+  message: string;
+
+  /** panic 位置（文件:行:列） */
+  location: string | null;
+
+  thread: string | null;
+  backtrace: string;
+
+  /** 最近的日志（已脱敏） */
+  logTail: string[];
+
+  platform: {
+    os: string;
+    arch: string;
+    agentVersion: string;
+    portable: boolean;
+  };
+}
@@ -0,0 +1,26 @@
+/**
+ * 数据库文件快照相关类型定义
+ */
+
+/**
+ * 数据库文件快照（state.vscdb 及 .backup、-wal 的整体复制）
+ */
+export interface DbSnapshot {
+  /** 快照 ID（目录名） */
+  id: string;
+
+  /** 创建时间（RFC 3339） */
+  createdAt: string;
+
+  /** 触发快照的操作，如 switch_account、clear_data */
+  reason: string;
+
+  /** 快照中包含的文件名 */
+  files: string[];
+
+  /** 快照总大小（字节） */
+  sizeBytes: number;
+
+  /** 来源数据库路径（旧快照没有记录，回滚到当前使用的安装） */
+  dbPath?: string;
+}
@@ -0,0 +1,57 @@
+/**
+ * 后端命令错误类型定义
+ */
+
+/**
+ * 后端统一错误码
+ */
+export type AgentErrorCode =
+  | 'ANTIGRAVITY_NOT_FOUND'
+  | 'DATA_DIR_UNREACHABLE'
+  | 'DATABASE_NOT_FOUND'
+  | 'DATABASE_ERROR'
+  | 'NOT_LOGGED_IN'
+  | 'BACKUP_NOT_FOUND'
+  | 'INVALID_BACKUP'
+  | 'SESSION_MISMATCH'
+  | 'PROCESS_NOT_FOUND'
+  | 'ANTIGRAVITY_RUNNING'
+  | 'PROCESS_KILL_FAILED'
+  | 'TRAY_ERROR'
+  | 'CLIPBOARD_ERROR'
+  | 'HOOK_FAILED'
+  | 'UPDATE_FAILED'
+  | 'NETWORK_ERROR'
+  | 'TASK_CANCELLED'
+  | 'OPERATION_IN_PROGRESS'
+  | 'READ_ONLY_MODE'
+  | 'CONFIRMATION_REQUIRED'
+  | 'INSUFFICIENT_DISK_SPACE'
+  | 'IO_ERROR'
+  | 'SERIALIZATION_ERROR'
+  | 'INVALID_INPUT'
+  | 'INTERNAL_ERROR';
+
+/**
+ * 后端命令返回的结构化错误
+ */
+export interface AgentError {
+  /** 稳定的错误码 */
+  code: AgentErrorCode;
+
+  /** 可直接展示给用户的错误信息 */
+  message: string;
+
+  /** 附加信息（如路径、备份名） */
+  details: Record<string, unknown> | null;
+}
+
+/**
+ * 判断是否为后端结构化错误
+ */
+export function isAgentError(error: unknown): error is AgentError {
+  return typeof error === 'object'
+    && error !== null
+    && typeof (error as AgentError).code === 'string'
+    && typeof (error as AgentError).message === 'string';
+}
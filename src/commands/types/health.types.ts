@@ -0,0 +1,56 @@
+/**
+ * 健康检查相关类型定义
+ */
+
+/**
+ * 检查结果状态
+ */
+export type HealthStatus = 'pass' | 'warn' | 'fail';
+
+/**
+ * 单项检查结果
+ */
+export interface HealthCheckItem {
+  /** 检查项标识 */
+  id: string;
+
+  /** 检查项名称 */
+  label: string;
+
+  status: HealthStatus;
+
+  /** 检查结果说明 */
+  message: string;
+
+  /** 修复建议 */
+  suggestion: string | null;
+}
+
+/**
+ * 健康检查报告
+ */
+export interface HealthReport {
+  /** 整体状态（取最差的检查结果） */
+  overall: HealthStatus;
+
+  checks: HealthCheckItem[];
+}
+
+/**
+ * 无法修复权限的路径
+ */
+export interface PermissionFixFailure {
+  path: string;
+
+  error: string;
+}
+
+/**
+ * 权限修复结果
+ */
+export interface PermissionFixReport {
+  /** 已收紧权限的路径 */
+  fixed: string[];
+
+  failed: PermissionFixFailure[];
+}
@@ -0,0 +1,67 @@
+/**
+ * 生命周期钩子与审计日志相关类型定义
+ */
+import type { FormattedTime } from './settings.types';
+
+/**
+ * 钩子事件
+ */
+export type HookEvent =
+  | 'pre-backup'
+  | 'post-backup'
+  | 'pre-restore'
+  | 'post-restore'
+  | 'pre-kill'
+  | 'post-switch';
+
+/**
+ * 钩子定义
+ */
+export interface HookDefinition {
+  /** 触发事件 */
+  event: HookEvent;
+
+  /** 可执行文件路径 */
+  command: string;
+
+  /** 命令参数 */
+  args?: string[];
+
+  /** 超时时间（秒，1-600） */
+  timeoutSecs?: number;
+
+  /** 是否启用 */
+  enabled?: boolean;
+}
+
+/**
+ * 钩子配置
+ */
+export interface HooksConfig {
+  hooks: HookDefinition[];
+}
+
+/**
+ * 审计日志条目
+ */
+export interface AuditEntry {
+  /** 记录时间（RFC 3339） */
+  timestamp: string;
+
+  /** 分类，如 hook */
+  category: string;
+
+  /** 具体操作 */
+  action: string;
+
+  success: boolean;
+
+  /** 附加信息 */
+  detail: unknown;
+
+  /** 所属操作的 ID（在任务中记录时） */
+  operationId?: string;
+
+  /** 按时间显示设置格式化的记录时间 */
+  time?: FormattedTime | null;
+}
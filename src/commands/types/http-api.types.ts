@@ -0,0 +1,20 @@
+/**
+ * 本地 HTTP 控制接口相关类型定义
+ */
+
+/**
+ * HTTP 控制接口状态
+ */
+export interface HttpApiStatus {
+  /** 设置中是否启用 */
+  enabled: boolean;
+
+  /** 服务是否正在运行 */
+  running: boolean;
+
+  /** 监听端口（仅监听 127.0.0.1） */
+  port: number;
+
+  /** Bearer 访问令牌 */
+  token: string | null;
+}
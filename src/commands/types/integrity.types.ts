@@ -0,0 +1,37 @@
+/**
+ * 数据库完整性相关类型定义
+ */
+
+/**
+ * 完整性检查结果
+ */
+export interface IntegrityReport {
+  /** 被检查的数据库路径 */
+  dbPath: string;
+
+  /** 是否通过 integrity_check */
+  ok: boolean;
+
+  /** 发现的问题（或无法打开数据库的原因） */
+  issues: string[];
+
+  /** 检查时间（RFC 3339） */
+  checkedAt: string;
+}
+
+/**
+ * 数据库修复结果
+ */
+export interface RepairResult {
+  /** 修复前的数据库快照 ID（可用于 restore_db_snapshot 回滚） */
+  snapshotId: string | null;
+
+  /** 导入新数据库的行数 */
+  recoveredRows: number;
+
+  /** 无法读取而丢弃的行数 */
+  lostRows: number;
+
+  /** 修复后的检查结果 */
+  report: IntegrityReport;
+}
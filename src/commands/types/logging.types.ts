@@ -1,25 +1,46 @@
 /**
  * 日志相关类型定义
  */
+import type { FormattedTime } from './settings.types';
+import type { AuditEntry } from './hooks.types';
+
+/**
+ * 日志文件类型：正在写入、往日滚动生成、其他备份或归档文件
+ */
+export type LogFileKind = 'current' | 'rotated' | 'backup';
 
 /**
  * 日志文件信息
  */
-export interface LogInfo {
-  /** 日志文件是否存在 */
-  exists: boolean;
+export interface LogFileInfo {
+  name: string;
 
-  /** 日志文件路径 */
+  /** 绝对路径 */
   path: string;
 
-  /** 文件大小（字节） */
-  size_bytes: number;
+  kind: LogFileKind;
 
-  /** 人类可读的文件大小 */
-  size_human: string;
+  /** 文件大小（字节） */
+  sizeBytes: number;
 
   /** 最后修改时间 */
-  last_modified: string;
+  modified: FormattedTime | null;
+}
+
+/**
+ * 日志目录信息
+ */
+export interface LogInfo {
+  /** 日志目录的绝对路径 */
+  directory: string;
+
+  /** 正在写入的日志文件的绝对路径 */
+  currentFile: string | null;
+
+  totalBytes: number;
+
+  /** 日志文件（最新修改的在前） */
+  files: LogFileInfo[];
 }
 
 /**
@@ -37,4 +58,54 @@ export interface FrontendLogEntry {
 
   /** 会话 ID */
   sessionId?: string;
+
+  /** 所属操作的 ID（与传给后端命令的 taskId 相同） */
+  operationId?: string;
+}
+
+/**
+ * 一次操作的全部记录
+ */
+export interface OperationTrace {
+  operationId: string;
+
+  /** 后端与前端日志中属于该操作的行（按时间顺序） */
+  logLines: string[];
+
+  /** 超出行数上限，只返回了最早的部分 */
+  truncated: boolean;
+
+  audit: AuditEntry[];
+}
+
+/**
+ * 批量写入前端日志的结果
+ */
+export interface FrontendLogBatchResult {
+  accepted: number;
+
+  /** 因限流被丢弃的条目数 */
+  dropped: number;
+
+  /** 会话已被限流时，建议暂停提交的时长（毫秒） */
+  retryAfterMs: number | null;
+}
+
+/**
+ * 单个前端会话的日志统计
+ */
+export interface FrontendLogSessionStats {
+  sessionId: string;
+
+  received: number;
+
+  written: number;
+
+  dropped: number;
+
+  /** 单秒内的最高提交数 */
+  peakPerSecond: number;
+
+  /** 首次提交时间（RFC 3339） */
+  firstSeen: string;
 }
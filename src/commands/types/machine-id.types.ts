@@ -0,0 +1,47 @@
+/**
+ * Antigravity 设备标识相关类型定义
+ */
+
+/**
+ * 设备标识
+ */
+export interface MachineIds {
+  /** storage.json: telemetry.machineId */
+  machineId: string | null;
+
+  /** storage.json: telemetry.macMachineId */
+  macMachineId: string | null;
+
+  /** storage.json: telemetry.devDeviceId */
+  devDeviceId: string | null;
+
+  /** storage.json: telemetry.sqmId */
+  sqmId: string | null;
+
+  /** state.vscdb: storage.serviceMachineId */
+  serviceMachineId: string | null;
+}
+
+/**
+ * 设备标识查看结果
+ */
+export interface MachineIdReport {
+  /** storage.json 路径 */
+  storagePath: string | null;
+
+  /** state.vscdb 路径 */
+  dbPath: string | null;
+
+  ids: MachineIds;
+}
+
+/**
+ * 设备标识重置结果
+ */
+export interface MachineIdResetResult {
+  previous: MachineIds;
+  current: MachineIds;
+
+  /** 重置前的快照目录 */
+  snapshotDir: string;
+}
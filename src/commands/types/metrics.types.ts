@@ -0,0 +1,36 @@
+/**
+ * 命令性能统计相关类型定义
+ */
+
+/**
+ * 耗时分布桶
+ */
+export interface LatencyBucket {
+  /** 桶上界（毫秒），溢出桶为 null */
+  leMs: number | null;
+
+  count: number;
+}
+
+/**
+ * 单个命令的统计
+ */
+export interface CommandMetrics {
+  command: string;
+  calls: number;
+  errors: number;
+  avgMs: number;
+  maxMs: number;
+  buckets: LatencyBucket[];
+}
+
+/**
+ * 统计快照
+ */
+export interface MetricsSnapshot {
+  /** 开始统计的时间，尚无记录时为 null */
+  since: string | null;
+
+  /** 按调用次数降序排列 */
+  commands: CommandMetrics[];
+}
@@ -0,0 +1,23 @@
+/**
+ * 操作锁相关类型定义
+ */
+
+/**
+ * 受保护的资源
+ */
+export type LockResource = 'state_db' | 'backup_dir';
+
+/**
+ * 正在进行的操作
+ */
+export interface ActiveOperation {
+  id: number;
+
+  /** 操作名，如 switch_account */
+  operation: string;
+
+  /** 占用的资源 */
+  resources: LockResource[];
+
+  startedAt: string;
+}
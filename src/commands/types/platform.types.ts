@@ -29,6 +29,52 @@ export interface PlatformInfo {
 
   /** 用户主目录路径 */
   home_dir?: string;
+
+  /** 是否处于便携模式（数据存放在可执行文件同级的 data 目录） */
+  portable_mode: boolean;
+
+  /** 当前系统是否支持系统托盘（Linux 需要 AppIndicator/StatusNotifier 支持） */
+  tray_supported: boolean;
+
+  /** 不支持系统托盘的原因 */
+  tray_unsupported_reason: string | null;
+
+  /** Agent 自身的数据目录（配置、日志、备份） */
+  agent_data_dir: string;
+
+  /** 检测到的 Antigravity 安装方式 */
+  install_flavor: InstallFlavor;
+
+  /** Agent 是否运行在 WSL 中 */
+  wsl: boolean;
+
+  /** 安装位置的检测时间（RFC 3339，检测结果会被缓存） */
+  detected_at: string;
+}
+
+/**
+ * Antigravity 的安装方式
+ */
+export type InstallFlavor = 'native' | 'snap' | 'flatpak' | 'appimage';
+
+/**
+ * 缓存的 Antigravity 安装检测结果
+ */
+export interface PlatformDetection {
+  /** 找到的状态数据库路径（主路径在前） */
+  dbPaths: string[];
+
+  /** 自动检测到的可执行文件（不含用户自定义路径） */
+  executable: string | null;
+
+  /** 安装方式（用户自定义的可执行文件优先） */
+  flavor: InstallFlavor;
+
+  /** Antigravity 是否可用（找到状态数据库或可执行文件） */
+  available: boolean;
+
+  /** 检测时间（RFC 3339） */
+  detectedAt: string;
 }
 
 /**
@@ -51,4 +97,93 @@ export interface DetectionResult {
 export interface PathConfig {
   /** 可执行文件路径 */
   executablePath?: string | null;
+
+  /** 设置中保存的自定义数据目录 */
+  dataDir?: string | null;
+
+  /** ANTIGRAVITY_AGENT_DATA_DIR 环境变量（设置时优先于自定义数据目录） */
+  dataDirEnv?: string | null;
+}
+
+/**
+ * Antigravity 版本更新快照（antigravity:version-changed 事件）
+ */
+export interface UpdateSnapshot {
+  /** 更新前版本 */
+  previousVersion: string;
+
+  /** 更新后版本 */
+  currentVersion: string;
+
+  /** 快照目录 */
+  snapshotDir: string;
+
+  /** 快照中包含的账户邮箱 */
+  email: string | null;
+}
+
+/**
+ * 可执行文件验证结果
+ */
+export interface ExecutableValidation {
+  /** 可以保存为自定义路径（签名问题不影响） */
+  valid: boolean;
+
+  /** 路径是否存在 */
+  exists: boolean;
+
+  /** 是否具有可执行权限（.app 包检查其中的主程序） */
+  executable: boolean;
+
+  /** .app 包结构是否完整（不是 .app 包时为 null） */
+  bundleValid: boolean | null;
+
+  /** macOS 代码签名是否有效（非 macOS 或无法检查时为 null） */
+  signatureValid: boolean | null;
+
+  /** 从安装目录读取的 Antigravity 版本 */
+  version: string | null;
+
+  /** 发现的问题 */
+  problems: string[];
+}
+
+/**
+ * Antigravity 安装状态变化（antigravity:availability-changed 事件）
+ */
+export interface AvailabilityChangedEvent {
+  /** 是否已安装 */
+  available: boolean;
+
+  /** 主数据库路径 */
+  dbPath: string | null;
+
+  /** 自动检测到的可执行文件 */
+  executable: string | null;
+}
+
+/**
+ * 检测到的一份 Antigravity 安装
+ */
+export interface AntigravityInstall {
+  /** 数据目录（选择安装时作为标识） */
+  dataDir: string;
+
+  /** 状态数据库路径 */
+  dbPath: string;
+
+  /** 与数据目录对应的可执行文件 */
+  executable: string | null;
+
+  /** Antigravity 版本 */
+  version: string | null;
+
+  /** 安装方式 */
+  flavor: InstallFlavor;
+
+  /** 是否为当前使用的安装 */
+  active: boolean;
+
+  /** 是否为用户选择的安装 */
+  selected: boolean;
 }
@@ -3,21 +3,35 @@
  */
 
 /**
- * 进程信息
+ * 匹配到的 Antigravity 进程（关闭 Antigravity 时会被关闭）
  */
-export interface ProcessInfo {
+export interface MatchedProcess {
   /** 进程 ID */
-  pid: string;
+  pid: number;
 
   /** 进程名称 */
   name: string;
 
-  /** 命令行参数 */
+  /** 可执行文件路径（无权限读取时为 null） */
+  exe: string | null;
+
+  /** 命令行 */
   command: string;
 
-  /** 匹配的模式索引 */
-  matched_pattern: number;
+  /** 可执行文件位于检测到的安装目录内；为 false 时无法确定安装目录或进程路径，仅按名称匹配 */
+  inInstallDir: boolean;
+}
+
+/**
+ * Agent 启动且仍在运行的 Antigravity 进程
+ */
+export interface LaunchedProcess {
+  /** 进程 ID */
+  pid: number;
+
+  /** 启动的程序 */
+  program: string;
 
-  /** 模式描述 */
-  pattern_description: string;
+  /** 启动时间（RFC 3339） */
+  launchedAt: string;
 }
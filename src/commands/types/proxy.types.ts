@@ -0,0 +1,36 @@
+/**
+ * 代理设置相关类型定义
+ */
+
+/**
+ * 代理类型
+ */
+export type ProxyKind = 'http' | 'socks5';
+
+/**
+ * 代理设置
+ */
+export interface ProxySettings {
+  enabled: boolean;
+  kind: ProxyKind;
+  host: string;
+  port: number;
+  username?: string | null;
+  password?: string | null;
+
+  /** 不走代理的主机，支持 example.com、*.example.com、.example.com */
+  bypass: string[];
+}
+
+/**
+ * 代理连接测试结果
+ */
+export interface ProxyTestResult {
+  success: boolean;
+
+  /** 测试目标（host:port） */
+  target: string;
+
+  latencyMs: number;
+  message: string;
+}
@@ -0,0 +1,51 @@
+/**
+ * 账户配额相关类型定义
+ */
+
+/**
+ * 单个模型的配额
+ */
+export interface QuotaSample {
+  model: string;
+
+  /** 剩余比例（0-1） */
+  remainingFraction: number;
+
+  /** 配额重置时间 */
+  resetTime: string | null;
+}
+
+/**
+ * 最近一次记录的模型配额
+ */
+export interface ModelQuota extends QuotaSample {
+  /** 重置时间已过（比较时按剩余 1.0 计算） */
+  resetPassed: boolean;
+
+  recordedAt: string;
+}
+
+/**
+ * 单个账户的配额
+ */
+export interface AccountQuota {
+  email: string;
+  models: ModelQuota[];
+
+  /** 用于比较的剩余比例（指定模型时为该模型，否则为所有模型的平均值；没有记录时为 null） */
+  score: number | null;
+
+  /** 最近一次记录的时间 */
+  lastRecordedAt: string | null;
+}
+
+/**
+ * 配额比较结果
+ */
+export interface QuotaComparison {
+  /** 按剩余配额从多到少排序，没有记录的账户排在最后 */
+  accounts: AccountQuota[];
+
+  /** 剩余配额最多的账户 */
+  suggested: string | null;
+}
@@ -0,0 +1,73 @@
+/**
+ * 定时任务相关类型定义
+ */
+
+/**
+ * 任务的运行计划
+ */
+export type Schedule =
+  /** 每隔固定分钟数运行 */
+  | { type: 'interval'; minutes: number }
+  /** 按 Cron 表达式运行（分 时 日 月 周，支持 @daily 等简写） */
+  | { type: 'cron'; expression: string };
+
+/**
+ * 定时任务执行的操作
+ * - auto_backup: 备份当前登录的账户
+ * - quota_poll: 刷新各账户的配额数据
+ */
+export type JobAction = 'auto_backup' | 'quota_poll';
+
+/**
+ * 新建或修改定时任务时提交的内容
+ */
+export interface JobInput {
+  /** 任务名称（唯一，同名时修改已有任务） */
+  name: string;
+  action: JobAction;
+  schedule: Schedule;
+  enabled?: boolean;
+}
+
+/**
+ * 定时任务及其下次运行时间
+ */
+export interface JobStatus {
+  name: string;
+  action: JobAction;
+  schedule: Schedule;
+  enabled: boolean;
+  createdAt: string;
+  /** 上次运行时间 */
+  lastRunAt: string | null;
+  /** 下次运行时间，任务已停用时为 null */
+  nextRunAt: string | null;
+}
+
+/**
+ * 一次运行的结果（skipped 表示条件不满足而跳过，如未登录、低电量）
+ */
+export type RunOutcome = 'succeeded' | 'failed' | 'skipped';
+
+/**
+ * 运行历史中的一项
+ */
+export interface RunRecord {
+  job: string;
+  action: JobAction;
+  startedAt: string;
+  finishedAt: string;
+  outcome: RunOutcome;
+  message: string | null;
+}
+
+/**
+ * Cron 表达式的校验结果
+ */
+export interface CronValidation {
+  valid: boolean;
+  /** 无效的原因 */
+  error: string | null;
+  /** 接下来几次运行时间 */
+  nextRuns: string[];
+}
@@ -1,6 +1,8 @@
 /**
  * 设置相关类型定义
  */
+import type { RetentionPolicy } from './storage.types';
+import type { TelemetrySettings } from './telemetry.types';
 
 /**
  * 应用设置
@@ -17,4 +19,285 @@ export interface AppSettings {
 
   /** 隐私模式：用户卡片信息打码（邮箱/用户名） */
   privateMode: boolean;
+
+  /** 是否已完成首次运行引导 */
+  setupCompleted: boolean;
+
+  /** 检测到 Antigravity 内登录账户变化时自动备份新账户 */
+  autoBackupOnAccountChange: boolean;
+
+  /** 是否在后台定期检查 Agent 更新 */
+  autoCheckUpdates: boolean;
+
+  /** 自定义更新源地址（为空时使用 GitHub Releases） */
+  updateEndpoint: string | null;
+
+  /** 只读模式：拒绝修改账户、备份与设置，用于演示或共享电脑 */
+  readOnlyMode: boolean;
+
+  /** 仅托盘运行时隐藏 Dock 图标（仅 macOS） */
+  hideDockIcon: boolean;
+
+  /** 清除登录数据等危险操作需要先申请确认令牌 */
+  confirmDangerousOperations: boolean;
+
+  /** 恢复、清除数据库前整体复制 state.vscdb 到 db-snapshots 目录 */
+  dbSnapshotBeforeWrite: boolean;
+
+  /** 清除登录数据前先备份当前登录的账户 */
+  backupBeforeClear: boolean;
+
+  /** 日志、托盘与导出中邮箱的打码风格 */
+  emailMaskStyle: EmailMaskStyle;
+
+  /** 新备份文件的命名模板（支持 {email} {alias} {date} {hash} {id}） */
+  backupFileNameTemplate: string;
+
+  /** 当前账户超过多少天未备份时提醒，0 表示不提醒 */
+  backupStaleDays: number;
+
+  /** 日志信息、备份列表与审计记录中时间的显示语言与时区 */
+  timeDisplay: TimeDisplaySettings;
+
+  /** 启动 Antigravity 时发现已有实例在运行的处理策略 */
+  launchPolicy: LaunchPolicy;
+
+  /** 重启 Antigravity（切换账户、登录新账户等）后重新打开之前的工作区 */
+  reopenWorkspaceOnRestart: boolean;
+
+  /** 关闭 Antigravity 后等待进程退出、数据库解锁的超时时间 */
+  waitSettings: WaitSettings;
+
+  /** 切换账户后验证 Antigravity 是否接受恢复的登录状态 */
+  verifyLoginAfterSwitch: boolean;
+
+  /** 托盘菜单等后端文案的界面语言 */
+  language: Language;
+
+  /** 选择过「不再询问」的原生确认对话框 */
+  skippedConfirmPrompts: ConfirmPrompt[];
+
+  /** 运行在 WSL 中时操作 Windows 上的 Antigravity */
+  wslWindowsMode: boolean;
+
+  /** 除账户状态外额外备份的数据库键 */
+  backupExtraKeys: string[];
+
+  /** 备份、恢复与清除时不处理的键 */
+  backupExcludedKeys: string[];
+
+  /** 定时任务的运行条件 */
+  schedulerSettings: SchedulerSettings;
+
+  /** 使用统计（默认关闭） */
+  telemetry: TelemetrySettings;
+
+  /** 备份、快照、恢复与写日志前要求的最小磁盘剩余空间（MB，0 表示不检查） */
+  minFreeDiskMb: number;
+
+  /** 界面主题（跟随系统时随系统深浅色切换） */
+  theme: ThemePreference;
+
+  /** 强调色、界面密度与字号缩放 */
+  uiPreferences: UiPreferences;
+
+  /** 快照保留策略 */
+  retentionPolicy: RetentionPolicy;
+}
+
+/**
+ * 界面密度
+ * - comfortable：默认间距
+ * - compact：紧凑模式，减小列表与卡片的间距
+ */
+export type Density = 'comfortable' | 'compact';
+
+/**
+ * 界面偏好（所有窗口共用，`appearance:ui-preferences-changed` 事件负载）
+ */
+export interface UiPreferences {
+  /** 强调色（#RRGGBB） */
+  accentColor: string;
+
+  density: Density;
+
+  /** 字号缩放比例（0.8-1.5） */
+  fontScale: number;
+}
+
+/**
+ * 界面主题偏好
+ * - light / dark：固定浅色或深色
+ * - system：跟随系统
+ */
+export type ThemePreference = 'light' | 'dark' | 'system';
+
+/**
+ * 实际生效的主题
+ */
+export type EffectiveTheme = 'light' | 'dark';
+
+/**
+ * 当前主题（`appearance:theme-changed` 事件负载）
+ */
+export interface ThemeState {
+  preference: ThemePreference;
+  effective: EffectiveTheme;
+}
+
+/**
+ * 合并用户设置后，备份、恢复与清除实际处理的键
+ */
+export interface EffectiveKeys {
+  /** 备份时写入备份文件、恢复时写回的键 */
+  backup: string[];
+
+  /** 恢复时删除的键 */
+  removeOnRestore: string[];
+
+  /** 清除登录数据时删除的键 */
+  removeOnClear: string[];
+
+  /** 清除后设为 true 以跳过首次启动引导的键（被排除时为 null） */
+  onboarding: string | null;
+}
+
+/**
+ * 需要原生确认的托盘操作
+ * - quit_while_busy: 任务进行中退出应用
+ * - clear_all_data: 清除 Antigravity 全部登录数据
+ */
+export type ConfirmPrompt = 'quit_while_busy' | 'clear_all_data';
+
+/**
+ * 界面语言
+ */
+export type Language = 'zh-CN' | 'en-US';
+
+/**
+ * 等待超时设置（毫秒，最大 120000）
+ */
+export interface WaitSettings {
+  /** 关闭 Antigravity 后等待进程退出的最长时间 */
+  processExitTimeoutMs: number;
+
+  /** 等待 Antigravity 数据库写锁释放的最长时间 */
+  dbUnlockTimeoutMs: number;
+}
+
+/**
+ * 定时任务设置
+ */
+export interface SchedulerSettings {
+  /** 系统空闲多少分钟后才运行备份等耗时任务（0 表示不等待，最大 1440） */
+  idleMinutes: number;
+
+  /** 使用电池且电量低于阈值时的处理方式 */
+  batteryPolicy: BatteryPolicy;
+
+  /** 电量阈值（百分比，100 表示使用电池时总是按策略处理） */
+  batteryThresholdPercent: number;
+}
+
+/**
+ * 使用电池且电量低于阈值时耗时任务的处理方式
+ * - ignore：照常运行
+ * - postpone：推迟到接通电源或电量回升后运行
+ * - skip：跳过本次运行（记入运行历史）
+ */
+export type BatteryPolicy = 'ignore' | 'postpone' | 'skip';
+
+/**
+ * 启动策略
+ * - focus：切换到已运行的实例，不启动新实例（仅 macOS 能激活窗口）
+ * - refuse：拒绝启动并提示
+ * - new_instance：仍然启动新实例
+ */
+export type LaunchPolicy = 'focus' | 'refuse' | 'new_instance';
+
+/**
+ * 时间显示设置
+ * - locale：zh-CN（2024-01-02 15:04:05）、en-US（01/02/2024 03:04:05 PM）、iso（RFC 3339）
+ * - timeZone：local 使用系统时区，utc 使用 UTC
+ */
+export interface TimeDisplaySettings {
+  locale: 'zh-CN' | 'en-US' | 'iso';
+  timeZone: 'local' | 'utc';
+}
+
+/**
+ * 后端格式化后的时间
+ */
+export interface FormattedTime {
+  /** Unix 毫秒时间戳 */
+  epochMs: number;
+
+  /** 按时间显示设置格式化的文本 */
+  display: string;
+}
+
+/**
+ * 邮箱打码风格
+ * - full: 完全隐藏本地部分与子域名（`***@***.com`）
+ * - partial: 保留首尾字符与域名（`u***r@gmail.com`）
+ * - none: 不打码，仅建议在可信的个人电脑上使用
+ */
+export type EmailMaskStyle = 'full' | 'partial' | 'none';
+
+/**
+ * 首次运行环境检测结果
+ */
+export interface SetupDetection {
+  /** Antigravity 用户数据目录 */
+  antigravityDataDir: string | null;
+
+  /** Antigravity 状态数据库路径 */
+  antigravityDbPath: string | null;
+
+  /** 状态数据库是否存在 */
+  antigravityDbExists: boolean;
+
+  /** Antigravity 可执行文件路径 */
+  antigravityExecutable: string | null;
+
+  /** 可执行文件路径是否来自用户自定义配置 */
+  executableIsCustom: boolean;
+
+  /** Antigravity 是否正在运行 */
+  antigravityRunning: boolean;
+
+  /** Agent 数据目录 */
+  agentDataDir: string;
+
+  /** 是否处于便携模式 */
+  portableMode: boolean;
+}
+
+/**
+ * 首次运行建议的默认配置
+ */
+export interface SetupDefaults {
+  systemTrayEnabled: boolean;
+  silentStartEnabled: boolean;
+  /** 开机自启（当前版本尚不支持，仅作为建议项） */
+  autostartEnabled: boolean;
+  /** 账户备份目录 */
+  backupDir: string;
+}
+
+/**
+ * 首次运行报告
+ */
+export interface SetupReport {
+  /** 本次是否为首次运行 */
+  firstRun: boolean;
+
+  /** 是否已写入默认设置 */
+  settingsApplied: boolean;
+
+  detection: SetupDetection;
+  defaults: SetupDefaults;
+
+  /** 需要用户关注的问题 */
+  warnings: string[];
 }
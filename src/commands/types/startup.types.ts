@@ -0,0 +1,28 @@
+/**
+ * 启动性能统计相关类型定义
+ */
+
+/**
+ * 单个启动阶段
+ */
+export interface StartupPhase {
+  name: string;
+
+  /** 相对进程启动的开始时间（毫秒） */
+  startMs: number;
+
+  durationMs: number;
+
+  /** 是否在后台执行（不阻塞窗口显示） */
+  background: boolean;
+}
+
+/**
+ * 启动性能统计
+ */
+export interface StartupMetrics {
+  phases: StartupPhase[];
+
+  /** 进程启动到页面首次加载完成的耗时（毫秒） */
+  timeToWindowMs: number | null;
+}
@@ -0,0 +1,87 @@
+/**
+ * 状态订阅相关类型定义
+ */
+import type { FormattedTime } from './settings.types';
+
+/**
+ * Agent 状态快照（`agent:state` 事件的内容），null 表示尚未检测
+ */
+export interface AgentStateSnapshot {
+  /** 每次变化递增，可据此丢弃过期快照 */
+  revision: number;
+
+  trayEnabled: boolean | null;
+
+  antigravityRunning: boolean | null;
+
+  /** Antigravity 是否已安装 */
+  antigravityAvailable: boolean | null;
+
+  /** 当前登录账户（未检测或未登录时为 null，见 accountChecked） */
+  activeAccount: string | null;
+
+  /** 是否已检测过当前登录账户 */
+  accountChecked: boolean;
+
+  /** 可用的 Agent 新版本 */
+  updateAvailable: string | null;
+
+  /** 进行中的操作名 */
+  activeOperations: string[];
+
+  /** Agent 启动且仍在运行的 Antigravity 进程 PID */
+  launchedPids: number[];
+}
+
+/**
+ * 订阅信息
+ */
+export interface StateSubscription {
+  /** 需要监听的事件名 */
+  event: string;
+
+  /** 当前快照 */
+  snapshot: AgentStateSnapshot;
+}
+
+/**
+ * 首页待处理提醒
+ * - antigravity_not_found：未找到 Antigravity 安装位置
+ * - read_only_mode：只读模式已开启
+ * - backup_missing：当前账户尚未备份
+ * - backup_stale：当前账户超过设置的天数未备份
+ */
+export interface DashboardWarning {
+  code: 'antigravity_not_found' | 'read_only_mode' | 'backup_missing' | 'backup_stale';
+  message: string;
+}
+
+/**
+ * 首页概览
+ */
+export interface DashboardSummary {
+  /** 当前登录的账户，null 表示未登录 */
+  activeAccount: string | null;
+
+  /** 当前账户的别名 */
+  activeAlias: string | null;
+
+  antigravityRunning: boolean;
+
+  /** Agent 启动且仍在运行的 Antigravity 进程 PID */
+  launchedPids: number[];
+
+  /** 当前账户最近一次成功备份的时间 */
+  lastBackupAt: FormattedTime | null;
+
+  /** 已备份的账户数量 */
+  backupCount: number;
+
+  /** 进行中的操作名 */
+  activeOperations: string[];
+
+  /** 可用的 Agent 新版本 */
+  updateAvailable: string | null;
+
+  warnings: DashboardWarning[];
+}
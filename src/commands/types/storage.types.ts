@@ -0,0 +1,116 @@
+/**
+ * 存储占用相关类型定义
+ */
+
+/**
+ * 单个账户备份的占用
+ */
+export interface AccountUsage {
+  email: string;
+
+  /** 备份文件路径 */
+  file: string;
+
+  sizeBytes: number;
+}
+
+/**
+ * 一类快照的数量与占用
+ */
+export interface SnapshotUsage {
+  count: number;
+  sizeBytes: number;
+}
+
+/**
+ * 存储占用统计
+ */
+export interface StorageStats {
+  accountCount: number;
+  accountBytes: number;
+
+  /** 占用最大的账户备份（从大到小，最多 5 个） */
+  largestAccounts: AccountUsage[];
+
+  /** 数据库文件快照（db-snapshots） */
+  dbSnapshots: SnapshotUsage;
+
+  /** Antigravity 更新前的快照 */
+  updateSnapshots: SnapshotUsage;
+
+  /** 设备标识快照 */
+  machineIdSnapshots: SnapshotUsage;
+
+  totalBytes: number;
+
+  /** 总占用超过阈值，建议清理旧快照或无用备份 */
+  pruneRecommended: boolean;
+}
+
+/**
+ * 磁盘剩余空间低于设置下限、操作被中止时推送的事件（disk:low-space）
+ */
+export interface LowDiskSpaceEvent {
+  /** 被检查的目录 */
+  path: string;
+
+  /** 被中止的操作，如 backup、restore、db_snapshot */
+  operation: string;
+
+  availableBytes: number;
+
+  requiredBytes: number;
+}
+
+/**
+ * 快照类型
+ * - db：数据库文件快照（db-snapshots）
+ * - update：Antigravity 更新前的快照
+ * - machine_id：设备标识快照
+ */
+export type SnapshotKind = 'db' | 'update' | 'machine_id';
+
+/**
+ * 快照保留策略：每类快照始终保留最新的 keepLast 份，其余超过 maxAgeDays 天的删除
+ */
+export interface RetentionPolicy {
+  /** 自动清理的快照类型（未列出的类型不清理） */
+  kinds: SnapshotKind[];
+
+  /** 每类快照始终保留最新的份数（至少 1 份） */
+  keepLast: number;
+
+  /** 超出保留份数的快照再保留的天数，null 表示超出份数即删除 */
+  maxAgeDays: number | null;
+}
+
+/**
+ * 单个快照
+ */
+export interface SnapshotEntry {
+  kind: SnapshotKind;
+
+  /** 快照目录名 */
+  id: string;
+
+  createdAt: string;
+
+  sizeBytes: number;
+}
+
+/**
+ * 按保留策略清理的结果（模拟时不删除任何文件）
+ */
+export interface RetentionPlan {
+  /** 将被删除（或已删除）的快照，最新的在前 */
+  deleted: SnapshotEntry[];
+
+  /** 保留的快照数量 */
+  keptCount: number;
+
+  /** 可回收（或已回收）的空间 */
+  reclaimedBytes: number;
+
+  /** 删除失败的快照 ID（仅实际清理时） */
+  failed: string[];
+}
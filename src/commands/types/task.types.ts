@@ -0,0 +1,66 @@
+/**
+ * 长耗时任务相关类型定义
+ */
+import type { AgentError } from './error.types';
+
+/**
+ * 任务状态
+ */
+export type TaskStatus = 'running' | 'completed' | 'failed' | 'cancelled';
+
+/**
+ * 任务信息（task:progress / task:finished 事件的负载）
+ */
+export interface TaskInfo {
+  id: string;
+
+  /** 任务类型，如 switch_account、export_accounts、import_accounts */
+  kind: string;
+
+  status: TaskStatus;
+  current: number;
+
+  /** 总步数（未知时为 null） */
+  total: number | null;
+
+  message: string | null;
+  startedAt: string;
+
+  /** 失败原因 */
+  error: AgentError | null;
+}
+
+/**
+ * 上次运行时中断、尚未处理的任务
+ */
+export interface InterruptedTask {
+  id: string;
+
+  /** 任务类型，如 switch_account、restore_account */
+  kind: string;
+
+  /** 操作对象（如账户备份名） */
+  target: string | null;
+
+  /** 影响操作结果的选项（如 replaceSession），继续执行时原样使用；没有时省略 */
+  options?: Record<string, unknown>;
+
+  startedAt: string;
+
+  /** 中断前最后一次上报的进度 */
+  message: string | null;
+
+  /** 能否重新执行 */
+  resumable: boolean;
+
+  /** 可回滚到的数据库快照 ID（没有时为 null） */
+  rollbackSnapshot: string | null;
+}
+
+/**
+ * 中断任务的处理方式
+ * - resume: 重新执行同一操作
+ * - rollback: 回滚到操作开始后创建的数据库快照
+ * - dismiss: 忽略
+ */
+export type InterruptedTaskAction = 'resume' | 'rollback' | 'dismiss';
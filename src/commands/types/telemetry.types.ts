@@ -0,0 +1,71 @@
+/**
+ * 使用统计相关类型定义
+ */
+
+/**
+ * 使用统计模式
+ * - off：不收集
+ * - local_only：只在本地汇总，供统计面板查看
+ * - share：本地汇总，预览并确认后可发送
+ */
+export type TelemetryMode = 'off' | 'local_only' | 'share';
+
+/**
+ * 使用统计设置
+ */
+export interface TelemetrySettings {
+  mode: TelemetryMode;
+
+  /** 共享模式下的发送地址（仅支持 HTTPS） */
+  endpoint: string | null;
+}
+
+/**
+ * 某个功能的汇总
+ */
+export interface FeatureUsage {
+  feature: string;
+  calls: number;
+  errors: number;
+
+  /** 失败率（0-1） */
+  errorRate: number;
+}
+
+/**
+ * 一天的汇总
+ */
+export interface DailyUsage {
+  /** 日期（YYYY-MM-DD） */
+  day: string;
+  calls: number;
+  errors: number;
+}
+
+/**
+ * 本地使用统计
+ */
+export interface TelemetrySummary {
+  mode: TelemetryMode;
+
+  /** 保留期（30 天）内按功能汇总，按调用次数降序排列 */
+  features: FeatureUsage[];
+
+  /** 按天汇总，最早的在前 */
+  days: DailyUsage[];
+
+  /** 已发送过的最后一天 */
+  lastSentDay: string | null;
+}
+
+/**
+ * 待发送的使用统计报告
+ */
+export interface TelemetryReport {
+  /** 确认发送时传回 */
+  id: string;
+  endpoint: string;
+
+  /** 将要发送的完整内容（原样发送） */
+  payload: string;
+}
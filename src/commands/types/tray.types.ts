@@ -0,0 +1,30 @@
+/**
+ * 系统托盘相关类型定义
+ */
+
+/**
+ * 托盘状态（tray:state-changed 事件负载）
+ */
+export interface TrayState {
+  /** 是否启用系统托盘 */
+  enabled: boolean;
+}
+
+/**
+ * 托盘请求切换账户（tray-switch-account 事件负载）
+ */
+export interface TraySwitchAccountPayload {
+  /** 不透明的账户 ID */
+  id: string;
+  email: string;
+  /** 账户别名，未设置时为 null */
+  alias: string | null;
+}
+
+/**
+ * 托盘操作结果（tray:backup-finished、tray:clear-data-finished、tray:copy-finished 事件负载）
+ */
+export interface TrayActionEvent {
+  success: boolean;
+  message: string;
+}
@@ -0,0 +1,31 @@
+/**
+ * Agent 自更新相关类型定义
+ */
+
+/**
+ * 可用更新信息
+ */
+export interface AgentUpdateInfo {
+  /** 新版本号 */
+  version: string;
+
+  /** 当前版本号 */
+  currentVersion: string;
+
+  /** 发布时间 */
+  date: string | null;
+
+  /** 更新说明 */
+  body: string | null;
+}
+
+/**
+ * 更新包下载进度（update:download-progress 事件）
+ */
+export interface AgentUpdateDownloadProgress {
+  /** 已下载字节数 */
+  downloaded: number;
+
+  /** 总字节数（服务器未返回时为 null） */
+  total: number | null;
+}
@@ -0,0 +1,28 @@
+/**
+ * 窗口状态相关类型定义
+ */
+
+/**
+ * 窗口 label（各窗口的状态分别保存）
+ */
+export type WindowLabel = 'main' | 'log-viewer' | 'dashboard';
+
+/**
+ * 窗口布局预设
+ */
+export type WindowPreset = 'left-half' | 'right-half' | 'centered-small' | 'centered-large';
+
+/**
+ * 窗口状态（物理像素）
+ */
+export interface WindowState {
+  x: number;
+
+  y: number;
+
+  width: number;
+
+  height: number;
+
+  maximized: boolean;
+}
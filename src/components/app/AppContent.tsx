@@ -10,6 +10,10 @@ import {useAppGlobalLoader} from "@/modules/use-app-global-loader.ts";
 import {AccountSessionList, AccountSessionListAccountItem} from "@/components/business/AccountSessionList.tsx";
 import AccountsListToolbar, {type ListToolbarValue} from "@/components/business/AccountsListToolbar.tsx";
 import {logger} from "@/lib/logger.ts";
+import {getErrorMessage} from "@/lib/utils.ts";
+import {PlatformCommands} from "@/commands/PlatformCommands.ts";
+import {useAppSettings} from "@/modules/use-app-settings.ts";
+import {listen} from "@tauri-apps/api/event";
 
 const tierRank: Record<UserTier, number> = {
   'g1-ultra-tier': 0,
@@ -33,13 +37,26 @@ export function AppContent() {
   // 初始化托盘菜单更新
   useTrayMenu();
 
+  // 已启用托盘但当前桌面环境不支持时提示用户
+  const systemTrayEnabled = useAppSettings(state => state.systemTrayEnabled);
+  useEffect(() => {
+    if (!systemTrayEnabled) {
+      return;
+    }
+    PlatformCommands.getInfo().then(info => {
+      if (!info.tray_supported) {
+        toast(`系统托盘不可用：${info.tray_unsupported_reason ?? '当前系统不支持'}`, {id: 'tray-unsupported'});
+      }
+    }).catch(() => {});
+  }, [systemTrayEnabled]);
+
   // 组件挂载时获取用户列表
   useEffect(() => {
     const loadUsers = async () => {
       try {
         await antigravityAccount.getAccounts();
       } catch (error) {
-        toast.error(`获取用户列表失败: ${error}`);
+        toast.error(`获取用户列表失败: ${getErrorMessage(error)}`);
       } finally {
       }
     };
@@ -75,8 +92,12 @@ export function AppContent() {
 
     task()
 
+    // 定时任务中的配额刷新任务到期时立即刷新
+    const unlistenQuotaPoll = listen('scheduler:quota-poll', () => task())
+
     return () => {
       clearInterval(fetchAccountAdditionDataTimer.current)
+      unlistenQuotaPoll.then(unlisten => unlisten())
     }
   }, [antigravityAccount.accounts]);
 
@@ -145,7 +166,7 @@ export function AppContent() {
       await antigravityAccount.clearAllAccounts();
       toast.success('清空所有备份成功');
     } catch (error) {
-      toast.error(`清空备份失败: ${error}`);
+      toast.error(`清空备份失败: ${getErrorMessage(error)}`);
       throw error;
     }
   };
@@ -0,0 +1,54 @@
+import React from "react";
+import {CrashCommands} from "@/commands/CrashCommands.ts";
+import {logger} from "@/lib/logger.ts";
+
+interface AppErrorBoundaryState {
+  error: Error | null;
+}
+
+/**
+ * 全局错误边界：渲染异常时写入崩溃报告，并显示可恢复的错误页面
+ */
+export class AppErrorBoundary extends React.Component<React.PropsWithChildren, AppErrorBoundaryState> {
+  state: AppErrorBoundaryState = {error: null};
+
+  static getDerivedStateFromError(error: Error): AppErrorBoundaryState {
+    return {error};
+  }
+
+  componentDidCatch(error: Error, info: React.ErrorInfo) {
+    const stack = [error.stack, info.componentStack].filter(Boolean).join('\n\nComponent stack:');
+    logger.error('前端渲染异常', {
+      module: 'AppErrorBoundary',
+      action: 'render_crash',
+      error: error.message
+    });
+    CrashCommands.reportFrontendCrash(error.message, stack).catch(() => {
+      // 写入崩溃报告失败时无需再处理，避免二次异常
+    });
+  }
+
+  render() {
+    if (!this.state.error) {
+      return this.props.children;
+    }
+
+    return (
+      <div className="flex h-screen flex-col items-center justify-center gap-4 p-6 text-center">
+        <h1 className="text-lg font-semibold">界面发生错误</h1>
+        <p className="max-w-md text-sm text-gray-500">
+          错误信息已写入崩溃报告（配置目录下的 crash-reports 文件夹），反馈问题时可一并附上。
+        </p>
+        <pre className="max-w-md overflow-auto rounded bg-gray-100 p-2 text-left text-xs text-gray-700">
+          {this.state.error.message}
+        </pre>
+        <button
+          className="rounded bg-blue-500 px-4 py-2 text-sm text-white hover:bg-blue-600"
+          onClick={() => window.location.reload()}
+        >
+          重新加载
+        </button>
+      </div>
+    );
+  }
+}
@@ -5,9 +5,10 @@ import {getVersion} from '@tauri-apps/api/app';
 import {BaseButton} from '@/components/base-ui/BaseButton';
 import {cn} from '@/lib/utils.ts';
 import {PlatformCommands} from "@/commands/PlatformCommands.ts";
-import {Modal} from "antd";
+import {Modal, Select} from "antd";
 import {useAppSettings} from "@/modules/use-app-settings.ts";
 import {LoggingCommands} from "@/commands/LoggingCommands.ts";
+import type {AntigravityInstall} from "@/commands/types/platform.types.ts";
 
 interface BusinessSettingsDialogProps {
   isOpen: boolean;
@@ -21,6 +22,10 @@ const BusinessSettingsDialog: React.FC<BusinessSettingsDialogProps> = ({
   const [execPath, setExecPath] = useState<string>('');
   const [logDirPath, setLogDirPath] = useState<string>('');
   const [appVersion, setAppVersion] = useState<string>('');
+  // 当前系统不支持托盘时隐藏托盘相关设置
+  const [traySupported, setTraySupported] = useState<boolean>(true);
+  // 检测到的 Antigravity 安装（多于一个时可选择）
+  const [installs, setInstalls] = useState<AntigravityInstall[]>([]);
 
   
   // 应用设置（统一管理）
@@ -41,6 +46,8 @@ const BusinessSettingsDialog: React.FC<BusinessSettingsDialogProps> = ({
       loadCurrentPaths();
       loadLogDirectoryPath();
       loadAppVersion();
+      loadTraySupport();
+      loadInstalls();
     }
   }, [isOpen]);
 
@@ -49,6 +56,28 @@ const BusinessSettingsDialog: React.FC<BusinessSettingsDialogProps> = ({
     setAppVersion(version);
   };
 
+  const loadTraySupport = async () => {
+    const info = await PlatformCommands.getInfo();
+    setTraySupported(info.tray_supported);
+  };
+
+  const loadInstalls = async () => {
+    try {
+      setInstalls(await PlatformCommands.listInstalls());
+    } catch (_error) {
+      setInstalls([]);
+    }
+  };
+
+  const handleSelectInstall = async (dataDir: string) => {
+    try {
+      await PlatformCommands.selectInstall(dataDir);
+      await Promise.all([loadInstalls(), loadCurrentPaths()]);
+    } catch (error) {
+      Modal.error({title: '切换安装失败', content: String(error)});
+    }
+  };
+
   const loadCurrentPaths = async () => {
     const paths = await PlatformCommands.getCurrentPaths();
     let finalExecPath = paths.executablePath;
@@ -85,11 +114,30 @@ const BusinessSettingsDialog: React.FC<BusinessSettingsDialogProps> = ({
       });
 
       if (result && typeof result === 'string') {
-        const valid = await PlatformCommands.validateExecutable(result);
-        if (valid) {
+        const validation = await PlatformCommands.validateCustomExecutable(result);
+        if (!validation.valid) {
+          Modal.error({
+            title: '无效的可执行文件',
+            content: validation.problems.join('；'),
+          });
+          return;
+        }
+
+        const save = async () => {
           await PlatformCommands.saveAntigravityExecutable(result);
           setExecPath(result);
+        };
+        // 签名无效或读不到版本时由用户确认是否仍然使用
+        if (validation.problems.length > 0) {
+          Modal.confirm({
+            title: '确认使用该可执行文件？',
+            content: validation.problems.join('；'),
+            okText: '仍然使用',
+            cancelText: '取消',
+            onOk: save,
+          });
         } else {
+          await save();
         }
       }
     } catch (error) {
@@ -127,6 +175,23 @@ const BusinessSettingsDialog: React.FC<BusinessSettingsDialogProps> = ({
               actionIcon={<FileCode className="h-4 w-4 text-gray-500"/>}
             />
 
+            {installs.length > 1 && (
+              <div>
+                <label className="text-sm font-medium text-gray-900 dark:text-gray-100 mb-1.5 block px-1">
+                  Antigravity 安装
+                </label>
+                <Select
+                  className="w-full"
+                  value={installs.find(install => install.active)?.dataDir}
+                  onChange={handleSelectInstall}
+                  options={installs.map(install => ({
+                    value: install.dataDir,
+                    label: `${install.flavor}${install.version ? ` v${install.version}` : ''} — ${install.dataDir}`,
+                  }))}
+                />
+              </div>
+            )}
+
             <PathSettingRow
               label="日志目录"
               value={logDirPath}
@@ -140,6 +205,7 @@ const BusinessSettingsDialog: React.FC<BusinessSettingsDialogProps> = ({
         <div className="h-px bg-gray-100 dark:bg-gray-800"/>
 
         <div className="space-y-1">
+          {traySupported && <>
           <SettingToggle
             icon={<Monitor className="h-4 w-4 text-blue-500"/>}
             title="系统托盘"
@@ -157,6 +223,7 @@ const BusinessSettingsDialog: React.FC<BusinessSettingsDialogProps> = ({
             onChange={setSilentStartEnabled}
             isLoading={loading.silentStart}
           />
+          </>}
 
           <SettingToggle
             icon={<EyeOff className="h-4 w-4 text-emerald-500"/>}
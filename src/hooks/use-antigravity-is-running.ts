@@ -1,10 +1,13 @@
 /**
  * Antigravity 进程运行状态 Store
- * 全局单例，每 5 秒自动检测 Antigravity 是否在运行
+ * 全局单例，订阅后端推送的 `agent:state` 状态快照，无需轮询
  */
 
 import {create} from 'zustand';
+import {listen, UnlistenFn} from '@tauri-apps/api/event';
 import {ProcessCommands} from '@/commands/ProcessCommands';
+import {StateCommands} from '@/commands/StateCommands';
+import type {AgentStateSnapshot} from '@/commands/types/state.types';
 import {logger} from '../lib/logger.ts';
 
 // 状态接口
@@ -21,17 +24,35 @@ interface AntigravityIsRunningState {
 interface AntigravityIsRunningActions {
   /** 检查运行状态 */
   check: () => Promise<void>;
-  /** 启动自动检查 */
+  /** 订阅状态推送 */
   start: () => void;
-  /** 停止自动检查 */
+  /** 取消订阅 */
   stop: () => void;
 }
 
-// 全局定时器 ID
-let checkIntervalId: NodeJS.Timeout | null = null;
+// 全局取消监听函数
+let unlistenFn: UnlistenFn | null = null;
 
-// 检查间隔（5 秒）
-const CHECK_INTERVAL = 5000;
+// 订阅序号，取消订阅后丢弃仍在进行中的订阅
+let subscriptionId = 0;
+
+// 已处理的最新快照版本
+let lastRevision = -1;
+
+// 应用状态快照中的运行状态（旧快照与未检测时忽略）
+const applySnapshot = (
+  snapshot: AgentStateSnapshot,
+  set: (state: Partial<AntigravityIsRunningState>) => void,
+) => {
+  if (snapshot.revision < lastRevision || snapshot.antigravityRunning === null) {
+    return;
+  }
+  lastRevision = snapshot.revision;
+  set({
+    isRunning: snapshot.antigravityRunning,
+    lastChecked: new Date(),
+  });
+};
 
 /**
  * Antigravity 运行状态 Store
@@ -75,36 +96,63 @@ export const useAntigravityIsRunning = create<
     }
   },
 
-  // 启动自动检查
+  // 订阅状态推送
   start: () => {
-    // 清除已存在的定时器
-    if (checkIntervalId !== null) {
-      clearInterval(checkIntervalId);
+    if (unlistenFn !== null) {
+      return;
     }
+    // 先占位，防止订阅完成前重复调用
+    unlistenFn = () => {};
+    const id = ++subscriptionId;
 
-    // 立即检查一次
-    get().check();
+    (async () => {
+      try {
+        const subscription = await StateCommands.subscribeState();
+        const unlisten = await listen<AgentStateSnapshot>(subscription.event, (event) => {
+          applySnapshot(event.payload, set);
+        });
+        if (id !== subscriptionId) {
+          unlisten();
+          return;
+        }
+        unlistenFn = unlisten;
 
-    // 启动定时检查
-    checkIntervalId = setInterval(() => {
-      get().check();
-    }, CHECK_INTERVAL);
+        // 后端尚未检测时主动检查一次
+        if (subscription.snapshot.antigravityRunning === null) {
+          await get().check();
+        } else {
+          applySnapshot(subscription.snapshot, set);
+        }
 
-    logger.info('已启动自动检查', {
-        module: 'AntigravityIsRunning',
-        action: 'start_auto_check',
-        interval: CHECK_INTERVAL
-      });
+        logger.info('已订阅状态推送', {
+          module: 'AntigravityIsRunning',
+          action: 'subscribe_state',
+          event: subscription.event
+        });
+      } catch (error) {
+        if (id !== subscriptionId) {
+          return;
+        }
+        unlistenFn = null;
+        logger.error('订阅状态推送失败', {
+          module: 'AntigravityIsRunning',
+          action: 'subscribe_state_failed',
+          error: error instanceof Error ? error.message : String(error)
+        });
+        await get().check();
+      }
+    })();
   },
 
-  // 停止自动检查
+  // 取消订阅
   stop: () => {
-    if (checkIntervalId !== null) {
-      clearInterval(checkIntervalId);
-      checkIntervalId = null;
-      logger.info('已停止自动检查', {
+    if (unlistenFn !== null) {
+      subscriptionId++;
+      unlistenFn();
+      unlistenFn = null;
+      logger.info('已取消订阅状态推送', {
         module: 'AntigravityIsRunning',
-        action: 'stop_auto_check'
+        action: 'unsubscribe_state'
       });
     }
   },
@@ -1,5 +1,6 @@
 import {create} from 'zustand';
 import {logger} from '../lib/logger.ts';
+import {getErrorMessage} from "@/lib/utils.ts";
 import toast from 'react-hot-toast';
 import {AccountManageCommands} from "@/commands/AccountManageCommands.ts";
 
@@ -28,7 +29,7 @@ export const useSignInNewAntigravityAccount = create<AntigravityProcessState & A
       } catch (e) {
         logger.error('登录新账户操作失败', {
           module: 'AntigravityProcessStore',
-          error: e instanceof Error ? e.message : String(e)
+          error: getErrorMessage(e)
         });
         toast.error('登录新账户操作失败');
       } finally {
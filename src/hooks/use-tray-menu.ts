@@ -4,6 +4,8 @@ import {logger} from "../lib/logger.ts";
 import {useAntigravityAccount} from "@/modules/use-antigravity-account.ts";
 import {TrayCommands} from "@/commands/TrayCommands.ts";
 import toast from "react-hot-toast";
+import {getErrorMessage} from "@/lib/utils.ts";
+import type {TraySwitchAccountPayload} from "@/commands/types/tray.types.ts";
 
 /**
  * 系统托盘菜单更新 Hook
@@ -28,16 +30,16 @@ export function useTrayMenu() {
 
   // 监听来自后端的账户切换请求
   useEffect(() => {
-    const unlisten = listen("tray-switch-account", async (event) => {
-      const email = event.payload as string;
-      logger.info("收到托盘账户切换请求", { email });
+    const unlisten = listen<TraySwitchAccountPayload>("tray-switch-account", async (event) => {
+      const { id, email, alias } = event.payload;
+      logger.info("收到托盘账户切换请求", { accountId: id });
 
       try {
         await switchToAccount(email);
-        toast.success(`已切换到账户: ${email}`);
+        toast.success(`已切换到账户: ${alias ?? email}`);
       } catch (error) {
         logger.error("托盘账户切换失败", error);
-        toast.error(`切换账户失败: ${error}`);
+        toast.error(`切换账户失败: ${getErrorMessage(error)}`);
       }
     });
 
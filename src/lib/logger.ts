@@ -2,26 +2,77 @@
 import {LoggingCommands} from "@/commands/LoggingCommands.ts";
 import {FrontendLogEntry} from "@/commands/types/logging.types.ts";
 
+/** 缓冲日志的提交间隔（毫秒） */
+const FLUSH_INTERVAL_MS = 500;
+
+/** 缓冲达到该条数时立即提交 */
+const FLUSH_BATCH_SIZE = 50;
+
+/** 后端限流期间最多缓冲的条数，超出时丢弃最早的日志 */
+const MAX_BUFFERED = 500;
+
+/** 绑定到某次操作的日志记录器 */
+export interface OperationLogger {
+  /** 操作 ID，作为 taskId 传给后端命令 */
+  id: string;
+  info(...args: any[]): void;
+  warn(...args: any[]): void;
+  error(...args: any[]): void;
+  debug(...args: any[]): void;
+}
+
+/**
+ * 生成操作 ID
+ */
+export function createOperationId(): string {
+  return crypto.randomUUID();
+}
+
 export class Logger {
   private sessionId = Math.random().toString(36).substring(7);
+  private buffer: FrontendLogEntry[] = [];
+  private flushTimer: ReturnType<typeof setTimeout> | null = null;
+  private pausedUntil = 0;
+  private flushing = false;
+  private droppedLocally = 0;
+
+  constructor() {
+    if (typeof window !== 'undefined') {
+      window.addEventListener('pagehide', () => this.flush());
+    }
+  }
 
   info(...args: any[]) {
-    this.writeLog('info', ...args);
+    this.writeLog('info', args);
   }
 
   warn(...args: any[]) {
-    this.writeLog('warn', ...args);
+    this.writeLog('warn', args);
   }
 
   error(...args: any[]) {
-    this.writeLog('error', ...args);
+    this.writeLog('error', args);
   }
 
   debug(...args: any[]) {
-    this.writeLog('debug', ...args);
+    this.writeLog('debug', args);
+  }
+
+  /**
+   * 为用户发起的操作创建日志记录器：日志带有操作 ID，与后端同一任务的日志、审计记录关联
+   * @param operationId 操作 ID（省略时自动生成）
+   */
+  forOperation(operationId: string = createOperationId()): OperationLogger {
+    return {
+      id: operationId,
+      info: (...args: any[]) => this.writeLog('info', args, operationId),
+      warn: (...args: any[]) => this.writeLog('warn', args, operationId),
+      error: (...args: any[]) => this.writeLog('error', args, operationId),
+      debug: (...args: any[]) => this.writeLog('debug', args, operationId),
+    };
   }
 
-  private writeLog(level: FrontendLogEntry["level"], ...args: any[]) {
+  private writeLog(level: FrontendLogEntry["level"], args: any[], operationId?: string) {
     // 处理多参数，类似 console.log
     let message = '';
     let details: any;
@@ -55,16 +106,82 @@ export class Logger {
       level,
       message,
       details: details ? JSON.stringify(details) : undefined,
-      sessionId: this.sessionId
+      sessionId: this.sessionId,
+      operationId
     };
 
-    LoggingCommands.writeFrontendLog(logEntry);
+    this.enqueue(logEntry);
 
     // 同时输出到浏览器控制台
     const consoleMethod = console[level as keyof Console] || console.log;
     // @ts-ignore
     consoleMethod(...args);
   }
+
+  /**
+   * 缓冲日志，按间隔或条数批量提交
+   */
+  private enqueue(entry: FrontendLogEntry) {
+    this.buffer.push(entry);
+    if (this.buffer.length > MAX_BUFFERED) {
+      const overflow = this.buffer.length - MAX_BUFFERED;
+      this.buffer.splice(0, overflow);
+      this.droppedLocally += overflow;
+    }
+
+    if (this.buffer.length >= FLUSH_BATCH_SIZE && Date.now() >= this.pausedUntil) {
+      void this.flush();
+    } else {
+      this.scheduleFlush(FLUSH_INTERVAL_MS);
+    }
+  }
+
+  private scheduleFlush(delay: number) {
+    if (this.flushTimer !== null) return;
+    this.flushTimer = setTimeout(() => {
+      this.flushTimer = null;
+      void this.flush();
+    }, delay);
+  }
+
+  /**
+   * 提交缓冲中的日志；后端限流时暂停提交，期间的日志留在缓冲中
+   */
+  async flush(): Promise<void> {
+    if (this.flushing || this.buffer.length === 0) return;
+
+    const waitMs = this.pausedUntil - Date.now();
+    if (waitMs > 0) {
+      this.scheduleFlush(waitMs);
+      return;
+    }
+
+    const entries = this.buffer.splice(0, this.buffer.length);
+    if (this.droppedLocally > 0) {
+      entries.push({
+        level: 'warn',
+        message: `[logger] 日志提交受限，前端已丢弃 ${this.droppedLocally} 条`,
+        sessionId: this.sessionId,
+      });
+      this.droppedLocally = 0;
+    }
+
+    this.flushing = true;
+    try {
+      const result = await LoggingCommands.writeFrontendLogs(entries);
+      if (result.retryAfterMs) {
+        this.pausedUntil = Date.now() + result.retryAfterMs;
+      }
+    } catch (error) {
+      console.error('写入前端日志失败', error);
+    } finally {
+      this.flushing = false;
+    }
+
+    if (this.buffer.length > 0) {
+      this.scheduleFlush(Math.max(this.pausedUntil - Date.now(), FLUSH_INTERVAL_MS));
+    }
+  }
 }
 
 export const logger = new Logger();
@@ -1,6 +1,20 @@
 import { clsx, type ClassValue } from "clsx"
 import { twMerge } from "tailwind-merge"
+import { isAgentError } from "@/commands/types/error.types"
 
 export function cn(...inputs: ClassValue[]) {
   return twMerge(clsx(inputs))
 }
+
+/**
+ * 提取错误信息，兼容 Error、后端结构化错误和字符串
+ */
+export function getErrorMessage(error: unknown): string {
+  if (error instanceof Error) {
+    return error.message;
+  }
+  if (isAgentError(error)) {
+    return error.message;
+  }
+  return String(error);
+}
@@ -1,8 +1,11 @@
 import React from 'react';
 import ReactDOM from 'react-dom/client';
 import App from './App';
+import {AppErrorBoundary} from './components/app/AppErrorBoundary';
 import './index.css';
 
 ReactDOM.createRoot(document.getElementById('app')).render(
-    <App />
+    <AppErrorBoundary>
+        <App />
+    </AppErrorBoundary>
 );
@@ -4,6 +4,8 @@ import { CloudCodeAPI } from "@/services/cloudcode-api.ts";
 import { CloudCodeAPITypes } from "@/services/cloudcode-api.types.ts";
 import { AccountCommands } from "@/commands/AccountCommands.ts";
 import { ProcessCommands } from "@/commands/ProcessCommands.ts";
+import { AccountMetadataCommands } from "@/commands/AccountMetadataCommands.ts";
+import { QuotaCommands } from "@/commands/QuotaCommands.ts";
 import { logger } from "@/lib/logger";
 
 type State = {
@@ -63,6 +65,28 @@ export const useAccountAdditionData = create<State & Actions>((setState, getStat
     const modelsResponse = await CloudCodeAPI.fetchAvailableModels(antigravityAccount.auth.access_token, codeAssistResponse.cloudaicompanionProject);
     const userInfoResponse = await CloudCodeAPI.userinfo(antigravityAccount.auth.access_token);
 
+    // 优先使用本地缓存的头像，离线时也能显示
+    const avatar = await AccountMetadataCommands.cacheAvatar(antigravityAccount.context.email, userInfoResponse.picture)
+      .catch(() => null);
+
+    // 记录配额历史，用于切换账户前比较剩余配额
+    const trackedQuotas = {
+      "gemini-3-pro-high": modelsResponse.models["gemini-3-pro-high"].quotaInfo,
+      "gemini-3-flash": modelsResponse.models["gemini-3-flash"].quotaInfo,
+      "gemini-3-pro-image": modelsResponse.models["gemini-3-pro-image"].quotaInfo,
+      "claude-opus-4-5-thinking": modelsResponse.models["claude-opus-4-5-thinking"].quotaInfo,
+    }
+    await QuotaCommands.record(antigravityAccount.context.email, Object.entries(trackedQuotas)
+      .map(([model, quotaInfo]) => ({
+        model,
+        remainingFraction: quotaInfo.remainingFraction ?? 0,
+        resetTime: quotaInfo.resetTime ?? null,
+      })))
+      .catch(error => logger.warn('记录配额历史失败', {
+        module: 'use-account-addition-data',
+        error: String(error),
+      }));
+
     logger.debug('获取 AccountAdditionData 成功', {
       module: 'use-account-addition-data',
       email: antigravityAccount.context.email,
@@ -80,7 +104,7 @@ export const useAccountAdditionData = create<State & Actions>((setState, getStat
           geminiImageQuoteRestIn: modelsResponse.models["gemini-3-pro-image"].quotaInfo.resetTime,
           claudeQuote: modelsResponse.models["claude-opus-4-5-thinking"].quotaInfo.remainingFraction,
           claudeQuoteRestIn: modelsResponse.models["claude-opus-4-5-thinking"].quotaInfo.resetTime,
-          userAvatar: userInfoResponse.picture,
+          userAvatar: avatar?.dataUrl ?? userInfoResponse.picture,
           userId: userInfoResponse.id,
         }
       }
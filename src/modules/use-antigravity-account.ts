@@ -1,5 +1,6 @@
 import {create} from 'zustand';
 import {logger} from '../lib/logger.ts';
+import {getErrorMessage} from "@/lib/utils.ts";
 import {AccountCommands} from '@/commands/AccountCommands.ts';
 import type {AntigravityAccount} from '@/commands/types/account.types.ts';
 import {AccountManageCommands} from "@/commands/AccountManageCommands.ts";
@@ -45,7 +46,7 @@ export const useAntigravityAccount = create<AntigravityAccountState & Antigravit
       logger.error('用户删除失败', {
         module: 'UserManagement',
         email,
-        error: error instanceof Error ? error.message : String(error)
+        error: getErrorMessage(error)
       });
       throw error;
     }
@@ -76,21 +77,23 @@ export const useAntigravityAccount = create<AntigravityAccountState & Antigravit
     } catch (error) {
       logger.error('备份当前用户失败', {
         module: 'UserManagement',
-        error: error instanceof Error ? error.message : String(error)
+        error: getErrorMessage(error)
       });
       throw error;
     }
   },
 
   switchToAccount: async (email: string): Promise<void> => {
+    // 操作 ID 同时作为任务 ID，前后端日志与审计记录据此关联
+    const operation = logger.forOperation();
     try {
       // 调用后端切换用户命令
-      await AccountCommands.switchToAntigravityAccount(email);
+      await AccountCommands.switchToAntigravityAccount(email, operation.id);
     } catch (error) {
-      logger.error('切换用户失败', {
+      operation.error('切换用户失败', {
         module: 'UserManagement',
         email,
-        error: error instanceof Error ? error.message : String(error)
+        error: getErrorMessage(error)
       });
       throw error;
     }
@@ -118,7 +121,7 @@ export const useAntigravityAccount = create<AntigravityAccountState & Antigravit
     } catch (error) {
       logger.error('获取用户列表失败', {
         module: 'UserManagement',
-        error: error instanceof Error ? error.message : String(error)
+        error: getErrorMessage(error)
       });
       // 如果读取失败，返回当前 store 中的用户
       return get().accounts;
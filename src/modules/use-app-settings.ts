@@ -1,5 +1,6 @@
 import {create} from "zustand";
 import {SettingsCommands} from "@/commands/SettingsCommands.ts";
+import {TrayCommands} from "@/commands/TrayCommands.ts";
 import {logger} from "@/lib/logger.ts";
 import {relaunch} from "@tauri-apps/plugin-process";
 
@@ -36,7 +37,19 @@ export const useAppSettings = create<State & Actions>((setState, getState) => {
     debugMode: boolean;
     privateMode: boolean;
   }> => {
-    const settings = await SettingsCommands.getAll();
+    let settings = await SettingsCommands.getAll();
+
+    // 首次运行：由后端统一检测环境并写入默认设置
+    if (settings && settings.setupCompleted === false) {
+      const report = await SettingsCommands.runFirstTimeSetup();
+      logger.info('首次运行引导完成', {
+        module: 'AppSettings',
+        action: 'first_time_setup',
+        settingsApplied: report.settingsApplied,
+        warnings: report.warnings
+      })
+      settings = await SettingsCommands.getAll();
+    }
 
     return {
       systemTrayEnabled: typeof settings?.system_tray_enabled === 'boolean' ? settings.system_tray_enabled : false,
@@ -110,7 +123,7 @@ export const useAppSettings = create<State & Actions>((setState, getState) => {
       setState(state => ({loading: {...state.loading, systemTray: true}}))
 
       try {
-        await SettingsCommands.saveSystemTrayState(enabled);
+        await TrayCommands.setEnabled(enabled);
 
         const next = await loadAllSettings();
         setState({
@@ -137,7 +150,7 @@ export const useAppSettings = create<State & Actions>((setState, getState) => {
         if (enabled && !getState().systemTrayEnabled) {
           setState(state => ({loading: {...state.loading, systemTray: true}}))
           try {
-            const trayEnabled = await SettingsCommands.saveSystemTrayState(true);
+            const trayEnabled = await TrayCommands.setEnabled(true);
             setState({systemTrayEnabled: trayEnabled})
           } finally {
             setState(state => ({loading: {...state.loading, systemTray: false}}))
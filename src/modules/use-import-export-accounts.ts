@@ -11,6 +11,7 @@ import toast from 'react-hot-toast';
 import {AccountManageCommands} from "@/commands/AccountManageCommands.ts";
 import {BackupData} from "@/commands/types/account-manage.types.ts";
 import {LoggingCommands} from "@/commands/LoggingCommands.ts";
+import {getErrorMessage} from "@/lib/utils.ts";
 
 interface EncryptedConfigData {
   version: string;
@@ -102,6 +103,7 @@ export const useImportExportAccount = create<ConfigState & ConfigActions>()(
           return;
         }
 
+        const operation = logger.forOperation();
         try {
           get().closeImportDialog();
           set({ isImporting: true });
@@ -117,16 +119,23 @@ export const useImportExportAccount = create<ConfigState & ConfigActions>()(
             throw new Error('配置文件格式无效');
           }
 
-          logger.info('开始恢复备份数据', {
+          operation.info('开始恢复备份数据', {
             module: 'useImportExportAccount',
             backupCount: configData.backups.length
           });
           toast.loading('正在恢复账户数据...', {duration: 1});
 
-          const result = await AccountManageCommands.restoreBackupFiles(configData.backups);
+          const result = await AccountManageCommands.restoreBackupFiles(configData.backups, operation.id);
+
+          if (result.converted.length > 0) {
+            operation.info('部分备份已从其他格式转换', {
+              module: 'useImportExportAccount',
+              converted: result.converted
+            });
+          }
 
           if (result.failed.length > 0) {
-            logger.warn('部分文件恢复失败', {
+            operation.warn('部分文件恢复失败', {
               module: 'useImportExportAccount',
               restoredCount: result.restoredCount,
               failedCount: result.failed.length,
@@ -134,19 +143,19 @@ export const useImportExportAccount = create<ConfigState & ConfigActions>()(
             });
             toast.success(`配置文件导入成功，已恢复 ${result.restoredCount} 个账户，${result.failed.length} 个失败`);
           } else {
-            logger.info('所有文件恢复成功', {
+            operation.info('所有文件恢复成功', {
               module: 'useImportExportAccount',
               restoredCount: result.restoredCount
             });
             toast.success(`配置文件导入成功，已恢复 ${result.restoredCount} 个账户`);
           }
         } catch (error) {
-          logger.error('导入失败', {
+          operation.error('导入失败', {
             module: 'useImportExportAccount',
             stage: 'import_process',
-            error: error instanceof Error ? error.message : String(error)
+            error: getErrorMessage(error)
           });
-          toast.error(`配置文件导入失败: ${error instanceof Error ? error.message : String(error)}`);
+          toast.error(`配置文件导入失败: ${getErrorMessage(error)}`);
         } finally {
           set({ isImporting: false });
         }
@@ -222,9 +231,9 @@ export const useImportExportAccount = create<ConfigState & ConfigActions>()(
           logger.error('导出失败', {
             module: 'useImportExportAccount',
             stage: 'password_validation',
-            error: error instanceof Error ? error.message : String(error)
+            error: getErrorMessage(error)
           });
-          toast.error(`导出配置文件失败: ${error instanceof Error ? error.message : String(error)}`);
+          toast.error(`导出配置文件失败: ${getErrorMessage(error)}`);
         } finally {
           set({ isExporting: false });
         }
@@ -271,31 +280,32 @@ export const useImportExportAccount = create<ConfigState & ConfigActions>()(
           logger.error('文件操作失败', {
             module: 'useImportExportAccount',
             stage: 'file_selection',
-            error: error instanceof Error ? error.message : String(error)
+            error: getErrorMessage(error)
           });
-          toast.error(`文件操作失败: ${error instanceof Error ? error.message : String(error)}`);
+          toast.error(`文件操作失败: ${getErrorMessage(error)}`);
         }
       },
 
       // ============ 导出配置 ============
       exportConfig: async (): Promise<void> => {
-        logger.info('开始导出配置', { module: 'useImportExportAccount' });
+        const operation = logger.forOperation();
+        operation.info('开始导出配置', { module: 'useImportExportAccount' });
 
         try {
           toast.loading('正在收集账户数据...', {duration: 1});
 
           // ✅ 获取包含完整内容的备份数据
-          const accountContents = await AccountManageCommands.collectAccountContents();
+          const accountContents = await AccountManageCommands.collectAccountContents(operation.id);
 
           if (accountContents.length === 0) {
-            logger.warn('没有找到账户信息', {
+            operation.warn('没有找到账户信息', {
               module: 'useImportExportAccount'
             });
             toast.error('没有找到任何账户信息，无法导出配置文件');
             return;
           }
 
-          logger.info('找到账户数据', {
+          operation.info('找到账户数据', {
             module: 'useImportExportAccount',
             backupCount: accountContents.length
           });
@@ -304,12 +314,12 @@ export const useImportExportAccount = create<ConfigState & ConfigActions>()(
           get().openExportDialog(accountContents);
 
         } catch (error) {
-          logger.error('检查数据失败', {
+          operation.error('检查数据失败', {
             module: 'useImportExportAccount',
             stage: 'data_collection',
-            error: error instanceof Error ? error.message : String(error)
+            error: getErrorMessage(error)
           });
-          toast.error(`检查数据失败: ${error instanceof Error ? error.message : String(error)}`);
+          toast.error(`检查数据失败: ${getErrorMessage(error)}`);
         }
       }
     };